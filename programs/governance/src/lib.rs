@@ -3,7 +3,7 @@
 
 use anchor_lang::prelude::*;
 use anchor_lang::Discriminator;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
 // Anchor programs must be deployed at their declared ID.
 // We support devnet vs localnet IDs via a build-time feature so CI/local tests keep working.
@@ -61,13 +61,290 @@ fn require_upgrade_authority(
     Ok(())
 }
 
+/// Default quorum when GovConfig.quorum_bps is unset (0).
 pub const QUORUM_BPS: u16 = 2000; // 20%
 /// Min voting period: 24h in prod; 1s when built with `--features governance/test` for tests.
 #[cfg(feature = "test")]
 pub const MIN_VOTING_PERIOD_SECS: i64 = 1;
 #[cfg(not(feature = "test"))]
 pub const MIN_VOTING_PERIOD_SECS: i64 = 24 * 3600;
+/// Hard floor on every proposal's voting period, whatever GovConfig says: 1h in prod; 1s with `governance/test`.
+/// GovConfig.min_voting_period_secs can raise the minimum but never lower it below this.
+#[cfg(feature = "test")]
+pub const VOTING_PERIOD_FLOOR_SECS: i64 = 1;
+#[cfg(not(feature = "test"))]
+pub const VOTING_PERIOD_FLOOR_SECS: i64 = 3600;
 pub const MAX_PROOF_URI_LEN: usize = 200;
+/// URI schemes create_proposal accepts for proof_uri.
+pub const PROOF_URI_SCHEMES: [&str; 3] = ["https://", "ipfs://", "ar://"];
+/// Max length of a proposal title.
+pub const MAX_PROPOSAL_TITLE_LEN: usize = 64;
+/// milestone_index marking a post-completion proposal voted by RWA holders (see WeightSource::RwaBalance).
+pub const RWA_PROPOSAL_INDEX: u8 = 254;
+/// milestone_index marking a backer proposal to slash an Active project (see create_slash_proposal).
+pub const SLASH_PROPOSAL_INDEX: u8 = 253;
+/// Max length of the reason logged by veto_proposal.
+pub const MAX_VETO_REASON_LEN: usize = 200;
+/// Proposal account size (proof_uri reserved at max length).
+pub const PROPOSAL_SPACE: usize =
+    PROPOSAL_BASE_LEN + 8 + 8 + 1 + 4 + MAX_PROPOSAL_TITLE_LEN + 32 + 8 + 8 + 8;
+/// Proposal account size before votes_abstain, executable_at, weight_source, title and description_hash were
+/// added; see migrate_proposal.
+const PROPOSAL_BASE_LEN: usize = 8 + 32 + 1 + 4 + MAX_PROOF_URI_LEN + 8 + 8 + 1 + 8 + 8 + 32;
+/// Current GovConfig account size. Older configs are shorter; see GovConfig::from_account_data.
+pub const GOV_CONFIG_SPACE: usize = 8 + 1 + 8 + 4 + 1 + 2 + 2 + 2 + 8 + 8 + 8 + 32 + 32;
+/// Size of the original GovConfig layout (allow_early_finalize, min_voting_period_secs).
+const GOV_CONFIG_BASE_LEN: usize = 8 + 1 + 8;
+/// Vote account size.
+pub const VOTE_SPACE: usize = VOTE_BASE_LEN + 32 + 8;
+/// Vote account size before the casting delegate was recorded; see migrate_vote.
+const VOTE_BASE_LEN: usize = 8 + 32 + 32 + 8 + 1;
+/// ProposalBond: discriminator + depositor + mint + amount.
+pub const PROPOSAL_BOND_SPACE: usize = 8 + 32 + 32 + 8;
+/// RwaVoteLock: discriminator + proposal + voter + mint + amount.
+pub const RWA_VOTE_LOCK_SPACE: usize = 8 + 32 + 32 + 32 + 8;
+/// VoteDelegation account size.
+pub const VOTE_DELEGATION_SPACE: usize = 8 + 32 + 32 + 32;
+/// Accounts per delegator in cast_vote remaining_accounts: (VoteDelegation, delegator Backer, delegator Vote PDA).
+pub const DELEGATION_ACCOUNTS_PER_DELEGATOR: usize = 3;
+/// Most delegators whose weight one cast_vote call can carry.
+pub const MAX_DELEGATIONS_PER_VOTE: usize = 8;
+
+/// Proposal tallies (for, against, abstain) after a vote of `weight` on `side`. A changed vote first removes
+/// its `previous` (side, weight) so the voter is never counted twice.
+pub fn tally_vote(
+    tallies: (u64, u64, u64),
+    previous: Option<(VoteSide, u64)>,
+    side: VoteSide,
+    weight: u64,
+) -> Result<(u64, u64, u64)> {
+    let (votes_for, votes_against, votes_abstain) = tallies;
+    let mut tallies = [votes_against, votes_for, votes_abstain];
+    if let Some((previous_side, previous_weight)) = previous {
+        let tally = &mut tallies[previous_side as usize];
+        *tally = tally
+            .checked_sub(previous_weight)
+            .ok_or(GovError::Overflow)?;
+    }
+    let tally = &mut tallies[side as usize];
+    *tally = tally.checked_add(weight).ok_or(GovError::Overflow)?;
+    let [votes_against, votes_for, votes_abstain] = tallies;
+    Ok((votes_for, votes_against, votes_abstain))
+}
+
+/// Extra weight from the voter's taste_token stake: sqrt(amount * multiplier). The stake must stay locked
+/// until the vote ends, so the same tokens cannot be unstaked and re-staked to boost another wallet.
+pub fn stake_vote_boost(
+    stake: &taste_token::StakeAccount,
+    voter: &Pubkey,
+    vote_end_ts: i64,
+) -> Result<u64> {
+    require!(stake.owner == *voter, GovError::InvalidStakeAccount);
+    require!(
+        stake.unlock_ts >= vote_end_ts,
+        GovError::StakeUnlocksBeforeVoteEnds
+    );
+    Ok(sqrt_u64(stake.boosted_amount()))
+}
+
+/// Number of delegators in cast_vote's remaining_accounts (whole groups, at most the cap).
+pub fn delegated_vote_count(remaining_len: usize) -> Result<usize> {
+    let count = remaining_len / DELEGATION_ACCOUNTS_PER_DELEGATOR;
+    require!(
+        count * DELEGATION_ACCOUNTS_PER_DELEGATOR == remaining_len,
+        GovError::InvalidDelegation
+    );
+    require!(
+        count <= MAX_DELEGATIONS_PER_VOTE,
+        GovError::InvalidDelegation
+    );
+    Ok(count)
+}
+
+/// Quadratic weight `delegate` may cast on behalf of `delegation.delegator` in `project`: sqrt of the
+/// delegator's escrowed amount. The delegation must point at `delegate` and the Backer must be the delegator's.
+pub fn delegated_weight(
+    project: &Pubkey,
+    delegate: &Pubkey,
+    delegation: &VoteDelegation,
+    backer: &project_escrow::Backer,
+) -> Result<u64> {
+    require!(
+        delegation.project == *project && delegation.delegate == *delegate,
+        GovError::InvalidDelegation
+    );
+    require!(
+        backer.project == *project && backer.wallet == delegation.delegator,
+        GovError::InvalidDelegation
+    );
+    require!(backer.amount > 0, GovError::NoContribution);
+    Ok(sqrt_u64(backer.amount))
+}
+
+/// Quorum threshold in vote-weight units: sqrt(quorum_bps of the basis amount), where the basis is
+/// the project's total raised or its funding goal depending on `basis`.
+pub fn quorum_votes(total_raised: u64, goal: u64, basis: QuorumBasis, quorum_bps: u16) -> u64 {
+    let denominator = match basis {
+        QuorumBasis::Raised => total_raised,
+        QuorumBasis::Goal => goal,
+    };
+    let quorum_raw = (denominator as u128 * quorum_bps as u128 / 10_000) as u64;
+    sqrt_u64(quorum_raw)
+}
+
+/// Proposal text fields must fit the space reserved for them in PROPOSAL_SPACE.
+pub fn validate_proposal_metadata(proof_uri: &str, title: &str) -> Result<()> {
+    require!(
+        proof_uri.len() <= MAX_PROOF_URI_LEN,
+        GovError::ProofUriTooLong
+    );
+    require!(
+        title.len() <= MAX_PROPOSAL_TITLE_LEN,
+        GovError::TitleTooLong
+    );
+    Ok(())
+}
+
+/// A proposal's proof_uri must point somewhere: an accepted scheme (PROOF_URI_SCHEMES) followed by a non-empty path.
+pub fn validate_proof_uri(proof_uri: &str) -> Result<()> {
+    let valid = PROOF_URI_SCHEMES.iter().any(|scheme| {
+        proof_uri
+            .strip_prefix(scheme)
+            .is_some_and(|rest| !rest.is_empty())
+    });
+    require!(valid, GovError::InvalidProofUri);
+    Ok(())
+}
+
+/// Attempt the next proposal on a milestone must use. A milestone counter that has never been used (0) continues
+/// from the legacy per-project counter, so new proposals never collide with a PDA created under the old scheme.
+pub fn next_proposal_attempt(milestone_attempt: u64, legacy_attempt: Option<u64>) -> u64 {
+    if milestone_attempt == 0 {
+        legacy_attempt.unwrap_or(0)
+    } else {
+        milestone_attempt
+    }
+}
+
+/// True for milestone_index values naming a milestone release (0..MAX_MILESTONES) rather than a special proposal.
+pub fn is_milestone_proposal(milestone_index: u8) -> bool {
+    (milestone_index as usize) < project_escrow::MAX_MILESTONES
+}
+
+/// A milestone vote on a project still short of its funding threshold must end by the funding deadline, so it
+/// cannot release funds from a project that fail_project should have closed. Once funded the project can no
+/// longer fail, and milestone votes run on their own schedule.
+pub fn check_milestone_voting_end(end_ts: i64, project: &project_escrow::Project) -> Result<()> {
+    if project.total_raised < project.funding_threshold() {
+        require!(
+            end_ts <= project.deadline,
+            GovError::VotingPeriodExceedsDeadline
+        );
+    }
+    Ok(())
+}
+
+/// Value of the legacy per-project ProposalAttempt counter, or None if it was never created.
+fn read_legacy_proposal_attempt(info: &AccountInfo) -> Result<Option<u64>> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+    let data = info.try_borrow_data()?;
+    Ok(Some(
+        ProposalAttempt::try_deserialize(&mut &data[..])?.attempt,
+    ))
+}
+
+/// Quorum for an RWA-holder proposal, in RWA token units: quorum_bps of the RWA mint supply.
+pub fn rwa_quorum_votes(rwa_supply: u64, quorum_bps: u16) -> u64 {
+    (rwa_supply as u128 * quorum_bps as u128 / 10_000) as u64
+}
+
+/// Address of a project's RWA mint (rwa_token PDA [b"rwa_mint", project]).
+pub fn rwa_mint_address(project: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"rwa_mint", project.as_ref()], &rwa_token::ID).0
+}
+
+/// Escrow must hold what release_milestone would pay out for the project's current milestone.
+pub fn require_escrow_covers_milestone(
+    escrow_amount: u64,
+    project: &project_escrow::Project,
+) -> Result<()> {
+    let (due, _) = project_escrow::milestone_tranche(
+        project.current_milestone_amount()?,
+        project.released_in_milestone,
+        None,
+    )?;
+    require!(escrow_amount >= due, GovError::InsufficientEscrow);
+    Ok(())
+}
+
+/// Whether a material-edit proposal passes: a simple majority of for over against and, when
+/// `threshold_bps` is set, votes_for at least threshold_bps of the decisive votes (for + against; abstain excluded).
+pub fn material_edit_passed(votes_for: u64, votes_against: u64, threshold_bps: u16) -> bool {
+    let decisive = votes_for as u128 + votes_against as u128;
+    votes_for > votes_against && votes_for as u128 * 10_000 >= decisive * threshold_bps as u128
+}
+
+/// Whether a proposal may be finalized before its voting period ends: early finalize must be enabled, the project
+/// vote weight known, the outcome decided against it (one side holds a majority of `total_vote_weight`) and
+/// turnout (for + against + abstain) at least `min_participation_bps` of `total_vote_weight`.
+pub fn early_finalize_allowed(
+    votes_for: u64,
+    votes_against: u64,
+    votes_abstain: u64,
+    total_vote_weight: Option<u64>,
+    config: Option<&GovConfig>,
+) -> bool {
+    let (Some(config), Some(tw)) = (config, total_vote_weight) else {
+        return false;
+    };
+    if !config.allow_early_finalize || tw == 0 {
+        return false;
+    }
+    let tw = tw as u128;
+    let two_for = (votes_for as u128) * 2;
+    let two_against = (votes_against as u128) * 2;
+    let outcome_decided = (two_for > tw) || (two_against >= tw);
+    let turnout = votes_for as u128 + votes_against as u128 + votes_abstain as u128;
+    let participation_met = turnout * 10_000 >= tw * config.min_participation_bps as u128;
+    outcome_decided && participation_met
+}
+
+/// Shortest voting period create_proposal accepts: the config's minimum (MIN_VOTING_PERIOD_SECS without a
+/// config), never below VOTING_PERIOD_FLOOR_SECS. Legacy configs set before the floor existed are clamped here.
+pub fn min_voting_period(config: Option<&GovConfig>) -> i64 {
+    config
+        .map(|c| c.min_voting_period_secs)
+        .unwrap_or(MIN_VOTING_PERIOD_SECS)
+        .max(VOTING_PERIOD_FLOOR_SECS)
+}
+
+/// Where a settled proposal bond goes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BondOutcome {
+    /// Back to the proposal creator.
+    Refund,
+    /// To the GovConfig bond_treasury.
+    Forfeit,
+}
+
+/// Settle a proposal bond: refunded once the proposal is honestly finalized (Passed or Rejected with quorum),
+/// forfeited when it was cancelled or vetoed, or when voting ended with total_votes short of quorum (such a
+/// proposal can never be finalized). Still-open proposals cannot be settled.
+pub fn bond_outcome(
+    status: ProposalStatus,
+    voting_ended: bool,
+    total_votes: u64,
+    quorum: u64,
+) -> Result<BondOutcome> {
+    match status {
+        ProposalStatus::Passed | ProposalStatus::Rejected => Ok(BondOutcome::Refund),
+        ProposalStatus::Cancelled => Ok(BondOutcome::Forfeit),
+        ProposalStatus::Active if voting_ended && total_votes < quorum => Ok(BondOutcome::Forfeit),
+        ProposalStatus::Active => err!(GovError::BondNotSettleable),
+    }
+}
 
 /// If the first remaining_account is the governance config PDA, deserialize and return it; else None.
 /// Requires account owner == this program and first 8 bytes match GovConfig Anchor discriminator.
@@ -86,19 +363,51 @@ pub(crate) fn read_gov_config_optional<'info>(
     if acc.owner != program_id {
         return Ok(None);
     }
-    if acc.data_len() < 17 {
-        return Ok(None);
-    }
     let data = acc.try_borrow_data()?;
-    if &data[0..8] != GovConfig::DISCRIMINATOR {
-        return Ok(None);
-    }
-    let allow_early_finalize = data[8] != 0;
-    let min_voting_period_secs = i64::from_le_bytes(data[9..17].try_into().unwrap());
-    Ok(Some(GovConfig {
-        allow_early_finalize,
-        min_voting_period_secs,
-    }))
+    Ok(GovConfig::from_account_data(&data))
+}
+
+/// Transfer RWA (a transfer-hook mint) via Token-2022, resolving the hook's extra accounts from `hook_accounts`
+/// (the hook program and its extra-account-metas PDA, passed in remaining_accounts).
+#[allow(clippy::too_many_arguments)]
+fn transfer_rwa<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    anchor_spl::token_interface::spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from.clone(),
+        mint.clone(),
+        to.clone(),
+        authority.clone(),
+        hook_accounts,
+        amount,
+        decimals,
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Write `config` into the GovConfig PDA, growing (and topping up rent for) configs created
+/// before newer fields were added.
+fn write_gov_config<'info>(
+    config_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    config: &GovConfig,
+) -> Result<()> {
+    rwa_token::grow_account(config_info, payer, system_program, GOV_CONFIG_SPACE)?;
+    let mut data = config_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data[..];
+    config.try_serialize(&mut writer)?;
+    Ok(())
 }
 
 /// Read optional GovConfig and optional total_vote_weight from remaining_accounts for early-finalize.
@@ -119,18 +428,9 @@ pub(crate) fn read_early_finalize_params<'info>(
     );
     // Presence-based inclusion: accept either order, but still validate owner + discriminator.
     for acc in remaining_accounts {
-        if gov_config.is_none()
-            && acc.key() == config_pda
-            && acc.owner == program_id
-            && acc.data_len() >= 17
-        {
+        if gov_config.is_none() && acc.key() == config_pda && acc.owner == program_id {
             let data = acc.try_borrow_data()?;
-            if &data[0..8] == GovConfig::DISCRIMINATOR {
-                gov_config = Some(GovConfig {
-                    allow_early_finalize: data[8] != 0,
-                    min_voting_period_secs: i64::from_le_bytes(data[9..17].try_into().unwrap()),
-                });
-            }
+            gov_config = GovConfig::from_account_data(&data);
         }
         if total_vote_weight.is_none()
             && acc.key() == vote_weight_pda
@@ -146,14 +446,38 @@ pub(crate) fn read_early_finalize_params<'info>(
     Ok((gov_config, total_vote_weight))
 }
 
-/// Babylonian method (from HYPNOSecosystem Governance.sol).
+/// The payout split recipient accounts in `remaining_accounts`, to forward to release_milestone: everything
+/// except the gov config and ProjectVoteWeight PDAs read by read_early_finalize_params.
+pub(crate) fn payout_recipient_accounts<'info>(
+    program_id: &Pubkey,
+    project_escrow_program_id: &Pubkey,
+    project_key: &Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Vec<AccountInfo<'info>> {
+    let (config_pda, _) = Pubkey::find_program_address(&[b"config"], program_id);
+    let (vote_weight_pda, _) = Pubkey::find_program_address(
+        &[b"vote_weight", project_key.as_ref()],
+        project_escrow_program_id,
+    );
+    remaining_accounts
+        .iter()
+        .filter(|a| a.key() != config_pda && a.key() != vote_weight_pda)
+        .cloned()
+        .collect()
+}
+
+/// Floor integer square root: the Babylonian method from HYPNOSecosystem Governance.sol, seeded from the bit
+/// length of x.
 #[inline]
 pub(crate) fn sqrt_u64(x: u64) -> u64 {
     if x == 0 {
         return 0;
     }
-    let mut z = x.div_ceil(2);
-    let mut y = x;
+    // 2^ceil(bits/2) is never below sqrt(x), so Newton's iteration descends straight to the floor root
+    // in a handful of steps instead of halving down from x.
+    let bits = u64::BITS - x.leading_zeros();
+    let mut y = 1u64 << bits.div_ceil(2);
+    let mut z = (x / y + y) / 2;
     while z < y {
         y = z;
         z = (x / z + z) / 2;
@@ -161,15 +485,183 @@ pub(crate) fn sqrt_u64(x: u64) -> u64 {
     y
 }
 
+/// Release the passed proposal's milestone via project_escrow CPI; when that completes the project, initialize
+/// the RWA mint, metadata, rights and revenue config. Shared by finalize_proposal (no execution delay) and
+/// execute_proposal.
+#[allow(clippy::too_many_arguments)]
+fn release_passed_milestone<'info>(
+    ctx: &mut Context<'_, '_, 'info, 'info, FinalizeProposal<'info>>,
+    rights_type: rwa_token::RightsType,
+    revenue_split_bps: u16,
+    artist_split_bps: u16,
+    duration_secs: i64,
+    effective_from: i64,
+    terms_hash: [u8; 32],
+    terms_uri: String,
+    jurisdiction: String,
+) -> Result<()> {
+    let bump_seed = ctx.bumps.release_authority;
+    let seeds: &[&[u8]] = &[b"release_authority", &[bump_seed]];
+    let signer_seeds = &[seeds];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.project_escrow_program.to_account_info(),
+        project_escrow::cpi::accounts::ReleaseMilestone {
+            governance_authority: ctx.accounts.release_authority.to_account_info(),
+            config: ctx.accounts.escrow_config.to_account_info(),
+            project: ctx.accounts.project.to_account_info(),
+            escrow: ctx.accounts.escrow.to_account_info(),
+            escrow_authority: ctx.accounts.escrow_authority.to_account_info(),
+            artist_token_account: ctx.accounts.artist_token_account.to_account_info(),
+            taste_mint: ctx.accounts.taste_mint.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            protocol_state: ctx
+                .accounts
+                .protocol_state
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            artist_state: ctx.accounts.artist_state.to_account_info(),
+            payout_split: ctx.accounts.payout_split.to_account_info(),
+        },
+        signer_seeds,
+    )
+    .with_remaining_accounts(payout_recipient_accounts(
+        ctx.program_id,
+        &ctx.accounts.project_escrow_program.key(),
+        &ctx.accounts.project.key(),
+        ctx.remaining_accounts,
+    ));
+    project_escrow::cpi::release_milestone(cpi_ctx, None)?;
+
+    // Re-read project after CPI mutated it (current_milestone, status).
+    ctx.accounts.project.reload()?;
+
+    // If project just completed (last milestone released), auto-init RWA mint (idempotent).
+    if ctx.accounts.project.current_milestone as usize
+        >= project_escrow::effective_milestone_count(&ctx.accounts.project.milestone_percentages)
+        && ctx.accounts.rwa_state.lamports() == 0
+    {
+        let rwa_cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.rwa_token_program.to_account_info(),
+            rwa_token::cpi::accounts::InitializeRwaMintByGovernance {
+                payer: ctx.accounts.payer.to_account_info(),
+                release_authority: ctx.accounts.release_authority.to_account_info(),
+                config: ctx.accounts.escrow_config.to_account_info(),
+                project: ctx.accounts.project.to_account_info(),
+                rwa_state: ctx.accounts.rwa_state.to_account_info(),
+                rwa_config: ctx.accounts.rwa_config.to_account_info(),
+                rwa_mint: ctx.accounts.rwa_mint.to_account_info(),
+                rwa_mint_authority: ctx.accounts.rwa_mint_authority.to_account_info(),
+                rwa_transfer_hook_program: ctx.accounts.rwa_transfer_hook_program.to_account_info(),
+                extra_account_metas: ctx.accounts.rwa_extra_account_metas.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            signer_seeds,
+        );
+        const RWA_TOTAL_SUPPLY: u64 = 1_000_000 * 1_000_000;
+        rwa_token::cpi::initialize_rwa_mint_by_governance(rwa_cpi_ctx, RWA_TOTAL_SUPPLY)?;
+    }
+
+    // If RWA mint was just created (or exists) and metadata not yet set, init Metaplex metadata.
+    let rwa_state_lamports = ctx.accounts.rwa_state.lamports();
+    let metadata_guard_uninit =
+        ctx.accounts.rwa_metadata_guard.owner == &anchor_lang::system_program::ID;
+    if rwa_state_lamports > 0 && metadata_guard_uninit {
+        let meta_cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.rwa_token_program.to_account_info(),
+            rwa_token::cpi::accounts::InitializeRwaMetadataByGovernance {
+                payer: ctx.accounts.payer.to_account_info(),
+                release_authority: ctx.accounts.release_authority.to_account_info(),
+                config: ctx.accounts.escrow_config.to_account_info(),
+                project: ctx.accounts.project.to_account_info(),
+                rwa_state: ctx.accounts.rwa_state.to_account_info(),
+                rwa_mint: ctx.accounts.rwa_mint.to_account_info(),
+                rwa_mint_authority: ctx.accounts.rwa_mint_authority.to_account_info(),
+                metadata_guard: ctx.accounts.rwa_metadata_guard.to_account_info(),
+                metadata: ctx.accounts.rwa_metadata.to_account_info(),
+                update_authority: ctx.accounts.artist.to_account_info(),
+                token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                sysvar_instructions: ctx.accounts.sysvar_instructions.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            signer_seeds,
+        );
+        rwa_token::cpi::initialize_rwa_metadata_by_governance(meta_cpi_ctx)?;
+    }
+
+    let last_milestone = ctx.accounts.project.current_milestone as usize
+        >= project_escrow::effective_milestone_count(&ctx.accounts.project.milestone_percentages);
+
+    if last_milestone && ctx.accounts.rwa_rights.lamports() == 0 {
+        let rights_cpi = CpiContext::new_with_signer(
+            ctx.accounts.rwa_token_program.to_account_info(),
+            rwa_token::cpi::accounts::InitializeRwaRightsByGovernance {
+                payer: ctx.accounts.payer.to_account_info(),
+                release_authority: ctx.accounts.release_authority.to_account_info(),
+                config: ctx.accounts.escrow_config.to_account_info(),
+                project: ctx.accounts.project.to_account_info(),
+                rwa_state: ctx.accounts.rwa_state.to_account_info(),
+                rwa_rights: ctx.accounts.rwa_rights.to_account_info(),
+                rwa_config: ctx.accounts.rwa_config.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            signer_seeds,
+        );
+        rwa_token::cpi::initialize_rwa_rights_by_governance(
+            rights_cpi,
+            rights_type,
+            revenue_split_bps,
+            artist_split_bps,
+            duration_secs,
+            effective_from,
+            terms_hash,
+            terms_uri,
+            jurisdiction,
+        )?;
+    }
+
+    if last_milestone && ctx.accounts.rev_config.lamports() == 0 {
+        let rev_cpi = CpiContext::new(
+            ctx.accounts.revenue_distribution_program.to_account_info(),
+            revenue_distribution::cpi::accounts::InitializeRevenueConfig {
+                payer: ctx.accounts.payer.to_account_info(),
+                project: ctx.accounts.project.to_account_info(),
+                rwa_state: ctx.accounts.rwa_state.to_account_info(),
+                rev_config: ctx.accounts.rev_config.to_account_info(),
+                rwa_mint: ctx.accounts.rwa_mint.to_account_info(),
+                rev_vault_authority: ctx.accounts.rev_vault_authority.to_account_info(),
+                rev_vault: ctx.accounts.rev_vault.to_account_info(),
+                taste_mint: ctx.accounts.taste_mint.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+        );
+        revenue_distribution::cpi::initialize_revenue_config(rev_cpi)?;
+    }
+    Ok(())
+}
+
 #[program]
 pub mod governance {
     use super::*;
 
-    /// One-time init: set allow_early_finalize and min_voting_period_secs. Only upgrade authority.
+    /// One-time init: set allow_early_finalize, min_voting_period_secs, min_backers_for_governance, quorum_basis,
+    /// min_participation_bps, quorum_bps, material_edit_threshold_bps, execution_delay_secs and min_vote_contribution.
+    /// Only upgrade authority.
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_config(
         ctx: Context<InitializeGovConfig>,
         allow_early_finalize: bool,
         min_voting_period_secs: i64,
+        min_backers_for_governance: u32,
+        quorum_basis: QuorumBasis,
+        min_participation_bps: u16,
+        quorum_bps: u16,
+        material_edit_threshold_bps: u16,
+        execution_delay_secs: i64,
+        min_vote_contribution: u64,
     ) -> Result<()> {
         let program_account = ctx.accounts.program_account.try_borrow_data()?;
         let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
@@ -181,23 +673,61 @@ pub mod governance {
             &program_data_account,
             &ctx.accounts.authority.key(),
         )?;
-        require!(min_voting_period_secs >= 1, GovError::VotingPeriodTooShort);
+        require!(
+            min_voting_period_secs >= VOTING_PERIOD_FLOOR_SECS,
+            GovError::VotingPeriodTooShort
+        );
+        require!(
+            min_participation_bps <= 10_000,
+            GovError::InvalidParticipationBps
+        );
+        require!(
+            quorum_bps > 0 && quorum_bps <= 10_000,
+            GovError::InvalidQuorumBps
+        );
+        require!(
+            material_edit_threshold_bps <= 10_000,
+            GovError::InvalidMaterialEditThreshold
+        );
+        require!(execution_delay_secs >= 0, GovError::InvalidExecutionDelay);
         let config = &mut ctx.accounts.config;
         config.allow_early_finalize = allow_early_finalize;
         config.min_voting_period_secs = min_voting_period_secs;
+        config.min_backers_for_governance = min_backers_for_governance;
+        config.quorum_basis = quorum_basis;
+        config.min_participation_bps = min_participation_bps;
+        config.quorum_bps = quorum_bps;
+        config.material_edit_threshold_bps = material_edit_threshold_bps;
+        config.execution_delay_secs = execution_delay_secs;
+        config.min_vote_contribution = min_vote_contribution;
         msg!(
-            "Gov config initialized: allow_early_finalize={} min_voting_period_secs={}",
+            "Gov config initialized: allow_early_finalize={} min_voting_period_secs={} min_backers_for_governance={} quorum_basis={:?} min_participation_bps={} quorum_bps={} material_edit_threshold_bps={} execution_delay_secs={} min_vote_contribution={}",
             allow_early_finalize,
-            min_voting_period_secs
+            min_voting_period_secs,
+            min_backers_for_governance,
+            quorum_basis,
+            min_participation_bps,
+            quorum_bps,
+            material_edit_threshold_bps,
+            execution_delay_secs,
+            min_vote_contribution
         );
         Ok(())
     }
 
-    /// Update config (allow_early_finalize, min_voting_period_secs). Only upgrade authority.
+    /// Update config. Only upgrade authority. Configs created before newer fields existed are grown in place.
+    #[allow(clippy::too_many_arguments)]
     pub fn update_config(
         ctx: Context<UpdateGovConfig>,
         allow_early_finalize: bool,
         min_voting_period_secs: i64,
+        min_backers_for_governance: u32,
+        quorum_basis: QuorumBasis,
+        min_participation_bps: u16,
+        quorum_bps: u16,
+        material_edit_threshold_bps: u16,
+        execution_delay_secs: i64,
+        min_vote_contribution: u64,
     ) -> Result<()> {
         let program_account = ctx.accounts.program_account.try_borrow_data()?;
         let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
@@ -209,18 +739,102 @@ pub mod governance {
             &program_data_account,
             &ctx.accounts.authority.key(),
         )?;
-        require!(min_voting_period_secs >= 1, GovError::VotingPeriodTooShort);
-        let config = &mut ctx.accounts.config;
+        require!(
+            min_voting_period_secs >= VOTING_PERIOD_FLOOR_SECS,
+            GovError::VotingPeriodTooShort
+        );
+        require!(
+            min_participation_bps <= 10_000,
+            GovError::InvalidParticipationBps
+        );
+        require!(
+            quorum_bps > 0 && quorum_bps <= 10_000,
+            GovError::InvalidQuorumBps
+        );
+        require!(
+            material_edit_threshold_bps <= 10_000,
+            GovError::InvalidMaterialEditThreshold
+        );
+        require!(execution_delay_secs >= 0, GovError::InvalidExecutionDelay);
+        let config_info = ctx.accounts.config.to_account_info();
+        let mut config = {
+            let data = config_info.try_borrow_data()?;
+            GovConfig::from_account_data(&data).ok_or(GovError::InvalidGovConfig)?
+        };
         config.allow_early_finalize = allow_early_finalize;
         config.min_voting_period_secs = min_voting_period_secs;
+        config.min_backers_for_governance = min_backers_for_governance;
+        config.quorum_basis = quorum_basis;
+        config.min_participation_bps = min_participation_bps;
+        config.quorum_bps = quorum_bps;
+        config.material_edit_threshold_bps = material_edit_threshold_bps;
+        config.execution_delay_secs = execution_delay_secs;
+        config.min_vote_contribution = min_vote_contribution;
+        write_gov_config(
+            &config_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &config,
+        )?;
         msg!(
-            "Gov config updated: allow_early_finalize={} min_voting_period_secs={}",
+            "Gov config updated: allow_early_finalize={} min_voting_period_secs={} min_backers_for_governance={} quorum_basis={:?} min_participation_bps={} quorum_bps={} material_edit_threshold_bps={} execution_delay_secs={} min_vote_contribution={}",
             allow_early_finalize,
-            min_voting_period_secs
+            min_voting_period_secs,
+            min_backers_for_governance,
+            quorum_basis,
+            min_participation_bps,
+            quorum_bps,
+            material_edit_threshold_bps,
+            execution_delay_secs,
+            min_vote_contribution
+        );
+        Ok(())
+    }
+
+    /// Set the bond create_proposal takes from the creator (0 = no bond), the mint it is paid in, and the token
+    /// account forfeited bonds go to. Only upgrade authority. Configs created before these fields are grown in place.
+    pub fn set_proposal_bond(
+        ctx: Context<UpdateGovConfig>,
+        proposal_bond: u64,
+        bond_mint: Pubkey,
+        bond_treasury: Pubkey,
+    ) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+        let config_info = ctx.accounts.config.to_account_info();
+        let mut config = {
+            let data = config_info.try_borrow_data()?;
+            GovConfig::from_account_data(&data).ok_or(GovError::InvalidGovConfig)?
+        };
+        config.proposal_bond = proposal_bond;
+        config.bond_mint = bond_mint;
+        config.bond_treasury = bond_treasury;
+        write_gov_config(
+            &config_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &config,
+        )?;
+        msg!(
+            "Proposal bond set: amount={} mint={} treasury={}",
+            proposal_bond,
+            bond_mint,
+            bond_treasury
         );
         Ok(())
     }
 
+    /// When GovConfig sets a proposal_bond, the creator deposits it into the bond vault (a bond_mint token account
+    /// owned by the [b"bond_vault"] PDA) and the amount is recorded in the ProposalBond PDA; see settle_proposal_bond.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_proposal(
         ctx: Context<CreateProposal>,
         project_key: Pubkey,
@@ -228,25 +842,47 @@ pub mod governance {
         proof_uri: String,
         voting_period_secs: i64,
         attempt: u64,
+        title: String,
+        description_hash: [u8; 32],
     ) -> Result<()> {
-        let min_required = if let Some(config) =
-            read_gov_config_optional(ctx.program_id, ctx.remaining_accounts)?
-        {
-            config.min_voting_period_secs
-        } else {
-            MIN_VOTING_PERIOD_SECS
-        };
+        validate_proposal_metadata(&proof_uri, &title)?;
+        validate_proof_uri(&proof_uri)?;
+        let gov_config =
+            read_gov_config_optional(ctx.program_id, &[ctx.accounts.gov_config.to_account_info()])?;
         require!(
-            voting_period_secs >= min_required,
+            voting_period_secs >= min_voting_period(gov_config.as_ref()),
             GovError::VotingPeriodTooShort
         );
-        // 0..5 = milestone release; 255 = material edit proposal
+        // 0..MAX_MILESTONES = milestone release; 254 = RWA-holder proposal; 255 = material edit proposal
         require!(
-            milestone_index < 5 || milestone_index == 255,
+            is_milestone_proposal(milestone_index)
+                || milestone_index == RWA_PROPOSAL_INDEX
+                || milestone_index == 255,
             GovError::InvalidMilestoneIndex
         );
+        let weight_source = if milestone_index == RWA_PROPOSAL_INDEX {
+            require!(
+                ctx.accounts.project.status == project_escrow::ProjectStatus::Completed,
+                GovError::ProjectNotCompleted
+            );
+            WeightSource::RwaBalance
+        } else {
+            WeightSource::Backer
+        };
+        if is_milestone_proposal(milestone_index) {
+            let min_backers = gov_config
+                .as_ref()
+                .map(|c| c.min_backers_for_governance)
+                .unwrap_or(0);
+            require!(
+                ctx.accounts.project.backer_count >= min_backers,
+                GovError::NotEnoughBackers
+            );
+        }
 
+        let legacy_attempt = read_legacy_proposal_attempt(&ctx.accounts.legacy_proposal_attempt)?;
         let attempt_acc = &mut ctx.accounts.proposal_attempt;
+        attempt_acc.attempt = next_proposal_attempt(attempt_acc.attempt, legacy_attempt);
         require!(
             attempt_acc.attempt == attempt,
             GovError::InvalidProposalAttempt
@@ -261,6 +897,9 @@ pub mod governance {
         let end_ts = start_ts
             .checked_add(voting_period_secs)
             .ok_or(GovError::Overflow)?;
+        if is_milestone_proposal(milestone_index) {
+            check_milestone_voting_end(end_ts, &ctx.accounts.project)?;
+        }
 
         let p = &mut ctx.accounts.proposal;
         p.project = project_key;
@@ -268,10 +907,68 @@ pub mod governance {
         p.proof_uri = proof_uri;
         p.votes_for = 0;
         p.votes_against = 0;
+        p.votes_abstain = 0;
+        p.executable_at = 0;
+        p.weight_source = weight_source;
+        p.title = title;
+        p.description_hash = description_hash;
         p.status = ProposalStatus::Active;
         p.start_ts = start_ts;
         p.end_ts = end_ts;
         p.creator = ctx.accounts.artist.key();
+
+        let bond_amount = gov_config.as_ref().map_or(0, |c| c.proposal_bond);
+        if bond_amount > 0 {
+            let bond_mint = gov_config.as_ref().map(|c| c.bond_mint).unwrap_or_default();
+            let (
+                Some(proposal_bond),
+                Some(mint),
+                Some(artist_bond_account),
+                Some(bond_vault),
+                Some(token_program),
+            ) = (
+                ctx.accounts.proposal_bond.as_mut(),
+                ctx.accounts.bond_mint.as_ref(),
+                ctx.accounts.artist_bond_account.as_ref(),
+                ctx.accounts.bond_vault.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            )
+            else {
+                return err!(GovError::BondAccountsRequired);
+            };
+            let (vault_authority, _) =
+                Pubkey::find_program_address(&[b"bond_vault"], ctx.program_id);
+            require!(
+                mint.key() == bond_mint
+                    && artist_bond_account.mint == bond_mint
+                    && bond_vault.mint == bond_mint
+                    && bond_vault.owner == vault_authority,
+                GovError::InvalidBondAccount
+            );
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: artist_bond_account.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: bond_vault.to_account_info(),
+                        authority: ctx.accounts.artist.to_account_info(),
+                    },
+                ),
+                bond_amount,
+                mint.decimals,
+            )?;
+            proposal_bond.depositor = ctx.accounts.artist.key();
+            proposal_bond.mint = bond_mint;
+            proposal_bond.amount = bond_amount;
+            msg!("Proposal bond deposited: {}", bond_amount);
+        } else {
+            // An empty bond record would only block close_proposal.
+            require!(
+                ctx.accounts.proposal_bond.is_none(),
+                GovError::InvalidBondAccount
+            );
+        }
         msg!(
             "Proposal created: project {} milestone {} proof_uri_len {}",
             project_key,
@@ -281,7 +978,21 @@ pub mod governance {
         Ok(())
     }
 
-    pub fn cast_vote(ctx: Context<CastVote>, side: bool) -> Result<()> {
+    /// Backer votes with sqrt(amount). A delegate also carries the weight of backers who delegated to them:
+    /// remaining_accounts holds (VoteDelegation, delegator Backer, delegator Vote PDA) per delegator. The
+    /// delegator's Vote PDA is created here, so their weight counts once per proposal (whoever votes first).
+    /// A backer with an active delegation cannot vote themselves until they revoke it.
+    /// Calling again before end_ts changes the vote: the weight already recorded (including carried delegations)
+    /// moves to the new side, plus any newly passed delegations.
+    /// When the optional stake_account is passed, the first vote adds stake_vote_boost to the voter's own weight.
+    pub fn cast_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CastVote<'info>>,
+        side: VoteSide,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.delegation.data_is_empty(),
+            GovError::VotingPowerDelegated
+        );
         let proposal = &ctx.accounts.proposal;
         require!(
             proposal.status == ProposalStatus::Active,
@@ -293,9 +1004,126 @@ pub mod governance {
             GovError::VotingEnded
         );
 
-        let backer = &ctx.accounts.backer;
-        require!(backer.amount > 0, GovError::NoContribution);
-        let weight = sqrt_u64(backer.amount);
+        // A record created by the voter's former delegate is also counted in the delegate's Vote; moving it here
+        // would let the same weight move twice.
+        require!(
+            ctx.accounts.vote.delegate == Pubkey::default(),
+            GovError::DelegatedVoteLocked
+        );
+        let previous = if ctx.accounts.vote.proposal == Pubkey::default() {
+            None
+        } else {
+            Some((ctx.accounts.vote.side, ctx.accounts.vote.weight))
+        };
+        let delegation_accounts = ctx.remaining_accounts;
+        let (own_weight, boost) = match previous {
+            Some((_, recorded)) => (recorded, ctx.accounts.vote.boost),
+            None => {
+                let backer = &ctx.accounts.backer;
+                require!(backer.amount > 0, GovError::NoContribution);
+                let min_contribution = read_gov_config_optional(
+                    ctx.program_id,
+                    &[ctx.accounts.gov_config.to_account_info()],
+                )?
+                .map_or(0, |c| c.min_vote_contribution);
+                require!(
+                    backer.amount >= min_contribution,
+                    GovError::ContributionBelowVoteMinimum
+                );
+                let boost = match &ctx.accounts.stake_account {
+                    Some(stake) => {
+                        stake_vote_boost(stake, &ctx.accounts.voter.key(), proposal.end_ts)?
+                    }
+                    None => 0,
+                };
+                let weight = sqrt_u64(backer.amount)
+                    .checked_add(boost)
+                    .ok_or(GovError::Overflow)?;
+                (weight, boost)
+            }
+        };
+
+        let proposal_key = proposal.key();
+        let project_key = proposal.project;
+        let voter_key = ctx.accounts.voter.key();
+        delegated_vote_count(delegation_accounts.len())?;
+        let mut weight = own_weight;
+        for group in delegation_accounts.chunks(DELEGATION_ACCOUNTS_PER_DELEGATOR) {
+            let (delegation_info, backer_info, vote_info) = (&group[0], &group[1], &group[2]);
+            require!(
+                delegation_info.owner == ctx.program_id,
+                GovError::InvalidDelegation
+            );
+            let delegation = {
+                let data = delegation_info.try_borrow_data()?;
+                VoteDelegation::try_deserialize(&mut &data[..])?
+            };
+            let (delegation_pda, _) = Pubkey::find_program_address(
+                &[
+                    b"delegation",
+                    project_key.as_ref(),
+                    delegation.delegator.as_ref(),
+                ],
+                ctx.program_id,
+            );
+            require!(
+                delegation_info.key() == delegation_pda,
+                GovError::InvalidDelegation
+            );
+            require!(
+                backer_info.owner == &project_escrow::ID,
+                GovError::InvalidDelegation
+            );
+            let delegator_backer = {
+                let data = backer_info.try_borrow_data()?;
+                project_escrow::Backer::try_deserialize(&mut &data[..])?
+            };
+            let delegator_weight =
+                delegated_weight(&project_key, &voter_key, &delegation, &delegator_backer)?;
+
+            let (vote_pda, vote_bump) = Pubkey::find_program_address(
+                &[
+                    b"vote",
+                    proposal_key.as_ref(),
+                    delegation.delegator.as_ref(),
+                ],
+                ctx.program_id,
+            );
+            require!(vote_info.key() == vote_pda, GovError::InvalidDelegation);
+            require!(vote_info.data_is_empty(), GovError::DelegatedWeightUsed);
+            let seeds: &[&[u8]] = &[
+                b"vote",
+                proposal_key.as_ref(),
+                delegation.delegator.as_ref(),
+                &[vote_bump],
+            ];
+            anchor_lang::system_program::create_account(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::CreateAccount {
+                        from: ctx.accounts.voter.to_account_info(),
+                        to: vote_info.clone(),
+                    },
+                    &[seeds],
+                ),
+                Rent::get()?.minimum_balance(VOTE_SPACE),
+                VOTE_SPACE as u64,
+                ctx.program_id,
+            )?;
+            let mut data = vote_info.try_borrow_mut_data()?;
+            Vote {
+                proposal: proposal_key,
+                voter: delegation.delegator,
+                weight: delegator_weight,
+                side,
+                delegate: voter_key,
+                boost: 0,
+            }
+            .try_serialize(&mut &mut data[..])?;
+            weight = weight
+                .checked_add(delegator_weight)
+                .ok_or(GovError::Overflow)?;
+        }
 
         let vote = &mut ctx.accounts.vote;
         vote.proposal = proposal.key();
@@ -304,230 +1132,356 @@ pub mod governance {
         vote.side = side;
 
         let proposal_acc = &mut ctx.accounts.proposal;
-        if side {
-            proposal_acc.votes_for = proposal_acc
-                .votes_for
-                .checked_add(weight)
-                .ok_or(GovError::Overflow)?;
-        } else {
-            proposal_acc.votes_against = proposal_acc
-                .votes_against
-                .checked_add(weight)
-                .ok_or(GovError::Overflow)?;
-        }
-        let side_str = if side { "for" } else { "against" };
+        (
+            proposal_acc.votes_for,
+            proposal_acc.votes_against,
+            proposal_acc.votes_abstain,
+        ) = tally_vote(
+            (
+                proposal_acc.votes_for,
+                proposal_acc.votes_against,
+                proposal_acc.votes_abstain,
+            ),
+            previous,
+            side,
+            weight,
+        )?;
+        (
+            proposal_acc.boost_for,
+            proposal_acc.boost_against,
+            proposal_acc.boost_abstain,
+        ) = tally_vote(
+            (
+                proposal_acc.boost_for,
+                proposal_acc.boost_against,
+                proposal_acc.boost_abstain,
+            ),
+            previous.map(|(previous_side, _)| (previous_side, boost)),
+            side,
+            boost,
+        )?;
+        ctx.accounts.vote.boost = boost;
         msg!(
-            "Vote cast: proposal {} side {} weight {}",
+            "Vote {}: proposal {} side {:?} weight {} ({} newly delegated)",
+            if previous.is_some() {
+                "changed"
+            } else {
+                "cast"
+            },
             ctx.accounts.proposal.key(),
-            side_str,
-            weight
+            side,
+            weight,
+            weight - own_weight
         );
         Ok(())
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn finalize_proposal(
-        ctx: Context<FinalizeProposal>,
-        rights_type: rwa_token::RightsType,
-        revenue_split_bps: u16,
-        artist_split_bps: u16,
-        duration_secs: i64,
-        effective_from: i64,
-        terms_hash: [u8; 32],
-        terms_uri: String,
-        jurisdiction: String,
+    /// RWA holder votes on an RWA-holder proposal with their RWA token balance. The first vote moves the whole
+    /// balance into the [b"rwa_vote_vault"] vault (recorded in an RwaVoteLock) and counts it as the Vote weight, so
+    /// the same tokens cannot vote again from another wallet; withdraw_rwa_vote returns them once voting ends. A changed vote moves the recorded
+    /// weight. remaining_accounts: the transfer hook's extra accounts (its extra-account-metas PDA and the hook
+    /// program).
+    pub fn cast_rwa_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CastRwaVote<'info>>,
+        side: VoteSide,
     ) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
+        let proposal = &ctx.accounts.proposal;
         require!(
             proposal.status == ProposalStatus::Active,
             GovError::ProposalNotActive
         );
-        let clock = Clock::get()?;
-
-        let total_votes = proposal
-            .votes_for
-            .checked_add(proposal.votes_against)
-            .ok_or(GovError::Overflow)?;
-        let project = &ctx.accounts.project;
-        let total_escrowed = project.total_raised;
-        let quorum_raw = (total_escrowed as u128 * QUORUM_BPS as u128 / 10_000) as u64;
-        let quorum_votes = sqrt_u64(quorum_raw);
-        require!(total_votes >= quorum_votes, GovError::QuorumNotMet);
-
-        let (gov_config, total_vote_weight) = read_early_finalize_params(
-            ctx.program_id,
-            &ctx.accounts.project_escrow_program.key(),
-            &ctx.accounts.project.key(),
-            ctx.remaining_accounts,
-        )?;
-        let voting_ended = clock.unix_timestamp >= proposal.end_ts;
-        let outcome_decided = if let Some(tw) = total_vote_weight {
-            if tw == 0 {
-                false
-            } else {
-                let two_for = (proposal.votes_for as u128) * 2;
-                let two_against = (proposal.votes_against as u128) * 2;
-                let tw = tw as u128;
-                (two_for > tw) || (two_against >= tw)
-            }
+        require!(
+            Clock::get()?.unix_timestamp < proposal.end_ts,
+            GovError::VotingEnded
+        );
+        let previous = if ctx.accounts.vote.proposal == Pubkey::default() {
+            None
         } else {
-            false
+            Some((ctx.accounts.vote.side, ctx.accounts.vote.weight))
         };
-        let early_ok = gov_config
-            .as_ref()
-            .map(|c| c.allow_early_finalize)
-            .unwrap_or(false)
-            && total_vote_weight.is_some()
-            && outcome_decided;
-        require!(voting_ended || early_ok, GovError::VotingNotEnded);
-
-        let passed = proposal.votes_for > proposal.votes_against;
-        proposal.status = if passed {
-            ProposalStatus::Passed
-        } else {
-            ProposalStatus::Rejected
+        let weight = match previous {
+            Some((_, recorded)) => recorded,
+            None => {
+                let balance = ctx.accounts.holder_rwa_account.amount;
+                require!(balance > 0, GovError::NoRwaBalance);
+                transfer_rwa(
+                    &ctx.accounts.token_program.to_account_info(),
+                    &ctx.accounts.holder_rwa_account.to_account_info(),
+                    &ctx.accounts.rwa_mint.to_account_info(),
+                    &ctx.accounts.rwa_vote_vault.to_account_info(),
+                    &ctx.accounts.voter.to_account_info(),
+                    ctx.remaining_accounts,
+                    balance,
+                    ctx.accounts.rwa_mint.decimals,
+                    &[],
+                )?;
+                let lock = &mut ctx.accounts.rwa_vote_lock;
+                lock.proposal = ctx.accounts.proposal.key();
+                lock.voter = ctx.accounts.voter.key();
+                lock.mint = ctx.accounts.rwa_mint.key();
+                lock.amount = balance;
+                balance
+            }
         };
 
-        if passed {
-            let bump_seed = ctx.bumps.release_authority;
-            let seeds: &[&[u8]] = &[b"release_authority", &[bump_seed]];
-            let signer_seeds = &[seeds];
-            let cpi_ctx = CpiContext::new_with_signer(
-                ctx.accounts.project_escrow_program.to_account_info(),
-                project_escrow::cpi::accounts::ReleaseMilestone {
-                    governance_authority: ctx.accounts.release_authority.to_account_info(),
-                    config: ctx.accounts.escrow_config.to_account_info(),
-                    project: ctx.accounts.project.to_account_info(),
-                    escrow: ctx.accounts.escrow.to_account_info(),
-                    escrow_authority: ctx.accounts.escrow_authority.to_account_info(),
-                    artist_token_account: ctx.accounts.artist_token_account.to_account_info(),
-                    taste_mint: ctx.accounts.taste_mint.to_account_info(),
-                    token_program: ctx.accounts.token_program.to_account_info(),
-                },
-                signer_seeds,
-            );
-            project_escrow::cpi::release_milestone(cpi_ctx)?;
-
-            // Re-read project after CPI mutated it (current_milestone, status).
-            ctx.accounts.project.reload()?;
+        let vote = &mut ctx.accounts.vote;
+        vote.proposal = ctx.accounts.proposal.key();
+        vote.voter = ctx.accounts.voter.key();
+        vote.weight = weight;
+        vote.side = side;
 
-            // If project just completed (last milestone released), auto-init RWA mint (idempotent).
-            if ctx.accounts.project.current_milestone as usize
-                >= project_escrow::effective_milestone_count(
-                    &ctx.accounts.project.milestone_percentages,
-                )
-                && ctx.accounts.rwa_state.lamports() == 0
-            {
-                let rwa_cpi_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.rwa_token_program.to_account_info(),
-                    rwa_token::cpi::accounts::InitializeRwaMintByGovernance {
-                        payer: ctx.accounts.payer.to_account_info(),
-                        release_authority: ctx.accounts.release_authority.to_account_info(),
-                        config: ctx.accounts.escrow_config.to_account_info(),
-                        project: ctx.accounts.project.to_account_info(),
-                        rwa_state: ctx.accounts.rwa_state.to_account_info(),
-                        rwa_config: ctx.accounts.rwa_config.to_account_info(),
-                        rwa_mint: ctx.accounts.rwa_mint.to_account_info(),
-                        rwa_mint_authority: ctx.accounts.rwa_mint_authority.to_account_info(),
-                        rwa_transfer_hook_program: ctx
-                            .accounts
-                            .rwa_transfer_hook_program
-                            .to_account_info(),
-                        extra_account_metas: ctx.accounts.rwa_extra_account_metas.to_account_info(),
-                        token_program: ctx.accounts.token_program.to_account_info(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                    signer_seeds,
-                );
-                const RWA_TOTAL_SUPPLY: u64 = 1_000_000 * 1_000_000;
-                rwa_token::cpi::initialize_rwa_mint_by_governance(rwa_cpi_ctx, RWA_TOTAL_SUPPLY)?;
-            }
+        let proposal_acc = &mut ctx.accounts.proposal;
+        (
+            proposal_acc.votes_for,
+            proposal_acc.votes_against,
+            proposal_acc.votes_abstain,
+        ) = tally_vote(
+            (
+                proposal_acc.votes_for,
+                proposal_acc.votes_against,
+                proposal_acc.votes_abstain,
+            ),
+            previous,
+            side,
+            weight,
+        )?;
+        msg!(
+            "RWA vote {}: proposal {} side {:?} weight {}",
+            if previous.is_some() {
+                "changed"
+            } else {
+                "cast"
+            },
+            ctx.accounts.proposal.key(),
+            side,
+            weight
+        );
+        Ok(())
+    }
 
-            // If RWA mint was just created (or exists) and metadata not yet set, init Metaplex metadata.
-            let rwa_state_lamports = ctx.accounts.rwa_state.lamports();
-            let metadata_guard_uninit =
-                ctx.accounts.rwa_metadata_guard.owner == &anchor_lang::system_program::ID;
-            if rwa_state_lamports > 0 && metadata_guard_uninit {
-                let meta_cpi_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.rwa_token_program.to_account_info(),
-                    rwa_token::cpi::accounts::InitializeRwaMetadataByGovernance {
-                        payer: ctx.accounts.payer.to_account_info(),
-                        release_authority: ctx.accounts.release_authority.to_account_info(),
-                        config: ctx.accounts.escrow_config.to_account_info(),
-                        project: ctx.accounts.project.to_account_info(),
-                        rwa_state: ctx.accounts.rwa_state.to_account_info(),
-                        rwa_mint: ctx.accounts.rwa_mint.to_account_info(),
-                        rwa_mint_authority: ctx.accounts.rwa_mint_authority.to_account_info(),
-                        metadata_guard: ctx.accounts.rwa_metadata_guard.to_account_info(),
-                        metadata: ctx.accounts.rwa_metadata.to_account_info(),
-                        update_authority: ctx.accounts.artist.to_account_info(),
-                        token_metadata_program: ctx
-                            .accounts
-                            .token_metadata_program
-                            .to_account_info(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                        sysvar_instructions: ctx.accounts.sysvar_instructions.to_account_info(),
-                        token_program: ctx.accounts.token_program.to_account_info(),
-                    },
-                    signer_seeds,
-                );
-                rwa_token::cpi::initialize_rwa_metadata_by_governance(meta_cpi_ctx)?;
-            }
+    /// Returns the RWA tokens cast_rwa_vote locked for the voter and closes the RwaVoteLock (rent back to them).
+    /// Allowed once the proposal's voting period has ended, it is no longer Active, or it has been closed; the
+    /// tallies are unaffected. remaining_accounts: the transfer hook's extra accounts, as for cast_rwa_vote.
+    pub fn withdraw_rwa_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawRwaVote<'info>>,
+    ) -> Result<()> {
+        let proposal_info = ctx.accounts.proposal.to_account_info();
+        if !proposal_info.data_is_empty() {
+            require!(proposal_info.owner == &crate::ID, GovError::InvalidProposal);
+            let data = proposal_info.try_borrow_data()?;
+            let proposal = Proposal::try_deserialize(&mut &data[..])?;
+            require!(
+                proposal.status != ProposalStatus::Active
+                    || Clock::get()?.unix_timestamp >= proposal.end_ts,
+                GovError::RwaVoteLocked
+            );
+        }
+        let amount = ctx.accounts.rwa_vote_lock.amount;
+        let bump_seed = ctx.bumps.rwa_vote_vault_authority;
+        let seeds: &[&[u8]] = &[b"rwa_vote_vault", &[bump_seed]];
+        transfer_rwa(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.rwa_vote_vault.to_account_info(),
+            &ctx.accounts.rwa_mint.to_account_info(),
+            &ctx.accounts.holder_rwa_account.to_account_info(),
+            &ctx.accounts.rwa_vote_vault_authority.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.rwa_mint.decimals,
+            &[seeds],
+        )?;
+        msg!(
+            "RWA vote withdrawn: proposal {} voter {} amount {}",
+            ctx.accounts.rwa_vote_lock.proposal,
+            ctx.accounts.voter.key(),
+            amount
+        );
+        Ok(())
+    }
 
-            let last_milestone = ctx.accounts.project.current_milestone as usize
-                >= project_escrow::effective_milestone_count(
-                    &ctx.accounts.project.milestone_percentages,
-                );
+    /// Backer delegates their voting power in a project to another backer of the same project. One delegation
+    /// per backer; revoke it to vote directly or to pick another delegate.
+    pub fn delegate_vote(ctx: Context<DelegateVote>) -> Result<()> {
+        require!(ctx.accounts.backer.amount > 0, GovError::NoContribution);
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.project = ctx.accounts.backer.project;
+        delegation.delegator = ctx.accounts.delegator.key();
+        delegation.delegate = ctx.accounts.delegate_backer.wallet;
+        msg!(
+            "Vote delegated: project {} {} -> {}",
+            delegation.project,
+            delegation.delegator,
+            delegation.delegate
+        );
+        Ok(())
+    }
 
-            if last_milestone && ctx.accounts.rwa_rights.lamports() == 0 {
-                let rights_cpi = CpiContext::new_with_signer(
-                    ctx.accounts.rwa_token_program.to_account_info(),
-                    rwa_token::cpi::accounts::InitializeRwaRightsByGovernance {
-                        payer: ctx.accounts.payer.to_account_info(),
-                        release_authority: ctx.accounts.release_authority.to_account_info(),
-                        config: ctx.accounts.escrow_config.to_account_info(),
-                        project: ctx.accounts.project.to_account_info(),
-                        rwa_state: ctx.accounts.rwa_state.to_account_info(),
-                        rwa_rights: ctx.accounts.rwa_rights.to_account_info(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                    signer_seeds,
-                );
-                rwa_token::cpi::initialize_rwa_rights_by_governance(
-                    rights_cpi,
+    /// Delegator closes their VoteDelegation (rent back to them). Weight a delegate already cast on their behalf
+    /// stays counted for those proposals.
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        msg!(
+            "Vote delegation revoked: project {} delegator {}",
+            ctx.accounts.delegation.project,
+            ctx.accounts.delegation.delegator
+        );
+        Ok(())
+    }
+
+    /// Permissionless: removes the vote of a backer who has since left the project via refund or opt_out_refund.
+    /// Those zero Backer.amount and shrink ProjectVoteWeight, so a vote left in the tallies would be compared to the
+    /// smaller total_vote_weight on early finalize. Clients bundle this with opt_out_refund for each active
+    /// proposal; anyone may call it afterwards. The whole recorded weight goes, including weight the voter carried
+    /// for delegators. Votes a delegate cast on a delegator's behalf (delegation still open) stay with the delegate;
+    /// once the delegation is revoked, rescinding such a record also takes its weight out of the delegate's Vote
+    /// (passed as delegate_vote) and off the side that Vote currently counts it on.
+    pub fn rescind_refunded_vote(ctx: Context<RescindRefundedVote>) -> Result<()> {
+        require!(
+            ctx.accounts.delegation.data_is_empty(),
+            GovError::VotingPowerDelegated
+        );
+        require!(
+            ctx.accounts.proposal.status == ProposalStatus::Active,
+            GovError::ProposalNotActive
+        );
+        require!(ctx.accounts.backer.amount == 0, GovError::BackerNotRefunded);
+        let vote = &mut ctx.accounts.vote;
+        require!(vote.weight > 0, GovError::VoteAlreadyRescinded);
+        let weight = vote.weight;
+        let side = if vote.delegate == Pubkey::default() {
+            vote.side
+        } else {
+            let delegate_vote = ctx
+                .accounts
+                .delegate_vote
+                .as_mut()
+                .ok_or(GovError::DelegateVoteRequired)?;
+            delegate_vote.weight = delegate_vote
+                .weight
+                .checked_sub(weight)
+                .ok_or(GovError::Overflow)?;
+            delegate_vote.side
+        };
+        vote.weight = 0;
+
+        let proposal = &mut ctx.accounts.proposal;
+        (
+            proposal.votes_for,
+            proposal.votes_against,
+            proposal.votes_abstain,
+        ) = tally_vote(
+            (
+                proposal.votes_for,
+                proposal.votes_against,
+                proposal.votes_abstain,
+            ),
+            Some((side, weight)),
+            side,
+            0,
+        )?;
+        msg!(
+            "Vote rescinded: proposal {} voter {} side {:?} weight {}",
+            proposal.key(),
+            vote.voter,
+            side,
+            weight
+        );
+        Ok(())
+    }
+
+    /// remaining_accounts: optional gov config / vote weight for early finalize (see read_early_finalize_params),
+    /// then, if the project has a payout split, one $TASTE token account per split entry in entry order. Those
+    /// recipient accounts are forwarded to release_milestone.
+    #[allow(clippy::too_many_arguments)]
+    pub fn finalize_proposal<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, FinalizeProposal<'info>>,
+        rights_type: rwa_token::RightsType,
+        revenue_split_bps: u16,
+        artist_split_bps: u16,
+        duration_secs: i64,
+        effective_from: i64,
+        terms_hash: [u8; 32],
+        terms_uri: String,
+        jurisdiction: String,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.status == ProposalStatus::Active,
+            GovError::ProposalNotActive
+        );
+        require!(
+            proposal.weight_source == WeightSource::Backer,
+            GovError::WrongWeightSource
+        );
+        // A passed slash vote must cancel the project, never release a milestone.
+        require!(
+            proposal.milestone_index != SLASH_PROPOSAL_INDEX,
+            GovError::InvalidMilestoneIndex
+        );
+        let clock = Clock::get()?;
+
+        let total_votes = proposal.total_votes()?;
+        let project = &ctx.accounts.project;
+        let config =
+            read_gov_config_optional(ctx.program_id, &[ctx.accounts.gov_config.to_account_info()])?;
+        let (quorum_basis, quorum_bps) = config
+            .as_ref()
+            .map(|c| (c.quorum_basis, c.effective_quorum_bps()))
+            .unwrap_or((QuorumBasis::default(), QUORUM_BPS));
+        let execution_delay_secs = config.as_ref().map_or(0, |c| c.execution_delay_secs);
+        require!(
+            total_votes
+                >= quorum_votes(project.total_raised, project.goal, quorum_basis, quorum_bps),
+            GovError::QuorumNotMet
+        );
+
+        let (gov_config, total_vote_weight) = read_early_finalize_params(
+            ctx.program_id,
+            &ctx.accounts.project_escrow_program.key(),
+            &ctx.accounts.project.key(),
+            ctx.remaining_accounts,
+        )?;
+        let voting_ended = clock.unix_timestamp >= proposal.end_ts;
+        let (unboosted_for, unboosted_against, unboosted_abstain) = proposal.unboosted_votes();
+        let early_ok = early_finalize_allowed(
+            unboosted_for,
+            unboosted_against,
+            unboosted_abstain,
+            total_vote_weight,
+            gov_config.as_ref(),
+        );
+        require!(voting_ended || early_ok, GovError::VotingNotEnded);
+
+        let passed = proposal.votes_for > proposal.votes_against;
+        if passed {
+            // Fail before marking Passed rather than leave a passed proposal whose release cannot succeed.
+            require_escrow_covers_milestone(ctx.accounts.escrow.amount, &ctx.accounts.project)?;
+        }
+        proposal.status = if passed {
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Rejected
+        };
+
+        if passed {
+            if execution_delay_secs > 0 {
+                proposal.executable_at = clock
+                    .unix_timestamp
+                    .checked_add(execution_delay_secs)
+                    .ok_or(GovError::Overflow)?;
+                msg!("Execution queued until {}", proposal.executable_at);
+            } else {
+                release_passed_milestone(
+                    &mut ctx,
                     rights_type,
                     revenue_split_bps,
                     artist_split_bps,
                     duration_secs,
                     effective_from,
                     terms_hash,
-                    terms_uri.clone(),
-                    jurisdiction.clone(),
+                    terms_uri,
+                    jurisdiction,
                 )?;
             }
-
-            if last_milestone && ctx.accounts.rev_config.lamports() == 0 {
-                let rev_cpi = CpiContext::new(
-                    ctx.accounts.revenue_distribution_program.to_account_info(),
-                    revenue_distribution::cpi::accounts::InitializeRevenueConfig {
-                        payer: ctx.accounts.payer.to_account_info(),
-                        project: ctx.accounts.project.to_account_info(),
-                        rwa_state: ctx.accounts.rwa_state.to_account_info(),
-                        rev_config: ctx.accounts.rev_config.to_account_info(),
-                        rwa_mint: ctx.accounts.rwa_mint.to_account_info(),
-                        rev_vault_authority: ctx.accounts.rev_vault_authority.to_account_info(),
-                        rev_vault: ctx.accounts.rev_vault.to_account_info(),
-                        taste_mint: ctx.accounts.taste_mint.to_account_info(),
-                        token_program: ctx.accounts.token_program.to_account_info(),
-                        associated_token_program: ctx
-                            .accounts
-                            .associated_token_program
-                            .to_account_info(),
-                        system_program: ctx.accounts.system_program.to_account_info(),
-                    },
-                );
-                revenue_distribution::cpi::initialize_revenue_config(rev_cpi)?;
-            }
         }
         let status_str = if passed { "Passed" } else { "Rejected" };
         msg!(
@@ -538,6 +1492,172 @@ pub mod governance {
         Ok(())
     }
 
+    /// Finalize an RWA-holder proposal after its voting period. Quorum is measured against the RWA mint supply;
+    /// the outcome is recorded on the proposal for revenue decisions and moves no funds.
+    pub fn finalize_rwa_proposal(ctx: Context<FinalizeRwaProposal>) -> Result<()> {
+        let quorum_bps =
+            read_gov_config_optional(ctx.program_id, &[ctx.accounts.gov_config.to_account_info()])?
+                .map_or(QUORUM_BPS, |c| c.effective_quorum_bps());
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.status == ProposalStatus::Active,
+            GovError::ProposalNotActive
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= proposal.end_ts,
+            GovError::VotingNotEnded
+        );
+        require!(
+            proposal.total_votes()? >= rwa_quorum_votes(ctx.accounts.rwa_mint.supply, quorum_bps),
+            GovError::QuorumNotMet
+        );
+        let passed = proposal.votes_for > proposal.votes_against;
+        proposal.status = if passed {
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Rejected
+        };
+        msg!(
+            "RWA proposal finalized: {} status {}",
+            ctx.accounts.proposal.key(),
+            if passed { "Passed" } else { "Rejected" }
+        );
+        Ok(())
+    }
+
+    /// Release the milestone of a proposal that passed under an execution delay, once executable_at is reached.
+    /// Takes the same accounts and RWA arguments as finalize_proposal.
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_proposal<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, FinalizeProposal<'info>>,
+        rights_type: rwa_token::RightsType,
+        revenue_split_bps: u16,
+        artist_split_bps: u16,
+        duration_secs: i64,
+        effective_from: i64,
+        terms_hash: [u8; 32],
+        terms_uri: String,
+        jurisdiction: String,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.awaiting_execution(),
+            GovError::ProposalNotAwaitingExecution
+        );
+        require!(
+            proposal.project == ctx.accounts.project.key(),
+            GovError::InvalidProposal
+        );
+        // A stale proposal must not release a later milestone than the one it voted on.
+        require!(
+            proposal.milestone_index == ctx.accounts.project.current_milestone,
+            GovError::InvalidMilestoneIndex
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= proposal.executable_at,
+            GovError::ExecutionDelayNotElapsed
+        );
+        proposal.executable_at = 0;
+        release_passed_milestone(
+            &mut ctx,
+            rights_type,
+            revenue_split_bps,
+            artist_split_bps,
+            duration_secs,
+            effective_from,
+            terms_hash,
+            terms_uri,
+            jurisdiction,
+        )?;
+        msg!("Proposal executed: {}", ctx.accounts.proposal.key());
+        Ok(())
+    }
+
+    /// Retry the release of a Passed milestone proposal whose release never landed. Idempotent: once the
+    /// release succeeds the project moves past proposal.milestone_index and further calls fail.
+    /// Takes the same accounts and RWA arguments as finalize_proposal.
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_passed_proposal<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, FinalizeProposal<'info>>,
+        rights_type: rwa_token::RightsType,
+        revenue_split_bps: u16,
+        artist_split_bps: u16,
+        duration_secs: i64,
+        effective_from: i64,
+        terms_hash: [u8; 32],
+        terms_uri: String,
+        jurisdiction: String,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(
+            proposal.project == ctx.accounts.project.key(),
+            GovError::InvalidProposal
+        );
+        require!(
+            !proposal.awaiting_execution(),
+            GovError::ProposalAwaitingExecution
+        );
+        require!(
+            proposal.release_pending(ctx.accounts.project.current_milestone),
+            GovError::NoPendingRelease
+        );
+        release_passed_milestone(
+            &mut ctx,
+            rights_type,
+            revenue_split_bps,
+            artist_split_bps,
+            duration_secs,
+            effective_from,
+            terms_hash,
+            terms_uri,
+            jurisdiction,
+        )?;
+        msg!("Proposal release retried: {}", ctx.accounts.proposal.key());
+        Ok(())
+    }
+
+    /// Permissionless: grow a proposal created before the trailing fields (votes_abstain onward) to the current
+    /// layout; new bytes read as 0, i.e. an empty title, zero description hash and no stake boosts.
+    pub fn migrate_proposal(ctx: Context<MigrateProposal>) -> Result<()> {
+        let info = ctx.accounts.proposal.to_account_info();
+        {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == *Proposal::DISCRIMINATOR,
+                GovError::InvalidProposal
+            );
+        }
+        rwa_token::grow_account(
+            &info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            PROPOSAL_SPACE,
+        )?;
+        msg!("Proposal migrated: {}", info.key());
+        Ok(())
+    }
+
+    /// Permissionless: grow a vote created before Vote.delegate / Vote.boost existed to the current layout. The
+    /// new fields read as default, i.e. the voter's own, unboosted vote.
+    pub fn migrate_vote(ctx: Context<MigrateVote>) -> Result<()> {
+        let info = ctx.accounts.vote.to_account_info();
+        {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= VOTE_BASE_LEN && data[..8] == *Vote::DISCRIMINATOR,
+                GovError::InvalidVoteAccount
+            );
+        }
+        rwa_token::grow_account(
+            &info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            VOTE_SPACE,
+        )?;
+        msg!("Vote migrated: {}", info.key());
+        Ok(())
+    }
+
     pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
         let proposal = &mut ctx.accounts.proposal;
         require!(
@@ -553,6 +1673,152 @@ pub mod governance {
         Ok(())
     }
 
+    /// Close a Vote record once its proposal is no longer Active (or has been closed), returning rent to the voter.
+    pub fn close_vote(ctx: Context<CloseVote>) -> Result<()> {
+        let proposal_info = ctx.accounts.proposal.to_account_info();
+        if !proposal_info.data_is_empty() {
+            require!(proposal_info.owner == &crate::ID, GovError::InvalidProposal);
+            let data = proposal_info.try_borrow_data()?;
+            let proposal = Proposal::try_deserialize(&mut &data[..])?;
+            require!(
+                proposal.status != ProposalStatus::Active,
+                GovError::ProposalStillActive
+            );
+        }
+        msg!(
+            "Vote closed: proposal {} voter {}",
+            ctx.accounts.vote.proposal,
+            ctx.accounts.voter.key()
+        );
+        Ok(())
+    }
+
+    /// Creator closes a finalized or cancelled proposal, reclaiming its rent. Any proposal bond must be settled first.
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        require!(
+            ctx.accounts.proposal.status != ProposalStatus::Active,
+            GovError::ProposalStillActive
+        );
+        require!(
+            !ctx.accounts.proposal.awaiting_execution(),
+            GovError::ProposalAwaitingExecution
+        );
+        require!(
+            ctx.accounts.proposal_bond.data_is_empty(),
+            GovError::BondNotSettled
+        );
+        msg!("Proposal closed: {}", ctx.accounts.proposal.key());
+        Ok(())
+    }
+
+    /// Permissionless: pay out a proposal bond per bond_outcome, refunding the depositor or sending it to the
+    /// GovConfig bond_treasury, and close the ProposalBond PDA to the depositor. Quorum for an unfinalized proposal
+    /// is measured as at finalize; RWA-holder proposals need rwa_mint for the supply.
+    pub fn settle_proposal_bond(ctx: Context<SettleProposalBond>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let project = &ctx.accounts.project;
+        let config =
+            read_gov_config_optional(ctx.program_id, &[ctx.accounts.gov_config.to_account_info()])?;
+        let (quorum_basis, quorum_bps) = config
+            .as_ref()
+            .map(|c| (c.quorum_basis, c.effective_quorum_bps()))
+            .unwrap_or((QuorumBasis::default(), QUORUM_BPS));
+        let quorum = match proposal.weight_source {
+            WeightSource::Backer => {
+                quorum_votes(project.total_raised, project.goal, quorum_basis, quorum_bps)
+            }
+            WeightSource::RwaBalance => {
+                let rwa_mint = ctx
+                    .accounts
+                    .rwa_mint
+                    .as_ref()
+                    .ok_or(GovError::InvalidRwaAccount)?;
+                require!(
+                    rwa_mint.key() == rwa_mint_address(&project.key()),
+                    GovError::InvalidRwaAccount
+                );
+                rwa_quorum_votes(rwa_mint.supply, quorum_bps)
+            }
+        };
+        let outcome = bond_outcome(
+            proposal.status,
+            Clock::get()?.unix_timestamp >= proposal.end_ts,
+            proposal.total_votes()?,
+            quorum,
+        )?;
+
+        let destination = &ctx.accounts.destination;
+        match outcome {
+            BondOutcome::Refund => require!(
+                destination.owner == ctx.accounts.proposal_bond.depositor,
+                GovError::InvalidBondAccount
+            ),
+            BondOutcome::Forfeit => require!(
+                config
+                    .as_ref()
+                    .is_some_and(|c| c.bond_treasury == destination.key()),
+                GovError::InvalidBondAccount
+            ),
+        }
+
+        let amount = ctx.accounts.proposal_bond.amount;
+        let bump_seed = ctx.bumps.bond_vault_authority;
+        let seeds: &[&[u8]] = &[b"bond_vault", &[bump_seed]];
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.bond_vault.to_account_info(),
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: ctx.accounts.bond_vault_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            ctx.accounts.bond_mint.decimals,
+        )?;
+        msg!(
+            "Proposal bond settled: proposal {} amount {} outcome {:?}",
+            ctx.accounts.proposal.key(),
+            amount,
+            outcome
+        );
+        Ok(())
+    }
+
+    /// Emergency veto: the program upgrade authority cancels any Active proposal, or a Passed one still waiting
+    /// out its execution delay, regardless of creator.
+    pub fn veto_proposal(ctx: Context<VetoProposal>, reason: String) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+        require!(
+            reason.len() <= MAX_VETO_REASON_LEN,
+            GovError::VetoReasonTooLong
+        );
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.status == ProposalStatus::Active || proposal.awaiting_execution(),
+            GovError::ProposalNotActive
+        );
+        proposal.status = ProposalStatus::Cancelled;
+        proposal.executable_at = 0;
+        msg!(
+            "Proposal vetoed: {} reason: {}",
+            ctx.accounts.proposal.key(),
+            reason
+        );
+        Ok(())
+    }
+
     /// Finalize a material-edit proposal (milestone_index == 255). On pass, CPIs project_escrow::apply_material_edit.
     pub fn finalize_material_edit_proposal(
         ctx: Context<FinalizeMaterialEditProposal>,
@@ -573,15 +1839,19 @@ pub mod governance {
         );
         let clock = Clock::get()?;
 
-        let total_votes = proposal
-            .votes_for
-            .checked_add(proposal.votes_against)
-            .ok_or(GovError::Overflow)?;
+        let total_votes = proposal.total_votes()?;
         let project = &ctx.accounts.project;
-        let total_escrowed = project.total_raised;
-        let quorum_raw = (total_escrowed as u128 * QUORUM_BPS as u128 / 10_000) as u64;
-        let quorum_votes = sqrt_u64(quorum_raw);
-        require!(total_votes >= quorum_votes, GovError::QuorumNotMet);
+        let config =
+            read_gov_config_optional(ctx.program_id, &[ctx.accounts.gov_config.to_account_info()])?;
+        let (quorum_basis, quorum_bps) = config
+            .as_ref()
+            .map(|c| (c.quorum_basis, c.effective_quorum_bps()))
+            .unwrap_or((QuorumBasis::default(), QUORUM_BPS));
+        require!(
+            total_votes
+                >= quorum_votes(project.total_raised, project.goal, quorum_basis, quorum_bps),
+            GovError::QuorumNotMet
+        );
 
         let (gov_config, total_vote_weight) = read_early_finalize_params(
             ctx.program_id,
@@ -590,27 +1860,24 @@ pub mod governance {
             ctx.remaining_accounts,
         )?;
         let voting_ended = clock.unix_timestamp >= proposal.end_ts;
-        let outcome_decided = if let Some(tw) = total_vote_weight {
-            if tw == 0 {
-                false
-            } else {
-                let two_for = (proposal.votes_for as u128) * 2;
-                let two_against = (proposal.votes_against as u128) * 2;
-                let tw = tw as u128;
-                (two_for > tw) || (two_against >= tw)
-            }
-        } else {
-            false
-        };
-        let early_ok = gov_config
-            .as_ref()
-            .map(|c| c.allow_early_finalize)
-            .unwrap_or(false)
-            && total_vote_weight.is_some()
-            && outcome_decided;
+        let (unboosted_for, unboosted_against, unboosted_abstain) = proposal.unboosted_votes();
+        let early_ok = early_finalize_allowed(
+            unboosted_for,
+            unboosted_against,
+            unboosted_abstain,
+            total_vote_weight,
+            gov_config.as_ref(),
+        );
         require!(voting_ended || early_ok, GovError::VotingNotEnded);
 
-        let passed = proposal.votes_for > proposal.votes_against;
+        let passed = material_edit_passed(
+            proposal.votes_for,
+            proposal.votes_against,
+            config
+                .as_ref()
+                .map(|c| c.material_edit_threshold_bps)
+                .unwrap_or(0),
+        );
         proposal.status = if passed {
             ProposalStatus::Passed
         } else {
@@ -649,6 +1916,140 @@ pub mod governance {
         );
         Ok(())
     }
+
+    /// Any backer with a contribution proposes slashing an Active project whose artist stopped delivering.
+    /// Backers vote with cast_vote; finalize_slash_proposal applies the outcome.
+    pub fn create_slash_proposal(
+        ctx: Context<CreateSlashProposal>,
+        project_key: Pubkey,
+        proof_uri: String,
+        voting_period_secs: i64,
+        attempt: u64,
+        title: String,
+        description_hash: [u8; 32],
+    ) -> Result<()> {
+        validate_proposal_metadata(&proof_uri, &title)?;
+        let gov_config =
+            read_gov_config_optional(ctx.program_id, &[ctx.accounts.gov_config.to_account_info()])?;
+        require!(
+            voting_period_secs >= min_voting_period(gov_config.as_ref()),
+            GovError::VotingPeriodTooShort
+        );
+        require!(
+            ctx.accounts.project.status == project_escrow::ProjectStatus::Active,
+            GovError::ProjectNotActive
+        );
+
+        let attempt_acc = &mut ctx.accounts.proposal_attempt;
+        require!(
+            attempt_acc.attempt == attempt,
+            GovError::InvalidProposalAttempt
+        );
+        attempt_acc.attempt = attempt_acc
+            .attempt
+            .checked_add(1)
+            .ok_or(GovError::Overflow)?;
+
+        let start_ts = Clock::get()?.unix_timestamp;
+        let end_ts = start_ts
+            .checked_add(voting_period_secs)
+            .ok_or(GovError::Overflow)?;
+
+        let p = &mut ctx.accounts.proposal;
+        p.project = project_key;
+        p.milestone_index = SLASH_PROPOSAL_INDEX;
+        p.proof_uri = proof_uri;
+        p.votes_for = 0;
+        p.votes_against = 0;
+        p.votes_abstain = 0;
+        p.executable_at = 0;
+        p.weight_source = WeightSource::Backer;
+        p.title = title;
+        p.description_hash = description_hash;
+        p.status = ProposalStatus::Active;
+        p.start_ts = start_ts;
+        p.end_ts = end_ts;
+        p.creator = ctx.accounts.proposer.key();
+        msg!("Slash proposal created: project {}", project_key);
+        Ok(())
+    }
+
+    /// Finalize a slash proposal (milestone_index == SLASH_PROPOSAL_INDEX). Same quorum and early-finalize rules
+    /// as finalize_proposal; on pass, CPIs project_escrow::slash_project, which cancels the project and opens
+    /// pro-rata refunds of the remaining escrow.
+    pub fn finalize_slash_proposal(ctx: Context<FinalizeSlashProposal>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.status == ProposalStatus::Active,
+            GovError::ProposalNotActive
+        );
+        let clock = Clock::get()?;
+
+        let total_votes = proposal.total_votes()?;
+        let project = &ctx.accounts.project;
+        let config =
+            read_gov_config_optional(ctx.program_id, &[ctx.accounts.gov_config.to_account_info()])?;
+        let (quorum_basis, quorum_bps) = config
+            .as_ref()
+            .map(|c| (c.quorum_basis, c.effective_quorum_bps()))
+            .unwrap_or((QuorumBasis::default(), QUORUM_BPS));
+        require!(
+            total_votes
+                >= quorum_votes(project.total_raised, project.goal, quorum_basis, quorum_bps),
+            GovError::QuorumNotMet
+        );
+
+        let (gov_config, total_vote_weight) = read_early_finalize_params(
+            ctx.program_id,
+            &ctx.accounts.project_escrow_program.key(),
+            &ctx.accounts.project.key(),
+            ctx.remaining_accounts,
+        )?;
+        let voting_ended = clock.unix_timestamp >= proposal.end_ts;
+        let (unboosted_for, unboosted_against, unboosted_abstain) = proposal.unboosted_votes();
+        let early_ok = early_finalize_allowed(
+            unboosted_for,
+            unboosted_against,
+            unboosted_abstain,
+            total_vote_weight,
+            gov_config.as_ref(),
+        );
+        require!(voting_ended || early_ok, GovError::VotingNotEnded);
+
+        let passed = proposal.votes_for > proposal.votes_against;
+        proposal.status = if passed {
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Rejected
+        };
+
+        if passed {
+            let bump_seed = ctx.bumps.release_authority;
+            let seeds: &[&[u8]] = &[b"release_authority", &[bump_seed]];
+            let signer_seeds = &[seeds];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.project_escrow_program.to_account_info(),
+                project_escrow::cpi::accounts::SlashProject {
+                    governance_authority: ctx.accounts.release_authority.to_account_info(),
+                    config: ctx.accounts.escrow_config.to_account_info(),
+                    project: ctx.accounts.project.to_account_info(),
+                    escrow: ctx.accounts.escrow.to_account_info(),
+                    slash_record: ctx.accounts.slash_record.to_account_info(),
+                    artist_state: ctx.accounts.artist_state.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                signer_seeds,
+            );
+            project_escrow::cpi::slash_project(cpi_ctx)?;
+        }
+        let status_str = if passed { "Passed" } else { "Rejected" };
+        msg!(
+            "Slash proposal finalized: {} status {}",
+            ctx.accounts.proposal.key(),
+            status_str
+        );
+        Ok(())
+    }
 }
 
 #[error_code]
@@ -677,6 +2078,84 @@ pub enum GovError {
     NotArtist,
     #[msg("Invalid proposal attempt")]
     InvalidProposalAttempt,
+    #[msg("Voting period would end after the deadline of a project that has not reached its funding threshold")]
+    VotingPeriodExceedsDeadline,
+    #[msg("Project has too few backers for milestone governance")]
+    NotEnoughBackers,
+    #[msg("Governance config account is invalid")]
+    InvalidGovConfig,
+    #[msg("Participation bps must not exceed 10000")]
+    InvalidParticipationBps,
+    #[msg("Vote delegation accounts are invalid")]
+    InvalidDelegation,
+    #[msg("Voting power is delegated; revoke the delegation to vote directly")]
+    VotingPowerDelegated,
+    #[msg("Delegator already voted or their weight was already cast on this proposal")]
+    DelegatedWeightUsed,
+    #[msg("Proposal account is invalid")]
+    InvalidProposal,
+    #[msg("Veto reason too long")]
+    VetoReasonTooLong,
+    #[msg("Quorum bps must be between 1 and 10000")]
+    InvalidQuorumBps,
+    #[msg("Material-edit threshold bps must not exceed 10000")]
+    InvalidMaterialEditThreshold,
+    #[msg("Proposal is still active")]
+    ProposalStillActive,
+    #[msg("Execution delay must not be negative")]
+    InvalidExecutionDelay,
+    #[msg("Proposal is not awaiting execution")]
+    ProposalNotAwaitingExecution,
+    #[msg("Execution delay has not elapsed")]
+    ExecutionDelayNotElapsed,
+    #[msg("Proposal passed and is awaiting execution")]
+    ProposalAwaitingExecution,
+    #[msg("Proposal uses a different vote weight source")]
+    WrongWeightSource,
+    #[msg("Project is not completed")]
+    ProjectNotCompleted,
+    #[msg("RWA token account is not for this project's RWA mint")]
+    InvalidRwaAccount,
+    #[msg("No RWA token balance")]
+    NoRwaBalance,
+    #[msg("Escrow balance does not cover the milestone release")]
+    InsufficientEscrow,
+    #[msg("Proof URI too long")]
+    ProofUriTooLong,
+    #[msg("Proposal title too long")]
+    TitleTooLong,
+    #[msg("Contribution below the minimum required to vote")]
+    ContributionBelowVoteMinimum,
+    #[msg("Proposal has no pending milestone release")]
+    NoPendingRelease,
+    #[msg("Invalid stake account")]
+    InvalidStakeAccount,
+    #[msg("Stake unlocks before the vote ends")]
+    StakeUnlocksBeforeVoteEnds,
+    #[msg("Backer still holds a contribution; only refunded votes can be rescinded")]
+    BackerNotRefunded,
+    #[msg("Vote already rescinded")]
+    VoteAlreadyRescinded,
+    #[msg("Project is not active")]
+    ProjectNotActive,
+    #[msg("A proposal bond is required; pass the bond accounts")]
+    BondAccountsRequired,
+    #[msg("Proposal bond account is invalid")]
+    InvalidBondAccount,
+    #[msg("Proposal bond cannot be settled yet")]
+    BondNotSettleable,
+    #[msg("Proposal bond has not been settled")]
+    BondNotSettled,
+    #[msg("Proof URI must start with https://, ipfs:// or ar:// and not be empty")]
+    InvalidProofUri,
+    #[msg("Vote was cast by a delegate; only the delegate's vote moves it")]
+    DelegatedVoteLocked,
+    #[msg("The delegate's vote account is required")]
+    DelegateVoteRequired,
+    #[msg("Account is not a vote")]
+    InvalidVoteAccount,
+    #[msg("RWA vote tokens stay locked until voting ends")]
+    RwaVoteLocked,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -698,6 +2177,100 @@ pub struct Proposal {
     pub start_ts: i64,
     pub end_ts: i64,
     pub creator: Pubkey,
+    /// Weight of abstain votes: counts toward quorum and participation, not toward the outcome.
+    pub votes_abstain: u64,
+    /// When a Passed proposal may be executed (execution delay). 0 = nothing pending.
+    pub executable_at: i64,
+    /// What vote weight derives from: Backer contributions, or RWA balances for RWA_PROPOSAL_INDEX proposals.
+    pub weight_source: WeightSource,
+    /// Human-readable title for UIs (at most MAX_PROPOSAL_TITLE_LEN bytes).
+    pub title: String,
+    /// Hash of the full off-chain description; all zeros when none.
+    pub description_hash: [u8; 32],
+    /// Stake boosts included in votes_for / votes_against / votes_abstain. ProjectVoteWeight.total_vote_weight
+    /// counts contributions only, so quorum and early finalize leave these out.
+    pub boost_for: u64,
+    pub boost_against: u64,
+    pub boost_abstain: u64,
+}
+
+/// Source of voting weight for a proposal. Backer (0) keeps proposals from before this field on the original path.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum WeightSource {
+    /// sqrt(Backer.amount), cast via cast_vote.
+    #[default]
+    Backer,
+    /// RWA token balance, cast via cast_rwa_vote.
+    RwaBalance,
+}
+
+impl Proposal {
+    /// All weight cast on the proposal (for + against + abstain) less stake boosts, as measured against quorum.
+    pub fn total_votes(&self) -> Result<u64> {
+        let (votes_for, votes_against, votes_abstain) = self.unboosted_votes();
+        votes_for
+            .checked_add(votes_against)
+            .and_then(|v| v.checked_add(votes_abstain))
+            .ok_or(error!(GovError::Overflow))
+    }
+
+    /// (for, against, abstain) without stake boosts: the contribution weight early finalize compares with
+    /// total_vote_weight. The outcome itself still counts boosted weight.
+    pub fn unboosted_votes(&self) -> (u64, u64, u64) {
+        (
+            self.votes_for.saturating_sub(self.boost_for),
+            self.votes_against.saturating_sub(self.boost_against),
+            self.votes_abstain.saturating_sub(self.boost_abstain),
+        )
+    }
+
+    /// Passed under an execution delay and not yet executed or vetoed.
+    pub fn awaiting_execution(&self) -> bool {
+        self.status == ProposalStatus::Passed && self.executable_at != 0
+    }
+
+    /// Passed milestone proposal (not queued) whose milestone the project has not yet moved past.
+    pub fn release_pending(&self, current_milestone: u8) -> bool {
+        self.status == ProposalStatus::Passed
+            && self.executable_at == 0
+            && is_milestone_proposal(self.milestone_index)
+            && self.milestone_index == current_milestone
+    }
+}
+
+/// Bond deposited at create_proposal (seeds = [b"proposal_bond", proposal]); closed by settle_proposal_bond.
+#[account]
+pub struct ProposalBond {
+    pub depositor: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// RWA tokens cast_rwa_vote moved into the [b"rwa_vote_vault"] vault (seeds = [b"rwa_vote_lock", proposal,
+/// voter]); closed by withdraw_rwa_vote. Kept apart from the Vote so closing the Vote or the proposal cannot
+/// strand the tokens.
+#[account]
+pub struct RwaVoteLock {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// A vote's side. Variant order keeps the byte layout of the former `side: bool` (false = Against, true = For).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VoteSide {
+    Against,
+    For,
+    Abstain,
+}
+
+/// A backer's delegation of their voting power in one project (seeds = [b"delegation", project, delegator]).
+#[account]
+pub struct VoteDelegation {
+    pub project: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
 }
 
 #[account]
@@ -705,19 +2278,81 @@ pub struct Vote {
     pub proposal: Pubkey,
     pub voter: Pubkey,
     pub weight: u64,
-    pub side: bool,
+    pub side: VoteSide,
+    /// Delegate whose cast_vote created this record for `voter`; default for the voter's own vote. The weight
+    /// is also counted in the delegate's Vote, so only that record may move it.
+    pub delegate: Pubkey,
+    /// Stake boost included in `weight` (0 for RWA votes); tallied into the proposal's boost_* fields.
+    pub boost: u64,
 }
 
+/// Per-(project, milestone_index) proposal counter (seeds = [b"proposal_attempt", project, milestone_index]).
+/// Projects created before the split also have a per-project counter at [b"proposal_attempt", project]; it is
+/// frozen and only read to seed milestone counters.
 #[account]
 pub struct ProposalAttempt {
     pub attempt: u64,
 }
 
 /// Optional governance config (seeds = [b"config"]). When set, allows early finalize and custom min voting period.
+/// Fields are append-only and default to zero so configs created with an older layout stay readable.
 #[account]
 pub struct GovConfig {
     pub allow_early_finalize: bool,
     pub min_voting_period_secs: i64,
+    /// Minimum project.backer_count before milestone proposals can be created. 0 = no minimum.
+    pub min_backers_for_governance: u32,
+    /// Denominator for the quorum threshold (default Raised).
+    pub quorum_basis: QuorumBasis,
+    /// Minimum turnout for early finalize, in basis points of the project's total vote weight. 0 = no floor.
+    pub min_participation_bps: u16,
+    /// Quorum in basis points of the quorum basis. 0 (configs from before this field) = QUORUM_BPS.
+    pub quorum_bps: u16,
+    /// Share of decisive votes a material edit needs to pass, e.g. 6667 for two-thirds. 0 = simple majority.
+    pub material_edit_threshold_bps: u16,
+    /// Delay between a milestone proposal passing and execute_proposal releasing funds. 0 = release at finalize.
+    pub execution_delay_secs: i64,
+    /// Minimum backer.amount (lamports) to cast a backer vote. 0 = any contribution.
+    pub min_vote_contribution: u64,
+    /// Bond create_proposal takes from the creator, in bond_mint base units. 0 = no bond.
+    pub proposal_bond: u64,
+    pub bond_mint: Pubkey,
+    /// Token account (bond_mint) that receives forfeited bonds.
+    pub bond_treasury: Pubkey,
+}
+
+/// Amount quorum is measured against. Raised (0) keeps configs from before this field on the original behavior.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum QuorumBasis {
+    /// project.total_raised
+    #[default]
+    Raised,
+    /// project.goal; a higher bar for under-funded projects.
+    Goal,
+}
+
+impl GovConfig {
+    /// Parse raw account data (discriminator included). Data shorter than GOV_CONFIG_SPACE but at least
+    /// the original layout is zero-extended, so fields added later read as 0.
+    pub(crate) fn from_account_data(data: &[u8]) -> Option<GovConfig> {
+        if data.len() < GOV_CONFIG_BASE_LEN || &data[0..8] != GovConfig::DISCRIMINATOR {
+            return None;
+        }
+        let mut body = data[8..].to_vec();
+        if body.len() < GOV_CONFIG_SPACE - 8 {
+            body.resize(GOV_CONFIG_SPACE - 8, 0);
+        }
+        GovConfig::deserialize(&mut &body[..]).ok()
+    }
+
+    /// Quorum bps applied at finalize: quorum_bps, or the QUORUM_BPS default when unset.
+    pub fn effective_quorum_bps(&self) -> u16 {
+        if self.quorum_bps == 0 {
+            QUORUM_BPS
+        } else {
+            self.quorum_bps
+        }
+    }
 }
 
 #[derive(Accounts)]
@@ -728,7 +2363,7 @@ pub struct InitializeGovConfig<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 1 + 8,
+        space = GOV_CONFIG_SPACE,
         seeds = [b"config"],
         bump,
     )]
@@ -745,16 +2380,22 @@ pub struct InitializeGovConfig<'info> {
 
 #[derive(Accounts)]
 pub struct UpdateGovConfig<'info> {
+    /// Pays rent when an older, smaller config is grown to the current layout.
+    #[account(mut)]
     pub authority: Signer<'info>,
 
-    #[account(mut, seeds = [b"config"], bump)]
-    pub config: Account<'info, GovConfig>,
+    /// GovConfig PDA. Read and written in instruction so older layouts can be migrated.
+    /// CHECK: address validated by seeds; owner and discriminator validated in instruction
+    #[account(mut, seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
 
     /// CHECK: validated in instruction
     pub program_account: UncheckedAccount<'info>,
 
     /// CHECK: validated in instruction
     pub program_data_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -767,15 +2408,20 @@ pub struct CreateProposal<'info> {
         init_if_needed,
         payer = artist,
         space = 8 + 8,
-        seeds = [b"proposal_attempt", project_key.as_ref()],
+        seeds = [b"proposal_attempt", project_key.as_ref(), &[milestone_index]],
         bump,
     )]
     pub proposal_attempt: Account<'info, ProposalAttempt>,
 
+    /// Legacy per-project attempt counter; may be uninitialized.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(seeds = [b"proposal_attempt", project_key.as_ref()], bump)]
+    pub legacy_proposal_attempt: UncheckedAccount<'info>,
+
     #[account(
         init,
         payer = artist,
-        space = 8 + 32 + 1 + 4 + MAX_PROOF_URI_LEN + 8 + 8 + 1 + 8 + 8 + 32,
+        space = PROPOSAL_SPACE,
         seeds = [b"proposal", project_key.as_ref(), &[milestone_index], &attempt.to_le_bytes()],
         bump,
     )]
@@ -787,16 +2433,46 @@ pub struct CreateProposal<'info> {
     )]
     pub project: Account<'info, project_escrow::Project>,
 
+    /// GovConfig PDA; may be uninitialized, in which case defaults apply.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(seeds = [b"config"], bump)]
+    pub gov_config: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
+
+    /// Required (with the accounts below) when GovConfig sets a proposal_bond; omit otherwise.
+    #[account(
+        init,
+        payer = artist,
+        space = PROPOSAL_BOND_SPACE,
+        seeds = [b"proposal_bond", proposal.key().as_ref()],
+        bump,
+    )]
+    pub proposal_bond: Option<Account<'info, ProposalBond>>,
+
+    pub bond_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Artist's bond_mint token account the bond is paid from.
+    #[account(mut)]
+    pub artist_bond_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// bond_mint token account owned by the [b"bond_vault"] PDA; checked in instruction.
+    #[account(mut)]
+    pub bond_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-#[instruction(side: bool)]
+#[instruction(side: VoteSide)]
 pub struct CastVote<'info> {
     #[account(mut)]
     pub voter: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = proposal.weight_source == WeightSource::Backer @ GovError::WrongWeightSource,
+    )]
     pub proposal: Account<'info, Proposal>,
 
     #[account(
@@ -806,99 +2482,327 @@ pub struct CastVote<'info> {
     pub backer: Account<'info, project_escrow::Backer>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = voter,
-        space = 8 + 32 + 32 + 8 + 1,
+        space = VOTE_SPACE,
         seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
         bump,
     )]
     pub vote: Account<'info, Vote>,
 
+    /// The voter's VoteDelegation PDA; must not exist (a delegating backer cannot vote directly).
+    /// CHECK: only checked for emptiness; address bound by seeds.
+    #[account(
+        seeds = [b"delegation", proposal.project.as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub delegation: UncheckedAccount<'info>,
+
+    /// GovConfig PDA; may be uninitialized, in which case any contribution can vote.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(seeds = [b"config"], bump)]
+    pub gov_config: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
+
+    /// The voter's taste_token StakeAccount; when passed, its locked stake boosts the voter's own weight.
+    #[account(
+        seeds = [b"stake", voter.key().as_ref()],
+        bump,
+        seeds::program = taste_token::ID,
+    )]
+    pub stake_account: Option<Account<'info, taste_token::StakeAccount>>,
 }
 
 #[derive(Accounts)]
-pub struct FinalizeProposal<'info> {
+pub struct CastRwaVote<'info> {
     #[account(mut)]
-    pub proposal: Account<'info, Proposal>,
+    pub voter: Signer<'info>,
 
-    #[account(mut)]
-    pub project: Account<'info, project_escrow::Project>,
+    #[account(
+        mut,
+        constraint = proposal.weight_source == WeightSource::RwaBalance @ GovError::WrongWeightSource,
+    )]
+    pub proposal: Account<'info, Proposal>,
 
-    /// Payer for RWA state/mint init when last milestone is released (optional CPI).
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = holder_rwa_account.owner == voter.key() @ GovError::InvalidRwaAccount,
+        constraint = holder_rwa_account.mint == rwa_mint.key() @ GovError::InvalidRwaAccount,
+    )]
+    pub holder_rwa_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// PDA that signs for governance CPI to project_escrow
-    /// CHECK: validated by seeds
-    #[account(mut, seeds = [b"release_authority"], bump)]
-    pub release_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VOTE_SPACE,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub vote: Account<'info, Vote>,
 
-    /// Project escrow config PDA (seeds = [b"config"]). Must match project_escrow program's config PDA.
     #[account(
-        constraint = escrow_config.key() == Pubkey::find_program_address(&[b"config"], &project_escrow_program.key()).0
+        init_if_needed,
+        payer = voter,
+        space = RWA_VOTE_LOCK_SPACE,
+        seeds = [b"rwa_vote_lock", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
     )]
-    pub escrow_config: Account<'info, project_escrow::Config>,
+    pub rwa_vote_lock: Account<'info, RwaVoteLock>,
 
-    #[account(mut)]
-    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = rwa_mint_address(&proposal.project) @ GovError::InvalidRwaAccount)]
+    pub rwa_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// CHECK: escrow authority PDA from project_escrow (validated by CPI target)
-    pub escrow_authority: UncheckedAccount<'info>,
+    /// CHECK: PDA owning the RWA vote vaults
+    #[account(seeds = [b"rwa_vote_vault"], bump)]
+    pub rwa_vote_vault_authority: UncheckedAccount<'info>,
+
+    /// Holds RWA tokens locked by votes until withdraw_rwa_vote.
+    #[account(
+        init_if_needed,
+        payer = voter,
+        associated_token::mint = rwa_mint,
+        associated_token::authority = rwa_vote_vault_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub rwa_vote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
 
+#[derive(Accounts)]
+pub struct WithdrawRwaVote<'info> {
     #[account(mut)]
-    pub artist_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub voter: Signer<'info>,
 
-    pub taste_mint: InterfaceAccount<'info, Mint>,
+    /// The lock's proposal; may already be closed.
+    /// CHECK: address bound to rwa_vote_lock.proposal; status checked in instruction when the account still exists
+    #[account(address = rwa_vote_lock.proposal @ GovError::InvalidProposal)]
+    pub proposal: UncheckedAccount<'info>,
 
-    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        mut,
+        close = voter,
+        has_one = voter,
+        seeds = [b"rwa_vote_lock", rwa_vote_lock.proposal.as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub rwa_vote_lock: Account<'info, RwaVoteLock>,
 
-    pub project_escrow_program: Program<'info, project_escrow::program::ProjectEscrow>,
+    #[account(
+        mut,
+        constraint = holder_rwa_account.owner == voter.key() @ GovError::InvalidRwaAccount,
+        constraint = holder_rwa_account.mint == rwa_mint.key() @ GovError::InvalidRwaAccount,
+    )]
+    pub holder_rwa_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// RWA state PDA (rwa_token). Used only when finalizing last milestone to auto-init RWA mint.
-    /// CHECK: validated by rwa_token CPI; may be uninitialized (lamports == 0)
-    #[account(mut)]
-    pub rwa_state: UncheckedAccount<'info>,
+    #[account(address = rwa_vote_lock.mint @ GovError::InvalidRwaAccount)]
+    pub rwa_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// RWA mint PDA (rwa_token). Used only when finalizing last milestone.
-    /// CHECK: validated by rwa_token CPI
-    #[account(mut)]
-    pub rwa_mint: UncheckedAccount<'info>,
+    /// CHECK: PDA signer for the RWA vote vaults
+    #[account(seeds = [b"rwa_vote_vault"], bump)]
+    pub rwa_vote_vault_authority: UncheckedAccount<'info>,
 
-    /// RWA mint authority PDA (rwa_token).
-    /// CHECK: validated by rwa_token CPI
-    pub rwa_mint_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = rwa_mint,
+        associated_token::authority = rwa_vote_vault_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub rwa_vote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// RwaConfig PDA (rwa_token). Stores transfer_hook_program_id for RWA mint creation.
-    /// CHECK: validated by rwa_token CPI
-    pub rwa_config: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
 
-    /// rwa_transfer_hook program. Used when auto-initing RWA mint with TransferHook.
-    /// CHECK: validated by rwa_token CPI (must match rwa_config.transfer_hook_program_id)
-    pub rwa_transfer_hook_program: UncheckedAccount<'info>,
+#[derive(Accounts)]
+pub struct FinalizeRwaProposal<'info> {
+    #[account(
+        mut,
+        constraint = proposal.weight_source == WeightSource::RwaBalance @ GovError::WrongWeightSource,
+    )]
+    pub proposal: Account<'info, Proposal>,
 
-    /// Extra-account-metas PDA [b"extra-account-metas", rwa_mint] for rwa_transfer_hook.
-    /// CHECK: validated by rwa_token CPI
-    #[account(mut)]
-    pub rwa_extra_account_metas: UncheckedAccount<'info>,
+    #[account(address = rwa_mint_address(&proposal.project) @ GovError::InvalidRwaAccount)]
+    pub rwa_mint: InterfaceAccount<'info, Mint>,
 
-    /// RWA metadata guard PDA (rwa_token). Used when auto-initing Metaplex metadata after RWA mint.
-    /// CHECK: validated by rwa_token CPI; may be uninitialized
-    #[account(mut)]
-    pub rwa_metadata_guard: UncheckedAccount<'info>,
+    /// GovConfig PDA; may be uninitialized, in which case defaults apply.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(seeds = [b"config"], bump)]
+    pub gov_config: UncheckedAccount<'info>,
+}
 
-    /// Metaplex metadata PDA for rwa_mint. Used when auto-initing RWA metadata.
-    /// CHECK: validated by rwa_token CPI
+#[derive(Accounts)]
+pub struct DelegateVote<'info> {
     #[account(mut)]
-    pub rwa_metadata: UncheckedAccount<'info>,
+    pub delegator: Signer<'info>,
 
-    /// Artist wallet (project.artist). Must sign when finalizing last milestone so RWA metadata can use artist as Metaplex update_authority (CPI signer rule).
-    #[account(constraint = artist.key() == project.artist)]
-    pub artist: Signer<'info>,
+    #[account(constraint = backer.wallet == delegator.key() @ GovError::NoContribution)]
+    pub backer: Account<'info, project_escrow::Backer>,
 
-    /// Metaplex Token Metadata program (metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s).
-    /// CHECK: validated by rwa_token CPI
-    pub token_metadata_program: UncheckedAccount<'info>,
+    /// Backer record of the delegate: same project, different wallet.
+    #[account(
+        constraint = delegate_backer.project == backer.project @ GovError::InvalidDelegation,
+        constraint = delegate_backer.wallet != delegator.key() @ GovError::InvalidDelegation,
+    )]
+    pub delegate_backer: Account<'info, project_escrow::Backer>,
+
+    #[account(
+        init,
+        payer = delegator,
+        space = VOTE_DELEGATION_SPACE,
+        seeds = [b"delegation", backer.project.as_ref(), delegator.key().as_ref()],
+        bump,
+    )]
+    pub delegation: Account<'info, VoteDelegation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = delegator,
+        has_one = delegator @ GovError::InvalidDelegation,
+        seeds = [b"delegation", delegation.project.as_ref(), delegator.key().as_ref()],
+        bump,
+    )]
+    pub delegation: Account<'info, VoteDelegation>,
+}
+
+#[derive(Accounts)]
+pub struct RescindRefundedVote<'info> {
+    #[account(
+        mut,
+        constraint = proposal.weight_source == WeightSource::Backer @ GovError::WrongWeightSource,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"vote", proposal.key().as_ref(), vote.voter.as_ref()],
+        bump,
+    )]
+    pub vote: Account<'info, Vote>,
+
+    #[account(
+        constraint = backer.project == proposal.project,
+        constraint = backer.wallet == vote.voter,
+    )]
+    pub backer: Account<'info, project_escrow::Backer>,
+
+    /// The voter's VoteDelegation PDA; must not exist (a delegated vote belongs to the delegate's tally).
+    /// CHECK: only checked for emptiness; address bound by seeds.
+    #[account(
+        seeds = [b"delegation", proposal.project.as_ref(), vote.voter.as_ref()],
+        bump,
+    )]
+    pub delegation: UncheckedAccount<'info>,
+
+    /// The delegate's Vote; required when `vote` was cast by a delegate (vote.delegate is set).
+    #[account(
+        mut,
+        seeds = [b"vote", proposal.key().as_ref(), vote.delegate.as_ref()],
+        bump,
+    )]
+    pub delegate_vote: Option<Account<'info, Vote>>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub project: Account<'info, project_escrow::Project>,
+
+    /// Payer for RWA state/mint init when last milestone is released (optional CPI).
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// PDA that signs for governance CPI to project_escrow
+    /// CHECK: validated by seeds
+    #[account(mut, seeds = [b"release_authority"], bump)]
+    pub release_authority: UncheckedAccount<'info>,
+
+    /// Project escrow config PDA (seeds = [b"config"]). Must match project_escrow program's config PDA.
+    /// CHECK: key pinned by the constraint; project_escrow parses it with Config::from_account_data.
+    #[account(
+        constraint = escrow_config.key() == Pubkey::find_program_address(&[b"config"], &project_escrow_program.key()).0
+    )]
+    pub escrow_config: UncheckedAccount<'info>,
+
+    /// Project escrow token account; its balance is checked before a passing proposal is marked Passed.
+    #[account(
+        mut,
+        constraint = escrow.key() == Pubkey::find_program_address(&[b"escrow", project.key().as_ref()], &project_escrow_program.key()).0
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: escrow authority PDA from project_escrow (seeds = [b"project", project]); checked before the CPI.
+    #[account(
+        constraint = escrow_authority.key() == Pubkey::find_program_address(&[b"project", project.key().as_ref()], &project_escrow_program.key()).0
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub artist_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub taste_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub project_escrow_program: Program<'info, project_escrow::program::ProjectEscrow>,
+
+    /// RWA state PDA (rwa_token). Used only when finalizing last milestone to auto-init RWA mint.
+    /// CHECK: validated by rwa_token CPI; may be uninitialized (lamports == 0)
+    #[account(mut)]
+    pub rwa_state: UncheckedAccount<'info>,
+
+    /// RWA mint PDA (rwa_token). Used only when finalizing last milestone.
+    /// CHECK: validated by rwa_token CPI
+    #[account(mut)]
+    pub rwa_mint: UncheckedAccount<'info>,
+
+    /// RWA mint authority PDA (rwa_token).
+    /// CHECK: validated by rwa_token CPI
+    pub rwa_mint_authority: UncheckedAccount<'info>,
+
+    /// RwaConfig PDA (rwa_token). Stores transfer_hook_program_id for RWA mint creation.
+    /// CHECK: validated by rwa_token CPI
+    pub rwa_config: UncheckedAccount<'info>,
+
+    /// rwa_transfer_hook program. Used when auto-initing RWA mint with TransferHook.
+    /// CHECK: validated by rwa_token CPI (must match rwa_config.transfer_hook_program_id)
+    pub rwa_transfer_hook_program: UncheckedAccount<'info>,
+
+    /// Extra-account-metas PDA [b"extra-account-metas", rwa_mint] for rwa_transfer_hook.
+    /// CHECK: validated by rwa_token CPI
+    #[account(mut)]
+    pub rwa_extra_account_metas: UncheckedAccount<'info>,
+
+    /// RWA metadata guard PDA (rwa_token). Used when auto-initing Metaplex metadata after RWA mint.
+    /// CHECK: validated by rwa_token CPI; may be uninitialized
+    #[account(mut)]
+    pub rwa_metadata_guard: UncheckedAccount<'info>,
+
+    /// Metaplex metadata PDA for rwa_mint. Used when auto-initing RWA metadata.
+    /// CHECK: validated by rwa_token CPI
+    #[account(mut)]
+    pub rwa_metadata: UncheckedAccount<'info>,
+
+    /// Artist wallet (project.artist). Must sign when finalizing last milestone so RWA metadata can use artist as Metaplex update_authority (CPI signer rule).
+    #[account(constraint = artist.key() == project.artist)]
+    pub artist: Signer<'info>,
+
+    /// Metaplex Token Metadata program (metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s).
+    /// CHECK: validated by rwa_token CPI
+    pub token_metadata_program: UncheckedAccount<'info>,
 
     /// Sysvar Instructions (required by Metaplex CreateV1).
     /// CHECK: required by Metaplex
@@ -927,6 +2831,147 @@ pub struct FinalizeProposal<'info> {
         Program<'info, revenue_distribution::program::RevenueDistribution>,
 
     pub system_program: Program<'info, System>,
+
+    /// GovConfig PDA; may be uninitialized, in which case defaults apply.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(seeds = [b"config"], bump)]
+    pub gov_config: UncheckedAccount<'info>,
+
+    /// Optional taste_token ProtocolState, forwarded to release_milestone; the release fails while paused.
+    /// CHECK: validated by project_escrow CPI
+    pub protocol_state: Option<UncheckedAccount<'info>>,
+
+    /// project_escrow ArtistState [b"artist_state", project.artist], forwarded to release_milestone so the
+    /// completing release counts toward the artist's track record.
+    /// CHECK: validated by project_escrow CPI
+    #[account(mut)]
+    pub artist_state: UncheckedAccount<'info>,
+
+    /// project_escrow PayoutSplit PDA [b"payout_split", project], forwarded to release_milestone. May be
+    /// uninitialized; when it holds a split, the recipients must follow in remaining_accounts.
+    /// CHECK: validated by project_escrow CPI
+    pub payout_split: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner checked here; discriminator validated in instruction
+    #[account(mut, owner = crate::ID)]
+    pub proposal: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateVote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner checked here; discriminator and length validated in instruction
+    #[account(mut, owner = crate::ID)]
+    pub vote: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// The vote's proposal; may already be closed.
+    /// CHECK: address bound to vote.proposal; status checked in instruction when the account still exists
+    #[account(address = vote.proposal @ GovError::InvalidProposal)]
+    pub proposal: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = voter,
+        has_one = voter,
+        seeds = [b"vote", vote.proposal.as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub vote: Account<'info, Vote>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(mut, close = creator, has_one = creator @ GovError::NotProposalCreator)]
+    pub proposal: Account<'info, Proposal>,
+
+    /// ProposalBond PDA; must be empty (never created, or settled).
+    /// CHECK: address validated by seeds; emptiness checked in instruction
+    #[account(seeds = [b"proposal_bond", proposal.key().as_ref()], bump)]
+    pub proposal_bond: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleProposalBond<'info> {
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(constraint = project.key() == proposal.project @ GovError::InvalidProposal)]
+    pub project: Account<'info, project_escrow::Project>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal_bond", proposal.key().as_ref()],
+        bump,
+        close = depositor,
+    )]
+    pub proposal_bond: Account<'info, ProposalBond>,
+
+    /// Proposal creator; receives the bond account's rent.
+    /// CHECK: address matched to proposal_bond.depositor
+    #[account(mut, address = proposal_bond.depositor)]
+    pub depositor: UncheckedAccount<'info>,
+
+    /// CHECK: PDA signer for the bond vault
+    #[account(seeds = [b"bond_vault"], bump)]
+    pub bond_vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = bond_mint,
+        token::authority = bond_vault_authority,
+    )]
+    pub bond_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Depositor's token account on refund; GovConfig bond_treasury on forfeit. Checked in instruction.
+    #[account(mut, token::mint = bond_mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = proposal_bond.mint @ GovError::InvalidBondAccount)]
+    pub bond_mint: InterfaceAccount<'info, Mint>,
+
+    /// Project RWA mint; required to settle an unfinalized RWA-holder proposal.
+    pub rwa_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// GovConfig PDA; may be uninitialized, in which case defaults apply.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(seeds = [b"config"], bump)]
+    pub gov_config: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: validated in instruction
+    pub program_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated in instruction
+    pub program_data_account: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -950,10 +2995,12 @@ pub struct FinalizeMaterialEditProposal<'info> {
     #[account(mut, seeds = [b"release_authority"], bump)]
     pub release_authority: UncheckedAccount<'info>,
 
+    /// Project escrow config PDA.
+    /// CHECK: key pinned by the constraint; project_escrow parses it with Config::from_account_data.
     #[account(
         constraint = escrow_config.key() == Pubkey::find_program_address(&[b"config"], &project_escrow_program.key()).0
     )]
-    pub escrow_config: Account<'info, project_escrow::Config>,
+    pub escrow_config: UncheckedAccount<'info>,
 
     /// ProjectTerms PDA (seeds = [b"project_terms", project.key()]). May be uninitialized; project_escrow will init_if_needed.
     /// CHECK: validated by project_escrow CPI
@@ -963,6 +3010,102 @@ pub struct FinalizeMaterialEditProposal<'info> {
     pub system_program: Program<'info, System>,
 
     pub project_escrow_program: Program<'info, project_escrow::program::ProjectEscrow>,
+
+    /// GovConfig PDA; may be uninitialized, in which case defaults apply.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(seeds = [b"config"], bump)]
+    pub gov_config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(project_key: Pubkey, proof_uri: String, voting_period_secs: i64, attempt: u64)]
+pub struct CreateSlashProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        constraint = backer.project == project_key @ GovError::NoContribution,
+        constraint = backer.wallet == proposer.key() @ GovError::NoContribution,
+        constraint = backer.amount > 0 @ GovError::NoContribution,
+    )]
+    pub backer: Account<'info, project_escrow::Backer>,
+
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = 8 + 8,
+        seeds = [b"proposal_attempt", project_key.as_ref(), &[SLASH_PROPOSAL_INDEX]],
+        bump,
+    )]
+    pub proposal_attempt: Account<'info, ProposalAttempt>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = PROPOSAL_SPACE,
+        seeds = [b"proposal", project_key.as_ref(), &[SLASH_PROPOSAL_INDEX], &attempt.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(constraint = project.key() == project_key)]
+    pub project: Account<'info, project_escrow::Project>,
+
+    /// GovConfig PDA; may be uninitialized, in which case defaults apply.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(seeds = [b"config"], bump)]
+    pub gov_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSlashProposal<'info> {
+    #[account(
+        mut,
+        constraint = proposal.milestone_index == SLASH_PROPOSAL_INDEX @ GovError::InvalidMilestoneIndex,
+        constraint = proposal.project == project.key() @ GovError::InvalidProposal,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub project: Account<'info, project_escrow::Project>,
+
+    /// PDA that signs for governance CPI to project_escrow
+    /// CHECK: validated by seeds
+    #[account(mut, seeds = [b"release_authority"], bump)]
+    pub release_authority: UncheckedAccount<'info>,
+
+    /// Project escrow config PDA.
+    /// CHECK: key pinned by the constraint; project_escrow parses it with Config::from_account_data.
+    #[account(
+        constraint = escrow_config.key() == Pubkey::find_program_address(&[b"config"], &project_escrow_program.key()).0
+    )]
+    pub escrow_config: UncheckedAccount<'info>,
+
+    /// Project escrow token account (seeds = [b"escrow", project.key()]).
+    /// CHECK: validated by project_escrow CPI
+    pub escrow: UncheckedAccount<'info>,
+
+    /// SlashRecord PDA (seeds = [b"slash", project.key()]); created by project_escrow on pass.
+    /// CHECK: validated by project_escrow CPI
+    #[account(mut)]
+    pub slash_record: UncheckedAccount<'info>,
+
+    /// project_escrow ArtistState [b"artist_state", project.artist], forwarded to slash_project so the slash
+    /// counts toward the artist's track record.
+    /// CHECK: validated by project_escrow CPI
+    #[account(mut)]
+    pub artist_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub project_escrow_program: Program<'info, project_escrow::program::ProjectEscrow>,
+
+    /// GovConfig PDA; may be uninitialized, in which case defaults apply.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(seeds = [b"config"], bump)]
+    pub gov_config: UncheckedAccount<'info>,
 }
 
 #[cfg(test)]
@@ -984,6 +3127,398 @@ mod tests {
         assert!(weight > 0 && weight * weight <= amount && (weight + 1) * (weight + 1) > amount);
     }
 
+    /// The original Babylonian sqrt_u64 (initial guess x/2), returning its result and loop iterations.
+    fn legacy_sqrt_u64(x: u64) -> (u64, u32) {
+        if x == 0 {
+            return (0, 0);
+        }
+        let (mut z, mut y, mut iterations) = (x.div_ceil(2), x, 0);
+        while z < y {
+            y = z;
+            z = (x / z + z) / 2;
+            iterations += 1;
+        }
+        (y, iterations)
+    }
+
+    /// Loop iterations sqrt_u64 takes from its bit-length initial guess.
+    fn sqrt_u64_iterations(x: u64) -> u32 {
+        if x == 0 {
+            return 0;
+        }
+        let mut y = 1u64 << (u64::BITS - x.leading_zeros()).div_ceil(2);
+        let (mut z, mut iterations) = ((x / y + y) / 2, 0);
+        while z < y {
+            y = z;
+            z = (x / z + z) / 2;
+            iterations += 1;
+        }
+        iterations
+    }
+
+    #[test]
+    fn test_sqrt_u64_matches_legacy_with_fewer_iterations() {
+        let mut inputs: Vec<u64> = (0..=10_000).collect();
+        inputs.extend([u64::MAX, u64::MAX - 1, u32::MAX as u64 * u32::MAX as u64]);
+        for shift in 0..64 {
+            let p = 1u64 << shift;
+            inputs.extend([p - 1, p, p.saturating_add(1)]);
+        }
+        for root in [3u64, 1_000, 65_535, 1 << 20, 3_037_000_499, u32::MAX as u64] {
+            let square = root * root;
+            inputs.extend([square - 1, square, square + 1]);
+        }
+        // Deterministic pseudo-random sweep across the whole range (64-bit LCG).
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        for _ in 0..100_000 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            inputs.push(state >> (state % 64));
+        }
+        for x in inputs {
+            let (expected, legacy_iterations) = legacy_sqrt_u64(x);
+            let root = sqrt_u64(x);
+            assert_eq!(root, expected, "sqrt_u64({x})");
+            assert!(
+                (root as u128) * (root as u128) <= x as u128
+                    && (root as u128 + 1) * (root as u128 + 1) > x as u128
+            );
+            let iterations = sqrt_u64_iterations(x);
+            // Tiny inputs (e.g. 1, 2) start the legacy loop already converged; the new one takes at most one step.
+            assert!(iterations <= legacy_iterations.max(1), "iterations for {x}");
+            if x >= 1 << 16 {
+                assert!(iterations < legacy_iterations, "iterations for {x}");
+            }
+        }
+        assert_eq!(sqrt_u64(u64::MAX), u32::MAX as u64);
+        assert!(sqrt_u64_iterations(u64::MAX) <= 6);
+        assert!(legacy_sqrt_u64(u64::MAX).1 > 30);
+    }
+
+    #[test]
+    fn test_quorum_basis_underfunded_project() {
+        // Project raised 40k of a 100k goal.
+        let goal: u64 = 100_000 * 1_000_000_000;
+        let total_raised: u64 = 40_000 * 1_000_000_000;
+        let by_raised = quorum_votes(total_raised, goal, QuorumBasis::Raised, QUORUM_BPS);
+        let by_goal = quorum_votes(total_raised, goal, QuorumBasis::Goal, QUORUM_BPS);
+        assert_eq!(by_raised, sqrt_u64(8_000 * 1_000_000_000));
+        assert_eq!(by_goal, sqrt_u64(20_000 * 1_000_000_000));
+        // Goal basis sets a higher bar when under-funded; the bases agree when fully funded.
+        assert!(by_goal > by_raised);
+        assert_eq!(
+            quorum_votes(goal, goal, QuorumBasis::Raised, QUORUM_BPS),
+            quorum_votes(goal, goal, QuorumBasis::Goal, QUORUM_BPS)
+        );
+        assert_eq!(QuorumBasis::default(), QuorumBasis::Raised);
+    }
+
+    #[test]
+    fn test_material_edit_supermajority() {
+        // 66 for / 34 against is 66%: short of a two-thirds threshold.
+        assert!(!material_edit_passed(66, 34, 6_667));
+        // 67 for / 33 against clears it.
+        assert!(material_edit_passed(67, 33, 6_667));
+        // Without a threshold, material edits keep simple majority.
+        assert!(material_edit_passed(51, 49, 0));
+        assert!(!material_edit_passed(50, 50, 0));
+        // A low threshold never lets the minority side win.
+        assert!(!material_edit_passed(40, 60, 3_000));
+    }
+
+    #[test]
+    fn test_rwa_balances_decide_rwa_proposals() {
+        // One holder with 10_000 RWA against four holders with 1_000 each.
+        let whale = 10_000u64;
+        let small = [1_000u64; 4];
+        let mut rwa = (0, 0, 0);
+        rwa = tally_vote(rwa, None, VoteSide::Against, whale).unwrap();
+        for balance in small {
+            rwa = tally_vote(rwa, None, VoteSide::For, balance).unwrap();
+        }
+        // Balance-weighted, the larger holding wins.
+        assert_eq!(rwa, (4_000, 10_000, 0));
+        assert!(rwa.0 <= rwa.1);
+        // Under backer-style sqrt weighting the four smaller holders would have carried it.
+        assert!(4 * sqrt_u64(1_000) > sqrt_u64(whale));
+        // Quorum is a share of RWA supply: 14_000 of 20_000 clears the default 20%.
+        assert_eq!(rwa_quorum_votes(20_000, QUORUM_BPS), 4_000);
+        assert!(rwa.0 + rwa.1 >= rwa_quorum_votes(20_000, QUORUM_BPS));
+    }
+
+    #[test]
+    fn test_finalize_requires_escrow_to_cover_milestone() {
+        let mut project = project_escrow::Project {
+            artist: Pubkey::new_unique(),
+            name: "p".to_string(),
+            goal: 1_000,
+            milestone_percentages: [40, 60, 0, 0, 0],
+            deadline: 1_800_000_000,
+            status: project_escrow::ProjectStatus::Active,
+            taste_mint: Pubkey::new_unique(),
+            total_raised: 1_000,
+            backer_count: 3,
+            current_milestone: 0,
+            version: project_escrow::PROJECT_VERSION,
+            deadline_extension_secs: 0,
+            min_funding: 0,
+            max_per_backer: 0,
+            soft_cap: 0,
+            released_in_milestone: 0,
+            original_goal: 1_000,
+            fee_exempt: false,
+        };
+        // Milestone 0 is 40% of 1_000.
+        assert!(require_escrow_covers_milestone(400, &project).is_ok());
+        assert!(require_escrow_covers_milestone(399, &project).is_err());
+        // Partial releases already paid count against the milestone.
+        project.released_in_milestone = 100;
+        assert!(require_escrow_covers_milestone(300, &project).is_ok());
+        assert!(require_escrow_covers_milestone(299, &project).is_err());
+        // Past the last milestone there is nothing to release.
+        project.current_milestone = project_escrow::MAX_MILESTONES as u8;
+        assert!(require_escrow_covers_milestone(u64::MAX, &project).is_err());
+    }
+
+    #[test]
+    fn test_milestone_voting_end_bounded_only_while_underfunded() {
+        let mut project = project_escrow::Project {
+            artist: Pubkey::new_unique(),
+            name: "p".to_string(),
+            goal: 1_000,
+            milestone_percentages: [40, 60, 0, 0, 0],
+            deadline: 1_800_000_000,
+            status: project_escrow::ProjectStatus::Active,
+            taste_mint: Pubkey::new_unique(),
+            total_raised: 999,
+            backer_count: 3,
+            current_milestone: 0,
+            version: project_escrow::PROJECT_VERSION,
+            deadline_extension_secs: 0,
+            min_funding: 0,
+            max_per_backer: 0,
+            soft_cap: 0,
+            released_in_milestone: 0,
+            original_goal: 1_000,
+            fee_exempt: false,
+        };
+        // Short of the goal: the vote must end by the funding deadline.
+        assert!(check_milestone_voting_end(1_800_000_000, &project).is_ok());
+        assert!(check_milestone_voting_end(1_800_000_001, &project).is_err());
+        // Funded projects cannot fail, so later milestone votes run past the funding deadline.
+        project.total_raised = 1_000;
+        assert!(check_milestone_voting_end(1_900_000_000, &project).is_ok());
+        // A soft cap is the threshold when set.
+        project.total_raised = 600;
+        project.soft_cap = 600;
+        assert!(check_milestone_voting_end(1_900_000_000, &project).is_ok());
+        project.total_raised = 599;
+        assert!(check_milestone_voting_end(1_900_000_000, &project).is_err());
+    }
+
+    #[test]
+    fn test_is_milestone_proposal() {
+        assert!(is_milestone_proposal(0));
+        assert!(is_milestone_proposal(
+            project_escrow::MAX_MILESTONES as u8 - 1
+        ));
+        assert!(!is_milestone_proposal(project_escrow::MAX_MILESTONES as u8));
+        assert!(!is_milestone_proposal(SLASH_PROPOSAL_INDEX));
+        assert!(!is_milestone_proposal(RWA_PROPOSAL_INDEX));
+        assert!(!is_milestone_proposal(255));
+    }
+
+    #[test]
+    fn test_next_proposal_attempt_continues_legacy_counter() {
+        // Fresh project: every milestone starts at 0.
+        assert_eq!(next_proposal_attempt(0, None), 0);
+        // Legacy counter reached 3 across milestones: an unused milestone counter starts past it.
+        assert_eq!(next_proposal_attempt(0, Some(3)), 3);
+        // Once a milestone counter has advanced it ignores the legacy counter.
+        assert_eq!(next_proposal_attempt(4, Some(3)), 4);
+        assert_eq!(next_proposal_attempt(1, None), 1);
+    }
+
+    #[test]
+    fn test_release_pending_only_until_milestone_advances() {
+        let passed = Proposal {
+            project: Pubkey::new_unique(),
+            milestone_index: 1,
+            proof_uri: String::new(),
+            votes_for: 10,
+            votes_against: 0,
+            status: ProposalStatus::Passed,
+            start_ts: 0,
+            end_ts: 0,
+            creator: Pubkey::new_unique(),
+            votes_abstain: 0,
+            executable_at: 0,
+            weight_source: WeightSource::Backer,
+            title: String::new(),
+            description_hash: [0; 32],
+            boost_for: 0,
+            boost_against: 0,
+            boost_abstain: 0,
+        };
+        // Release did not land: project still on the proposal's milestone.
+        assert!(passed.release_pending(1));
+        // Release landed: retry is a no-op error.
+        assert!(!passed.release_pending(2));
+        // Queued proposals go through execute_proposal instead.
+        let queued = Proposal {
+            executable_at: 100,
+            ..passed.clone()
+        };
+        assert!(!queued.release_pending(1));
+        let rejected = Proposal {
+            status: ProposalStatus::Rejected,
+            ..passed.clone()
+        };
+        assert!(!rejected.release_pending(1));
+        let material_edit = Proposal {
+            milestone_index: 255,
+            ..passed.clone()
+        };
+        assert!(!material_edit.release_pending(255));
+        // A passed slash proposal cancels the project; it never leaves a milestone release to retry.
+        let slash = Proposal {
+            milestone_index: SLASH_PROPOSAL_INDEX,
+            ..passed
+        };
+        assert!(!slash.release_pending(SLASH_PROPOSAL_INDEX));
+    }
+
+    #[test]
+    fn test_proposal_metadata_bounds() {
+        let uri = "u".repeat(MAX_PROOF_URI_LEN);
+        let title = "t".repeat(MAX_PROPOSAL_TITLE_LEN);
+        assert!(validate_proposal_metadata(&uri, &title).is_ok());
+        assert!(validate_proposal_metadata("", "").is_ok());
+        assert!(validate_proposal_metadata(&uri, &format!("{title}t")).is_err());
+        assert!(validate_proposal_metadata(&format!("{uri}u"), "").is_err());
+
+        // A proposal at both maxima fits PROPOSAL_SPACE exactly.
+        let proposal = Proposal {
+            project: Pubkey::new_unique(),
+            milestone_index: 0,
+            proof_uri: uri,
+            votes_for: 0,
+            votes_against: 0,
+            status: ProposalStatus::Active,
+            start_ts: 0,
+            end_ts: 0,
+            creator: Pubkey::new_unique(),
+            votes_abstain: 0,
+            executable_at: 0,
+            weight_source: WeightSource::Backer,
+            title,
+            description_hash: [7; 32],
+            boost_for: u64::MAX,
+            boost_against: u64::MAX,
+            boost_abstain: u64::MAX,
+        };
+        let mut data = Vec::new();
+        proposal.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), PROPOSAL_SPACE);
+    }
+
+    #[test]
+    fn test_proof_uri_requires_accepted_scheme() {
+        for uri in [
+            "https://proof.example/m0",
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            "ar://Xc9PqX1FpUcA6Xo0e2Y3cOY8LqGZ0bQ0z8l9Wl6t3Hk",
+        ] {
+            assert!(validate_proof_uri(uri).is_ok(), "{uri}");
+        }
+        for uri in [
+            "",
+            "https://",
+            "http://proof.example/m0",
+            "proof.example/m0",
+            "HTTPS://proof.example/m0",
+            " https://proof.example/m0",
+            "ftp://proof.example/m0",
+        ] {
+            assert!(validate_proof_uri(uri).is_err(), "{uri:?}");
+        }
+    }
+
+    #[test]
+    fn test_custom_quorum_bps() {
+        let total_raised: u64 = 100_000 * 1_000_000_000;
+        // Turnout of sqrt(10% of raised): short of the default 20% quorum, enough for a 10% quorum.
+        let turnout = sqrt_u64(10_000 * 1_000_000_000);
+        assert!(
+            turnout < quorum_votes(total_raised, total_raised, QuorumBasis::Raised, QUORUM_BPS)
+        );
+        let config = GovConfig {
+            allow_early_finalize: false,
+            min_voting_period_secs: 1,
+            min_backers_for_governance: 0,
+            quorum_basis: QuorumBasis::Raised,
+            min_participation_bps: 0,
+            quorum_bps: 1_000,
+            material_edit_threshold_bps: 0,
+            execution_delay_secs: 0,
+            min_vote_contribution: 0,
+            proposal_bond: 0,
+            bond_mint: Pubkey::default(),
+            bond_treasury: Pubkey::default(),
+        };
+        assert!(
+            turnout
+                >= quorum_votes(
+                    total_raised,
+                    total_raised,
+                    config.quorum_basis,
+                    config.effective_quorum_bps()
+                )
+        );
+        // Unset quorum_bps keeps the 20% default.
+        let unset = GovConfig {
+            quorum_bps: 0,
+            ..config
+        };
+        assert_eq!(unset.effective_quorum_bps(), QUORUM_BPS);
+    }
+
+    #[test]
+    fn test_min_voting_period_floor() {
+        let config = GovConfig {
+            allow_early_finalize: false,
+            min_voting_period_secs: 1,
+            min_backers_for_governance: 0,
+            quorum_basis: QuorumBasis::Raised,
+            min_participation_bps: 0,
+            quorum_bps: 0,
+            material_edit_threshold_bps: 0,
+            execution_delay_secs: 0,
+            min_vote_contribution: 0,
+            proposal_bond: 0,
+            bond_mint: Pubkey::default(),
+            bond_treasury: Pubkey::default(),
+        };
+        assert_eq!(min_voting_period(None), MIN_VOTING_PERIOD_SECS);
+        // A sub-floor config (e.g. a legacy one) is clamped up to the floor.
+        assert_eq!(min_voting_period(Some(&config)), VOTING_PERIOD_FLOOR_SECS);
+        // A config can still raise the minimum.
+        let strict = GovConfig {
+            min_voting_period_secs: 7 * 24 * 3600,
+            ..config
+        };
+        assert_eq!(min_voting_period(Some(&strict)), 7 * 24 * 3600);
+        #[cfg(not(feature = "test"))]
+        assert_eq!(
+            min_voting_period(Some(&GovConfig {
+                min_voting_period_secs: 60,
+                ..config
+            })),
+            3600
+        );
+    }
+
     #[test]
     fn test_quorum_calculation() {
         // Quorum = sqrt(20% * total_raised). Same units as vote weights (sqrt of lamports).
@@ -997,6 +3532,33 @@ mod tests {
         assert!(quorum_votes <= 150_000_000); // sanity: sqrt(20e12) ≈ 4.47e6
     }
 
+    #[test]
+    fn test_proposal_bond_outcome() {
+        use ProposalStatus::*;
+        // Honest finalize refunds either way.
+        assert_eq!(
+            bond_outcome(Passed, true, 10, 5).unwrap(),
+            BondOutcome::Refund
+        );
+        assert_eq!(
+            bond_outcome(Rejected, true, 10, 5).unwrap(),
+            BondOutcome::Refund
+        );
+        // Cancelled or vetoed proposals forfeit.
+        assert_eq!(
+            bond_outcome(Cancelled, false, 0, 5).unwrap(),
+            BondOutcome::Forfeit
+        );
+        // Voting ended below quorum: the proposal can never finalize, so the bond is forfeited.
+        assert_eq!(
+            bond_outcome(Active, true, 4, 5).unwrap(),
+            BondOutcome::Forfeit
+        );
+        // Still open, or ended with quorum but not yet finalized: nothing to settle.
+        assert!(bond_outcome(Active, false, 0, 5).is_err());
+        assert!(bond_outcome(Active, true, 5, 5).is_err());
+    }
+
     #[test]
     fn test_read_gov_config_optional_empty_remaining_accounts() {
         let program_id = crate::ID;
@@ -1004,6 +3566,378 @@ mod tests {
         assert!(out.is_none());
     }
 
+    #[test]
+    fn test_gov_config_from_legacy_layout() {
+        let mut legacy = GovConfig::DISCRIMINATOR.to_vec();
+        legacy.push(1);
+        legacy.extend_from_slice(&3600i64.to_le_bytes());
+        assert_eq!(legacy.len(), GOV_CONFIG_BASE_LEN);
+        let cfg = GovConfig::from_account_data(&legacy).unwrap();
+        assert!(cfg.allow_early_finalize);
+        assert_eq!(cfg.min_voting_period_secs, 3600);
+        assert_eq!(cfg.min_backers_for_governance, 0);
+        assert_eq!(cfg.quorum_basis, QuorumBasis::Raised);
+
+        let current = GovConfig {
+            allow_early_finalize: false,
+            min_voting_period_secs: 60,
+            min_backers_for_governance: 3,
+            quorum_basis: QuorumBasis::Goal,
+            min_participation_bps: 6_000,
+            quorum_bps: 1_500,
+            material_edit_threshold_bps: 6_667,
+            execution_delay_secs: 3_600,
+            min_vote_contribution: 5_000,
+            proposal_bond: 1_000,
+            bond_mint: Pubkey::new_unique(),
+            bond_treasury: Pubkey::new_unique(),
+        };
+        let mut data = Vec::new();
+        current.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), GOV_CONFIG_SPACE);
+        let parsed = GovConfig::from_account_data(&data).unwrap();
+        assert_eq!(parsed.min_backers_for_governance, 3);
+        assert_eq!(parsed.quorum_basis, QuorumBasis::Goal);
+        assert_eq!(parsed.min_participation_bps, 6_000);
+        assert_eq!(parsed.effective_quorum_bps(), 1_500);
+        assert_eq!(parsed.material_edit_threshold_bps, 6_667);
+        assert_eq!(parsed.execution_delay_secs, 3_600);
+        assert_eq!(parsed.min_vote_contribution, 5_000);
+        assert_eq!(parsed.proposal_bond, 1_000);
+        assert_eq!(parsed.bond_treasury, current.bond_treasury);
+        // A config written before the proposal bond fields takes no bond.
+        let parsed = GovConfig::from_account_data(&data[..GOV_CONFIG_SPACE - 72]).unwrap();
+        assert_eq!(parsed.min_vote_contribution, 5_000);
+        assert_eq!(parsed.proposal_bond, 0);
+        assert_eq!(parsed.bond_mint, Pubkey::default());
+        // A config written before min_vote_contribution existed lets any contribution vote.
+        let parsed = GovConfig::from_account_data(&data[..GOV_CONFIG_SPACE - 80]).unwrap();
+        assert_eq!(parsed.execution_delay_secs, 3_600);
+        assert_eq!(parsed.min_vote_contribution, 0);
+        // A config written before execution_delay_secs existed releases at finalize.
+        let parsed = GovConfig::from_account_data(&data[..GOV_CONFIG_SPACE - 88]).unwrap();
+        assert_eq!(parsed.material_edit_threshold_bps, 6_667);
+        assert_eq!(parsed.execution_delay_secs, 0);
+        // A config written before material_edit_threshold_bps existed keeps simple majority.
+        let parsed = GovConfig::from_account_data(&data[..GOV_CONFIG_SPACE - 90]).unwrap();
+        assert_eq!(parsed.effective_quorum_bps(), 1_500);
+        assert_eq!(parsed.material_edit_threshold_bps, 0);
+        // A config written before quorum_bps existed uses the default quorum.
+        let parsed = GovConfig::from_account_data(&data[..GOV_CONFIG_SPACE - 92]).unwrap();
+        assert_eq!(parsed.min_participation_bps, 6_000);
+        assert_eq!(parsed.effective_quorum_bps(), QUORUM_BPS);
+        // A config written before min_participation_bps existed has no participation floor.
+        let parsed = GovConfig::from_account_data(&data[..GOV_CONFIG_SPACE - 94]).unwrap();
+        assert_eq!(parsed.quorum_basis, QuorumBasis::Goal);
+        assert_eq!(parsed.min_participation_bps, 0);
+        // A config written before quorum_basis existed reads as Raised.
+        let parsed = GovConfig::from_account_data(&data[..GOV_CONFIG_SPACE - 95]).unwrap();
+        assert_eq!(parsed.quorum_basis, QuorumBasis::Raised);
+
+        assert!(GovConfig::from_account_data(&legacy[..GOV_CONFIG_BASE_LEN - 1]).is_none());
+    }
+
+    #[test]
+    fn test_delegated_weight_single_delegation() {
+        let project = Pubkey::new_unique();
+        let delegator = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let delegation = VoteDelegation {
+            project,
+            delegator,
+            delegate,
+        };
+        let backer = project_escrow::Backer {
+            wallet: delegator,
+            project,
+            amount: 10_000,
+            claimed_rwa: false,
+            fee_paid: 0,
+            treasury_fee_paid: 0,
+        };
+        // The delegate votes with their own sqrt weight plus the delegator's.
+        let own = sqrt_u64(40_000);
+        let carried = delegated_weight(&project, &delegate, &delegation, &backer).unwrap();
+        assert_eq!(carried, 100);
+        assert_eq!(own + carried, 300);
+
+        // Only the named delegate can carry it, and only with the delegator's own Backer in this project.
+        assert!(delegated_weight(&project, &delegator, &delegation, &backer).is_err());
+        let other_wallet = project_escrow::Backer {
+            wallet: Pubkey::new_unique(),
+            ..backer.clone()
+        };
+        assert!(delegated_weight(&project, &delegate, &delegation, &other_wallet).is_err());
+        assert!(delegated_weight(&Pubkey::new_unique(), &delegate, &delegation, &backer).is_err());
+        let empty = project_escrow::Backer {
+            amount: 0,
+            ..backer.clone()
+        };
+        assert!(delegated_weight(&project, &delegate, &delegation, &empty).is_err());
+    }
+
+    #[test]
+    fn test_abstain_counts_toward_quorum_not_outcome() {
+        let mut p = Proposal {
+            project: Pubkey::default(),
+            milestone_index: 0,
+            proof_uri: String::new(),
+            votes_for: 0,
+            votes_against: 0,
+            status: ProposalStatus::Active,
+            start_ts: 0,
+            end_ts: 0,
+            creator: Pubkey::default(),
+            votes_abstain: 0,
+            executable_at: 0,
+            weight_source: WeightSource::Backer,
+            title: String::new(),
+            description_hash: [0; 32],
+            boost_for: 0,
+            boost_against: 0,
+            boost_abstain: 0,
+        };
+        let quorum = 100;
+        (p.votes_for, p.votes_against, p.votes_abstain) =
+            tally_vote((0, 0, 0), None, VoteSide::For, 30).unwrap();
+        (p.votes_for, p.votes_against, p.votes_abstain) = tally_vote(
+            (p.votes_for, p.votes_against, p.votes_abstain),
+            None,
+            VoteSide::Against,
+            20,
+        )
+        .unwrap();
+        assert!(p.total_votes().unwrap() < quorum);
+        // Abstain weight pushes turnout over quorum without changing which side wins.
+        (p.votes_for, p.votes_against, p.votes_abstain) = tally_vote(
+            (p.votes_for, p.votes_against, p.votes_abstain),
+            None,
+            VoteSide::Abstain,
+            60,
+        )
+        .unwrap();
+        assert_eq!(p.total_votes().unwrap(), 110);
+        assert!(p.total_votes().unwrap() >= quorum);
+        assert!(p.votes_for > p.votes_against);
+        // An abstain-only majority does not pass a proposal.
+        let (f, a, abstain) = tally_vote((0, 0, 0), None, VoteSide::Abstain, 100).unwrap();
+        assert_eq!(abstain, 100);
+        assert!(f <= a);
+    }
+
+    #[test]
+    fn test_stake_boost_excluded_from_quorum_and_early_finalize() {
+        let config = GovConfig {
+            allow_early_finalize: true,
+            min_voting_period_secs: 1,
+            min_backers_for_governance: 0,
+            quorum_basis: QuorumBasis::Raised,
+            min_participation_bps: 0,
+            quorum_bps: 0,
+            material_edit_threshold_bps: 0,
+            execution_delay_secs: 0,
+            min_vote_contribution: 0,
+            proposal_bond: 0,
+            bond_mint: Pubkey::default(),
+            bond_treasury: Pubkey::default(),
+        };
+        // One backer with sqrt weight 10 of a 100 total, plus a stake boost of 200.
+        let p = Proposal {
+            project: Pubkey::default(),
+            milestone_index: 0,
+            proof_uri: String::new(),
+            votes_for: 210,
+            votes_against: 0,
+            status: ProposalStatus::Active,
+            start_ts: 0,
+            end_ts: 0,
+            creator: Pubkey::default(),
+            votes_abstain: 0,
+            executable_at: 0,
+            weight_source: WeightSource::Backer,
+            title: String::new(),
+            description_hash: [0; 32],
+            boost_for: 200,
+            boost_against: 0,
+            boost_abstain: 0,
+        };
+        // Counted with the boost the vote alone would look decided.
+        assert!(early_finalize_allowed(210, 0, 0, Some(100), Some(&config)));
+        let (f, a, abstain) = p.unboosted_votes();
+        assert_eq!((f, a, abstain), (10, 0, 0));
+        assert!(!early_finalize_allowed(
+            f,
+            a,
+            abstain,
+            Some(100),
+            Some(&config)
+        ));
+        assert_eq!(p.total_votes().unwrap(), 10);
+    }
+
+    #[test]
+    fn test_vote_side_decodes_legacy_bool_byte() {
+        assert_eq!(VoteSide::try_from_slice(&[0]).unwrap(), VoteSide::Against);
+        assert_eq!(VoteSide::try_from_slice(&[1]).unwrap(), VoteSide::For);
+        assert_eq!(VoteSide::try_from_slice(&[2]).unwrap(), VoteSide::Abstain);
+        assert_eq!(
+            PROPOSAL_SPACE,
+            PROPOSAL_BASE_LEN + 8 + 8 + 1 + 4 + MAX_PROPOSAL_TITLE_LEN + 32 + 8 + 8 + 8
+        );
+        assert_eq!(WeightSource::default(), WeightSource::Backer);
+    }
+
+    #[test]
+    fn test_vote_change_moves_weight_without_double_counting() {
+        use VoteSide::*;
+        // Voter with weight 30 votes for; another voter has 50 against.
+        let t = tally_vote((0, 50, 0), None, For, 30).unwrap();
+        assert_eq!(t, (30, 50, 0));
+        // Flip to against: removed from for, added to against once.
+        let t = tally_vote(t, Some((For, 30)), Against, 30).unwrap();
+        assert_eq!(t, (0, 80, 0));
+        // Re-voting the same side leaves the tallies unchanged.
+        let t = tally_vote(t, Some((Against, 30)), Against, 30).unwrap();
+        assert_eq!(t, (0, 80, 0));
+        // A changed vote carrying a new delegation adds only the new weight.
+        let t = tally_vote(t, Some((Against, 30)), For, 40).unwrap();
+        assert_eq!(t, (40, 50, 0));
+        assert!(tally_vote((0, 0, 0), Some((For, 1)), Against, 1).is_err());
+    }
+
+    #[test]
+    fn test_delegated_vote_count() {
+        assert_eq!(delegated_vote_count(0).unwrap(), 0);
+        assert_eq!(
+            delegated_vote_count(DELEGATION_ACCOUNTS_PER_DELEGATOR).unwrap(),
+            1
+        );
+        assert!(delegated_vote_count(DELEGATION_ACCOUNTS_PER_DELEGATOR + 1).is_err());
+        assert!(delegated_vote_count(
+            DELEGATION_ACCOUNTS_PER_DELEGATOR * (MAX_DELEGATIONS_PER_VOTE + 1)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_stake_vote_boost() {
+        let voter = Pubkey::new_unique();
+        let stake = taste_token::StakeAccount {
+            owner: voter,
+            amount: 1_000_000,
+            unlock_ts: 500,
+            multiplier_bps: taste_token::MAX_STAKE_MULTIPLIER_BPS,
+            bump: 255,
+        };
+        // 2x multiplier: sqrt(2_000_000).
+        assert_eq!(
+            stake_vote_boost(&stake, &voter, 500).unwrap(),
+            sqrt_u64(2_000_000)
+        );
+        // Stake unlocking before the vote ends does not count.
+        assert!(stake_vote_boost(&stake, &voter, 501).is_err());
+        assert!(stake_vote_boost(&stake, &Pubkey::new_unique(), 500).is_err());
+    }
+
+    #[test]
+    fn test_early_finalize_participation_floor() {
+        let config = GovConfig {
+            allow_early_finalize: true,
+            min_voting_period_secs: 1,
+            min_backers_for_governance: 0,
+            quorum_basis: QuorumBasis::Raised,
+            min_participation_bps: 8_000,
+            quorum_bps: 0,
+            material_edit_threshold_bps: 0,
+            execution_delay_secs: 0,
+            min_vote_contribution: 0,
+            proposal_bond: 0,
+            bond_mint: Pubkey::default(),
+            bond_treasury: Pubkey::default(),
+        };
+        // Decided (60 of 100 for) but only 70% turnout: blocked under the 80% floor.
+        assert!(!early_finalize_allowed(60, 10, 0, Some(100), Some(&config)));
+        // Same majority with 80% turnout: allowed.
+        assert!(early_finalize_allowed(60, 20, 0, Some(100), Some(&config)));
+        // Abstain weight counts toward turnout.
+        assert!(early_finalize_allowed(60, 10, 10, Some(100), Some(&config)));
+        // Undecided outcomes stay blocked regardless of turnout.
+        assert!(!early_finalize_allowed(
+            45,
+            45,
+            10,
+            Some(100),
+            Some(&config)
+        ));
+        // No floor keeps the previous behavior; disabled early finalize or unknown weight never allows it.
+        let no_floor = GovConfig {
+            min_participation_bps: 0,
+            ..config.clone()
+        };
+        assert!(early_finalize_allowed(60, 0, 0, Some(100), Some(&no_floor)));
+        let disabled = GovConfig {
+            allow_early_finalize: false,
+            ..config.clone()
+        };
+        assert!(!early_finalize_allowed(
+            100,
+            0,
+            0,
+            Some(100),
+            Some(&disabled)
+        ));
+        assert!(!early_finalize_allowed(100, 0, 0, None, Some(&config)));
+        assert!(!early_finalize_allowed(100, 0, 0, Some(0), Some(&config)));
+        assert!(!early_finalize_allowed(100, 0, 0, Some(100), None));
+    }
+
+    #[test]
+    fn test_rescinded_refund_vote_does_not_decide_early() {
+        use VoteSide::*;
+        let config = GovConfig {
+            allow_early_finalize: true,
+            min_voting_period_secs: 1,
+            min_backers_for_governance: 0,
+            quorum_basis: QuorumBasis::Raised,
+            min_participation_bps: 0,
+            quorum_bps: 0,
+            material_edit_threshold_bps: 0,
+            execution_delay_secs: 0,
+            min_vote_contribution: 0,
+            proposal_bond: 0,
+            bond_mint: Pubkey::default(),
+            bond_treasury: Pubkey::default(),
+        };
+        // Backers of weight 40 (for), 20 (against) and 40 (not voted) out of 100: undecided.
+        let t = tally_vote((0, 0, 0), None, For, 40).unwrap();
+        let t = tally_vote(t, None, Against, 20).unwrap();
+        assert!(!early_finalize_allowed(
+            t.0,
+            t.1,
+            t.2,
+            Some(100),
+            Some(&config)
+        ));
+        // The for-voter refunds: total weight drops to 60 but their 40 still counts, deciding "for".
+        assert!(early_finalize_allowed(
+            t.0,
+            t.1,
+            t.2,
+            Some(60),
+            Some(&config)
+        ));
+        // Rescinding the refunded vote leaves 20 against of 60: undecided again.
+        let t = tally_vote(t, Some((For, 40)), For, 0).unwrap();
+        assert_eq!(t, (0, 20, 0));
+        assert!(!early_finalize_allowed(
+            t.0,
+            t.1,
+            t.2,
+            Some(60),
+            Some(&config)
+        ));
+    }
+
     #[test]
     fn test_read_early_finalize_params_empty_remaining_accounts() {
         let program_id = crate::ID;
@@ -1015,4 +3949,70 @@ mod tests {
         assert!(gov.is_none());
         assert!(tw.is_none());
     }
+
+    #[test]
+    fn test_vote_space_fits_delegate() {
+        let vote = Vote {
+            proposal: Pubkey::new_unique(),
+            voter: Pubkey::new_unique(),
+            weight: u64::MAX,
+            side: VoteSide::Abstain,
+            delegate: Pubkey::new_unique(),
+            boost: u64::MAX,
+        };
+        let mut data = Vec::new();
+        vote.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), VOTE_SPACE);
+        // migrate_vote grows the pre-delegate layout by exactly the new fields.
+        assert_eq!(VOTE_SPACE - VOTE_BASE_LEN, 32 + 8);
+    }
+
+    #[test]
+    fn test_rwa_vote_lock_space() {
+        let lock = RwaVoteLock {
+            proposal: Pubkey::new_unique(),
+            voter: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            amount: u64::MAX,
+        };
+        let mut data = Vec::new();
+        lock.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), RWA_VOTE_LOCK_SPACE);
+    }
+
+    #[test]
+    fn test_payout_recipient_accounts_skips_early_finalize_pdas() {
+        let program_id = Pubkey::new_unique();
+        let escrow_program = Pubkey::new_unique();
+        let project = Pubkey::new_unique();
+        let (config_pda, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+        let (vote_weight_pda, _) =
+            Pubkey::find_program_address(&[b"vote_weight", project.as_ref()], &escrow_program);
+        let keys = [
+            config_pda,
+            vote_weight_pda,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let owner = Pubkey::new_unique();
+        let mut lamports = [0u64; 4];
+        let mut data: [Vec<u8>; 4] = Default::default();
+        let infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, true, lamports, data, &owner, false, 0)
+            })
+            .collect();
+
+        let forwarded = payout_recipient_accounts(&program_id, &escrow_program, &project, &infos);
+        let forwarded: Vec<Pubkey> = forwarded.iter().map(|a| a.key()).collect();
+        assert_eq!(forwarded, keys[2..].to_vec());
+        // Only the early-finalize PDAs were passed: nothing is forwarded.
+        assert!(
+            payout_recipient_accounts(&program_id, &escrow_program, &project, &infos[..2])
+                .is_empty()
+        );
+    }
 }
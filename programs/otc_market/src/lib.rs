@@ -2,7 +2,12 @@
 //! Token-2022 only for asset and quote mint. Escrow: maker deposits on create; taker-only sign on accept.
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::spl_token_2022::{
+    extension::{transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as SplMint,
+};
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+use rwa_token::RwaState;
 
 #[cfg(not(feature = "devnet"))]
 declare_id!("Gm3e8Ky9mLBe2bjNbMh3qA3oVvaRca4p9BKKv3dLY7dD");
@@ -12,6 +17,283 @@ declare_id!("6FM7VKFLyzxubAhCY58rR1R42tuuVNY7QdAtNTq65EjN");
 /// SPL Token-2022 program ID. Marketplace accepts only Token-2022 assets and quote.
 pub static TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 
+/// Default platform fee on the quote leg of a fill (1%).
+pub const DEFAULT_FEE_BPS: u16 = 100;
+/// Upper bound for the platform fee (10%).
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+/// Accounts per offer in cancel_offers_batch remaining_accounts: (offer, escrow_authority, escrow_ata, mint, maker_ata).
+pub const BATCH_CANCEL_ACCOUNTS_PER_OFFER: usize = 5;
+/// Max offers per cancel_offers_batch. 5 offers = 25 remaining accounts + 2 fixed, which fits a legacy transaction.
+pub const MAX_BATCH_CANCEL: usize = 5;
+
+/// Current OtcConfig account size (including max_open_offers).
+pub const OTC_CONFIG_SPACE: usize = 8 + 2 + 32 + 1 + 32 + 32 + 4;
+/// Size of configs created before max_open_offers was added; grown by update_otc_config.
+const OTC_CONFIG_BASE_LEN: usize = 8 + 2 + 32 + 1 + 32 + 32;
+/// Current MakerState account size (including open_offers).
+pub const MAKER_STATE_SPACE: usize = 8 + 8 + 4;
+/// Size of maker states created before open_offers was added; grown by migrate_maker_state.
+const MAKER_STATE_BASE_LEN: usize = 8 + 8;
+
+/// Current Offer account size (including expiry_unix, filled and allowed_taker).
+pub const OFFER_SPACE: usize = OFFER_BASE_LEN + 8 + 8 + 1 + 32;
+/// Size of offers created before expiry_unix, filled and allowed_taker were added; grown by migrate_offer.
+const OFFER_BASE_LEN: usize = 8 + 32 + 32 + 8 + 8 + 1 + 1 + 8 + 1 + 8;
+
+/// Upgradeable loader: Program variant.
+const UPGRADEABLE_LOADER_PROGRAM_STATE: u8 = 2;
+/// Upgradeable loader: ProgramData variant.
+const UPGRADEABLE_LOADER_PROGRAM_DATA_STATE: u8 = 3;
+const MIN_PROGRAM_ACCOUNT_LEN: usize = 36;
+const MIN_PROGRAMDATA_METADATA_LEN: usize = 45;
+
+fn require_upgrade_authority(
+    program_id: &Pubkey,
+    program_account_key: &Pubkey,
+    program_account_data: &[u8],
+    program_data_account_key: &Pubkey,
+    program_data_account_data: &[u8],
+    authority_key: &Pubkey,
+) -> Result<()> {
+    require!(
+        program_account_key == program_id,
+        OtcError::NotUpgradeAuthority
+    );
+    require!(
+        program_account_data.len() >= MIN_PROGRAM_ACCOUNT_LEN
+            && u32::from_le_bytes(program_account_data[0..4].try_into().unwrap())
+                == UPGRADEABLE_LOADER_PROGRAM_STATE as u32,
+        OtcError::NotUpgradeAuthority
+    );
+    let programdata_address =
+        Pubkey::new_from_array(program_account_data[4..36].try_into().unwrap());
+    require!(
+        program_data_account_key == &programdata_address,
+        OtcError::NotUpgradeAuthority
+    );
+    require!(
+        program_data_account_data.len() >= MIN_PROGRAMDATA_METADATA_LEN
+            && u32::from_le_bytes(program_data_account_data[0..4].try_into().unwrap())
+                == UPGRADEABLE_LOADER_PROGRAM_DATA_STATE as u32,
+        OtcError::NotUpgradeAuthority
+    );
+    let option_byte = program_data_account_data[12];
+    require!(option_byte == 1, OtcError::NotUpgradeAuthority);
+    let upgrade_authority =
+        Pubkey::new_from_array(program_data_account_data[13..45].try_into().unwrap());
+    require!(
+        upgrade_authority == *authority_key,
+        OtcError::NotUpgradeAuthority
+    );
+    Ok(())
+}
+
+/// Open-offer count after a maker opens one more offer or auction. `max_open_offers` of 0 means unlimited.
+pub fn open_offer_slot(open_offers: u32, max_open_offers: u32) -> Result<u32> {
+    require!(
+        max_open_offers == 0 || open_offers < max_open_offers,
+        OtcError::MaxOpenOffersReached
+    );
+    Ok(open_offers.checked_add(1).ok_or(OtcError::Overflow)?)
+}
+
+/// Free one open-offer slot on a maker's MakerState once an offer is cancelled or fully taken, or an auction is
+/// cancelled or settled. Maker states still on the pre-counter layout are left alone; offers and auctions created
+/// before they were counted never took a slot, so the count saturates at zero.
+fn release_open_offer(maker_state: &AccountInfo) -> Result<()> {
+    if maker_state.owner != &crate::ID || maker_state.data_len() < MAKER_STATE_SPACE {
+        return Ok(());
+    }
+    let mut data = maker_state.try_borrow_mut_data()?;
+    let mut state = MakerState::try_deserialize(&mut &data[..])?;
+    state.open_offers = state.open_offers.saturating_sub(1);
+    state.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Upgrade raw Offer data created before `expiry_unix` existed. Fails if the data is not a legacy Offer.
+/// Such offers could only be accepted while `clock.slot <= expiry_slot`, so one with `expiry_slot == 0` was
+/// already expired; it gets `expiry_unix = 1` so 0 keeping its new "unused" meaning does not reopen it.
+pub fn migrate_offer_data(data: &[u8]) -> Result<Offer> {
+    require!(
+        data.len() >= OFFER_BASE_LEN && &data[..8] == Offer::DISCRIMINATOR,
+        OtcError::InvalidOfferAccount
+    );
+    require!(data.len() < OFFER_SPACE, OtcError::OfferAlreadyMigrated);
+    let mut buf = data.to_vec();
+    buf.resize(OFFER_SPACE, 0);
+    let mut offer = Offer::try_deserialize(&mut &buf[..])?;
+    if data.len() == OFFER_BASE_LEN && offer.expiry_slot == 0 {
+        offer.expiry_unix = 1;
+    }
+    Ok(offer)
+}
+
+/// Platform fee taken from a quote amount (rounded down).
+pub fn platform_fee(quote: u64, fee_bps: u16) -> Option<u64> {
+    let fee = (quote as u128).checked_mul(fee_bps as u128)? / 10_000;
+    u64::try_from(fee).ok()
+}
+
+/// Whether `mint` may be listed under `config`. With `restrict_assets` off every mint is allowed; with it on,
+/// the maker must name the asset's origin and the mint must be that origin's PDA (RWA mint or IOU receipt).
+pub fn asset_mint_allowed(config: &OtcConfig, origin: Option<&AssetOrigin>, mint: &Pubkey) -> bool {
+    if !config.restrict_assets {
+        return true;
+    }
+    let expected = match origin {
+        Some(AssetOrigin::Rwa { project }) => {
+            Pubkey::find_program_address(
+                &[b"rwa_mint", project.as_ref()],
+                &config.rwa_token_program,
+            )
+            .0
+        }
+        Some(AssetOrigin::Receipt { project, backer }) => {
+            Pubkey::find_program_address(
+                &[b"receipt", project.as_ref(), backer.as_ref()],
+                &config.project_escrow_program,
+            )
+            .0
+        }
+        None => return false,
+    };
+    expected == *mint
+}
+
+/// True when `mint` is a Token-2022 mint carrying a TransferHook extension, as every rwa_token mint does.
+pub fn mint_has_transfer_hook(mint: &AccountInfo) -> Result<bool> {
+    let data = mint.try_borrow_data()?;
+    let Ok(state) = StateWithExtensions::<SplMint>::unpack(&data) else {
+        return Ok(false);
+    };
+    Ok(state.get_extension::<TransferHook>().is_ok())
+}
+
+/// RWA listings must show the project's tokens have been distributed. A listing counts as RWA when the maker
+/// names an Rwa origin or when the mint has a TransferHook extension, so leaving `asset_origin` unset does not
+/// skip the check. `rwa_state` must then be the rwa_token RwaState of the project whose rwa_mint PDA is
+/// `asset_mint` (and of the named project, if any), with `minted > 0`. Receipts are not checked.
+pub fn require_rwa_distributed(
+    config: &OtcConfig,
+    origin: Option<&AssetOrigin>,
+    asset_mint: &AccountInfo,
+    rwa_state: Option<&AccountInfo>,
+) -> Result<()> {
+    let named_project = match origin {
+        Some(AssetOrigin::Rwa { project }) => Some(*project),
+        _ => None,
+    };
+    if named_project.is_none() && !mint_has_transfer_hook(asset_mint)? {
+        return Ok(());
+    }
+    let rwa_state = rwa_state.ok_or(OtcError::RwaNotDistributed)?;
+    require!(
+        *rwa_state.owner == config.rwa_token_program,
+        OtcError::RwaNotDistributed
+    );
+    let data = rwa_state.try_borrow_data()?;
+    let state = RwaState::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(OtcError::RwaNotDistributed))?;
+    let (expected_state, _) = Pubkey::find_program_address(
+        &[b"rwa_state", state.project.as_ref()],
+        &config.rwa_token_program,
+    );
+    let (expected_mint, _) = Pubkey::find_program_address(
+        &[b"rwa_mint", state.project.as_ref()],
+        &config.rwa_token_program,
+    );
+    require!(
+        rwa_state.key() == expected_state
+            && asset_mint.key() == expected_mint
+            && named_project.is_none_or(|project| project == state.project),
+        OtcError::RwaNotDistributed
+    );
+    require!(state.minted > 0, OtcError::RwaNotDistributed);
+    Ok(())
+}
+
+/// Number of offers described by `remaining_len` batch-cancel accounts, if it is a whole number of groups within MAX_BATCH_CANCEL.
+pub fn batch_cancel_count(remaining_len: usize) -> Option<usize> {
+    let count = remaining_len / BATCH_CANCEL_ACCOUNTS_PER_OFFER;
+    let whole_groups = count * BATCH_CANCEL_ACCOUNTS_PER_OFFER == remaining_len;
+    (count > 0 && whole_groups && count <= MAX_BATCH_CANCEL).then_some(count)
+}
+
+/// Escrow ATA an offer's (or auction's) tokens sit in: the Token-2022 ATA of its escrow authority for
+/// `escrowed_mint`. The authority is unique per (maker, nonce), so this pins the escrow to exactly one offer or
+/// auction and mint.
+pub fn offer_escrow_ata(escrow_authority: &Pubkey, escrowed_mint: &Pubkey) -> Pubkey {
+    anchor_spl::associated_token::get_associated_token_address_with_program_id(
+        escrow_authority,
+        escrowed_mint,
+        &TOKEN_2022_PROGRAM_ID,
+    )
+}
+
+/// Fails when the optional taste_token ProtocolState passed in says the protocol is paused.
+fn require_not_paused(protocol_state: Option<&AccountInfo>) -> Result<()> {
+    if let Some(info) = protocol_state {
+        require!(
+            !taste_token::protocol_paused(info)?,
+            OtcError::ProtocolPaused
+        );
+    }
+    Ok(())
+}
+
+/// Smallest bid `place_bid` accepts: the reserve (at least 1) for the first bid, then the current
+/// highest bid plus `min_increment` (at least 1, so every new bid strictly outbids the last).
+pub fn min_next_bid(highest_bid: u64, reserve_price: u64, min_increment: u64) -> Option<u64> {
+    if highest_bid == 0 {
+        Some(reserve_price.max(1))
+    } else {
+        highest_bid.checked_add(min_increment.max(1))
+    }
+}
+
+/// Quote owed for filling `fill_amount` of an offer with `remaining_amount` left at `remaining_price` total.
+/// Rounds up when the taker pays (sell) and down when escrow pays (buy), so rounding always favors the maker.
+/// Filling the whole remainder returns exactly `remaining_price`, so partial fills sum to the listed total.
+pub fn quote_for_fill(
+    remaining_amount: u64,
+    remaining_price: u64,
+    fill_amount: u64,
+    round_up: bool,
+) -> Option<u64> {
+    if remaining_amount == 0 || fill_amount > remaining_amount {
+        return None;
+    }
+    let numerator = (remaining_price as u128).checked_mul(fill_amount as u128)?;
+    let denominator = remaining_amount as u128;
+    let quote = if round_up {
+        numerator.checked_add(denominator - 1)? / denominator
+    } else {
+        numerator / denominator
+    };
+    u64::try_from(quote).ok()
+}
+
+/// Taker-side slippage check for accept_offer: the offer's remaining total price must not exceed `max_price`
+/// and its remaining amount must be at least `min_amount` (None skips that check).
+pub fn check_offer_limits(
+    price: u64,
+    amount: u64,
+    max_price: Option<u64>,
+    min_amount: Option<u64>,
+) -> Result<()> {
+    require!(
+        max_price.is_none_or(|max| price <= max),
+        OtcError::PriceChanged
+    );
+    require!(
+        min_amount.is_none_or(|min| amount >= min),
+        OtcError::PriceChanged
+    );
+    Ok(())
+}
+
 #[program]
 pub mod otc_market {
     use super::*;
@@ -21,13 +303,115 @@ pub mod otc_market {
         Ok(())
     }
 
+    /// One-time init of marketplace config (fee and treasury). Only the program upgrade authority.
+    /// `fee_bps` defaults to DEFAULT_FEE_BPS when None. With `restrict_assets`, only RWA mints and IOU receipts
+    /// derived from the given rwa_token / project_escrow program IDs can be listed.
+    pub fn initialize_otc_config(
+        ctx: Context<InitializeOtcConfig>,
+        fee_bps: Option<u16>,
+        treasury: Pubkey,
+        restrict_assets: bool,
+        rwa_token_program: Pubkey,
+        project_escrow_program: Pubkey,
+    ) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+        let fee_bps = fee_bps.unwrap_or(DEFAULT_FEE_BPS);
+        require!(fee_bps <= MAX_FEE_BPS, OtcError::InvalidFeeBps);
+
+        let config = &mut ctx.accounts.otc_config;
+        config.fee_bps = fee_bps;
+        config.treasury = treasury;
+        config.restrict_assets = restrict_assets;
+        config.rwa_token_program = rwa_token_program;
+        config.project_escrow_program = project_escrow_program;
+        config.max_open_offers = 0;
+        msg!(
+            "OtcConfig initialized: fee_bps={} treasury={} restrict_assets={}",
+            fee_bps,
+            treasury,
+            restrict_assets
+        );
+        Ok(())
+    }
+
+    /// Update marketplace fee, treasury and the per-maker open-offer cap (0 = unlimited). Only the program upgrade
+    /// authority. Grows configs created before max_open_offers existed.
+    pub fn update_otc_config(
+        ctx: Context<UpdateOtcConfig>,
+        fee_bps: u16,
+        treasury: Pubkey,
+        restrict_assets: bool,
+        rwa_token_program: Pubkey,
+        project_escrow_program: Pubkey,
+        max_open_offers: u32,
+    ) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+        require!(fee_bps <= MAX_FEE_BPS, OtcError::InvalidFeeBps);
+
+        let config_info = ctx.accounts.otc_config.to_account_info();
+        let mut config = {
+            let data = config_info.try_borrow_data()?;
+            OtcConfig::from_account_data(&data).ok_or(OtcError::InvalidConfigAccount)?
+        };
+        config.fee_bps = fee_bps;
+        config.treasury = treasury;
+        config.restrict_assets = restrict_assets;
+        config.rwa_token_program = rwa_token_program;
+        config.project_escrow_program = project_escrow_program;
+        config.max_open_offers = max_open_offers;
+        if config_info.data_len() < OTC_CONFIG_SPACE {
+            rwa_token::grow_account(
+                &config_info,
+                &ctx.accounts.authority.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                OTC_CONFIG_SPACE,
+            )?;
+        }
+        {
+            let mut data = config_info.try_borrow_mut_data()?;
+            config.try_serialize(&mut &mut data[..])?;
+        }
+        msg!(
+            "OtcConfig updated: fee_bps={} treasury={} restrict_assets={} max_open_offers={}",
+            fee_bps,
+            treasury,
+            restrict_assets,
+            max_open_offers
+        );
+        Ok(())
+    }
+
     /// Create an offer (sell or buy). Maker deposits asset (sell) or TASTE (buy) into escrow. Token-2022 only.
+    /// Expiry may be set by slot, by unix timestamp, or both (0 = unused); the offer expires when any set bound passes.
+    /// `allowed_taker` makes the offer private: only that wallet may accept it. None = public.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_offer(
         ctx: Context<CreateOffer>,
         amount: u64,
         price: u64,
         offer_type: OfferType,
         expiry_slot: u64,
+        expiry_unix: i64,
+        asset_origin: Option<AssetOrigin>,
+        allowed_taker: Option<Pubkey>,
     ) -> Result<()> {
         require!(
             ctx.accounts.asset_token_program.key() == TOKEN_2022_PROGRAM_ID,
@@ -35,10 +419,33 @@ pub mod otc_market {
         );
         require!(amount > 0, OtcError::InvalidAmount);
         require!(price > 0, OtcError::InvalidPrice);
+        require!(expiry_unix >= 0, OtcError::InvalidExpiry);
+        require!(
+            asset_mint_allowed(
+                &ctx.accounts.otc_config,
+                asset_origin.as_ref(),
+                &ctx.accounts.asset_mint.key()
+            ),
+            OtcError::UnsupportedAsset
+        );
+        require_rwa_distributed(
+            &ctx.accounts.otc_config,
+            asset_origin.as_ref(),
+            &ctx.accounts.asset_mint.to_account_info(),
+            ctx.accounts
+                .rwa_state
+                .as_ref()
+                .map(|a| a.to_account_info())
+                .as_ref(),
+        )?;
 
         let maker_state = &mut ctx.accounts.maker_state;
         let nonce = maker_state.nonce;
         maker_state.nonce = nonce.checked_add(1).ok_or(OtcError::Overflow)?;
+        maker_state.open_offers = open_offer_slot(
+            maker_state.open_offers,
+            ctx.accounts.otc_config.max_open_offers,
+        )?;
 
         let offer = &mut ctx.accounts.offer;
         offer.maker = ctx.accounts.maker.key();
@@ -48,8 +455,11 @@ pub mod otc_market {
         offer.offer_type = offer_type;
         offer.status = OfferStatus::Open;
         offer.expiry_slot = expiry_slot;
+        offer.expiry_unix = expiry_unix;
+        offer.filled = 0;
         offer.bump = ctx.bumps.offer;
         offer.nonce = nonce;
+        offer.allowed_taker = allowed_taker;
 
         let asset_decimals = ctx.accounts.asset_mint.decimals;
         let maker = ctx.accounts.maker.to_account_info();
@@ -90,6 +500,19 @@ pub mod otc_market {
             }
         }
 
+        emit!(OfferCreated {
+            offer: offer.key(),
+            maker: offer.maker,
+            mint: offer.mint,
+            nonce,
+            offer_type,
+            amount,
+            price,
+            expiry_slot,
+            expiry_unix,
+            allowed_taker,
+        });
+
         let offer_type_str = match offer_type {
             OfferType::Sell => "sell",
             OfferType::Buy => "buy",
@@ -106,6 +529,7 @@ pub mod otc_market {
     }
 
     /// Cancel an open offer. Only the maker can cancel. Returns escrowed tokens to maker.
+    /// After partial fills only the unfilled remainder is still escrowed, so that is what is returned (see `Offer::escrow_balance`).
     pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
         let offer = &ctx.accounts.offer;
         require!(
@@ -127,9 +551,9 @@ pub mod otc_market {
         ];
         let signer_seeds = &[seeds];
 
+        let escrowed = offer.escrow_balance();
         match offer.offer_type {
             OfferType::Sell => {
-                let amount = offer.amount;
                 let decimals = ctx.accounts.asset_mint.decimals;
                 let transfer = TransferChecked {
                     from: ctx.accounts.escrow_ata.to_account_info(),
@@ -142,11 +566,10 @@ pub mod otc_market {
                     transfer,
                     signer_seeds,
                 );
-                anchor_spl::token_interface::transfer_checked(cpi, amount, decimals)
+                anchor_spl::token_interface::transfer_checked(cpi, escrowed, decimals)
                     .map_err(|_| OtcError::EscrowTransferFailed)?;
             }
             OfferType::Buy => {
-                let price = offer.price;
                 let decimals = ctx.accounts.quote_mint.decimals;
                 let transfer = TransferChecked {
                     from: ctx.accounts.escrow_ata.to_account_info(),
@@ -159,40 +582,340 @@ pub mod otc_market {
                     transfer,
                     signer_seeds,
                 );
-                anchor_spl::token_interface::transfer_checked(cpi, price, decimals)
+                anchor_spl::token_interface::transfer_checked(cpi, escrowed, decimals)
                     .map_err(|_| OtcError::EscrowTransferFailed)?;
             }
         }
 
         let offer = &mut ctx.accounts.offer;
         offer.status = OfferStatus::Cancelled;
-        msg!("Offer cancelled: {}", ctx.accounts.offer.key());
+        release_open_offer(&ctx.accounts.maker_state)?;
+        emit!(OfferCancelled {
+            offer: offer.key(),
+            maker: offer.maker,
+            mint: offer.mint,
+            nonce: offer.nonce,
+            returned: escrowed,
+            filled: offer.filled,
+        });
+        msg!(
+            "Offer cancelled: {} ({} returned, {} filled)",
+            ctx.accounts.offer.key(),
+            escrowed,
+            ctx.accounts.offer.filled
+        );
+        Ok(())
+    }
+
+    /// Cancel several of the signer's offers in one transaction. remaining_accounts holds one group per offer:
+    /// (offer, escrow_authority, escrow_ata, mint, maker_ata), where `mint` is the escrowed mint (asset for Sell,
+    /// quote for Buy) and `maker_ata` the maker's account for it. At most MAX_BATCH_CANCEL offers.
+    /// Offers that are no longer open are skipped; any offer not made by the signer or with mismatched accounts aborts.
+    pub fn cancel_offers_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelOffersBatch<'info>>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.token_program.key() == TOKEN_2022_PROGRAM_ID,
+            OtcError::NotToken2022
+        );
+        let count = batch_cancel_count(ctx.remaining_accounts.len())
+            .ok_or(OtcError::InvalidBatchAccounts)?;
+        let maker = ctx.accounts.maker.key();
+        let mut cancelled = 0u32;
+
+        for group in ctx
+            .remaining_accounts
+            .chunks_exact(BATCH_CANCEL_ACCOUNTS_PER_OFFER)
+        {
+            let [offer_info, escrow_authority, escrow_ata, mint, maker_ata] = group else {
+                return err!(OtcError::InvalidBatchAccounts);
+            };
+            require!(
+                offer_info.owner == ctx.program_id && offer_info.is_writable,
+                OtcError::InvalidBatchAccounts
+            );
+            let mut offer = {
+                let data = offer_info.try_borrow_data()?;
+                Offer::try_deserialize(&mut &data[..])?
+            };
+            require!(offer.maker == maker, OtcError::NotMaker);
+            if offer.status != OfferStatus::Open {
+                continue;
+            }
+
+            let nonce_bytes = offer.nonce.to_le_bytes();
+            let (expected_authority, escrow_bump) = Pubkey::find_program_address(
+                &[b"escrow", maker.as_ref(), nonce_bytes.as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                escrow_authority.key() == expected_authority,
+                OtcError::InvalidBatchAccounts
+            );
+            let escrow_account = InterfaceAccount::<TokenAccount>::try_from(escrow_ata)?;
+            let maker_account = InterfaceAccount::<TokenAccount>::try_from(maker_ata)?;
+            let mint_account = InterfaceAccount::<Mint>::try_from(mint)?;
+            require!(
+                escrow_ata.key() == offer_escrow_ata(&expected_authority, &mint.key())
+                    && escrow_account.owner == expected_authority
+                    && escrow_account.mint == mint.key()
+                    && maker_account.owner == maker
+                    && maker_account.mint == mint.key()
+                    && (offer.offer_type == OfferType::Buy || mint.key() == offer.mint),
+                OtcError::InvalidBatchAccounts
+            );
+
+            let escrowed = offer.escrow_balance();
+            let seeds: &[&[u8]] = &[
+                b"escrow",
+                maker.as_ref(),
+                nonce_bytes.as_ref(),
+                &[escrow_bump],
+            ];
+            let signer_seeds = &[seeds];
+            let transfer = TransferChecked {
+                from: escrow_ata.clone(),
+                mint: mint.clone(),
+                to: maker_ata.clone(),
+                authority: escrow_authority.clone(),
+            };
+            let cpi = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer,
+                signer_seeds,
+            );
+            anchor_spl::token_interface::transfer_checked(cpi, escrowed, mint_account.decimals)
+                .map_err(|_| OtcError::EscrowTransferFailed)?;
+
+            offer.status = OfferStatus::Cancelled;
+            {
+                let mut data = offer_info.try_borrow_mut_data()?;
+                let mut writer: &mut [u8] = &mut data[..];
+                offer.try_serialize(&mut writer)?;
+            }
+            release_open_offer(&ctx.accounts.maker_state)?;
+            emit!(OfferCancelled {
+                offer: offer_info.key(),
+                maker,
+                mint: offer.mint,
+                nonce: offer.nonce,
+                returned: escrowed,
+                filled: offer.filled,
+            });
+            cancelled += 1;
+        }
+
+        msg!(
+            "Batch cancel: {} of {} offers cancelled for maker {}",
+            cancelled,
+            count,
+            maker
+        );
+        Ok(())
+    }
+
+    /// Permissionless: once an open offer has expired, anyone can return its escrow to the maker and mark it
+    /// Cancelled, so funds are not stranded if the maker never comes back to cancel. Same refund as cancel_offer.
+    pub fn reclaim_expired_offer(ctx: Context<ReclaimExpiredOffer>) -> Result<()> {
+        let offer = &ctx.accounts.offer;
+        require!(
+            matches!(offer.status, OfferStatus::Open),
+            OtcError::OfferNotOpen
+        );
+        require!(offer.is_expired(&Clock::get()?), OtcError::OfferNotExpired);
+
+        let nonce_bytes = offer.nonce.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            b"escrow",
+            offer.maker.as_ref(),
+            nonce_bytes.as_ref(),
+            &[ctx.bumps.escrow_authority],
+        ];
+        let signer_seeds = &[seeds];
+
+        let escrowed = offer.escrow_balance();
+        let (mint, to, token_program) = match offer.offer_type {
+            OfferType::Sell => (
+                &ctx.accounts.asset_mint,
+                ctx.accounts.maker_asset_ata.to_account_info(),
+                ctx.accounts.asset_token_program.to_account_info(),
+            ),
+            OfferType::Buy => (
+                &ctx.accounts.quote_mint,
+                ctx.accounts.maker_quote_ata.to_account_info(),
+                ctx.accounts.quote_token_program.to_account_info(),
+            ),
+        };
+        let transfer = TransferChecked {
+            from: ctx.accounts.escrow_ata.to_account_info(),
+            mint: mint.to_account_info(),
+            to,
+            authority: ctx.accounts.escrow_authority.to_account_info(),
+        };
+        let cpi = CpiContext::new_with_signer(token_program, transfer, signer_seeds);
+        anchor_spl::token_interface::transfer_checked(cpi, escrowed, mint.decimals)
+            .map_err(|_| OtcError::EscrowTransferFailed)?;
+
+        let offer = &mut ctx.accounts.offer;
+        offer.status = OfferStatus::Cancelled;
+        release_open_offer(&ctx.accounts.maker_state)?;
+        emit!(OfferCancelled {
+            offer: offer.key(),
+            maker: offer.maker,
+            mint: offer.mint,
+            nonce: offer.nonce,
+            returned: escrowed,
+            filled: offer.filled,
+        });
+        msg!(
+            "Expired offer reclaimed: {} ({} returned to {} by {})",
+            ctx.accounts.offer.key(),
+            escrowed,
+            ctx.accounts.offer.maker,
+            ctx.accounts.caller.key()
+        );
+        Ok(())
+    }
+
+    /// Reprice an open offer and optionally change its expiry without cancelling. Only the maker.
+    /// For Buy offers the escrowed quote follows the price: the maker tops up or is refunded the difference
+    /// through maker_quote_ata, which Sell offers may omit.
+    pub fn update_offer(
+        ctx: Context<UpdateOffer>,
+        new_price: u64,
+        new_expiry_slot: Option<u64>,
+        new_expiry_unix: Option<i64>,
+    ) -> Result<()> {
+        let offer = &ctx.accounts.offer;
+        require!(
+            matches!(offer.status, OfferStatus::Open),
+            OtcError::OfferNotOpen
+        );
+        require!(new_price > 0, OtcError::InvalidPrice);
+        if let Some(expiry_unix) = new_expiry_unix {
+            require!(expiry_unix >= 0, OtcError::InvalidExpiry);
+        }
+        require!(
+            ctx.accounts.quote_token_program.key() == TOKEN_2022_PROGRAM_ID,
+            OtcError::NotToken2022
+        );
+
+        let old_price = offer.price;
+        if offer.offer_type == OfferType::Buy && new_price != old_price {
+            let maker_quote_ata = ctx
+                .accounts
+                .maker_quote_ata
+                .as_ref()
+                .ok_or(OtcError::QuoteAccountRequired)?;
+            let decimals = ctx.accounts.quote_mint.decimals;
+            if new_price > old_price {
+                let transfer = TransferChecked {
+                    from: maker_quote_ata.to_account_info(),
+                    mint: ctx.accounts.quote_mint.to_account_info(),
+                    to: ctx.accounts.escrow_ata.to_account_info(),
+                    authority: ctx.accounts.maker.to_account_info(),
+                };
+                let cpi =
+                    CpiContext::new(ctx.accounts.quote_token_program.to_account_info(), transfer);
+                anchor_spl::token_interface::transfer_checked(cpi, new_price - old_price, decimals)
+                    .map_err(|_| OtcError::EscrowTransferFailed)?;
+            } else {
+                let nonce_bytes = offer.nonce.to_le_bytes();
+                let seeds: &[&[u8]] = &[
+                    b"escrow",
+                    offer.maker.as_ref(),
+                    nonce_bytes.as_ref(),
+                    &[ctx.bumps.escrow_authority],
+                ];
+                let signer_seeds = &[seeds];
+                let transfer = TransferChecked {
+                    from: ctx.accounts.escrow_ata.to_account_info(),
+                    mint: ctx.accounts.quote_mint.to_account_info(),
+                    to: maker_quote_ata.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                };
+                let cpi = CpiContext::new_with_signer(
+                    ctx.accounts.quote_token_program.to_account_info(),
+                    transfer,
+                    signer_seeds,
+                );
+                anchor_spl::token_interface::transfer_checked(cpi, old_price - new_price, decimals)
+                    .map_err(|_| OtcError::EscrowTransferFailed)?;
+            }
+        }
+
+        let offer = &mut ctx.accounts.offer;
+        offer.price = new_price;
+        if let Some(expiry_slot) = new_expiry_slot {
+            offer.expiry_slot = expiry_slot;
+        }
+        if let Some(expiry_unix) = new_expiry_unix {
+            offer.expiry_unix = expiry_unix;
+        }
+        msg!(
+            "Offer updated: {} price {} -> {}",
+            ctx.accounts.offer.key(),
+            old_price,
+            new_price
+        );
         Ok(())
     }
 
-    /// Accept an open offer. Asset and $TASTE move via escrow; taker signs only.
+    /// Accept an open offer, fully or partially. Asset and $TASTE move via escrow; taker signs only.
     /// For Sell: escrow -> taker (asset), taker -> maker ($TASTE). For Buy: taker -> maker (asset), escrow -> taker ($TASTE).
-    pub fn accept_offer(ctx: Context<AcceptOffer>) -> Result<()> {
+    /// The platform fee (OtcConfig.fee_bps) is taken from the quote leg and sent to the treasury before paying the asset seller.
+    /// `fill_amount` of the asset trades for a proportional share of the remaining price (see `quote_for_fill`);
+    /// the offer stays open until the remaining amount reaches zero.
+    /// `max_price` / `min_amount` are the taker's limits on the offer's remaining price and amount as seen when
+    /// they signed; a racing update_offer or fill that moves past them fails with PriceChanged.
+    pub fn accept_offer(
+        ctx: Context<AcceptOffer>,
+        fill_amount: u64,
+        max_price: Option<u64>,
+        min_amount: Option<u64>,
+    ) -> Result<()> {
+        require_not_paused(ctx.accounts.protocol_state.as_deref())?;
         ctx.accounts.validate_token_2022()?;
         let offer = &ctx.accounts.offer;
         require!(
             matches!(offer.status, OfferStatus::Open),
             OtcError::OfferNotOpen
         );
+        check_offer_limits(offer.price, offer.amount, max_price, min_amount)?;
         require!(
             ctx.accounts.taker.key() != offer.maker,
             OtcError::TakerIsMaker
         );
+        require!(
+            offer.taker_allowed(&ctx.accounts.taker.key()),
+            OtcError::NotAllowedTaker
+        );
 
         let clock = Clock::get()?;
-        require!(clock.slot <= offer.expiry_slot, OtcError::OfferExpired);
+        require!(!offer.is_expired(&clock), OtcError::OfferExpired);
 
         let asset_mint = &ctx.accounts.asset_mint;
         let asset_decimals = asset_mint.decimals;
         let taste_decimals = ctx.accounts.quote_mint.decimals;
         let offer_type = offer.offer_type;
-        let offer_amount = offer.amount;
-        let offer_price = offer.price;
+        require!(
+            fill_amount > 0 && fill_amount <= offer.amount,
+            OtcError::InvalidFillAmount
+        );
+        let fill_quote = offer.fill_quote(fill_amount).ok_or(OtcError::Overflow)?;
+        let remaining_amount = offer.amount - fill_amount;
+        let remaining_price = offer
+            .price
+            .checked_sub(fill_quote)
+            .ok_or(OtcError::Overflow)?;
+        // A partial fill must pay something and must not leave unfilled asset with nothing left to pay for it.
+        require!(
+            fill_quote > 0 && (remaining_amount == 0 || remaining_price > 0),
+            OtcError::FillTooSmall
+        );
+        let fee =
+            platform_fee(fill_quote, ctx.accounts.otc_config.fee_bps).ok_or(OtcError::Overflow)?;
+        let net_quote = fill_quote - fee;
 
         let nonce_bytes = offer.nonce.to_le_bytes();
         let (_, escrow_bump) = Pubkey::find_program_address(
@@ -222,11 +945,25 @@ pub mod otc_market {
                 );
                 anchor_spl::token_interface::transfer_checked(
                     cpi_asset,
-                    offer_amount,
+                    fill_amount,
                     asset_decimals,
                 )
                 .map_err(|_| OtcError::EscrowTransferFailed)?;
 
+                if fee > 0 {
+                    let transfer_fee = TransferChecked {
+                        from: ctx.accounts.taker_quote_ata.to_account_info(),
+                        mint: ctx.accounts.quote_mint.to_account_info(),
+                        to: ctx.accounts.treasury_quote_ata.to_account_info(),
+                        authority: ctx.accounts.taker.to_account_info(),
+                    };
+                    let cpi_fee = CpiContext::new(
+                        ctx.accounts.quote_token_program.to_account_info(),
+                        transfer_fee,
+                    );
+                    anchor_spl::token_interface::transfer_checked(cpi_fee, fee, taste_decimals)?;
+                }
+
                 let transfer_quote = TransferChecked {
                     from: ctx.accounts.taker_quote_ata.to_account_info(),
                     mint: ctx.accounts.quote_mint.to_account_info(),
@@ -239,7 +976,7 @@ pub mod otc_market {
                 );
                 anchor_spl::token_interface::transfer_checked(
                     cpi_quote,
-                    offer_price,
+                    net_quote,
                     taste_decimals,
                 )?;
             }
@@ -256,10 +993,26 @@ pub mod otc_market {
                 );
                 anchor_spl::token_interface::transfer_checked(
                     cpi_asset,
-                    offer_amount,
+                    fill_amount,
                     asset_decimals,
                 )?;
 
+                if fee > 0 {
+                    let transfer_fee = TransferChecked {
+                        from: ctx.accounts.escrow_ata.to_account_info(),
+                        mint: ctx.accounts.quote_mint.to_account_info(),
+                        to: ctx.accounts.treasury_quote_ata.to_account_info(),
+                        authority: ctx.accounts.escrow_authority.to_account_info(),
+                    };
+                    let cpi_fee = CpiContext::new_with_signer(
+                        ctx.accounts.quote_token_program.to_account_info(),
+                        transfer_fee,
+                        signer_seeds,
+                    );
+                    anchor_spl::token_interface::transfer_checked(cpi_fee, fee, taste_decimals)
+                        .map_err(|_| OtcError::EscrowTransferFailed)?;
+                }
+
                 let transfer_quote = TransferChecked {
                     from: ctx.accounts.escrow_ata.to_account_info(),
                     mint: ctx.accounts.quote_mint.to_account_info(),
@@ -271,97 +1024,1165 @@ pub mod otc_market {
                     transfer_quote,
                     signer_seeds,
                 );
-                anchor_spl::token_interface::transfer_checked(
-                    cpi_quote,
-                    offer_price,
-                    taste_decimals,
-                )
-                .map_err(|_| OtcError::EscrowTransferFailed)?;
+                anchor_spl::token_interface::transfer_checked(cpi_quote, net_quote, taste_decimals)
+                    .map_err(|_| OtcError::EscrowTransferFailed)?;
             }
         }
 
         let offer = &mut ctx.accounts.offer;
-        offer.status = OfferStatus::Taken;
+        offer.amount = remaining_amount;
+        offer.price = remaining_price;
+        offer.filled = offer
+            .filled
+            .checked_add(fill_amount)
+            .ok_or(OtcError::Overflow)?;
+        if remaining_amount == 0 {
+            offer.status = OfferStatus::Taken;
+            release_open_offer(&ctx.accounts.maker_state)?;
+        }
+        emit!(OfferAccepted {
+            offer: offer.key(),
+            maker: offer.maker,
+            taker: ctx.accounts.taker.key(),
+            mint: offer.mint,
+            nonce: offer.nonce,
+            offer_type,
+            amount: fill_amount,
+            price: fill_quote,
+            fee,
+            remaining_amount,
+        });
         msg!(
-            "Offer accepted: {} tokens for {} lamports",
-            offer_amount,
-            offer_price
+            "Offer accepted: {} tokens for {} lamports (fee {}, {} remaining)",
+            fill_amount,
+            fill_quote,
+            fee,
+            remaining_amount
         );
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    /// Permissionless: grow a MakerState created before open_offers existed (count starts at zero, so offers
+    /// opened earlier are not counted). create_offer needs the grown layout; payer tops up rent.
+    pub fn migrate_maker_state(ctx: Context<MigrateMakerState>) -> Result<()> {
+        let state_info = ctx.accounts.maker_state.to_account_info();
+        {
+            let data = state_info.try_borrow_data()?;
+            require!(
+                data.len() >= MAKER_STATE_BASE_LEN && &data[..8] == MakerState::DISCRIMINATOR,
+                OtcError::InvalidMakerState
+            );
+        }
+        if state_info.data_len() >= MAKER_STATE_SPACE {
+            return Ok(());
+        }
+        rwa_token::grow_account(
+            &state_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            MAKER_STATE_SPACE,
+        )?;
+        msg!("Migrated maker state {}", state_info.key());
+        Ok(())
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum OfferType {
-    Sell,
-    Buy,
-}
+    /// Permissionless: grow an Offer created before expiry_unix, filled and allowed_taker existed to OFFER_SPACE
+    /// (public, nothing filled; see migrate_offer_data for expiry). Legacy offers must be migrated before
+    /// accept_offer, update_offer, cancel_offer or reclaim_expired_offer can load them; payer tops up rent.
+    pub fn migrate_offer(ctx: Context<MigrateOffer>) -> Result<()> {
+        let offer_info = ctx.accounts.offer.to_account_info();
+        let offer = {
+            let data = offer_info.try_borrow_data()?;
+            migrate_offer_data(&data)?
+        };
+        rwa_token::grow_account(
+            &offer_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            OFFER_SPACE,
+        )?;
+        let mut data = offer_info.try_borrow_mut_data()?;
+        offer.try_serialize(&mut &mut data[..])?;
+        msg!("Migrated offer {}", offer_info.key());
+        Ok(())
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum OfferStatus {
-    Open,
-    Taken,
-    Cancelled,
-}
+    /// Read-only: quote `accept_offer(fill_amount)` would charge right now (platform fee included), via return data.
+    /// Fails the same way accept_offer would if the offer is not open, has expired, or the fill amount is invalid.
+    /// Offers are fixed-price, so this is the prorated remaining price (see `Offer::fill_quote`).
+    pub fn get_offer_price(ctx: Context<GetOfferPrice>, fill_amount: u64) -> Result<u64> {
+        let offer = &ctx.accounts.offer;
+        require!(
+            matches!(offer.status, OfferStatus::Open),
+            OtcError::OfferNotOpen
+        );
+        require!(!offer.is_expired(&Clock::get()?), OtcError::OfferExpired);
+        require!(
+            fill_amount > 0 && fill_amount <= offer.amount,
+            OtcError::InvalidFillAmount
+        );
+        offer
+            .fill_quote(fill_amount)
+            .ok_or(OtcError::Overflow.into())
+    }
 
-#[account]
-pub struct MakerState {
-    pub nonce: u64,
-}
+    /// Read-only: the offer's remaining amount and price, status and expiry bounds, via return data, so clients need
+    /// not decode the raw `Offer` layout. Unlike get_offer_price it succeeds for closed and expired offers.
+    pub fn get_offer_state(ctx: Context<GetOfferState>) -> Result<OfferState> {
+        Ok(ctx.accounts.offer.state())
+    }
 
-#[account]
-pub struct Offer {
-    pub maker: Pubkey,
-    pub mint: Pubkey,
-    pub amount: u64,
-    pub price: u64,
-    pub offer_type: OfferType,
-    pub status: OfferStatus,
-    pub expiry_slot: u64,
-    pub bump: u8,
-    /// Nonce used in PDA seeds (maker_state.nonce at create time).
-    pub nonce: u64,
-}
+    /// Start an ascending auction. Maker deposits `amount` of the asset into escrow; bids are in the quote mint.
+    /// Bidding is open through `end_slot`; anyone can settle after it. Shares the maker nonce, escrow PDA and
+    /// open-offer cap with offers.
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        amount: u64,
+        reserve_price: u64,
+        min_increment: u64,
+        end_slot: u64,
+        asset_origin: Option<AssetOrigin>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.asset_token_program.key() == TOKEN_2022_PROGRAM_ID,
+            OtcError::NotToken2022
+        );
+        require!(amount > 0, OtcError::InvalidAmount);
+        let clock = Clock::get()?;
+        require!(end_slot > clock.slot, OtcError::InvalidEndSlot);
+        require!(
+            asset_mint_allowed(
+                &ctx.accounts.otc_config,
+                asset_origin.as_ref(),
+                &ctx.accounts.asset_mint.key()
+            ),
+            OtcError::UnsupportedAsset
+        );
+        require_rwa_distributed(
+            &ctx.accounts.otc_config,
+            asset_origin.as_ref(),
+            &ctx.accounts.asset_mint.to_account_info(),
+            ctx.accounts
+                .rwa_state
+                .as_ref()
+                .map(|a| a.to_account_info())
+                .as_ref(),
+        )?;
 
-#[error_code]
-pub enum OtcError {
-    #[msg("Amount must be positive")]
-    InvalidAmount,
-    #[msg("Price must be positive")]
-    InvalidPrice,
-    #[msg("Offer is not open")]
-    OfferNotOpen,
-    #[msg("Only the maker can cancel")]
-    NotMaker,
-    #[msg("Taker cannot be the maker")]
-    TakerIsMaker,
-    #[msg("Offer has expired")]
-    OfferExpired,
-    #[msg("Token program must be Token-2022")]
-    NotToken2022,
-    #[msg("Arithmetic overflow")]
-    Overflow,
-    #[msg("Escrow transfer failed")]
-    EscrowTransferFailed,
-}
+        let maker_state = &mut ctx.accounts.maker_state;
+        let nonce = maker_state.nonce;
+        maker_state.nonce = nonce.checked_add(1).ok_or(OtcError::Overflow)?;
+        maker_state.open_offers = open_offer_slot(
+            maker_state.open_offers,
+            ctx.accounts.otc_config.max_open_offers,
+        )?;
 
-#[derive(Accounts)]
-#[instruction(amount: u64, price: u64, offer_type: OfferType, expiry_slot: u64)]
-pub struct CreateOffer<'info> {
-    #[account(mut)]
+        let auction = &mut ctx.accounts.auction;
+        auction.maker = ctx.accounts.maker.key();
+        auction.mint = ctx.accounts.asset_mint.key();
+        auction.quote_mint = ctx.accounts.quote_mint.key();
+        auction.amount = amount;
+        auction.reserve_price = reserve_price;
+        auction.min_increment = min_increment;
+        auction.end_slot = end_slot;
+        auction.highest_bid = 0;
+        auction.highest_bidder = Pubkey::default();
+        auction.status = AuctionStatus::Open;
+        auction.bump = ctx.bumps.auction;
+        auction.nonce = nonce;
+
+        let transfer = TransferChecked {
+            from: ctx.accounts.maker_asset_ata.to_account_info(),
+            mint: ctx.accounts.asset_mint.to_account_info(),
+            to: ctx.accounts.escrow_ata.to_account_info(),
+            authority: ctx.accounts.maker.to_account_info(),
+        };
+        let cpi = CpiContext::new(ctx.accounts.asset_token_program.to_account_info(), transfer);
+        anchor_spl::token_interface::transfer_checked(
+            cpi,
+            amount,
+            ctx.accounts.asset_mint.decimals,
+        )
+        .map_err(|_| OtcError::EscrowTransferFailed)?;
+
+        msg!(
+            "Create auction: maker {} mint {} amount {} reserve {} end_slot {}",
+            ctx.accounts.maker.key(),
+            ctx.accounts.asset_mint.key(),
+            amount,
+            reserve_price,
+            end_slot
+        );
+        Ok(())
+    }
+
+    /// Bid on an open auction. The bid is escrowed; the previous highest bidder is refunded in full.
+    /// `bid` must be at least `min_next_bid` for the auction's current state.
+    pub fn place_bid(ctx: Context<PlaceBid>, bid: u64) -> Result<()> {
+        require!(
+            ctx.accounts.quote_token_program.key() == TOKEN_2022_PROGRAM_ID,
+            OtcError::NotToken2022
+        );
+        let auction = &ctx.accounts.auction;
+        require!(
+            auction.status == AuctionStatus::Open,
+            OtcError::AuctionNotOpen
+        );
+        require!(
+            ctx.accounts.bidder.key() != auction.maker,
+            OtcError::TakerIsMaker
+        );
+        let clock = Clock::get()?;
+        require!(clock.slot <= auction.end_slot, OtcError::AuctionEnded);
+        let min_bid = min_next_bid(
+            auction.highest_bid,
+            auction.reserve_price,
+            auction.min_increment,
+        )
+        .ok_or(OtcError::Overflow)?;
+        require!(bid >= min_bid, OtcError::BidTooLow);
+
+        let decimals = ctx.accounts.quote_mint.decimals;
+        let previous_bid = auction.highest_bid;
+        if previous_bid > 0 {
+            let previous_bidder_quote_ata = ctx
+                .accounts
+                .previous_bidder_quote_ata
+                .as_ref()
+                .ok_or(OtcError::MissingPreviousBidder)?;
+            require!(
+                previous_bidder_quote_ata.owner == auction.highest_bidder,
+                OtcError::MissingPreviousBidder
+            );
+            let nonce_bytes = auction.nonce.to_le_bytes();
+            let seeds: &[&[u8]] = &[
+                b"escrow",
+                auction.maker.as_ref(),
+                nonce_bytes.as_ref(),
+                &[ctx.bumps.escrow_authority],
+            ];
+            let signer_seeds = &[seeds];
+            let refund = TransferChecked {
+                from: ctx.accounts.bid_escrow_ata.to_account_info(),
+                mint: ctx.accounts.quote_mint.to_account_info(),
+                to: previous_bidder_quote_ata.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            };
+            let cpi = CpiContext::new_with_signer(
+                ctx.accounts.quote_token_program.to_account_info(),
+                refund,
+                signer_seeds,
+            );
+            anchor_spl::token_interface::transfer_checked(cpi, previous_bid, decimals)
+                .map_err(|_| OtcError::EscrowTransferFailed)?;
+        }
+
+        let transfer = TransferChecked {
+            from: ctx.accounts.bidder_quote_ata.to_account_info(),
+            mint: ctx.accounts.quote_mint.to_account_info(),
+            to: ctx.accounts.bid_escrow_ata.to_account_info(),
+            authority: ctx.accounts.bidder.to_account_info(),
+        };
+        let cpi = CpiContext::new(ctx.accounts.quote_token_program.to_account_info(), transfer);
+        anchor_spl::token_interface::transfer_checked(cpi, bid, decimals)
+            .map_err(|_| OtcError::EscrowTransferFailed)?;
+
+        let auction = &mut ctx.accounts.auction;
+        auction.highest_bid = bid;
+        auction.highest_bidder = ctx.accounts.bidder.key();
+        msg!(
+            "Bid placed: auction {} bidder {} bid {} (previous {})",
+            auction.key(),
+            ctx.accounts.bidder.key(),
+            bid,
+            previous_bid
+        );
+        Ok(())
+    }
+
+    /// Settle an auction after its end slot. Permissionless.
+    /// With a winning bid: asset escrow -> winner, bid escrow -> maker (less the platform fee to the treasury).
+    /// With no bids: the asset is returned to the maker.
+    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+        ctx.accounts.validate_token_2022()?;
+        let auction = &ctx.accounts.auction;
+        require!(
+            auction.status == AuctionStatus::Open,
+            OtcError::AuctionNotOpen
+        );
+        let clock = Clock::get()?;
+        require!(clock.slot > auction.end_slot, OtcError::AuctionNotEnded);
+
+        let nonce_bytes = auction.nonce.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            b"escrow",
+            auction.maker.as_ref(),
+            nonce_bytes.as_ref(),
+            &[ctx.bumps.escrow_authority],
+        ];
+        let signer_seeds = &[seeds];
+        let asset_decimals = ctx.accounts.asset_mint.decimals;
+        let quote_decimals = ctx.accounts.quote_mint.decimals;
+        let amount = auction.amount;
+        let winning_bid = auction.highest_bid;
+
+        let asset_destination = if winning_bid > 0 {
+            let winner_asset_ata = ctx
+                .accounts
+                .winner_asset_ata
+                .as_ref()
+                .ok_or(OtcError::MissingWinnerAccount)?;
+            require!(
+                winner_asset_ata.owner == auction.highest_bidder,
+                OtcError::MissingWinnerAccount
+            );
+            winner_asset_ata.to_account_info()
+        } else {
+            ctx.accounts.maker_asset_ata.to_account_info()
+        };
+        let transfer_asset = TransferChecked {
+            from: ctx.accounts.escrow_ata.to_account_info(),
+            mint: ctx.accounts.asset_mint.to_account_info(),
+            to: asset_destination,
+            authority: ctx.accounts.escrow_authority.to_account_info(),
+        };
+        let cpi_asset = CpiContext::new_with_signer(
+            ctx.accounts.asset_token_program.to_account_info(),
+            transfer_asset,
+            signer_seeds,
+        );
+        anchor_spl::token_interface::transfer_checked(cpi_asset, amount, asset_decimals)
+            .map_err(|_| OtcError::EscrowTransferFailed)?;
+
+        let mut fee = 0;
+        if winning_bid > 0 {
+            fee = platform_fee(winning_bid, ctx.accounts.otc_config.fee_bps)
+                .ok_or(OtcError::Overflow)?;
+            let net_quote = winning_bid - fee;
+            if fee > 0 {
+                let transfer_fee = TransferChecked {
+                    from: ctx.accounts.bid_escrow_ata.to_account_info(),
+                    mint: ctx.accounts.quote_mint.to_account_info(),
+                    to: ctx.accounts.treasury_quote_ata.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                };
+                let cpi_fee = CpiContext::new_with_signer(
+                    ctx.accounts.quote_token_program.to_account_info(),
+                    transfer_fee,
+                    signer_seeds,
+                );
+                anchor_spl::token_interface::transfer_checked(cpi_fee, fee, quote_decimals)
+                    .map_err(|_| OtcError::EscrowTransferFailed)?;
+            }
+            let transfer_quote = TransferChecked {
+                from: ctx.accounts.bid_escrow_ata.to_account_info(),
+                mint: ctx.accounts.quote_mint.to_account_info(),
+                to: ctx.accounts.maker_quote_ata.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            };
+            let cpi_quote = CpiContext::new_with_signer(
+                ctx.accounts.quote_token_program.to_account_info(),
+                transfer_quote,
+                signer_seeds,
+            );
+            anchor_spl::token_interface::transfer_checked(cpi_quote, net_quote, quote_decimals)
+                .map_err(|_| OtcError::EscrowTransferFailed)?;
+        }
+
+        let auction = &mut ctx.accounts.auction;
+        auction.status = AuctionStatus::Settled;
+        release_open_offer(&ctx.accounts.maker_state)?;
+        msg!(
+            "Auction settled: {} winner {} bid {} (fee {})",
+            auction.key(),
+            auction.highest_bidder,
+            winning_bid,
+            fee
+        );
+        Ok(())
+    }
+
+    /// Maker withdraws an auction that has no bids yet; the asset returns from escrow. Once someone has bid, the
+    /// auction can only be settled.
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+        require!(
+            auction.status == AuctionStatus::Open,
+            OtcError::AuctionNotOpen
+        );
+        require!(auction.highest_bid == 0, OtcError::AuctionHasBids);
+
+        let nonce_bytes = auction.nonce.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            b"escrow",
+            auction.maker.as_ref(),
+            nonce_bytes.as_ref(),
+            &[ctx.bumps.escrow_authority],
+        ];
+        let signer_seeds = &[seeds];
+        let transfer = TransferChecked {
+            from: ctx.accounts.escrow_ata.to_account_info(),
+            mint: ctx.accounts.asset_mint.to_account_info(),
+            to: ctx.accounts.maker_asset_ata.to_account_info(),
+            authority: ctx.accounts.escrow_authority.to_account_info(),
+        };
+        let cpi = CpiContext::new_with_signer(
+            ctx.accounts.asset_token_program.to_account_info(),
+            transfer,
+            signer_seeds,
+        );
+        anchor_spl::token_interface::transfer_checked(
+            cpi,
+            auction.amount,
+            ctx.accounts.asset_mint.decimals,
+        )
+        .map_err(|_| OtcError::EscrowTransferFailed)?;
+
+        let auction = &mut ctx.accounts.auction;
+        auction.status = AuctionStatus::Cancelled;
+        release_open_offer(&ctx.accounts.maker_state)?;
+        msg!("Auction cancelled: {}", auction.key());
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OfferType {
+    Sell,
+    Buy,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OfferStatus {
+    Open,
+    Taken,
+    Cancelled,
+}
+
+/// Marketplace config. PDA seeds = [b"otc_config"].
+#[account]
+pub struct OtcConfig {
+    /// Fee on the quote leg of each fill, in basis points.
+    pub fee_bps: u16,
+    /// Wallet whose quote-mint token account receives fees.
+    pub treasury: Pubkey,
+    /// When true, create_offer only accepts RWA mints and IOU receipt mints (see asset_mint_allowed).
+    pub restrict_assets: bool,
+    /// rwa_token program ID used to derive RWA mint PDAs [b"rwa_mint", project].
+    pub rwa_token_program: Pubkey,
+    /// project_escrow program ID used to derive receipt mint PDAs [b"receipt", project, backer].
+    pub project_escrow_program: Pubkey,
+    /// Most offers a single maker may have open at once; 0 = unlimited.
+    pub max_open_offers: u32,
+}
+
+impl OtcConfig {
+    /// Parse an OtcConfig of any historical length; a missing max_open_offers reads as 0 (unlimited).
+    /// Returns None if the discriminator or base layout doesn't match.
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        if data.len() < OTC_CONFIG_BASE_LEN || &data[..8] != OtcConfig::DISCRIMINATOR {
+            return None;
+        }
+        let mut buf = data.to_vec();
+        if buf.len() < OTC_CONFIG_SPACE {
+            buf.resize(OTC_CONFIG_SPACE, 0);
+        }
+        OtcConfig::try_deserialize(&mut &buf[..]).ok()
+    }
+}
+
+/// Where a listed asset comes from; lets create_offer re-derive the expected mint PDA.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AssetOrigin {
+    Rwa { project: Pubkey },
+    Receipt { project: Pubkey, backer: Pubkey },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionStatus {
+    Open,
+    Settled,
+    Cancelled,
+}
+
+#[account]
+pub struct MakerState {
+    pub nonce: u64,
+    /// Offers and auctions currently open (created and not yet cancelled, fully taken or settled).
+    pub open_offers: u32,
+}
+
+#[account]
+pub struct Offer {
+    pub maker: Pubkey,
+    pub mint: Pubkey,
+    /// Asset amount still available. Decreases with each partial fill.
+    pub amount: u64,
+    /// Total quote price for the remaining `amount`. Decreases with each partial fill.
+    pub price: u64,
+    pub offer_type: OfferType,
+    pub status: OfferStatus,
+    pub expiry_slot: u64,
+    pub bump: u8,
+    /// Nonce used in PDA seeds (maker_state.nonce at create time).
+    pub nonce: u64,
+    /// Unix timestamp after which the offer can no longer be accepted. 0 = no time-based expiry. With
+    /// `expiry_slot` also 0 the offer never expires (before this field, `expiry_slot == 0` meant already expired;
+    /// migrate_offer keeps such offers expired).
+    pub expiry_unix: i64,
+    /// Cumulative asset amount filled so far.
+    pub filled: u64,
+    /// Only wallet allowed to accept (private offer). None = anyone may accept.
+    pub allowed_taker: Option<Pubkey>,
+}
+
+impl Offer {
+    /// True if `taker` may accept: the offer is public or `taker` is its allowed taker.
+    pub fn taker_allowed(&self, taker: &Pubkey) -> bool {
+        self.allowed_taker.is_none_or(|allowed| allowed == *taker)
+    }
+
+    /// True once the clock has passed either expiry bound. A bound of 0 is ignored.
+    pub fn is_expired(&self, clock: &Clock) -> bool {
+        let slot_expired = self.expiry_slot != 0 && clock.slot > self.expiry_slot;
+        let unix_expired = self.expiry_unix != 0 && clock.unix_timestamp > self.expiry_unix;
+        slot_expired || unix_expired
+    }
+
+    /// Quote for filling `fill_amount` now, as charged by accept_offer (rounded in the maker's favor).
+    pub fn fill_quote(&self, fill_amount: u64) -> Option<u64> {
+        quote_for_fill(
+            self.amount,
+            self.price,
+            fill_amount,
+            self.offer_type == OfferType::Sell,
+        )
+    }
+
+    /// Tokens currently held in escrow for this offer: the unfilled asset `amount` for Sell,
+    /// the unspent quote `price` for Buy. Fills move exactly their share out of escrow and reduce these fields.
+    pub fn escrow_balance(&self) -> u64 {
+        match self.offer_type {
+            OfferType::Sell => self.amount,
+            OfferType::Buy => self.price,
+        }
+    }
+
+    /// Snapshot returned by get_offer_state.
+    pub fn state(&self) -> OfferState {
+        OfferState {
+            remaining_amount: self.amount,
+            remaining_price: self.price,
+            filled: self.filled,
+            status: self.status,
+            expiry_slot: self.expiry_slot,
+            expiry_unix: self.expiry_unix,
+        }
+    }
+
+    /// Mint held in escrow: the asset for Sell, `quote_mint` for Buy.
+    pub fn escrow_mint(&self, quote_mint: &Pubkey) -> Pubkey {
+        match self.offer_type {
+            OfferType::Sell => self.mint,
+            OfferType::Buy => *quote_mint,
+        }
+    }
+}
+
+/// Return data of get_offer_state. A stable view of an offer's fill state, independent of the `Offer` layout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct OfferState {
+    /// Asset amount still available to fill.
+    pub remaining_amount: u64,
+    /// Quote price for the whole remaining amount.
+    pub remaining_price: u64,
+    /// Cumulative asset amount filled so far.
+    pub filled: u64,
+    pub status: OfferStatus,
+    /// Slot after which the offer can no longer be accepted. 0 = none.
+    pub expiry_slot: u64,
+    /// Unix timestamp after which the offer can no longer be accepted. 0 = none.
+    pub expiry_unix: i64,
+}
+
+/// Ascending auction. PDA seeds = [b"auction", maker, nonce]; escrow PDA as for offers.
+#[account]
+pub struct Auction {
+    pub maker: Pubkey,
+    /// Asset mint being auctioned.
+    pub mint: Pubkey,
+    /// Mint bids are placed in.
+    pub quote_mint: Pubkey,
+    /// Asset amount held in escrow for the winner.
+    pub amount: u64,
+    /// Minimum first bid.
+    pub reserve_price: u64,
+    /// Minimum raise over the current highest bid.
+    pub min_increment: u64,
+    /// Last slot at which bids are accepted.
+    pub end_slot: u64,
+    /// Current highest bid, held in the bid escrow. 0 = no bids yet.
+    pub highest_bid: u64,
+    pub highest_bidder: Pubkey,
+    pub status: AuctionStatus,
+    pub bump: u8,
+    /// Nonce used in PDA seeds (maker_state.nonce at create time).
+    pub nonce: u64,
+}
+
+#[event]
+pub struct OfferCreated {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub mint: Pubkey,
+    /// Maker nonce in the offer PDA seeds [b"offer", maker, nonce].
+    pub nonce: u64,
+    pub offer_type: OfferType,
+    pub amount: u64,
+    pub price: u64,
+    pub expiry_slot: u64,
+    pub expiry_unix: i64,
+    /// Set for private offers; only this wallet may accept.
+    pub allowed_taker: Option<Pubkey>,
+}
+
+/// One fill of an offer. `amount` and `price` are for this fill only.
+#[event]
+pub struct OfferAccepted {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub mint: Pubkey,
+    pub nonce: u64,
+    pub offer_type: OfferType,
+    /// Asset amount filled.
+    pub amount: u64,
+    /// Quote paid for the fill, including the platform fee.
+    pub price: u64,
+    pub fee: u64,
+    /// Asset amount still open after this fill.
+    pub remaining_amount: u64,
+}
+
+#[event]
+pub struct OfferCancelled {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub mint: Pubkey,
+    pub nonce: u64,
+    /// Escrowed tokens returned to the maker (asset for Sell, quote for Buy).
+    pub returned: u64,
+    /// Asset amount filled before cancellation.
+    pub filled: u64,
+}
+
+#[error_code]
+pub enum OtcError {
+    #[msg("Amount must be positive")]
+    InvalidAmount,
+    #[msg("Price must be positive")]
+    InvalidPrice,
+    #[msg("Offer is not open")]
+    OfferNotOpen,
+    #[msg("Only the maker can cancel")]
+    NotMaker,
+    #[msg("Taker cannot be the maker")]
+    TakerIsMaker,
+    #[msg("Offer has expired")]
+    OfferExpired,
+    #[msg("Token program must be Token-2022")]
+    NotToken2022,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Escrow transfer failed")]
+    EscrowTransferFailed,
+    #[msg("Expiry timestamp must not be negative")]
+    InvalidExpiry,
+    #[msg("Fill amount must be positive and at most the remaining amount")]
+    InvalidFillAmount,
+    #[msg("Fill is too small to be priced")]
+    FillTooSmall,
+    #[msg("Only program upgrade authority can initialize or update config")]
+    NotUpgradeAuthority,
+    #[msg("Fee exceeds maximum")]
+    InvalidFeeBps,
+    #[msg("Asset mint is not a recognized RWA or receipt mint")]
+    UnsupportedAsset,
+    #[msg("Auction end slot must be in the future")]
+    InvalidEndSlot,
+    #[msg("Auction is not open")]
+    AuctionNotOpen,
+    #[msg("Auction has ended")]
+    AuctionEnded,
+    #[msg("Auction has not ended yet")]
+    AuctionNotEnded,
+    #[msg("Bid is below the reserve or minimum increment")]
+    BidTooLow,
+    #[msg("Previous highest bidder's quote account is required")]
+    MissingPreviousBidder,
+    #[msg("Winning bidder's asset account is required")]
+    MissingWinnerAccount,
+    #[msg("RWA tokens for this project have not been distributed")]
+    RwaNotDistributed,
+    #[msg("Batch accounts are missing, mismatched or exceed the per-transaction maximum")]
+    InvalidBatchAccounts,
+    #[msg("Maker already has the maximum number of open offers")]
+    MaxOpenOffersReached,
+    #[msg("Account is not an OtcConfig")]
+    InvalidConfigAccount,
+    #[msg("Account is not a MakerState")]
+    InvalidMakerState,
+    #[msg("Offer price or amount no longer matches the taker's limits")]
+    PriceChanged,
+    #[msg("Offer is private and the signer is not its allowed taker")]
+    NotAllowedTaker,
+    #[msg("Offer has not expired yet")]
+    OfferNotExpired,
+    #[msg("Escrow token account is not this offer's escrow ATA")]
+    InvalidEscrowAccount,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("Account is not an offer")]
+    InvalidOfferAccount,
+    #[msg("Offer already uses the current layout")]
+    OfferAlreadyMigrated,
+    #[msg("Maker quote account is required to reprice a Buy offer")]
+    QuoteAccountRequired,
+    #[msg("Auction already has a bid and can only be settled")]
+    AuctionHasBids,
+}
+
+#[derive(Accounts)]
+pub struct InitializeOtcConfig<'info> {
+    /// Must be the program upgrade authority (validated in instruction).
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = OTC_CONFIG_SPACE,
+        seeds = [b"otc_config"],
+        bump,
+    )]
+    pub otc_config: Account<'info, OtcConfig>,
+
+    /// CHECK: validated in instruction
+    pub program_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated in instruction
+    pub program_data_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateOtcConfig<'info> {
+    /// Must be the program upgrade authority (validated in instruction). Pays rent when a legacy config is grown.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: OtcConfig PDA; parsed with OtcConfig::from_account_data so legacy (shorter) layouts can be grown.
+    #[account(mut, seeds = [b"otc_config"], bump, owner = crate::ID)]
+    pub otc_config: UncheckedAccount<'info>,
+
+    /// CHECK: validated in instruction
+    pub program_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated in instruction
+    pub program_data_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMakerState<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner checked here; discriminator and length checked in instruction (may be the pre-counter layout).
+    #[account(mut, owner = crate::ID)]
+    pub maker_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateOffer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner checked here; discriminator and length checked in migrate_offer_data (legacy layouts do not deserialize).
+    #[account(mut, owner = crate::ID)]
+    pub offer: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, price: u64, offer_type: OfferType, expiry_slot: u64, expiry_unix: i64, asset_origin: Option<AssetOrigin>)]
+pub struct CreateOffer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = maker,
+        space = MAKER_STATE_SPACE,
+        seeds = [b"maker", maker.key().as_ref()],
+        bump,
+    )]
+    pub maker_state: Account<'info, MakerState>,
+
+    #[account(
+        init,
+        payer = maker,
+        space = OFFER_SPACE,
+        seeds = [b"offer", maker.key().as_ref(), maker_state.nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(seeds = [b"otc_config"], bump)]
+    pub otc_config: Box<Account<'info, OtcConfig>>,
+
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+
+    /// For Sell: maker's asset ATA (source). For Buy: unused but required for account layout.
+    #[account(
+        mut,
+        constraint = maker_asset_ata.mint == asset_mint.key(),
+        constraint = maker_asset_ata.owner == maker.key(),
+    )]
+    pub maker_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// For Buy: maker's quote ATA (source). For Sell: unused but required for account layout.
+    #[account(
+        mut,
+        constraint = maker_quote_ata.owner == maker.key(),
+    )]
+    pub maker_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// PDA: signer for escrow ATA. Seeds = [b"escrow", maker, nonce].
+    /// CHECK: PDA validated by seeds; used as token account authority for CPI with invoke_signed.
+    #[account(
+        seeds = [b"escrow", maker.key().as_ref(), maker_state.nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    /// Escrow ATA: for Sell holds asset; for Buy holds quote. Owner = escrow_authority.
+    #[account(
+        mut,
+        constraint = escrow_ata.owner == escrow_authority.key(),
+    )]
+    pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Quote mint ($TASTE). Required for Buy; for Sell used to validate quote_token_program.
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token program for the asset mint. Must be Token-2022.
+    pub asset_token_program: Interface<'info, TokenInterface>,
+
+    /// Token program for the quote mint. Must be Token-2022.
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+
+    /// rwa_token RwaState for the listed project. Required for RWA mints (see require_rwa_distributed).
+    /// CHECK: address, owner and discriminator validated in require_rwa_distributed.
+    pub rwa_state: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"offer", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        bump = offer.bump,
+        constraint = offer.maker == maker.key(),
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// CHECK: maker's MakerState PDA; its open-offer count is decremented when on the current layout.
+    #[account(mut, seeds = [b"maker", maker.key().as_ref()], bump)]
+    pub maker_state: UncheckedAccount<'info>,
+
+    pub asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = maker_asset_ata.owner == maker.key(),
+        constraint = maker_asset_ata.mint == offer.mint,
+    )]
+    pub maker_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = maker_quote_ata.owner == maker.key(),
+    )]
+    pub maker_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA validated by seeds; used as token authority for escrow return CPI with invoke_signed.
+    #[account(
+        seeds = [b"escrow", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow_ata.key() == offer_escrow_ata(&escrow_authority.key(), &offer.escrow_mint(&quote_mint.key())) @ OtcError::InvalidEscrowAccount,
+        constraint = escrow_ata.owner == escrow_authority.key(),
+    )]
+    pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimExpiredOffer<'info> {
+    /// Anyone can reclaim once the offer has expired; funds only ever go to the maker.
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"offer", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// CHECK: maker's MakerState PDA; its open-offer count is decremented when on the current layout.
+    #[account(mut, seeds = [b"maker", offer.maker.as_ref()], bump)]
+    pub maker_state: UncheckedAccount<'info>,
+
+    pub asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = maker_asset_ata.owner == offer.maker,
+        constraint = maker_asset_ata.mint == offer.mint,
+    )]
+    pub maker_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = maker_quote_ata.owner == offer.maker,
+        constraint = maker_quote_ata.mint == quote_mint.key(),
+    )]
+    pub maker_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA validated by seeds; used as token authority for escrow return CPI with invoke_signed.
+    #[account(
+        seeds = [b"escrow", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow_ata.key() == offer_escrow_ata(&escrow_authority.key(), &offer.escrow_mint(&quote_mint.key())) @ OtcError::InvalidEscrowAccount,
+        constraint = escrow_ata.owner == escrow_authority.key(),
+    )]
+    pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct GetOfferPrice<'info> {
+    #[account(
+        seeds = [b"offer", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+}
+
+#[derive(Accounts)]
+pub struct GetOfferState<'info> {
+    #[account(
+        seeds = [b"offer", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOffersBatch<'info> {
+    pub maker: Signer<'info>,
+
+    /// CHECK: maker's MakerState PDA; its open-offer count is decremented when on the current layout.
+    #[account(mut, seeds = [b"maker", maker.key().as_ref()], bump)]
+    pub maker_state: UncheckedAccount<'info>,
+
+    /// Token program for all escrowed mints. Must be Token-2022.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateOffer<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"offer", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        bump = offer.bump,
+        constraint = offer.maker == maker.key() @ OtcError::NotMaker,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// Quote mint ($TASTE). Used for Buy escrow top-up/refund; required for account layout on Sell.
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Maker's quote ATA: top-up source / refund destination when a Buy offer's price changes. Omit for Sell.
+    #[account(
+        mut,
+        constraint = maker_quote_ata.owner == maker.key(),
+        constraint = maker_quote_ata.mint == quote_mint.key(),
+    )]
+    pub maker_quote_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: PDA validated by seeds; used as token authority for escrow refund CPI with invoke_signed.
+    #[account(
+        seeds = [b"escrow", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    /// The offer's own escrow: the escrow authority's ATA for the escrowed mint.
+    #[account(
+        mut,
+        constraint = escrow_ata.key() == offer_escrow_ata(&escrow_authority.key(), &offer.escrow_mint(&quote_mint.key())) @ OtcError::InvalidEscrowAccount,
+        constraint = escrow_ata.mint == offer.escrow_mint(&quote_mint.key()) @ OtcError::InvalidEscrowAccount,
+        constraint = escrow_ata.owner == escrow_authority.key() @ OtcError::InvalidEscrowAccount,
+    )]
+    pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the quote mint. Must be Token-2022.
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    /// Taker: the only signer; instant execution.
+    pub taker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"offer", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// CHECK: maker's MakerState PDA; its open-offer count is decremented when the offer is fully taken.
+    #[account(mut, seeds = [b"maker", offer.maker.as_ref()], bump)]
+    pub maker_state: UncheckedAccount<'info>,
+
+    pub asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = maker_asset_ata.mint == offer.mint,
+        constraint = maker_asset_ata.owner == offer.maker,
+    )]
+    pub maker_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = taker_asset_ata.mint == offer.mint,
+        constraint = taker_asset_ata.owner == taker.key(),
+    )]
+    pub taker_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = maker_quote_ata.mint == quote_mint.key(),
+        constraint = maker_quote_ata.owner == offer.maker,
+    )]
+    pub maker_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = taker_quote_ata.mint == quote_mint.key(),
+        constraint = taker_quote_ata.owner == taker.key(),
+    )]
+    pub taker_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA validated by seeds; used as token authority for escrow CPI with invoke_signed.
+    #[account(
+        seeds = [b"escrow", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow_ata.key() == offer_escrow_ata(&escrow_authority.key(), &offer.escrow_mint(&quote_mint.key())) @ OtcError::InvalidEscrowAccount,
+        constraint = escrow_ata.owner == escrow_authority.key(),
+    )]
+    pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(seeds = [b"otc_config"], bump)]
+    pub otc_config: Box<Account<'info, OtcConfig>>,
+
+    /// Treasury quote account; receives the platform fee.
+    #[account(
+        mut,
+        constraint = treasury_quote_ata.mint == quote_mint.key(),
+        constraint = treasury_quote_ata.owner == otc_config.treasury,
+    )]
+    pub treasury_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the asset. Must be Token-2022.
+    pub asset_token_program: Interface<'info, TokenInterface>,
+
+    /// Token program for the quote ($TASTE). Must be Token-2022.
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    /// Optional taste_token ProtocolState; fills fail while it is paused.
+    /// CHECK: address and contents validated by taste_token::protocol_paused
+    pub protocol_state: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> AcceptOffer<'info> {
+    pub fn validate_token_2022(&self) -> Result<()> {
+        require!(
+            self.asset_token_program.key() == TOKEN_2022_PROGRAM_ID,
+            OtcError::NotToken2022
+        );
+        require!(
+            self.quote_token_program.key() == TOKEN_2022_PROGRAM_ID,
+            OtcError::NotToken2022
+        );
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    #[account(mut)]
     pub maker: Signer<'info>,
 
     #[account(
         init_if_needed,
         payer = maker,
-        space = 8 + 8,
+        space = MAKER_STATE_SPACE,
         seeds = [b"maker", maker.key().as_ref()],
         bump,
     )]
@@ -370,15 +2191,21 @@ pub struct CreateOffer<'info> {
     #[account(
         init,
         payer = maker,
-        space = 8 + 32 + 32 + 8 + 8 + 1 + 1 + 8 + 1 + 8,
-        seeds = [b"offer", maker.key().as_ref(), maker_state.nonce.to_le_bytes().as_ref()],
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 32 + 1 + 1 + 8,
+        seeds = [b"auction", maker.key().as_ref(), maker_state.nonce.to_le_bytes().as_ref()],
         bump,
     )]
-    pub offer: Account<'info, Offer>,
+    pub auction: Box<Account<'info, Auction>>,
 
-    pub asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(seeds = [b"otc_config"], bump)]
+    pub otc_config: Box<Account<'info, OtcConfig>>,
+
+    pub asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Quote mint bids will be placed in. Must be Token-2022.
+    #[account(constraint = quote_mint.to_account_info().owner == &TOKEN_2022_PROGRAM_ID @ OtcError::NotToken2022)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// For Sell: maker's asset ATA (source). For Buy: unused but required for account layout.
     #[account(
         mut,
         constraint = maker_asset_ata.mint == asset_mint.key(),
@@ -386,14 +2213,7 @@ pub struct CreateOffer<'info> {
     )]
     pub maker_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// For Buy: maker's quote ATA (source). For Sell: unused but required for account layout.
-    #[account(
-        mut,
-        constraint = maker_quote_ata.owner == maker.key(),
-    )]
-    pub maker_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
-
-    /// PDA: signer for escrow ATA. Seeds = [b"escrow", maker, nonce].
+    /// PDA: signer for escrow ATAs. Seeds = [b"escrow", maker, nonce].
     /// CHECK: PDA validated by seeds; used as token account authority for CPI with invoke_signed.
     #[account(
         seeds = [b"escrow", maker.key().as_ref(), maker_state.nonce.to_le_bytes().as_ref()],
@@ -401,136 +2221,151 @@ pub struct CreateOffer<'info> {
     )]
     pub escrow_authority: UncheckedAccount<'info>,
 
-    /// Escrow ATA: for Sell holds asset; for Buy holds quote. Owner = escrow_authority.
+    /// Escrow ATA for the auctioned asset: the escrow authority's ATA for the asset mint.
     #[account(
         mut,
-        constraint = escrow_ata.owner == escrow_authority.key(),
+        constraint = escrow_ata.key() == offer_escrow_ata(&escrow_authority.key(), &asset_mint.key()) @ OtcError::InvalidEscrowAccount,
     )]
     pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// Quote mint ($TASTE). Required for Buy; for Sell used to validate quote_token_program.
-    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
-
     /// Token program for the asset mint. Must be Token-2022.
     pub asset_token_program: Interface<'info, TokenInterface>,
 
-    /// Token program for the quote mint. Must be Token-2022.
-    pub quote_token_program: Interface<'info, TokenInterface>,
-
     pub system_program: Program<'info, System>,
+
+    /// rwa_token RwaState for the listed project. Required for RWA mints (see require_rwa_distributed).
+    /// CHECK: address, owner and discriminator validated in require_rwa_distributed.
+    pub rwa_state: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
-pub struct CancelOffer<'info> {
-    pub maker: Signer<'info>,
+pub struct PlaceBid<'info> {
+    pub bidder: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"offer", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
-        bump = offer.bump,
-        constraint = offer.maker == maker.key(),
+        seeds = [b"auction", auction.maker.as_ref(), auction.nonce.to_le_bytes().as_ref()],
+        bump = auction.bump,
     )]
-    pub offer: Account<'info, Offer>,
+    pub auction: Box<Account<'info, Auction>>,
 
-    pub asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(constraint = quote_mint.key() == auction.quote_mint)]
     pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         mut,
-        constraint = maker_asset_ata.owner == maker.key(),
-        constraint = maker_asset_ata.mint == offer.mint,
+        constraint = bidder_quote_ata.mint == quote_mint.key(),
+        constraint = bidder_quote_ata.owner == bidder.key(),
     )]
-    pub maker_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub bidder_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// Previous highest bidder's quote account; receives the outbid refund. Required once the auction has a bid.
     #[account(
         mut,
-        constraint = maker_quote_ata.owner == maker.key(),
+        constraint = previous_bidder_quote_ata.mint == quote_mint.key(),
     )]
-    pub maker_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub previous_bidder_quote_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    /// CHECK: PDA validated by seeds; used as token authority for escrow return CPI with invoke_signed.
+    /// CHECK: PDA validated by seeds; used as token authority for refund CPI with invoke_signed.
     #[account(
-        seeds = [b"escrow", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        seeds = [b"escrow", auction.maker.as_ref(), auction.nonce.to_le_bytes().as_ref()],
         bump,
     )]
     pub escrow_authority: UncheckedAccount<'info>,
 
+    /// Escrow ATA holding the highest bid: the escrow authority's ATA for the quote mint.
     #[account(
         mut,
-        constraint = escrow_ata.owner == escrow_authority.key(),
+        constraint = bid_escrow_ata.key() == offer_escrow_ata(&escrow_authority.key(), &quote_mint.key()) @ OtcError::InvalidEscrowAccount,
     )]
-    pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub bid_escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub asset_token_program: Interface<'info, TokenInterface>,
+    /// Token program for the quote mint. Must be Token-2022.
     pub quote_token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct AcceptOffer<'info> {
-    /// Taker: the only signer; instant execution.
-    pub taker: Signer<'info>,
+pub struct SettleAuction<'info> {
+    /// Anyone can settle once bidding has closed.
+    pub settler: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"offer", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
-        bump = offer.bump,
+        seeds = [b"auction", auction.maker.as_ref(), auction.nonce.to_le_bytes().as_ref()],
+        bump = auction.bump,
     )]
-    pub offer: Box<Account<'info, Offer>>,
+    pub auction: Box<Account<'info, Auction>>,
 
+    #[account(constraint = asset_mint.key() == auction.mint)]
     pub asset_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    #[account(mut)]
+    #[account(constraint = quote_mint.key() == auction.quote_mint)]
     pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// Maker's asset account; receives the asset back when there were no bids.
     #[account(
         mut,
-        constraint = maker_asset_ata.mint == offer.mint,
-        constraint = maker_asset_ata.owner == offer.maker,
+        constraint = maker_asset_ata.mint == auction.mint,
+        constraint = maker_asset_ata.owner == auction.maker,
     )]
     pub maker_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(
-        mut,
-        constraint = taker_asset_ata.mint == offer.mint,
-        constraint = taker_asset_ata.owner == taker.key(),
-    )]
-    pub taker_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
-
     #[account(
         mut,
         constraint = maker_quote_ata.mint == quote_mint.key(),
-        constraint = maker_quote_ata.owner == offer.maker,
+        constraint = maker_quote_ata.owner == auction.maker,
     )]
     pub maker_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// Winning bidder's asset account. Required when the auction has a bid.
     #[account(
         mut,
-        constraint = taker_quote_ata.mint == quote_mint.key(),
-        constraint = taker_quote_ata.owner == taker.key(),
+        constraint = winner_asset_ata.mint == auction.mint,
     )]
-    pub taker_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub winner_asset_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     /// CHECK: PDA validated by seeds; used as token authority for escrow CPI with invoke_signed.
     #[account(
-        seeds = [b"escrow", offer.maker.as_ref(), offer.nonce.to_le_bytes().as_ref()],
+        seeds = [b"escrow", auction.maker.as_ref(), auction.nonce.to_le_bytes().as_ref()],
         bump,
     )]
     pub escrow_authority: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        constraint = escrow_ata.owner == escrow_authority.key(),
+        constraint = escrow_ata.key() == offer_escrow_ata(&escrow_authority.key(), &auction.mint) @ OtcError::InvalidEscrowAccount,
     )]
     pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    #[account(
+        mut,
+        constraint = bid_escrow_ata.key() == offer_escrow_ata(&escrow_authority.key(), &auction.quote_mint) @ OtcError::InvalidEscrowAccount,
+    )]
+    pub bid_escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: maker's MakerState PDA; its open-offer count is decremented when on the current layout.
+    #[account(mut, seeds = [b"maker", auction.maker.as_ref()], bump)]
+    pub maker_state: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"otc_config"], bump)]
+    pub otc_config: Box<Account<'info, OtcConfig>>,
+
+    /// Treasury quote account; receives the platform fee.
+    #[account(
+        mut,
+        constraint = treasury_quote_ata.mint == quote_mint.key(),
+        constraint = treasury_quote_ata.owner == otc_config.treasury,
+    )]
+    pub treasury_quote_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
     /// Token program for the asset. Must be Token-2022.
     pub asset_token_program: Interface<'info, TokenInterface>,
 
-    /// Token program for the quote ($TASTE). Must be Token-2022.
+    /// Token program for the quote. Must be Token-2022.
     pub quote_token_program: Interface<'info, TokenInterface>,
 }
 
-impl<'info> AcceptOffer<'info> {
+impl<'info> SettleAuction<'info> {
     pub fn validate_token_2022(&self) -> Result<()> {
         require!(
             self.asset_token_program.key() == TOKEN_2022_PROGRAM_ID,
@@ -543,3 +2378,566 @@ impl<'info> AcceptOffer<'info> {
         Ok(())
     }
 }
+
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = maker @ OtcError::NotMaker,
+        seeds = [b"auction", auction.maker.as_ref(), auction.nonce.to_le_bytes().as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    /// CHECK: maker's MakerState PDA; its open-offer count is decremented when on the current layout.
+    #[account(mut, seeds = [b"maker", maker.key().as_ref()], bump)]
+    pub maker_state: UncheckedAccount<'info>,
+
+    #[account(constraint = asset_mint.key() == auction.mint)]
+    pub asset_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = maker_asset_ata.mint == auction.mint,
+        constraint = maker_asset_ata.owner == maker.key(),
+    )]
+    pub maker_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA validated by seeds; used as token authority for escrow CPI with invoke_signed.
+    #[account(
+        seeds = [b"escrow", auction.maker.as_ref(), auction.nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow_ata.key() == offer_escrow_ata(&escrow_authority.key(), &auction.mint) @ OtcError::InvalidEscrowAccount,
+    )]
+    pub escrow_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program for the asset. Must be Token-2022.
+    #[account(constraint = asset_token_program.key() == TOKEN_2022_PROGRAM_ID @ OtcError::NotToken2022)]
+    pub asset_token_program: Interface<'info, TokenInterface>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offer_with_expiry(expiry_slot: u64, expiry_unix: i64) -> Offer {
+        Offer {
+            maker: Pubkey::default(),
+            mint: Pubkey::default(),
+            amount: 1,
+            price: 1,
+            offer_type: OfferType::Sell,
+            status: OfferStatus::Open,
+            expiry_slot,
+            bump: 0,
+            nonce: 0,
+            expiry_unix,
+            filled: 0,
+            allowed_taker: None,
+        }
+    }
+
+    fn clock_at(slot: u64, unix_timestamp: i64) -> Clock {
+        Clock {
+            slot,
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn test_offer_expiry_bounds() {
+        let now = clock_at(100, 1_000);
+        assert!(!offer_with_expiry(0, 0).is_expired(&now));
+        assert!(!offer_with_expiry(100, 0).is_expired(&now));
+        assert!(offer_with_expiry(99, 0).is_expired(&now));
+        assert!(!offer_with_expiry(0, 1_000).is_expired(&now));
+        assert!(offer_with_expiry(0, 999).is_expired(&now));
+        // Either bound passing expires the offer.
+        assert!(offer_with_expiry(1_000, 999).is_expired(&now));
+        assert!(offer_with_expiry(99, 5_000).is_expired(&now));
+    }
+
+    #[test]
+    fn test_migrate_offer_data_keeps_legacy_expiry() {
+        let legacy = |expiry_slot: u64| {
+            let mut data = Vec::new();
+            offer_with_expiry(expiry_slot, 0)
+                .try_serialize(&mut data)
+                .unwrap();
+            data.truncate(OFFER_BASE_LEN);
+            data
+        };
+        let now = clock_at(100, 1_000);
+        // Legacy slot expiry carries over unchanged.
+        let live = migrate_offer_data(&legacy(200)).unwrap();
+        assert_eq!((live.expiry_slot, live.expiry_unix), (200, 0));
+        assert!(!live.is_expired(&now));
+        assert_eq!(live.filled, 0);
+        assert_eq!(live.allowed_taker, None);
+        // expiry_slot == 0 could never be accepted before; it must not become a never-expiring offer.
+        assert!(migrate_offer_data(&legacy(0)).unwrap().is_expired(&now));
+
+        let mut current = Vec::new();
+        offer_with_expiry(0, 0).try_serialize(&mut current).unwrap();
+        // Accounts are allocated at OFFER_SPACE; allowed_taker = None leaves the tail zeroed.
+        current.resize(OFFER_SPACE, 0);
+        assert!(migrate_offer_data(&current).is_err());
+        let mut other = legacy(200);
+        other[0] ^= 1;
+        assert!(migrate_offer_data(&other).is_err());
+    }
+
+    /// Replays accept_offer's bookkeeping and escrow movements for one fill.
+    fn apply_fill(offer: &mut Offer, escrow: &mut u64, fill_amount: u64) {
+        let quote = quote_for_fill(
+            offer.amount,
+            offer.price,
+            fill_amount,
+            offer.offer_type == OfferType::Sell,
+        )
+        .unwrap();
+        *escrow -= match offer.offer_type {
+            OfferType::Sell => fill_amount,
+            OfferType::Buy => quote,
+        };
+        offer.amount -= fill_amount;
+        offer.price -= quote;
+        offer.filled += fill_amount;
+    }
+
+    #[test]
+    fn test_cancel_after_partial_fill_returns_remainder() {
+        for offer_type in [OfferType::Sell, OfferType::Buy] {
+            let mut offer = offer_with_expiry(0, 0);
+            offer.offer_type = offer_type;
+            offer.amount = 1_000;
+            offer.price = 5_003;
+            let mut escrow = offer.escrow_balance();
+
+            apply_fill(&mut offer, &mut escrow, 300);
+            apply_fill(&mut offer, &mut escrow, 1);
+            assert_eq!(offer.filled, 301);
+            assert_eq!(offer.amount, 699);
+            // Cancelling returns what is left in escrow, not the original listing.
+            assert_eq!(offer.escrow_balance(), escrow);
+            assert!(offer.escrow_balance() < 5_003);
+        }
+    }
+
+    #[test]
+    fn test_offer_state_tracks_partial_fill() {
+        let mut offer = offer_with_expiry(500, 1_700_000_000);
+        offer.amount = 1_000;
+        offer.price = 2_000;
+        let mut escrow = offer.escrow_balance();
+        apply_fill(&mut offer, &mut escrow, 250);
+        let state = offer.state();
+        assert_eq!(state.remaining_amount, 750);
+        assert_eq!(state.remaining_price, 1_500);
+        assert_eq!(state.filled, 250);
+        assert!(state.status == OfferStatus::Open);
+        assert_eq!((state.expiry_slot, state.expiry_unix), (500, 1_700_000_000));
+        // The remaining price is what filling the rest would cost.
+        assert_eq!(
+            offer.fill_quote(state.remaining_amount),
+            Some(state.remaining_price)
+        );
+    }
+
+    #[test]
+    fn test_escrow_ata_pins_nonce_and_mint() {
+        let maker = Pubkey::new_unique();
+        let quote_mint = Pubkey::new_unique();
+        let authority = |nonce: u64| {
+            Pubkey::find_program_address(
+                &[b"escrow", maker.as_ref(), &nonce.to_le_bytes()],
+                &crate::ID,
+            )
+            .0
+        };
+        let mut offer = offer_with_expiry(0, 0);
+        offer.mint = Pubkey::new_unique();
+        let sell_escrow = offer_escrow_ata(&authority(1), &offer.escrow_mint(&quote_mint));
+        // A stale escrow from another nonce of the same maker never matches.
+        assert_ne!(sell_escrow, offer_escrow_ata(&authority(0), &offer.mint));
+        // Same authority, wrong mint: the quote ATA is not a Sell offer's escrow.
+        assert_ne!(sell_escrow, offer_escrow_ata(&authority(1), &quote_mint));
+        offer.offer_type = OfferType::Buy;
+        assert_eq!(
+            offer_escrow_ata(&authority(1), &offer.escrow_mint(&quote_mint)),
+            offer_escrow_ata(&authority(1), &quote_mint)
+        );
+    }
+
+    #[test]
+    fn test_fill_quote_matches_accept_computation() {
+        for offer_type in [OfferType::Sell, OfferType::Buy] {
+            let mut offer = offer_with_expiry(0, 0);
+            offer.offer_type = offer_type;
+            offer.amount = 3;
+            offer.price = 10;
+            let round_up = offer_type == OfferType::Sell;
+            for fill in 1..=3 {
+                assert_eq!(
+                    offer.fill_quote(fill),
+                    quote_for_fill(offer.amount, offer.price, fill, round_up)
+                );
+            }
+            assert_eq!(offer.fill_quote(3), Some(10));
+            assert_eq!(offer.fill_quote(4), None);
+        }
+    }
+
+    #[test]
+    fn test_offer_limits() {
+        assert!(check_offer_limits(2_000, 1_000, None, None).is_ok());
+        assert!(check_offer_limits(2_000, 1_000, Some(2_000), Some(1_000)).is_ok());
+        // The maker repriced above what the taker saw.
+        assert!(check_offer_limits(2_001, 1_000, Some(2_000), None).is_err());
+        // Someone else filled part of the offer first.
+        assert!(check_offer_limits(1_400, 700, None, Some(1_000)).is_err());
+        // A cheaper price is always fine.
+        assert!(check_offer_limits(1_500, 1_000, Some(2_000), Some(1_000)).is_ok());
+    }
+
+    #[test]
+    fn test_private_offer_taker() {
+        let mut offer = offer_with_expiry(0, 0);
+        let buyer = Pubkey::new_unique();
+        assert!(offer.taker_allowed(&buyer));
+        offer.allowed_taker = Some(buyer);
+        assert!(offer.taker_allowed(&buyer));
+        assert!(!offer.taker_allowed(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_quote_for_fill_rounding() {
+        // Sell: taker pays, rounds up. Buy: escrow pays, rounds down.
+        assert_eq!(quote_for_fill(3, 10, 1, true), Some(4));
+        assert_eq!(quote_for_fill(3, 10, 1, false), Some(3));
+        assert_eq!(quote_for_fill(3, 10, 3, true), Some(10));
+        assert_eq!(quote_for_fill(3, 10, 3, false), Some(10));
+        assert_eq!(quote_for_fill(3, 10, 4, true), None);
+        assert_eq!(quote_for_fill(0, 10, 0, true), None);
+        assert_eq!(
+            quote_for_fill(u64::MAX, u64::MAX, u64::MAX, true),
+            Some(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_open_offer_cap() {
+        // Blocked at the cap, freed once an offer closes.
+        assert_eq!(open_offer_slot(0, 2).unwrap(), 1);
+        assert_eq!(open_offer_slot(1, 2).unwrap(), 2);
+        assert!(open_offer_slot(2, 2).is_err());
+        // After a cancel releases a slot the maker can open another.
+        assert_eq!(open_offer_slot(1, 2).unwrap(), 2);
+        // 0 = unlimited.
+        assert_eq!(open_offer_slot(10_000, 0).unwrap(), 10_001);
+    }
+
+    #[test]
+    fn test_otc_config_legacy_layout_is_uncapped() {
+        let config = OtcConfig {
+            fee_bps: DEFAULT_FEE_BPS,
+            treasury: Pubkey::new_unique(),
+            restrict_assets: true,
+            rwa_token_program: Pubkey::new_unique(),
+            project_escrow_program: Pubkey::new_unique(),
+            max_open_offers: 7,
+        };
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), OTC_CONFIG_SPACE);
+        let parsed = OtcConfig::from_account_data(&data[..OTC_CONFIG_BASE_LEN]).unwrap();
+        assert_eq!(parsed.treasury, config.treasury);
+        assert_eq!(parsed.max_open_offers, 0);
+        assert_eq!(
+            OtcConfig::from_account_data(&data).unwrap().max_open_offers,
+            7
+        );
+    }
+
+    #[test]
+    fn test_platform_fee() {
+        assert_eq!(platform_fee(1_000_000, DEFAULT_FEE_BPS), Some(10_000));
+        assert_eq!(platform_fee(99, DEFAULT_FEE_BPS), Some(0));
+        assert_eq!(platform_fee(1_000_000, 0), Some(0));
+        assert_eq!(platform_fee(u64::MAX, MAX_FEE_BPS), Some(u64::MAX / 10));
+    }
+
+    #[test]
+    fn test_asset_mint_allowed() {
+        let mut config = OtcConfig {
+            fee_bps: DEFAULT_FEE_BPS,
+            treasury: Pubkey::default(),
+            restrict_assets: false,
+            rwa_token_program: Pubkey::new_unique(),
+            project_escrow_program: Pubkey::new_unique(),
+            max_open_offers: 0,
+        };
+        let project = Pubkey::new_unique();
+        let backer = Pubkey::new_unique();
+        let arbitrary = Pubkey::new_unique();
+        let rwa_mint = Pubkey::find_program_address(
+            &[b"rwa_mint", project.as_ref()],
+            &config.rwa_token_program,
+        )
+        .0;
+        let receipt_mint = Pubkey::find_program_address(
+            &[b"receipt", project.as_ref(), backer.as_ref()],
+            &config.project_escrow_program,
+        )
+        .0;
+        let rwa = AssetOrigin::Rwa { project };
+        let receipt = AssetOrigin::Receipt { project, backer };
+
+        assert!(asset_mint_allowed(&config, None, &arbitrary));
+
+        config.restrict_assets = true;
+        assert!(!asset_mint_allowed(&config, None, &arbitrary));
+        assert!(!asset_mint_allowed(&config, Some(&rwa), &arbitrary));
+        assert!(asset_mint_allowed(&config, Some(&rwa), &rwa_mint));
+        assert!(asset_mint_allowed(&config, Some(&receipt), &receipt_mint));
+        assert!(!asset_mint_allowed(&config, Some(&receipt), &rwa_mint));
+    }
+
+    /// Token-2022 mint account data, with a TransferHook extension when `hooked`.
+    fn mint_data(hooked: bool) -> Vec<u8> {
+        use anchor_lang::solana_program::program_pack::Pack;
+        use anchor_spl::token_interface::spl_token_2022::extension::{
+            BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+        };
+        let extensions: &[ExtensionType] = if hooked {
+            &[ExtensionType::TransferHook]
+        } else {
+            &[]
+        };
+        let len = ExtensionType::try_calculate_account_len::<SplMint>(extensions).unwrap();
+        let mut data = vec![0u8; len];
+        if hooked {
+            let mut state =
+                StateWithExtensionsMut::<SplMint>::unpack_uninitialized(&mut data).unwrap();
+            state.init_extension::<TransferHook>(true).unwrap();
+            state.base = SplMint {
+                decimals: 6,
+                is_initialized: true,
+                ..SplMint::default()
+            };
+            state.pack_base();
+            state.init_account_type().unwrap();
+        } else {
+            SplMint {
+                decimals: 6,
+                is_initialized: true,
+                ..SplMint::default()
+            }
+            .pack_into_slice(&mut data);
+        }
+        data
+    }
+
+    #[test]
+    fn test_rwa_listing_requires_distribution() {
+        let config = OtcConfig {
+            fee_bps: DEFAULT_FEE_BPS,
+            treasury: Pubkey::default(),
+            restrict_assets: false,
+            rwa_token_program: Pubkey::new_unique(),
+            project_escrow_program: Pubkey::new_unique(),
+            max_open_offers: 0,
+        };
+        let project = Pubkey::new_unique();
+        let origin = AssetOrigin::Rwa { project };
+        let (state_key, _) = Pubkey::find_program_address(
+            &[b"rwa_state", project.as_ref()],
+            &config.rwa_token_program,
+        );
+        let state_data = |minted: u64| {
+            let state = RwaState {
+                project,
+                authority: Pubkey::default(),
+                total_supply: 1_000,
+                minted,
+                mint_frozen: false,
+                holder_count: 0,
+            };
+            let mut data = Vec::new();
+            state.try_serialize(&mut data).unwrap();
+            data
+        };
+        let (rwa_mint, _) = Pubkey::find_program_address(
+            &[b"rwa_mint", project.as_ref()],
+            &config.rwa_token_program,
+        );
+        let token_2022 = TOKEN_2022_PROGRAM_ID;
+        let check = |origin: Option<&AssetOrigin>,
+                     mint_key: &Pubkey,
+                     mut mint_data: Vec<u8>,
+                     state: Option<(&Pubkey, &Pubkey, Vec<u8>)>| {
+            let (mut mint_lamports, mut state_lamports) = (0u64, 0u64);
+            let mint = AccountInfo::new(
+                mint_key,
+                false,
+                false,
+                &mut mint_lamports,
+                &mut mint_data,
+                &token_2022,
+                false,
+                0,
+            );
+            match state {
+                Some((key, owner, mut data)) => {
+                    let info = AccountInfo::new(
+                        key,
+                        false,
+                        false,
+                        &mut state_lamports,
+                        &mut data,
+                        owner,
+                        false,
+                        0,
+                    );
+                    require_rwa_distributed(&config, origin, &mint, Some(&info)).is_ok()
+                }
+                None => require_rwa_distributed(&config, origin, &mint, None).is_ok(),
+            }
+        };
+        let rwa_program = config.rwa_token_program;
+
+        // Before any claim nothing has been minted, so the listing is rejected.
+        assert!(!check(
+            Some(&origin),
+            &rwa_mint,
+            mint_data(true),
+            Some((&state_key, &rwa_program, state_data(0)))
+        ));
+        assert!(check(
+            Some(&origin),
+            &rwa_mint,
+            mint_data(true),
+            Some((&state_key, &rwa_program, state_data(1)))
+        ));
+        // Leaving the origin unset does not skip the check for a transfer-hook (RWA) mint.
+        assert!(!check(None, &rwa_mint, mint_data(true), None));
+        assert!(!check(
+            None,
+            &rwa_mint,
+            mint_data(true),
+            Some((&state_key, &rwa_program, state_data(0)))
+        ));
+        assert!(check(
+            None,
+            &rwa_mint,
+            mint_data(true),
+            Some((&state_key, &rwa_program, state_data(1)))
+        ));
+        // Wrong address, owner or mint is rejected even when minted.
+        assert!(!check(
+            Some(&origin),
+            &rwa_mint,
+            mint_data(true),
+            Some((&Pubkey::new_unique(), &rwa_program, state_data(1)))
+        ));
+        assert!(!check(
+            Some(&origin),
+            &rwa_mint,
+            mint_data(true),
+            Some((&state_key, &Pubkey::new_unique(), state_data(1)))
+        ));
+        assert!(!check(
+            None,
+            &Pubkey::new_unique(),
+            mint_data(true),
+            Some((&state_key, &rwa_program, state_data(1)))
+        ));
+        // A named Rwa origin must match the RwaState's project.
+        let other = AssetOrigin::Rwa {
+            project: Pubkey::new_unique(),
+        };
+        assert!(!check(
+            Some(&other),
+            &rwa_mint,
+            mint_data(true),
+            Some((&state_key, &rwa_program, state_data(1)))
+        ));
+        // Receipts and plain mints without an Rwa origin skip the check.
+        let receipt = AssetOrigin::Receipt {
+            project,
+            backer: Pubkey::new_unique(),
+        };
+        let plain = Pubkey::new_unique();
+        assert!(check(Some(&receipt), &plain, mint_data(false), None));
+        assert!(check(None, &plain, mint_data(false), None));
+    }
+
+    #[test]
+    fn test_batch_cancel_count() {
+        assert_eq!(batch_cancel_count(0), None);
+        assert_eq!(batch_cancel_count(4), None);
+        assert_eq!(batch_cancel_count(15), Some(3));
+        assert_eq!(
+            batch_cancel_count(MAX_BATCH_CANCEL * BATCH_CANCEL_ACCOUNTS_PER_OFFER),
+            Some(MAX_BATCH_CANCEL)
+        );
+        assert_eq!(
+            batch_cancel_count((MAX_BATCH_CANCEL + 1) * BATCH_CANCEL_ACCOUNTS_PER_OFFER),
+            None
+        );
+    }
+
+    #[test]
+    fn test_min_next_bid_outbidding() {
+        // First bid must meet the reserve; a zero reserve still requires a non-zero bid.
+        assert_eq!(min_next_bid(0, 500, 10), Some(500));
+        assert_eq!(min_next_bid(0, 0, 10), Some(1));
+        // Later bids must beat the highest bid by the increment, or by 1 when the increment is 0.
+        assert_eq!(min_next_bid(500, 500, 10), Some(510));
+        assert_eq!(min_next_bid(500, 500, 0), Some(501));
+        assert_eq!(min_next_bid(u64::MAX, 0, 1), None);
+
+        let (reserve, increment) = (100u64, 25u64);
+        let mut highest = 0u64;
+        for bid in [100u64, 125, 200, 225] {
+            let min = min_next_bid(highest, reserve, increment).unwrap();
+            assert!(bid >= min);
+            highest = bid;
+        }
+        assert!(min_next_bid(highest, reserve, increment).unwrap() > 249);
+    }
+
+    #[test]
+    fn test_auction_settlement_split() {
+        // Winning bid is split between treasury fee and maker; nothing is left in the bid escrow.
+        let winning_bid = 1_234_567u64;
+        let fee = platform_fee(winning_bid, DEFAULT_FEE_BPS).unwrap();
+        assert_eq!(fee, 12_345);
+        assert_eq!(fee + (winning_bid - fee), winning_bid);
+    }
+
+    #[test]
+    fn test_partial_fills_sum_to_listed_price() {
+        for round_up in [true, false] {
+            let (mut amount, mut price) = (1_000_000u64, 2_000_001u64);
+            let mut paid = 0u64;
+            for fill in [333_333u64, 1, 250_000, 416_666] {
+                let quote = quote_for_fill(amount, price, fill, round_up).unwrap();
+                amount -= fill;
+                price -= quote;
+                paid += quote;
+            }
+            assert_eq!(amount, 0);
+            assert_eq!(price, 0);
+            assert_eq!(paid, 2_000_001);
+        }
+    }
+}
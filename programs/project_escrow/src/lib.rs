@@ -2,7 +2,7 @@
 
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{
-    Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+    Burn, CloseAccount, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
 };
 use mpl_token_metadata::{
     accounts::{MasterEdition, Metadata},
@@ -19,14 +19,17 @@ declare_id!("EumKWobeYfq9Lx9zwVjfJvaghYC321cxENEcasKfuRrK");
 #[cfg(feature = "devnet")]
 declare_id!("bJch5cLcCHTypbXrvRMr9MxU5HmN2LBRwF8wR4dXpym");
 
-/// Babylonian method for quadratic vote weight (same as governance).
+/// Floor integer square root for quadratic vote weight (same as governance).
 #[inline]
 fn sqrt_u64(x: u64) -> u64 {
     if x == 0 {
         return 0;
     }
-    let mut z = x.div_ceil(2);
-    let mut y = x;
+    // 2^ceil(bits/2) is never below sqrt(x), so Newton's iteration descends straight to the floor root
+    // in a handful of steps instead of halving down from x.
+    let bits = u64::BITS - x.leading_zeros();
+    let mut y = 1u64 << bits.div_ceil(2);
+    let mut z = (x / y + y) / 2;
     while z < y {
         y = z;
         z = (x / z + z) / 2;
@@ -87,10 +90,202 @@ pub enum EscrowError {
     InvalidTokenMetadataProgram,
     #[msg("Project name too long (max 32 chars)")]
     ProjectNameTooLong,
+    #[msg("Burn vault is empty")]
+    BurnVaultEmpty,
+    #[msg("Account is not a Project")]
+    InvalidProjectAccount,
+    #[msg("Project is already at the current version")]
+    ProjectAlreadyMigrated,
+    #[msg("Backer account does not belong to this project")]
+    InvalidBackerAccount,
+    #[msg("Backer account passed more than once")]
+    DuplicateBackerAccount,
+    #[msg("Project deadline has already passed")]
+    DeadlinePassed,
+    #[msg("New deadline must be later than the current deadline")]
+    InvalidDeadlineExtension,
+    #[msg("Deadline extension exceeds the cap without governance approval")]
+    DeadlineExtensionTooLarge,
+    #[msg("Project deadline has not passed yet")]
+    DeadlineNotReached,
+    #[msg("Project reached its goal or soft cap")]
+    GoalReached,
+    #[msg("Backer token account mint does not match the project's taste_mint")]
+    InvalidBackerTokenAccount,
+    #[msg("Platform fee exceeds maximum")]
+    FeeTooHigh,
+    #[msg("Invalid config account")]
+    InvalidConfigAccount,
+    #[msg("Funding amount is below the project's minimum")]
+    BelowMinFunding,
+    #[msg("Too many items for one batch call")]
+    BatchTooLarge,
+    #[msg("Funding would exceed the project's per-backer cap")]
+    BackerCapExceeded,
+    #[msg("Platform treasury does not match the configured $TASTE treasury")]
+    InvalidTreasury,
+    #[msg("Soft cap must not exceed the goal")]
+    InvalidSoftCap,
+    #[msg("Referral bps must not exceed 10000")]
+    InvalidReferralBps,
+    #[msg("Referrer token account must be a $TASTE account not owned by the backer")]
+    InvalidReferrer,
+    #[msg("Payout split entries or recipient accounts are invalid")]
+    InvalidPayoutSplit,
+    #[msg("Release amount must be non-zero and at most what is left of the milestone")]
+    InvalidReleaseAmount,
+    #[msg("Project is not completed")]
+    ProjectNotCompleted,
+    #[msg("Escrow still holds funds")]
+    EscrowNotEmpty,
+    #[msg("Goal multiple must be at least 10000 bps")]
+    InvalidGoalMultiple,
+    #[msg("New goal must be greater than the current goal")]
+    GoalNotIncreased,
+    #[msg("New goal exceeds the allowed multiple of the original goal")]
+    GoalRaiseTooLarge,
+    #[msg("Refund window is shorter than the configured minimum")]
+    RefundWindowTooShort,
+    #[msg("Minimum refund window must not be negative")]
+    InvalidMinRefundWindow,
+    #[msg("Zero milestone percentages are only allowed after the last non-zero milestone")]
+    InteriorZeroMilestone,
+    #[msg("Receipt metadata URI must be a non-empty https://, ipfs:// or ar:// URI")]
+    InvalidMetadataUri,
+    #[msg("Backer does not hold a receipt to burn")]
+    ReceiptNotHeld,
+    #[msg("SOL contribution must be non-zero")]
+    EmptyPendingContribution,
+    #[msg("Settled $TASTE amount is below the backer's minimum")]
+    SettlementBelowMinimum,
+    #[msg("Collaborator entries are invalid")]
+    InvalidCollaborators,
+    #[msg("Backer accounts do not cover every active backer of the project")]
+    IncompleteBackerSet,
+    #[msg("Invalid slash record account")]
+    InvalidSlashRecord,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("Backer account is already on the current layout")]
+    BackerAlreadyMigrated,
+    #[msg("Backer has no treasury fee left to refund")]
+    NoFeeToRefund,
+    #[msg("Invalid artist state account")]
+    InvalidArtistStateAccount,
+    #[msg("Artist state is already on the current layout")]
+    ArtistStateAlreadyMigrated,
+    #[msg("Escrow holds no dust to withdraw")]
+    NoEscrowDust,
+    #[msg("Milestone funds were already released")]
+    FundsAlreadyReleased,
+    #[msg("Minimum funding must not exceed the goal")]
+    InvalidMinFunding,
 }
 
 pub const MAX_MILESTONES: usize = 5;
 
+/// Default fund_project fee split (2% treasury, 2% burn), used until Config stores its own values.
+pub const DEFAULT_TREASURY_FEE_BPS: u16 = 200;
+pub const DEFAULT_BURN_FEE_BPS: u16 = 200;
+/// Upper bound on treasury_fee_bps + burn_fee_bps.
+pub const MAX_PLATFORM_FEE_BPS: u16 = 1_000;
+/// Default cap for raise_goal: the goal may grow to 2x the original goal.
+pub const DEFAULT_MAX_GOAL_MULTIPLE_BPS: u16 = 20_000;
+/// Default floor for the apply_material_edit opt-out refund window (7 days).
+pub const DEFAULT_MIN_REFUND_WINDOW_SECS: i64 = 7 * 24 * 60 * 60;
+/// Current Config account size. Older configs are shorter; see Config::from_account_data.
+pub const CONFIG_SPACE: usize = CONFIG_REFUND_WINDOW_LEN + 1 + 32;
+/// Size of the original Config layout (governance_release_authority only).
+const CONFIG_BASE_LEN: usize = 8 + 32;
+/// Size of the Config layout that added treasury_fee_bps and burn_fee_bps.
+const CONFIG_FEES_LEN: usize = CONFIG_BASE_LEN + 2 + 2;
+/// Size of the Config layout that added referral_bps.
+const CONFIG_REFERRAL_LEN: usize = CONFIG_FEES_LEN + 2;
+/// Size of the Config layout that added max_goal_multiple_bps.
+const CONFIG_GOAL_MULTIPLE_LEN: usize = CONFIG_REFERRAL_LEN + 2;
+/// Size of the Config layout that added min_refund_window_secs.
+const CONFIG_REFUND_WINDOW_LEN: usize = CONFIG_GOAL_MULTIPLE_LEN + 8;
+
+/// SlashRecord account size: discriminator + escrow_balance + total_raised.
+pub const SLASH_RECORD_SPACE: usize = 8 + 8 + 8;
+
+/// Backer account size: discriminator + wallet + project + amount + claimed_rwa + fee_paid + treasury_fee_paid.
+pub const BACKER_SPACE: usize = LEGACY_BACKER_SPACE + 8 + 8;
+/// Size of Backer accounts created before `fee_paid` existed; migrate_backer grows them.
+const LEGACY_BACKER_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+
+/// ArtistState account size: discriminator + artist + project_count + completed_projects + cancelled_projects
+/// + total_raised_lifetime.
+pub const ARTIST_STATE_SPACE: usize = LEGACY_ARTIST_STATE_SPACE + 4 + 4 + 8;
+/// Size of ArtistState accounts created before the track-record fields existed; migrate_artist_state grows them.
+const LEGACY_ARTIST_STATE_SPACE: usize = 8 + 32 + 8;
+
+/// URI schemes wallets can resolve for receipt metadata.
+const RECEIPT_URI_SCHEMES: [&str; 3] = ["https://", "ipfs://", "ar://"];
+
+/// Receipt metadata URI: at most 200 bytes, an https/ipfs/ar scheme and a non-empty location after it.
+pub fn validate_receipt_uri(uri: &str) -> Result<()> {
+    require!(uri.len() <= 200, EscrowError::MetadataUriTooLong);
+    require!(
+        RECEIPT_URI_SCHEMES
+            .iter()
+            .any(|scheme| uri.len() > scheme.len() && uri.starts_with(scheme)),
+        EscrowError::InvalidMetadataUri
+    );
+    Ok(())
+}
+
+/// Rejects fee splits whose total exceeds MAX_PLATFORM_FEE_BPS.
+pub fn validate_fee_bps(treasury_fee_bps: u16, burn_fee_bps: u16) -> Result<()> {
+    require!(
+        treasury_fee_bps as u32 + burn_fee_bps as u32 <= MAX_PLATFORM_FEE_BPS as u32,
+        EscrowError::FeeTooHigh
+    );
+    Ok(())
+}
+
+/// Portion of the treasury fee routed to a referrer; the treasury keeps the rest.
+pub fn referral_cut(fee_treasury: u64, referral_bps: u16) -> Result<u64> {
+    require!(referral_bps <= 10_000, EscrowError::InvalidReferralBps);
+    Ok((fee_treasury as u128)
+        .checked_mul(referral_bps as u128)
+        .ok_or(EscrowError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(EscrowError::Overflow)? as u64)
+}
+
+/// (treasury_fee_bps, burn_fee_bps) charged on a funding: the configured split, or nothing for a fee-exempt project.
+pub fn funding_fee_bps(fee_exempt: bool, config: &Config) -> (u16, u16) {
+    if fee_exempt {
+        (0, 0)
+    } else {
+        (config.treasury_fee_bps, config.burn_fee_bps)
+    }
+}
+
+/// Splits a funding amount into (treasury fee, burn fee, amount to escrow).
+/// Rounding: the total fee is floor(amount * (treasury + burn bps) / 10_000), floored once so it is within 1 unit of
+/// nominal; the treasury leg is floor(amount * treasury_bps / 10_000) and the burn leg takes the remainder, so each
+/// leg is also within 1 unit of nominal and the three parts always sum to `amount`.
+pub fn platform_fee_split(
+    amount: u64,
+    treasury_fee_bps: u16,
+    burn_fee_bps: u16,
+) -> Result<(u64, u64, u64)> {
+    let fee_total = (amount as u128)
+        .checked_mul(treasury_fee_bps as u128 + burn_fee_bps as u128)
+        .ok_or(EscrowError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(EscrowError::Overflow)? as u64;
+    let fee_treasury = (amount as u128)
+        .checked_mul(treasury_fee_bps as u128)
+        .ok_or(EscrowError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(EscrowError::Overflow)? as u64;
+    let to_escrow = amount.checked_sub(fee_total).ok_or(EscrowError::Overflow)?;
+    Ok((fee_treasury, fee_total - fee_treasury, to_escrow))
+}
+
 /// Number of milestones that must be released before project completes.
 /// Derived from last non-zero percentage. [50,50,0,0,0] -> 2.
 pub fn effective_milestone_count(percentages: &[u16; MAX_MILESTONES]) -> usize {
@@ -101,6 +296,293 @@ pub fn effective_milestone_count(percentages: &[u16; MAX_MILESTONES]) -> usize {
         .unwrap_or(1)
 }
 
+/// Milestone percentages must sum to 100 and zeros may only form a trailing run, so milestone index i is
+/// always the i-th non-zero tranche: [50,50,0,0,0] is two milestones, [50,0,50,0,0] is rejected.
+pub fn validate_milestone_percentages(percentages: &[u16; MAX_MILESTONES]) -> Result<()> {
+    let sum: u32 = percentages.iter().map(|&p| p as u32).sum();
+    require!(sum == 100, EscrowError::InvalidMilestonePercentages);
+    let count = effective_milestone_count(percentages);
+    require!(
+        percentages[..count].iter().all(|&p| p > 0),
+        EscrowError::InteriorZeroMilestone
+    );
+    Ok(())
+}
+
+/// A project can be failed once it is still Active, its deadline has passed and it raised less than its
+/// funding threshold (soft cap, or goal when no soft cap is set). Nothing may have been released yet: refund
+/// pays each backer their full Backer.amount, which a partly paid-out escrow cannot cover for everyone.
+pub fn check_can_fail_project(
+    status: ProjectStatus,
+    deadline: i64,
+    total_raised: u64,
+    funding_threshold: u64,
+    has_released: bool,
+    now: i64,
+) -> Result<()> {
+    require!(
+        status == ProjectStatus::Active,
+        EscrowError::ProjectNotActive
+    );
+    require!(now >= deadline, EscrowError::DeadlineNotReached);
+    require!(total_raised < funding_threshold, EscrowError::GoalReached);
+    require!(!has_released, EscrowError::FundsAlreadyReleased);
+    Ok(())
+}
+
+/// A project's escrow and auxiliary accounts can be closed once it is Completed and its escrow is drained.
+pub fn check_can_close_project(status: ProjectStatus, escrow_amount: u64) -> Result<()> {
+    require!(
+        status == ProjectStatus::Completed,
+        EscrowError::ProjectNotCompleted
+    );
+    require!(escrow_amount == 0, EscrowError::EscrowNotEmpty);
+    Ok(())
+}
+
+/// withdraw_escrow_dust may only sweep a Completed project whose milestones have all been released, so it never
+/// takes funds a pending milestone is owed.
+pub fn check_can_withdraw_dust(
+    status: ProjectStatus,
+    current_milestone: u8,
+    percentages: &[u16; MAX_MILESTONES],
+    escrow_amount: u64,
+) -> Result<()> {
+    require!(
+        status == ProjectStatus::Completed,
+        EscrowError::ProjectNotCompleted
+    );
+    require!(
+        current_milestone as usize >= effective_milestone_count(percentages),
+        EscrowError::NotAllMilestonesReleased
+    );
+    require!(escrow_amount > 0, EscrowError::NoEscrowDust);
+    Ok(())
+}
+
+/// Number of distinct backers with `amount > 0` among `backers` (address, account) for `project`.
+/// Each must be the Backer PDA [b"backer", project, wallet] of this program and appear at most once.
+pub fn count_active_backers(project: &Pubkey, backers: &[(Pubkey, Backer)]) -> Result<u32> {
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(backers.len());
+    let mut active = 0u32;
+    for (address, backer) in backers {
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"backer", project.as_ref(), backer.wallet.as_ref()],
+            &crate::ID,
+        );
+        require!(
+            backer.project == *project && *address == expected,
+            EscrowError::InvalidBackerAccount
+        );
+        require!(!seen.contains(address), EscrowError::DuplicateBackerAccount);
+        seen.push(*address);
+        if backer.amount > 0 {
+            active = active.checked_add(1).ok_or(EscrowError::Overflow)?;
+        }
+    }
+    Ok(active)
+}
+
+/// Sum of sqrt(amount) over `backers` (address, account) for `project`, i.e. what fund_project and refunds keep in
+/// ProjectVoteWeight. Accounts are validated as in count_active_backers and must include every active backer:
+/// their count has to equal `backer_count`.
+pub fn rebuilt_vote_weight(
+    project: &Pubkey,
+    backers: &[(Pubkey, Backer)],
+    backer_count: u32,
+) -> Result<u64> {
+    require!(
+        count_active_backers(project, backers)? == backer_count,
+        EscrowError::IncompleteBackerSet
+    );
+    backers
+        .iter()
+        .try_fold(0u64, |total, (_, backer)| {
+            total.checked_add(sqrt_u64(backer.amount))
+        })
+        .ok_or(error!(EscrowError::Overflow))
+}
+
+/// Config from the [b"config"] PDA, with fields missing from older (shorter) layouts filled with their defaults.
+fn load_config(info: &AccountInfo) -> Result<Config> {
+    let data = info.try_borrow_data()?;
+    Config::from_account_data(&data).ok_or(error!(EscrowError::InvalidConfigAccount))
+}
+
+/// Load the config and require `authority` to be its governance_release_authority.
+fn require_governance_authority(config: &AccountInfo, authority: &Pubkey) -> Result<Config> {
+    let config = load_config(config)?;
+    require_keys_eq!(
+        config.governance_release_authority,
+        *authority,
+        EscrowError::GovernanceAuthorityMismatch
+    );
+    Ok(config)
+}
+
+/// Config from the [b"config"] PDA for its fee settings; defaults when it is missing or predates the fee fields.
+fn read_fee_settings(info: &AccountInfo) -> Result<Config> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(Config::default());
+    }
+    let data = info.try_borrow_data()?;
+    Config::from_account_data(&data).ok_or(error!(EscrowError::InvalidConfigAccount))
+}
+
+/// Write `value` into a config (or other program-owned) PDA, growing it to `space` (and topping up rent)
+/// when it was created with an older, shorter layout.
+fn write_grown_account<'info, T: AccountSerialize>(
+    config_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    value: &T,
+) -> Result<()> {
+    if config_info.data_len() < space {
+        let required = Rent::get()?.minimum_balance(space);
+        let shortfall = required.saturating_sub(config_info.lamports());
+        if shortfall > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    anchor_lang::system_program::Transfer {
+                        from: payer.clone(),
+                        to: config_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        config_info.resize(space)?;
+    }
+    let mut data = config_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data[..];
+    value.try_serialize(&mut writer)?;
+    Ok(())
+}
+
+/// Fails when the optional taste_token ProtocolState passed in says the protocol is paused.
+fn require_not_paused(protocol_state: Option<&AccountInfo>) -> Result<()> {
+    if let Some(info) = protocol_state {
+        require!(
+            !taste_token::protocol_paused(info)?,
+            EscrowError::ProtocolPaused
+        );
+    }
+    Ok(())
+}
+
+/// Reads the [b"slash", project] record; None when the project was cancelled without a slash.
+fn read_slash_record(info: &AccountInfo) -> Result<Option<SlashRecord>> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+    let data = info.try_borrow_data()?;
+    SlashRecord::try_deserialize(&mut &data[..])
+        .map(Some)
+        .map_err(|_| error!(EscrowError::InvalidSlashRecord))
+}
+
+/// Mints the single receipt NFT for `backer_key` on `project_key` into `backer_receipt_ata` and creates its
+/// Metaplex metadata, signing with the receipt authority and receipt mint PDAs (`bumps` in that order).
+#[allow(clippy::too_many_arguments)]
+fn issue_receipt<'info>(
+    project_key: Pubkey,
+    backer_key: Pubkey,
+    bumps: [u8; 2],
+    receipt_authority: &AccountInfo<'info>,
+    receipt_mint: &AccountInfo<'info>,
+    backer_receipt_ata: &AccountInfo<'info>,
+    metadata: &AccountInfo<'info>,
+    master_edition: &AccountInfo<'info>,
+    token_metadata_program: &AccountInfo<'info>,
+    sysvar_instructions: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    metadata_uri: String,
+) -> Result<()> {
+    let receipt_authority_seeds: &[&[u8]] = &[
+        b"receipt_authority".as_ref(),
+        project_key.as_ref(),
+        backer_key.as_ref(),
+        &[bumps[0]],
+    ];
+    let receipt_mint_seeds: &[&[u8]] = &[
+        b"receipt".as_ref(),
+        project_key.as_ref(),
+        backer_key.as_ref(),
+        &[bumps[1]],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[receipt_authority_seeds, receipt_mint_seeds];
+
+    anchor_spl::token_interface::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            MintTo {
+                mint: receipt_mint.clone(),
+                to: backer_receipt_ata.clone(),
+                authority: receipt_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    let (metadata_pda, _) = Metadata::find_pda(receipt_mint.key);
+    let (master_edition_pda, _) = MasterEdition::find_pda(receipt_mint.key);
+    require_keys_eq!(
+        metadata.key(),
+        metadata_pda,
+        EscrowError::InvalidMetadataAccount
+    );
+    require_keys_eq!(
+        master_edition.key(),
+        master_edition_pda,
+        EscrowError::InvalidMetadataAccount
+    );
+    require!(
+        token_metadata_program.key() == MPL_TOKEN_METADATA_ID,
+        EscrowError::InvalidTokenMetadataProgram
+    );
+
+    let name = "TasteMaker IOU".to_string();
+    let symbol = "TM-IOU".to_string();
+    CreateV1CpiBuilder::new(token_metadata_program)
+        .metadata(metadata)
+        .master_edition(Some(master_edition))
+        .mint(receipt_mint, true)
+        .authority(receipt_authority)
+        .payer(payer)
+        .update_authority(receipt_authority, true)
+        .system_program(system_program)
+        .sysvar_instructions(sysvar_instructions)
+        .spl_token_program(Some(token_program))
+        .name(name)
+        .symbol(symbol)
+        .uri(metadata_uri.clone())
+        .seller_fee_basis_points(0)
+        .primary_sale_happened(false)
+        .is_mutable(true)
+        .token_standard(TokenStandard::NonFungible)
+        .print_supply(PrintSupply::Zero)
+        .invoke_signed(signer_seeds)?;
+
+    emit!(ReceiptMinted {
+        project: project_key,
+        backer: backer_key,
+        receipt_mint: receipt_mint.key(),
+        metadata_uri,
+    });
+
+    msg!(
+        "Receipt minted for backer {} on project {}",
+        backer_key,
+        project_key
+    );
+    Ok(())
+}
+
 /// Validates that the signer is the program's upgrade authority by reading upgradeable loader
 /// state (4-byte bincode layout).
 fn require_upgrade_authority(
@@ -170,6 +652,11 @@ pub mod project_escrow {
 
         let config = &mut ctx.accounts.config;
         config.governance_release_authority = governance_release_authority;
+        config.treasury_fee_bps = DEFAULT_TREASURY_FEE_BPS;
+        config.burn_fee_bps = DEFAULT_BURN_FEE_BPS;
+        config.referral_bps = 0;
+        config.max_goal_multiple_bps = DEFAULT_MAX_GOAL_MULTIPLE_BPS;
+        config.min_refund_window_secs = DEFAULT_MIN_REFUND_WINDOW_SECS;
         msg!(
             "Config initialized: governance_release_authority = {}",
             config.governance_release_authority
@@ -199,10 +686,72 @@ pub mod project_escrow {
         Ok(())
     }
 
-    /// Update the stored governance release authority (key rotation). Only the program upgrade authority can call this.
+    /// Permissionless: recompute total_vote_weight from the project's Backer accounts in remaining_accounts (sum of
+    /// sqrt(amount)), replacing a value backfilled with set_vote_weight. Every backer with funds in must be passed;
+    /// their count is checked against Project.backer_count (run recount_backers first if that has drifted). Projects
+    /// with more backers than fit in one transaction still need set_vote_weight.
+    pub fn rebuild_vote_weight<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RebuildVoteWeight<'info>>,
+    ) -> Result<()> {
+        let mut backers = Vec::with_capacity(ctx.remaining_accounts.len());
+        for info in ctx.remaining_accounts {
+            require!(
+                info.owner == ctx.program_id,
+                EscrowError::InvalidBackerAccount
+            );
+            let data = info.try_borrow_data()?;
+            let backer = Backer::try_deserialize(&mut &data[..])?;
+            backers.push((info.key(), backer));
+        }
+        let project = &ctx.accounts.project;
+        let total = rebuilt_vote_weight(&project.key(), &backers, project.backer_count)?;
+        let vw = &mut ctx.accounts.vote_weight;
+        let previous = vw.total_vote_weight;
+        vw.total_vote_weight = total;
+        msg!(
+            "Vote weight rebuilt for project {}: {} -> {} ({} backer accounts)",
+            project.key(),
+            previous,
+            total,
+            backers.len()
+        );
+        Ok(())
+    }
+
+    /// Waive (or restore) the fund_project platform fee for one project. Only the program upgrade authority can
+    /// call this. Exempt fundings send the full amount to escrow, so backer amounts and total_raised (and the RWA
+    /// shares derived from them) stay in the same post-fee units as for other projects.
+    pub fn set_fee_exempt(ctx: Context<SetFeeExempt>, fee_exempt: bool) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+        ctx.accounts.project.fee_exempt = fee_exempt;
+        msg!(
+            "Fee exemption set to {} for project {}",
+            fee_exempt,
+            ctx.accounts.project.key()
+        );
+        Ok(())
+    }
+
+    /// Update the stored governance release authority (key rotation), the fund_project fee split, the
+    /// referral share of the treasury fee, the raise_goal cap and the material-edit refund window floor. Only the
+    /// program upgrade authority can call this. Grows configs created before the fee fields existed.
     pub fn update_config(
         ctx: Context<UpdateConfig>,
         governance_release_authority: Pubkey,
+        treasury_fee_bps: u16,
+        burn_fee_bps: u16,
+        referral_bps: u16,
+        max_goal_multiple_bps: u16,
+        min_refund_window_secs: i64,
     ) -> Result<()> {
         let program_account = ctx.accounts.program_account.try_borrow_data()?;
         let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
@@ -215,11 +764,158 @@ pub mod project_escrow {
             &ctx.accounts.authority.key(),
         )?;
 
-        let config = &mut ctx.accounts.config;
+        validate_fee_bps(treasury_fee_bps, burn_fee_bps)?;
+        require!(referral_bps <= 10_000, EscrowError::InvalidReferralBps);
+        require!(
+            max_goal_multiple_bps >= 10_000,
+            EscrowError::InvalidGoalMultiple
+        );
+        require!(
+            min_refund_window_secs >= 0,
+            EscrowError::InvalidMinRefundWindow
+        );
+
+        let config_info = ctx.accounts.config.to_account_info();
+        let mut config = {
+            let data = config_info.try_borrow_data()?;
+            Config::from_account_data(&data).ok_or(EscrowError::InvalidConfigAccount)?
+        };
         config.governance_release_authority = governance_release_authority;
+        config.treasury_fee_bps = treasury_fee_bps;
+        config.burn_fee_bps = burn_fee_bps;
+        config.referral_bps = referral_bps;
+        config.max_goal_multiple_bps = max_goal_multiple_bps;
+        config.min_refund_window_secs = min_refund_window_secs;
+        write_grown_account(
+            &config_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            CONFIG_SPACE,
+            &config,
+        )?;
         msg!(
-            "Config updated: governance_release_authority = {}",
-            config.governance_release_authority
+            "Config updated: governance_release_authority = {} treasury_fee_bps = {} burn_fee_bps = {} referral_bps = {} max_goal_multiple_bps = {} min_refund_window_secs = {}",
+            governance_release_authority,
+            treasury_fee_bps,
+            burn_fee_bps,
+            referral_bps,
+            max_goal_multiple_bps,
+            min_refund_window_secs
+        );
+        Ok(())
+    }
+
+    /// Switch fund_project between burning the burn fee per funding and deferring it to sweep_burn_vault.
+    /// Only the program upgrade authority can call this. Grows Configs created before defer_burn existed.
+    pub fn update_fee_config(ctx: Context<UpdateFeeConfig>, defer_burn: bool) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+
+        let config_info = ctx.accounts.config.to_account_info();
+        let mut config = {
+            let data = config_info.try_borrow_data()?;
+            Config::from_account_data(&data).ok_or(EscrowError::InvalidConfigAccount)?
+        };
+        config.defer_burn = defer_burn;
+        write_grown_account(
+            &config_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            CONFIG_SPACE,
+            &config,
+        )?;
+        msg!("Config updated: defer_burn = {}", defer_burn);
+        Ok(())
+    }
+
+    /// Rotate the platform treasury that fund_project pays the treasury fee into. Only the program upgrade
+    /// authority can call this. The new treasury must be a $TASTE token account. Grows Configs created
+    /// before the treasury field existed.
+    pub fn update_fee_treasury(ctx: Context<UpdateFeeTreasury>) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+
+        let config_info = ctx.accounts.config.to_account_info();
+        let mut config = {
+            let data = config_info.try_borrow_data()?;
+            Config::from_account_data(&data).ok_or(EscrowError::InvalidConfigAccount)?
+        };
+        let previous = config.treasury;
+        config.treasury = ctx.accounts.new_treasury.key();
+        write_grown_account(
+            &config_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            CONFIG_SPACE,
+            &config,
+        )?;
+        msg!(
+            "Config treasury updated: {} -> {}",
+            previous,
+            config.treasury
+        );
+        Ok(())
+    }
+
+    /// Upgrade authority: recompute Project.backer_count from Backer accounts in remaining_accounts, counting those with amount > 0.
+    /// Pass every Backer of the project. For projects too large for one transaction, call first with `append = false`
+    /// and then with `append = true` for further batches (each backer in exactly one batch).
+    pub fn recount_backers<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecountBackers<'info>>,
+        append: bool,
+    ) -> Result<()> {
+        {
+            let program_account = ctx.accounts.program_account.try_borrow_data()?;
+            let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+            require_upgrade_authority(
+                ctx.program_id,
+                &ctx.accounts.program_account.key(),
+                &program_account,
+                &ctx.accounts.program_data_account.key(),
+                &program_data_account,
+                &ctx.accounts.authority.key(),
+            )?;
+        }
+
+        let mut backers = Vec::with_capacity(ctx.remaining_accounts.len());
+        for info in ctx.remaining_accounts {
+            require!(
+                info.owner == ctx.program_id,
+                EscrowError::InvalidBackerAccount
+            );
+            let data = info.try_borrow_data()?;
+            let backer = Backer::try_deserialize(&mut &data[..])?;
+            backers.push((info.key(), backer));
+        }
+        let project = &mut ctx.accounts.project;
+        let counted = count_active_backers(&project.key(), &backers)?;
+        let previous = project.backer_count;
+        project.backer_count = if append {
+            previous.checked_add(counted).ok_or(EscrowError::Overflow)?
+        } else {
+            counted
+        };
+        msg!(
+            "Backer count recounted: {} -> {} ({} active in batch)",
+            previous,
+            project.backer_count,
+            counted
         );
         Ok(())
     }
@@ -230,22 +926,36 @@ pub mod project_escrow {
         goal: u64,
         milestone_percentages: [u16; MAX_MILESTONES],
         deadline: i64,
+        limits: FundingLimits,
     ) -> Result<()> {
+        let FundingLimits {
+            min_funding,
+            max_per_backer,
+            soft_cap,
+        } = limits;
         require!(
             name.len() <= MAX_PROJECT_NAME_LEN,
             EscrowError::ProjectNameTooLong
         );
-        let sum: u16 = milestone_percentages.iter().sum();
-        require!(sum == 100, EscrowError::InvalidMilestonePercentages);
+        require!(soft_cap <= goal, EscrowError::InvalidSoftCap);
+        require!(min_funding <= goal, EscrowError::InvalidMinFunding);
+        validate_milestone_percentages(&milestone_percentages)?;
         let project = &mut ctx.accounts.project;
         project.artist = ctx.accounts.artist.key();
         project.name = name;
         project.goal = goal;
+        project.original_goal = goal;
         project.milestone_percentages = milestone_percentages;
         project.deadline = deadline;
         project.status = ProjectStatus::Active;
         project.taste_mint = ctx.accounts.taste_mint.key();
         project.current_milestone = 0;
+        project.version = PROJECT_VERSION;
+        project.deadline_extension_secs = 0;
+        project.min_funding = min_funding;
+        project.max_per_backer = max_per_backer;
+        project.soft_cap = soft_cap;
+        project.fee_exempt = false;
         let artist_state = &mut ctx.accounts.artist_state;
         if artist_state.project_count == 0 {
             artist_state.artist = ctx.accounts.artist.key();
@@ -281,6 +991,7 @@ pub mod project_escrow {
     }
 
     pub fn fund_project(ctx: Context<FundProject>, amount: u64) -> Result<()> {
+        require_not_paused(ctx.accounts.protocol_state.as_deref())?;
         let project = &ctx.accounts.project;
         require!(
             project.status == ProjectStatus::Active,
@@ -291,23 +1002,28 @@ pub mod project_escrow {
             clock.unix_timestamp < project.deadline,
             EscrowError::ProjectDeadlinePassed
         );
+        require!(
+            meets_min_funding(amount, project.min_funding, project.remaining_goal()),
+            EscrowError::BelowMinFunding
+        );
 
-        // 4% platform fee: 2% treasury, 2% burn, 96% to escrow
-        let fee_treasury = (amount as u128)
-            .checked_mul(2)
-            .ok_or(EscrowError::Overflow)?
-            .checked_div(100)
-            .ok_or(EscrowError::Overflow)? as u64;
-        let fee_burn = (amount as u128)
-            .checked_mul(2)
-            .ok_or(EscrowError::Overflow)?
-            .checked_div(100)
-            .ok_or(EscrowError::Overflow)? as u64;
-        let to_escrow = amount
-            .checked_sub(fee_treasury)
-            .ok_or(EscrowError::Overflow)?
-            .checked_sub(fee_burn)
-            .ok_or(EscrowError::Overflow)?;
+        let fee_settings = read_fee_settings(&ctx.accounts.config)?;
+        require!(
+            fee_settings.treasury_matches(&ctx.accounts.platform_treasury.key()),
+            EscrowError::InvalidTreasury
+        );
+
+        // Platform fee (default 2% treasury, 2% burn; none for fee-exempt projects); the rest goes to escrow.
+        let (treasury_fee_bps, burn_fee_bps) = funding_fee_bps(project.fee_exempt, &fee_settings);
+        let (fee_treasury, fee_burn, to_escrow) =
+            platform_fee_split(amount, treasury_fee_bps, burn_fee_bps)?;
+        // A referred funding routes part of the treasury fee to the referrer; the backer pays the same.
+        let fee_referral = if ctx.accounts.referrer_token_account.is_some() {
+            referral_cut(fee_treasury, fee_settings.referral_bps)?
+        } else {
+            0
+        };
+        let fee_treasury = fee_treasury - fee_referral;
 
         require!(
             (project.total_raised as u128) + (to_escrow as u128) <= project.goal as u128,
@@ -316,19 +1032,30 @@ pub mod project_escrow {
 
         let backer = &mut ctx.accounts.backer;
         let existing = backer.amount;
-        backer.amount = existing
-            .checked_add(to_escrow)
-            .ok_or(EscrowError::Overflow)?;
+        backer.amount = backer_total_within_cap(existing, to_escrow, project.max_per_backer)?;
         if existing == 0 {
             backer.wallet = ctx.accounts.backer_wallet.key();
             backer.project = project.key();
         }
+        backer.fee_paid = backer
+            .fee_paid
+            .checked_add(amount - to_escrow)
+            .ok_or(EscrowError::Overflow)?;
+        backer.treasury_fee_paid = backer
+            .treasury_fee_paid
+            .checked_add(fee_treasury)
+            .ok_or(EscrowError::Overflow)?;
 
         let project_acc = &mut ctx.accounts.project;
         project_acc.total_raised = project_acc
             .total_raised
             .checked_add(to_escrow)
             .ok_or(EscrowError::Overflow)?;
+        let artist_state = &mut ctx.accounts.artist_state;
+        artist_state.total_raised_lifetime = artist_state
+            .total_raised_lifetime
+            .checked_add(to_escrow)
+            .ok_or(EscrowError::Overflow)?;
         if existing == 0 {
             project_acc.backer_count = project_acc
                 .backer_count
@@ -375,6 +1102,24 @@ pub mod project_escrow {
             )?;
         }
 
+        if let Some(referrer_token_account) = &ctx.accounts.referrer_token_account {
+            if fee_referral > 0 {
+                anchor_spl::token_interface::transfer_checked(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        TransferChecked {
+                            from: ctx.accounts.backer_token_account.to_account_info(),
+                            mint: ctx.accounts.taste_mint.to_account_info(),
+                            to: referrer_token_account.to_account_info(),
+                            authority: ctx.accounts.backer_wallet.to_account_info(),
+                        },
+                    ),
+                    fee_referral,
+                    decimals,
+                )?;
+            }
+        }
+
         if fee_burn > 0 {
             anchor_spl::token_interface::transfer_checked(
                 CpiContext::new(
@@ -389,20 +1134,23 @@ pub mod project_escrow {
                 fee_burn,
                 decimals,
             )?;
-            let (_, bump) = Pubkey::find_program_address(&[b"burn_vault"], ctx.program_id);
-            let seeds: &[&[u8]] = &[b"burn_vault", &[bump]];
-            anchor_spl::token_interface::burn(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Burn {
-                        mint: ctx.accounts.taste_mint.to_account_info(),
-                        from: ctx.accounts.burn_vault_token_account.to_account_info(),
-                        authority: ctx.accounts.burn_vault_authority.to_account_info(),
-                    },
-                    &[seeds],
-                ),
-                fee_burn,
-            )?;
+            // With defer_burn the fee accumulates in the vault and is burned later by sweep_burn_vault.
+            if !fee_settings.defer_burn {
+                let (_, bump) = Pubkey::find_program_address(&[b"burn_vault"], ctx.program_id);
+                let seeds: &[&[u8]] = &[b"burn_vault", &[bump]];
+                anchor_spl::token_interface::burn(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Burn {
+                            mint: ctx.accounts.taste_mint.to_account_info(),
+                            from: ctx.accounts.burn_vault_token_account.to_account_info(),
+                            authority: ctx.accounts.burn_vault_authority.to_account_info(),
+                        },
+                        &[seeds],
+                    ),
+                    fee_burn,
+                )?;
+            }
         }
 
         msg!(
@@ -411,80 +1159,603 @@ pub mod project_escrow {
             to_escrow,
             fee_treasury + fee_burn
         );
+        emit!(ProjectFunded {
+            project: ctx.accounts.project.key(),
+            backer: ctx.accounts.backer_wallet.key(),
+            gross_amount: amount,
+            to_escrow,
+            fee_treasury,
+            fee_burn,
+            fee_referral,
+            new_total_raised: ctx.accounts.project.total_raised,
+        });
         Ok(())
     }
 
-    pub fn release_milestone(ctx: Context<ReleaseMilestone>) -> Result<()> {
-        let project = &mut ctx.accounts.project;
+    /// Backer without $TASTE pledges `lamports` to an Active project. The SOL is held in the backer's
+    /// PendingContribution PDA until the artist settles it in $TASTE or the backer cancels. There is no on-chain
+    /// price: the artist chooses the $TASTE amount at settlement, and `min_taste` is the least gross $TASTE (before
+    /// the platform fee) the backer accepts for this pledge. Pledging again adds to both.
+    pub fn fund_project_with_sol(
+        ctx: Context<FundProjectWithSol>,
+        lamports: u64,
+        min_taste: u64,
+    ) -> Result<()> {
+        let project = &ctx.accounts.project;
         require!(
             project.status == ProjectStatus::Active,
             EscrowError::ProjectNotActive
         );
-        let idx = project.current_milestone as usize;
-        require!(idx < MAX_MILESTONES, EscrowError::InvalidMilestone);
-        let pct = project.milestone_percentages[idx];
-        let amount = (project.total_raised as u128)
-            .checked_mul(pct as u128)
-            .ok_or(EscrowError::Overflow)?
-            .checked_div(100)
-            .ok_or(EscrowError::Overflow)? as u64;
+        require!(
+            Clock::get()?.unix_timestamp < project.deadline,
+            EscrowError::ProjectDeadlinePassed
+        );
+        require!(lamports > 0, EscrowError::EmptyPendingContribution);
 
-        let project_key = project.key();
-        let seeds = &[
-            b"project",
-            project_key.as_ref(),
-            &[ctx.bumps.escrow_authority],
-        ];
-        anchor_spl::token_interface::transfer_checked(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                TransferChecked {
-                    from: ctx.accounts.escrow.to_account_info(),
-                    mint: ctx.accounts.taste_mint.to_account_info(),
-                    to: ctx.accounts.artist_token_account.to_account_info(),
-                    authority: ctx.accounts.escrow_authority.to_account_info(),
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.backer_wallet.to_account_info(),
+                    to: ctx.accounts.pending_contribution.to_account_info(),
                 },
-                &[seeds],
             ),
-            amount,
-            ctx.accounts.taste_mint.decimals,
+            lamports,
         )?;
 
-        project.current_milestone = project
-            .current_milestone
-            .checked_add(1)
+        let pending = &mut ctx.accounts.pending_contribution;
+        pending.backer = ctx.accounts.backer_wallet.key();
+        pending.project = project.key();
+        pending.lamports = pending
+            .lamports
+            .checked_add(lamports)
             .ok_or(EscrowError::Overflow)?;
-        if project.current_milestone as usize
-            >= effective_milestone_count(&project.milestone_percentages)
-        {
-            project.status = ProjectStatus::Completed;
-        }
-        msg!("Released milestone {}: {} $TASTE", idx, amount);
+        pending.min_taste = pending
+            .min_taste
+            .checked_add(min_taste)
+            .ok_or(EscrowError::Overflow)?;
+        msg!(
+            "Pending SOL contribution: {} lamports (total {}) for project {}",
+            lamports,
+            pending.lamports,
+            pending.project
+        );
         Ok(())
     }
 
-    pub fn complete_project(ctx: Context<CompleteProject>) -> Result<()> {
-        let project = &mut ctx.accounts.project;
+    /// Artist settles a pending SOL contribution: pays `taste_amount` $TASTE from their own account as if the
+    /// backer had funded that gross amount, so the platform fee is split off exactly as in fund_project and the
+    /// rest goes to escrow, credited to the backer. The artist then receives the pledged lamports and the
+    /// PendingContribution's rent goes back to the backer. Like funding, settlement closes at the deadline.
+    pub fn settle_pending_contribution(
+        ctx: Context<SettlePendingContribution>,
+        taste_amount: u64,
+    ) -> Result<()> {
+        require_not_paused(ctx.accounts.protocol_state.as_deref())?;
+        let project = &ctx.accounts.project;
         require!(
             project.status == ProjectStatus::Active,
             EscrowError::ProjectNotActive
         );
         require!(
-            project.current_milestone as usize
-                >= effective_milestone_count(&project.milestone_percentages),
-            EscrowError::NotAllMilestonesReleased
+            Clock::get()?.unix_timestamp < project.deadline,
+            EscrowError::ProjectDeadlinePassed
         );
-        project.status = ProjectStatus::Completed;
-        msg!("Project completed");
-        Ok(())
-    }
+        let pending = &ctx.accounts.pending_contribution;
+        check_pending_settlement(
+            taste_amount,
+            pending.min_taste,
+            project.min_funding,
+            project.remaining_goal(),
+        )?;
 
-    /// Recovery: mark project Completed when all milestones are released but status stuck Active
-    /// (e.g. finalized with old program that expected 5 milestones). Upgrade authority only.
-    pub fn force_complete_project(ctx: Context<ForceCompleteProject>) -> Result<()> {
-        let program_account = ctx.accounts.program_account.try_borrow_data()?;
-        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
-        require_upgrade_authority(
+        let fee_settings = read_fee_settings(&ctx.accounts.config)?;
+        require!(
+            fee_settings.treasury_matches(&ctx.accounts.platform_treasury.key()),
+            EscrowError::InvalidTreasury
+        );
+        let (treasury_fee_bps, burn_fee_bps) = funding_fee_bps(project.fee_exempt, &fee_settings);
+        let (fee_treasury, fee_burn, to_escrow) =
+            platform_fee_split(taste_amount, treasury_fee_bps, burn_fee_bps)?;
+        require!(
+            (project.total_raised as u128) + (to_escrow as u128) <= project.goal as u128,
+            EscrowError::GoalExceeded
+        );
+        let lamports = pending.lamports;
+
+        let backer = &mut ctx.accounts.backer;
+        let existing = backer.amount;
+        backer.amount = backer_total_within_cap(existing, to_escrow, project.max_per_backer)?;
+        if existing == 0 {
+            backer.wallet = ctx.accounts.backer_wallet.key();
+            backer.project = project.key();
+        }
+        backer.fee_paid = backer
+            .fee_paid
+            .checked_add(taste_amount - to_escrow)
+            .ok_or(EscrowError::Overflow)?;
+        backer.treasury_fee_paid = backer
+            .treasury_fee_paid
+            .checked_add(fee_treasury)
+            .ok_or(EscrowError::Overflow)?;
+
+        let project_acc = &mut ctx.accounts.project;
+        project_acc.total_raised = project_acc
+            .total_raised
+            .checked_add(to_escrow)
+            .ok_or(EscrowError::Overflow)?;
+        if existing == 0 {
+            project_acc.backer_count = project_acc
+                .backer_count
+                .checked_add(1)
+                .ok_or(EscrowError::Overflow)?;
+        }
+        let artist_state = &mut ctx.accounts.artist_state;
+        artist_state.total_raised_lifetime = artist_state
+            .total_raised_lifetime
+            .checked_add(to_escrow)
+            .ok_or(EscrowError::Overflow)?;
+
+        let vw = &mut ctx.accounts.vote_weight;
+        vw.total_vote_weight = vw
+            .total_vote_weight
+            .checked_add(sqrt_u64(to_escrow))
+            .ok_or(EscrowError::Overflow)?;
+
+        let decimals = ctx.accounts.taste_mint.decimals;
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.artist_token_account.to_account_info(),
+                    mint: ctx.accounts.taste_mint.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                    authority: ctx.accounts.artist.to_account_info(),
+                },
+            ),
+            to_escrow,
+            decimals,
+        )?;
+
+        if fee_treasury > 0 {
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.artist_token_account.to_account_info(),
+                        mint: ctx.accounts.taste_mint.to_account_info(),
+                        to: ctx.accounts.platform_treasury.to_account_info(),
+                        authority: ctx.accounts.artist.to_account_info(),
+                    },
+                ),
+                fee_treasury,
+                decimals,
+            )?;
+        }
+
+        if fee_burn > 0 {
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.artist_token_account.to_account_info(),
+                        mint: ctx.accounts.taste_mint.to_account_info(),
+                        to: ctx.accounts.burn_vault_token_account.to_account_info(),
+                        authority: ctx.accounts.artist.to_account_info(),
+                    },
+                ),
+                fee_burn,
+                decimals,
+            )?;
+            if !fee_settings.defer_burn {
+                let seeds: &[&[u8]] = &[b"burn_vault", &[ctx.bumps.burn_vault_authority]];
+                anchor_spl::token_interface::burn(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Burn {
+                            mint: ctx.accounts.taste_mint.to_account_info(),
+                            from: ctx.accounts.burn_vault_token_account.to_account_info(),
+                            authority: ctx.accounts.burn_vault_authority.to_account_info(),
+                        },
+                        &[seeds],
+                    ),
+                    fee_burn,
+                )?;
+            }
+        }
+
+        // The pledged SOL goes to the artist; the remaining rent follows via `close = backer_wallet`.
+        let pending_info = ctx.accounts.pending_contribution.to_account_info();
+        **pending_info.try_borrow_mut_lamports()? = pending_info
+            .lamports()
+            .checked_sub(lamports)
+            .ok_or(EscrowError::Overflow)?;
+        let artist_info = ctx.accounts.artist.to_account_info();
+        **artist_info.try_borrow_mut_lamports()? = artist_info
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(EscrowError::Overflow)?;
+
+        msg!(
+            "Settled {} lamports as {} $TASTE for backer {}",
+            lamports,
+            taste_amount,
+            ctx.accounts.backer_wallet.key()
+        );
+        emit!(ProjectFunded {
+            project: ctx.accounts.project.key(),
+            backer: ctx.accounts.backer_wallet.key(),
+            gross_amount: taste_amount,
+            to_escrow,
+            fee_treasury,
+            fee_burn,
+            fee_referral: 0,
+            new_total_raised: ctx.accounts.project.total_raised,
+        });
+        Ok(())
+    }
+
+    /// Backer withdraws an unsettled SOL pledge; the PendingContribution closes and returns everything it holds.
+    pub fn cancel_pending_contribution(ctx: Context<CancelPendingContribution>) -> Result<()> {
+        msg!(
+            "Pending SOL contribution of {} lamports cancelled for project {}",
+            ctx.accounts.pending_contribution.lamports,
+            ctx.accounts.pending_contribution.project
+        );
+        Ok(())
+    }
+
+    /// Permissionless: burn everything accumulated in the burn vault (see Config.defer_burn).
+    pub fn sweep_burn_vault(ctx: Context<SweepBurnVault>) -> Result<()> {
+        let amount = ctx.accounts.burn_vault_token_account.amount;
+        require!(amount > 0, EscrowError::BurnVaultEmpty);
+        let seeds: &[&[u8]] = &[b"burn_vault", &[ctx.bumps.burn_vault_authority]];
+        anchor_spl::token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.taste_mint.to_account_info(),
+                    from: ctx.accounts.burn_vault_token_account.to_account_info(),
+                    authority: ctx.accounts.burn_vault_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+        msg!("Burn vault swept: {} $TASTE burned", amount);
+        Ok(())
+    }
+
+    /// Permissionless: upgrade an older Project account to the current layout (realloc, new fields defaulted).
+    /// Only accounts with version < PROJECT_VERSION are accepted; payer tops up rent for the larger account.
+    pub fn migrate_project(ctx: Context<MigrateProject>) -> Result<()> {
+        let project_info = ctx.accounts.project.to_account_info();
+        let project = {
+            let data = project_info.try_borrow_data()?;
+            migrate_project_data(&data)?
+        };
+        write_grown_account(
+            &project_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            PROJECT_ACCOUNT_SPACE,
+            &project,
+        )?;
+        msg!(
+            "Project {} migrated to version {}",
+            project_info.key(),
+            PROJECT_VERSION
+        );
+        Ok(())
+    }
+
+    /// Permissionless: grow a Backer created before `fee_paid` existed to BACKER_SPACE (fee_paid and
+    /// treasury_fee_paid start at 0).
+    /// Legacy backers must be migrated before fund_project, refund or refund_fee can load them.
+    pub fn migrate_backer(ctx: Context<MigrateBacker>) -> Result<()> {
+        let backer_info = ctx.accounts.backer.to_account_info();
+        let backer = {
+            let data = backer_info.try_borrow_data()?;
+            migrate_backer_data(&data)?
+        };
+        write_grown_account(
+            &backer_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            BACKER_SPACE,
+            &backer,
+        )?;
+        msg!("Backer {} migrated", backer_info.key());
+        Ok(())
+    }
+
+    /// Permissionless: grow an ArtistState created before the track-record fields existed to ARTIST_STATE_SPACE.
+    /// Counters start at 0, so history before the migration is not reflected. Legacy artist states must be
+    /// migrated before create_project, fund_project, settle_pending_contribution, cancel_project, fail_project,
+    /// slash_project or completion can load them.
+    pub fn migrate_artist_state(ctx: Context<MigrateArtistState>) -> Result<()> {
+        let artist_state_info = ctx.accounts.artist_state.to_account_info();
+        let artist_state = {
+            let data = artist_state_info.try_borrow_data()?;
+            migrate_artist_state_data(&data)?
+        };
+        write_grown_account(
+            &artist_state_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ARTIST_STATE_SPACE,
+            &artist_state,
+        )?;
+        msg!("ArtistState {} migrated", artist_state_info.key());
+        Ok(())
+    }
+
+    /// Artist sets (or replaces) how milestone payouts are split among collaborators. Entries are
+    /// (recipient wallet, bps) and must sum to 10_000. Only while the project is Active.
+    pub fn set_payout_split(ctx: Context<SetPayoutSplit>, entries: Vec<SplitEntry>) -> Result<()> {
+        require!(
+            ctx.accounts.project.status == ProjectStatus::Active,
+            EscrowError::ProjectNotActive
+        );
+        validate_payout_split(&entries)?;
+        let split = &mut ctx.accounts.payout_split;
+        split.project = ctx.accounts.project.key();
+        split.entries = entries;
+        msg!(
+            "Payout split set for project {}: {} recipients",
+            split.project,
+            split.entries.len()
+        );
+        Ok(())
+    }
+
+    /// Artist records (or replaces) the project's rights holders: (wallet, role, bps) entries summing to 10_000
+    /// with distinct wallets. Informational for clients and metadata; payouts still follow PayoutSplit.
+    pub fn set_collaborators(
+        ctx: Context<SetCollaborators>,
+        entries: Vec<Collaborator>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.project.status != ProjectStatus::Cancelled,
+            EscrowError::ProjectNotActive
+        );
+        validate_collaborators(&entries)?;
+        let collaborators = &mut ctx.accounts.collaborators;
+        collaborators.project = ctx.accounts.project.key();
+        collaborators.entries = entries;
+        msg!(
+            "Collaborators set for project {}: {} entries",
+            collaborators.project,
+            collaborators.entries.len()
+        );
+        Ok(())
+    }
+
+    /// Governance-only: pay the current milestone out of escrow. `release_amount` releases a tranche of what is
+    /// left of the milestone (None = all of it); the milestone index only advances once its full share is out.
+    /// Without a payout split the amount goes to artist_token_account. When the project's payout_split PDA holds a
+    /// split, remaining_accounts must be one $TASTE token account per entry in entry order and each recipient
+    /// receives its bps share.
+    pub fn release_milestone<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReleaseMilestone<'info>>,
+        release_amount: Option<u64>,
+    ) -> Result<()> {
+        require_not_paused(ctx.accounts.protocol_state.as_deref())?;
+        require_governance_authority(
+            &ctx.accounts.config,
+            &ctx.accounts.governance_authority.key(),
+        )?;
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            EscrowError::ProjectNotActive
+        );
+        let idx = project.current_milestone as usize;
+        let milestone_amount = project.current_milestone_amount()?;
+        let (amount, completes_milestone) = milestone_tranche(
+            milestone_amount,
+            project.released_in_milestone,
+            release_amount,
+        )?;
+
+        let project_key = project.key();
+        let seeds: &[&[u8]] = &[
+            b"project",
+            project_key.as_ref(),
+            &[ctx.bumps.escrow_authority],
+        ];
+        let signer_seeds = &[seeds];
+        let split_info = ctx.accounts.payout_split.to_account_info();
+        let split = if split_info.owner == ctx.program_id && split_info.data_len() > 0 {
+            let data = split_info.try_borrow_data()?;
+            Some(PayoutSplit::try_deserialize(&mut &data[..])?)
+        } else {
+            None
+        };
+
+        if let Some(split) = split {
+            let recipients = ctx.remaining_accounts;
+            require!(
+                recipients.len() == split.entries.len(),
+                EscrowError::InvalidPayoutSplit
+            );
+            let amounts = split_amounts(amount, &split.entries)?;
+            for ((entry, recipient_info), share) in
+                split.entries.iter().zip(recipients).zip(amounts)
+            {
+                let recipient_account = InterfaceAccount::<TokenAccount>::try_from(recipient_info)?;
+                require!(
+                    recipient_account.owner == entry.recipient
+                        && recipient_account.mint == project.taste_mint,
+                    EscrowError::InvalidPayoutSplit
+                );
+                if share == 0 {
+                    continue;
+                }
+                anchor_spl::token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        TransferChecked {
+                            from: ctx.accounts.escrow.to_account_info(),
+                            mint: ctx.accounts.taste_mint.to_account_info(),
+                            to: recipient_info.clone(),
+                            authority: ctx.accounts.escrow_authority.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    share,
+                    ctx.accounts.taste_mint.decimals,
+                )?;
+            }
+        } else {
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.escrow.to_account_info(),
+                        mint: ctx.accounts.taste_mint.to_account_info(),
+                        to: ctx.accounts.artist_token_account.to_account_info(),
+                        authority: ctx.accounts.escrow_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+                ctx.accounts.taste_mint.decimals,
+            )?;
+        }
+
+        emit!(MilestoneReleased {
+            project: project_key,
+            milestone: idx as u8,
+            amount,
+            completes_milestone,
+        });
+        if !completes_milestone {
+            project.released_in_milestone = project
+                .released_in_milestone
+                .checked_add(amount)
+                .ok_or(EscrowError::Overflow)?;
+            msg!(
+                "Released {} $TASTE of milestone {} ({} of {} so far)",
+                amount,
+                idx,
+                project.released_in_milestone,
+                milestone_amount
+            );
+            return Ok(());
+        }
+        project.released_in_milestone = 0;
+        project.current_milestone = project
+            .current_milestone
+            .checked_add(1)
+            .ok_or(EscrowError::Overflow)?;
+        if project.current_milestone as usize
+            >= effective_milestone_count(&project.milestone_percentages)
+        {
+            project.status = ProjectStatus::Completed;
+            ctx.accounts.artist_state.record_outcome(project.status)?;
+            emit!(ProjectCompleted {
+                project: project_key,
+                total_raised: project.total_raised,
+            });
+        }
+        msg!("Released milestone {}: {} $TASTE", idx, amount);
+        Ok(())
+    }
+
+    pub fn complete_project(ctx: Context<CompleteProject>) -> Result<()> {
+        require_governance_authority(
+            &ctx.accounts.config,
+            &ctx.accounts.governance_authority.key(),
+        )?;
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            EscrowError::ProjectNotActive
+        );
+        require!(
+            project.current_milestone as usize
+                >= effective_milestone_count(&project.milestone_percentages),
+            EscrowError::NotAllMilestonesReleased
+        );
+        project.status = ProjectStatus::Completed;
+        ctx.accounts.artist_state.record_outcome(project.status)?;
+        emit!(ProjectCompleted {
+            project: project.key(),
+            total_raised: project.total_raised,
+        });
+        msg!("Project completed");
+        Ok(())
+    }
+
+    /// Artist reclaims rent after a completed project's escrow is drained: closes the escrow token account and, when
+    /// passed, the vote_weight and project_terms PDAs. The Project account stays open since rwa_token claims and
+    /// revenue_distribution deposits keep reading it.
+    pub fn close_project_accounts(ctx: Context<CloseProjectAccounts>) -> Result<()> {
+        check_can_close_project(ctx.accounts.project.status, ctx.accounts.escrow.amount)?;
+
+        let project_key = ctx.accounts.project.key();
+        let seeds: &[&[u8]] = &[
+            b"project",
+            project_key.as_ref(),
+            &[ctx.bumps.escrow_authority],
+        ];
+        let signer_seeds = &[seeds];
+        anchor_spl::token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow.to_account_info(),
+                destination: ctx.accounts.artist.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+        msg!("Closed escrow accounts for project {}", project_key);
+        Ok(())
+    }
+
+    /// Artist sweeps the rounding remainder left in escrow once every milestone has released (each tranche is
+    /// floor(total_raised * pct / 100)). Clears the way for close_project_accounts.
+    pub fn withdraw_escrow_dust(ctx: Context<WithdrawEscrowDust>) -> Result<()> {
+        let project = &ctx.accounts.project;
+        let dust = ctx.accounts.escrow.amount;
+        check_can_withdraw_dust(
+            project.status,
+            project.current_milestone,
+            &project.milestone_percentages,
+            dust,
+        )?;
+
+        let project_key = project.key();
+        let seeds: &[&[u8]] = &[
+            b"project",
+            project_key.as_ref(),
+            &[ctx.bumps.escrow_authority],
+        ];
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    mint: ctx.accounts.taste_mint.to_account_info(),
+                    to: ctx.accounts.artist_token_account.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            dust,
+            ctx.accounts.taste_mint.decimals,
+        )?;
+        msg!("Withdrew {} $TASTE of escrow dust to the artist", dust);
+        Ok(())
+    }
+
+    /// Recovery: mark project Completed when all milestones are released but status stuck Active
+    /// (e.g. finalized with old program that expected 5 milestones). Upgrade authority only.
+    pub fn force_complete_project(ctx: Context<ForceCompleteProject>) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
             ctx.program_id,
             &ctx.accounts.program_account.key(),
             &program_account,
@@ -503,10 +1774,72 @@ pub mod project_escrow {
             EscrowError::NotAllMilestonesReleased
         );
         project.status = ProjectStatus::Completed;
+        ctx.accounts.artist_state.record_outcome(project.status)?;
+        emit!(ProjectCompleted {
+            project: project.key(),
+            total_raised: project.total_raised,
+        });
         msg!("Project force-completed (recovery)");
         Ok(())
     }
 
+    /// Artist pushes out the deadline of an active project before it passes. Cumulative extensions are capped at
+    /// MAX_DEADLINE_EXTENSION_SECS; going further needs a material edit.
+    pub fn extend_deadline(ctx: Context<ExtendDeadline>, new_deadline: i64) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            EscrowError::ProjectNotActive
+        );
+        let clock = Clock::get()?;
+        let old_deadline = project.deadline;
+        project.deadline_extension_secs = deadline_extension(
+            old_deadline,
+            new_deadline,
+            clock.unix_timestamp,
+            project.deadline_extension_secs,
+        )?;
+        project.deadline = new_deadline;
+        emit!(DeadlineExtended {
+            project: project.key(),
+            old_deadline,
+            new_deadline,
+            total_extension_secs: project.deadline_extension_secs,
+        });
+        msg!("Deadline extended: {} -> {}", old_deadline, new_deadline);
+        Ok(())
+    }
+
+    /// Artist raises the funding goal of an active project before its deadline, up to the configured
+    /// multiple of the original goal (Config.max_goal_multiple_bps).
+    pub fn raise_goal(ctx: Context<RaiseGoal>, new_goal: u64) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            EscrowError::ProjectNotActive
+        );
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp < project.deadline,
+            EscrowError::ProjectDeadlinePassed
+        );
+        check_goal_raise(
+            project.goal,
+            project.original_goal,
+            new_goal,
+            load_config(&ctx.accounts.config)?.max_goal_multiple_bps,
+        )?;
+        let old_goal = project.goal;
+        project.goal = new_goal;
+        emit!(GoalRaised {
+            project: project.key(),
+            old_goal,
+            new_goal,
+        });
+        msg!("Goal raised: {} -> {}", old_goal, new_goal);
+        Ok(())
+    }
+
     pub fn cancel_project(ctx: Context<CancelProject>) -> Result<()> {
         let project = &mut ctx.accounts.project;
         require!(
@@ -518,47 +1851,240 @@ pub mod project_escrow {
             EscrowError::NotArtist
         );
         project.status = ProjectStatus::Cancelled;
+        ctx.accounts.artist_state.record_outcome(project.status)?;
         msg!("Project cancelled");
         Ok(())
     }
 
-    pub fn refund(ctx: Context<Refund>) -> Result<()> {
-        let project = &ctx.accounts.project;
+    /// Permissionless: mark an abandoned project Cancelled once its deadline passed without reaching the goal,
+    /// which opens the existing refund path for backers.
+    pub fn fail_project(ctx: Context<FailProject>) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        let clock = Clock::get()?;
+        check_can_fail_project(
+            project.status,
+            project.deadline,
+            project.total_raised,
+            project.funding_threshold(),
+            project.has_released(),
+            clock.unix_timestamp,
+        )?;
+        project.status = ProjectStatus::Cancelled;
+        ctx.accounts.artist_state.record_outcome(project.status)?;
+        msg!(
+            "Project failed: raised {} of threshold {} by deadline {}",
+            project.total_raised,
+            project.funding_threshold(),
+            project.deadline
+        );
+        Ok(())
+    }
+
+    /// Governance-only: cancel an Active project after a passed slash proposal. Records what is left in escrow
+    /// so refund and refund_batch pay each backer that remainder pro rata to their Backer.amount.
+    pub fn slash_project(ctx: Context<SlashProject>) -> Result<()> {
+        require_governance_authority(
+            &ctx.accounts.config,
+            &ctx.accounts.governance_authority.key(),
+        )?;
+        let project = &mut ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Active,
+            EscrowError::ProjectNotActive
+        );
+        let record = &mut ctx.accounts.slash_record;
+        record.escrow_balance = ctx.accounts.escrow.amount;
+        record.total_raised = project.total_raised;
+        project.status = ProjectStatus::Cancelled;
+        ctx.accounts.artist_state.record_outcome(project.status)?;
+
+        emit!(ProjectSlashed {
+            project: project.key(),
+            escrow_balance: record.escrow_balance,
+            total_raised: record.total_raised,
+        });
+        msg!(
+            "Project slashed: {} of {} $TASTE left for refunds",
+            record.escrow_balance,
+            record.total_raised
+        );
+        Ok(())
+    }
+
+    pub fn refund(ctx: Context<Refund>) -> Result<()> {
+        let project = &ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Cancelled,
+            EscrowError::ProjectNotCancelled
+        );
+        let amount = ctx.accounts.backer.amount;
+        require!(amount > 0, EscrowError::NothingToRefund);
+        let slash = read_slash_record(&ctx.accounts.slash_record)?;
+        let payout = refund_amount(amount, slash.as_ref())?;
+
+        let project_key = project.key();
+        let seeds = &[
+            b"project",
+            project_key.as_ref(),
+            &[ctx.bumps.escrow_authority],
+        ];
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    mint: ctx.accounts.taste_mint.to_account_info(),
+                    to: ctx.accounts.backer_token_account.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            payout,
+            ctx.accounts.taste_mint.decimals,
+        )?;
+
+        let backer_acc = &mut ctx.accounts.backer;
+        backer_acc.amount = 0;
+
+        let vw = &mut ctx.accounts.vote_weight;
+        vw.total_vote_weight = vw.total_vote_weight.saturating_sub(sqrt_u64(amount));
+
+        msg!("Refunded {} $TASTE", payout);
+        Ok(())
+    }
+
+    /// Permissionless: refund several backers of a cancelled project in one call. remaining_accounts holds
+    /// (backer, backer_token_account) pairs, at most MAX_REFUND_BATCH. Backers with nothing left are skipped.
+    /// Returns the number of backers refunded so clients can chunk deterministically.
+    pub fn refund_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundBatch<'info>>,
+    ) -> Result<u32> {
+        let project = &ctx.accounts.project;
+        require!(
+            project.status == ProjectStatus::Cancelled,
+            EscrowError::ProjectNotCancelled
+        );
+        let count = refund_batch_count(ctx.remaining_accounts.len())?;
+        let slash = read_slash_record(&ctx.accounts.slash_record)?;
+        let project_key = project.key();
+        let seeds: &[&[u8]] = &[
+            b"project",
+            project_key.as_ref(),
+            &[ctx.bumps.escrow_authority],
+        ];
+        let signer_seeds = &[seeds];
+        let mut refunded = 0u32;
+        let mut total = 0u64;
+
+        for pair in ctx
+            .remaining_accounts
+            .chunks_exact(REFUND_BATCH_ACCOUNTS_PER_BACKER)
+        {
+            let [backer_info, backer_token_info] = pair else {
+                return err!(EscrowError::InvalidBackerAccount);
+            };
+            require!(
+                backer_info.owner == ctx.program_id && backer_info.is_writable,
+                EscrowError::InvalidBackerAccount
+            );
+            let mut backer = {
+                let data = backer_info.try_borrow_data()?;
+                Backer::try_deserialize(&mut &data[..])?
+            };
+            let (expected, _) = Pubkey::find_program_address(
+                &[b"backer", project_key.as_ref(), backer.wallet.as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                backer.project == project_key && backer_info.key() == expected,
+                EscrowError::InvalidBackerAccount
+            );
+            if backer.amount == 0 {
+                continue;
+            }
+            let backer_token_account =
+                InterfaceAccount::<TokenAccount>::try_from(backer_token_info)?;
+            require!(
+                backer_token_account.owner == backer.wallet
+                    && backer_token_account.mint == project.taste_mint,
+                EscrowError::InvalidBackerTokenAccount
+            );
+
+            let amount = backer.amount;
+            let payout = refund_amount(amount, slash.as_ref())?;
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.escrow.to_account_info(),
+                        mint: ctx.accounts.taste_mint.to_account_info(),
+                        to: backer_token_info.clone(),
+                        authority: ctx.accounts.escrow_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                payout,
+                ctx.accounts.taste_mint.decimals,
+            )?;
+
+            backer.amount = 0;
+            {
+                let mut data = backer_info.try_borrow_mut_data()?;
+                let mut writer: &mut [u8] = &mut data[..];
+                backer.try_serialize(&mut writer)?;
+            }
+            let vw = &mut ctx.accounts.vote_weight;
+            vw.total_vote_weight = vw.total_vote_weight.saturating_sub(sqrt_u64(amount));
+            total = total.checked_add(payout).ok_or(EscrowError::Overflow)?;
+            refunded += 1;
+        }
+
+        msg!(
+            "Batch refund: {} of {} backers refunded, {} $TASTE",
+            refunded,
+            count,
+            total
+        );
+        Ok(refunded)
+    }
+
+    /// Return the treasury share of a backer's fee once the project is cancelled (cancel_project, fail_project
+    /// or a slash). The platform treasury's owner signs, so refunding fees stays a treasury decision; the
+    /// burned share and any referral cut are not refunded. Pays Backer.treasury_fee_paid, the amount the treasury
+    /// actually received, and zeroes it.
+    pub fn refund_fee(ctx: Context<RefundFee>) -> Result<()> {
         require!(
-            project.status == ProjectStatus::Cancelled,
+            ctx.accounts.project.status == ProjectStatus::Cancelled,
             EscrowError::ProjectNotCancelled
         );
-        let amount = ctx.accounts.backer.amount;
-        require!(amount > 0, EscrowError::NothingToRefund);
+        let fee_settings = read_fee_settings(&ctx.accounts.config)?;
+        require!(
+            fee_settings.treasury_matches(&ctx.accounts.platform_treasury.key()),
+            EscrowError::InvalidTreasury
+        );
+        let fee = ctx.accounts.backer.treasury_fee_paid;
+        require!(fee > 0, EscrowError::NoFeeToRefund);
 
-        let project_key = project.key();
-        let seeds = &[
-            b"project",
-            project_key.as_ref(),
-            &[ctx.bumps.escrow_authority],
-        ];
         anchor_spl::token_interface::transfer_checked(
-            CpiContext::new_with_signer(
+            CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
                 TransferChecked {
-                    from: ctx.accounts.escrow.to_account_info(),
+                    from: ctx.accounts.platform_treasury.to_account_info(),
                     mint: ctx.accounts.taste_mint.to_account_info(),
                     to: ctx.accounts.backer_token_account.to_account_info(),
-                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                    authority: ctx.accounts.treasury_authority.to_account_info(),
                 },
-                &[seeds],
             ),
-            amount,
+            fee,
             ctx.accounts.taste_mint.decimals,
         )?;
+        ctx.accounts.backer.treasury_fee_paid = 0;
 
-        let backer_acc = &mut ctx.accounts.backer;
-        backer_acc.amount = 0;
-
-        let vw = &mut ctx.accounts.vote_weight;
-        vw.total_vote_weight = vw.total_vote_weight.saturating_sub(sqrt_u64(amount));
-
-        msg!("Refunded {} $TASTE", amount);
+        msg!(
+            "Refunded {} $TASTE treasury fee to {}",
+            fee,
+            ctx.accounts.backer.wallet
+        );
         Ok(())
     }
 
@@ -571,19 +2097,24 @@ pub mod project_escrow {
         new_deadline: i64,
         new_milestone_percentages: [u16; MAX_MILESTONES],
     ) -> Result<()> {
-        let sum: u16 = new_milestone_percentages.iter().sum();
-        require!(sum == 100, EscrowError::InvalidMilestonePercentages);
+        let config = require_governance_authority(
+            &ctx.accounts.config,
+            &ctx.accounts.governance_authority.key(),
+        )?;
+        validate_milestone_percentages(&new_milestone_percentages)?;
         let project = &mut ctx.accounts.project;
         require!(
             project.status == ProjectStatus::Active,
             EscrowError::ProjectNotActive
         );
+        require!(project.soft_cap <= new_goal, EscrowError::InvalidSoftCap);
 
         let clock = Clock::get()?;
-        let refund_window_end = clock
-            .unix_timestamp
-            .checked_add(refund_window_secs)
-            .ok_or(EscrowError::Overflow)?;
+        let refund_window_end = material_edit_refund_window_end(
+            clock.unix_timestamp,
+            refund_window_secs,
+            config.min_refund_window_secs,
+        )?;
 
         let terms = &mut ctx.accounts.project_terms;
         terms.terms_hash = new_terms_hash;
@@ -591,6 +2122,7 @@ pub mod project_escrow {
         terms.refund_window_end = refund_window_end;
 
         project.goal = new_goal;
+        project.original_goal = new_goal;
         project.deadline = new_deadline;
         project.milestone_percentages = new_milestone_percentages;
 
@@ -668,89 +2200,114 @@ pub mod project_escrow {
     }
 
     /// Mints a single receipt NFT (Token-2022) for the backer at PDA [b"receipt", project, backer].
-    /// Call after fund_project (same tx or later). Client must insert (project_pda, wallet, mint=receipt_mint_pda) into receipt_mints so receipt-metadata API resolves.
+    /// Call after fund_project (same tx or later). Client must insert (project_pda, wallet, mint=receipt_mint_pda) into receipt_mints so receipt-metadata API resolves;
+    /// the ReceiptMinted event carries all three for indexers.
     pub fn mint_receipt(ctx: Context<MintReceipt>, metadata_uri: String) -> Result<()> {
-        require!(metadata_uri.len() <= 200, EscrowError::MetadataUriTooLong);
+        validate_receipt_uri(&metadata_uri)?;
         let backer = &ctx.accounts.backer;
         require!(backer.amount > 0, EscrowError::NothingToRefund);
 
-        let receipt_authority_bump = ctx.bumps.receipt_authority;
-        let receipt_mint_bump = ctx.bumps.receipt_mint;
-        let project_key = ctx.accounts.project.key();
-        let backer_key = ctx.accounts.backer_wallet.key();
-        let receipt_authority_seeds: &[&[u8]] = &[
-            b"receipt_authority".as_ref(),
-            project_key.as_ref(),
-            backer_key.as_ref(),
-            &[receipt_authority_bump],
-        ];
-        let receipt_mint_seeds: &[&[u8]] = &[
-            b"receipt".as_ref(),
-            project_key.as_ref(),
-            backer_key.as_ref(),
-            &[receipt_mint_bump],
-        ];
-        let signer_seeds: &[&[&[u8]]] = &[receipt_authority_seeds, receipt_mint_seeds];
+        issue_receipt(
+            ctx.accounts.project.key(),
+            ctx.accounts.backer_wallet.key(),
+            [ctx.bumps.receipt_authority, ctx.bumps.receipt_mint],
+            &ctx.accounts.receipt_authority,
+            &ctx.accounts.receipt_mint.to_account_info(),
+            &ctx.accounts.backer_receipt_ata.to_account_info(),
+            &ctx.accounts.metadata,
+            &ctx.accounts.master_edition,
+            &ctx.accounts.token_metadata_program,
+            &ctx.accounts.sysvar_instructions,
+            &ctx.accounts.backer_wallet.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            metadata_uri,
+        )
+    }
 
-        anchor_spl::token_interface::mint_to(
-            CpiContext::new_with_signer(
+    /// Mints the receipt NFT for a backer who funded before on-chain receipts existed, so legacy backers can move
+    /// to the receipt-gated claim. Same PDAs and metadata as mint_receipt; the upgrade authority signs and pays.
+    /// The receipt mint is created with `init`, so a backer who already has (or burned) a receipt cannot get a
+    /// second one.
+    pub fn backfill_receipt(ctx: Context<BackfillReceipt>, metadata_uri: String) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+        validate_receipt_uri(&metadata_uri)?;
+        require!(ctx.accounts.backer.amount > 0, EscrowError::NothingToRefund);
+
+        issue_receipt(
+            ctx.accounts.project.key(),
+            ctx.accounts.backer_wallet.key(),
+            [ctx.bumps.receipt_authority, ctx.bumps.receipt_mint],
+            &ctx.accounts.receipt_authority,
+            &ctx.accounts.receipt_mint.to_account_info(),
+            &ctx.accounts.backer_receipt_ata.to_account_info(),
+            &ctx.accounts.metadata,
+            &ctx.accounts.master_edition,
+            &ctx.accounts.token_metadata_program,
+            &ctx.accounts.sysvar_instructions,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            metadata_uri,
+        )
+    }
+
+    /// Backer burns their receipt NFT without claiming and closes the receipt ATA, returning its rent. The receipt
+    /// mint PDA stays (supply 0), so mint_receipt cannot issue a second one. Burning forfeits rwa_token's
+    /// receipt-gated claim_rwa_tokens; claim_rwa_tokens_legacy still works off the Backer record alone, and the
+    /// per-wallet ClaimRecord caps the backer at one claim across both paths.
+    pub fn burn_receipt(ctx: Context<BurnReceipt>) -> Result<()> {
+        let held = ctx.accounts.backer_receipt_ata.amount;
+        require!(held >= 1, EscrowError::ReceiptNotHeld);
+
+        anchor_spl::token_interface::burn(
+            CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                MintTo {
+                Burn {
                     mint: ctx.accounts.receipt_mint.to_account_info(),
-                    to: ctx.accounts.backer_receipt_ata.to_account_info(),
-                    authority: ctx.accounts.receipt_authority.to_account_info(),
+                    from: ctx.accounts.backer_receipt_ata.to_account_info(),
+                    authority: ctx.accounts.backer_wallet.to_account_info(),
                 },
-                signer_seeds,
             ),
-            1,
+            held,
         )?;
+        anchor_spl::token_interface::close_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.backer_receipt_ata.to_account_info(),
+                destination: ctx.accounts.backer_wallet.to_account_info(),
+                authority: ctx.accounts.backer_wallet.to_account_info(),
+            },
+        ))?;
 
-        let (metadata_pda, _) = Metadata::find_pda(&ctx.accounts.receipt_mint.key());
-        let (master_edition_pda, _) = MasterEdition::find_pda(&ctx.accounts.receipt_mint.key());
-        require_keys_eq!(
-            ctx.accounts.metadata.key(),
-            metadata_pda,
-            EscrowError::InvalidMetadataAccount
-        );
-        require_keys_eq!(
-            ctx.accounts.master_edition.key(),
-            master_edition_pda,
-            EscrowError::InvalidMetadataAccount
-        );
-        require!(
-            ctx.accounts.token_metadata_program.key() == MPL_TOKEN_METADATA_ID,
-            EscrowError::InvalidTokenMetadataProgram
-        );
-
-        let name = "TasteMaker IOU".to_string();
-        let symbol = "TM-IOU".to_string();
-        CreateV1CpiBuilder::new(ctx.accounts.token_metadata_program.as_ref())
-            .metadata(ctx.accounts.metadata.as_ref())
-            .master_edition(Some(ctx.accounts.master_edition.as_ref()))
-            .mint(ctx.accounts.receipt_mint.as_ref(), true)
-            .authority(ctx.accounts.receipt_authority.as_ref())
-            .payer(ctx.accounts.backer_wallet.as_ref())
-            .update_authority(ctx.accounts.receipt_authority.as_ref(), true)
-            .system_program(ctx.accounts.system_program.as_ref())
-            .sysvar_instructions(ctx.accounts.sysvar_instructions.as_ref())
-            .spl_token_program(Some(ctx.accounts.token_program.as_ref()))
-            .name(name)
-            .symbol(symbol)
-            .uri(metadata_uri)
-            .seller_fee_basis_points(0)
-            .primary_sale_happened(false)
-            .is_mutable(true)
-            .token_standard(TokenStandard::NonFungible)
-            .print_supply(PrintSupply::Zero)
-            .invoke_signed(signer_seeds)?;
-
+        let project_key = ctx.accounts.project.key();
+        let backer_key = ctx.accounts.backer_wallet.key();
+        emit!(ReceiptBurned {
+            project: project_key,
+            backer: backer_key,
+            receipt_mint: ctx.accounts.receipt_mint.key(),
+        });
         msg!(
-            "Receipt minted for backer {} on project {}",
+            "Receipt burned for backer {} on project {}",
             backer_key,
             project_key
         );
         Ok(())
     }
+
+    /// Read-only: returns the project's funding progress as return data, for CPI callers and simulated-tx clients.
+    pub fn get_project_stats(ctx: Context<GetProjectStats>) -> Result<ProjectStats> {
+        Ok(ctx.accounts.project.stats())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -763,6 +2320,292 @@ pub enum ProjectStatus {
 /// Max length for project name (used in metadata).
 pub const MAX_PROJECT_NAME_LEN: usize = 32;
 
+/// Current Project layout version. Bump when appending fields and default them in migrate_project_data.
+/// v1 projects predate the `version` field and read back as 0. v3 adds `deadline_extension_secs`, v4 `min_funding`,
+/// v5 `max_per_backer`, v6 `soft_cap`, v7 `released_in_milestone`, v8 `original_goal`, v9 `fee_exempt`.
+pub const PROJECT_VERSION: u8 = 9;
+/// Project account size for the current layout (name reserved at max length).
+pub const PROJECT_ACCOUNT_SPACE: usize = PROJECT_V1_SPACE + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+/// Size of the original (v1) Project layout.
+const PROJECT_V1_SPACE: usize =
+    8 + 32 + 4 + MAX_PROJECT_NAME_LEN + 8 + (2 * MAX_MILESTONES) + 8 + 1 + 32 + 8 + 4 + 1;
+
+/// Accounts per backer in refund_batch remaining_accounts: (backer, backer_token_account).
+pub const REFUND_BATCH_ACCOUNTS_PER_BACKER: usize = 2;
+/// Max backers per refund_batch call; keeps each call well under the compute limit.
+pub const MAX_REFUND_BATCH: usize = 8;
+
+/// Number of backers described by `remaining_len` refund_batch accounts. Fails with BatchTooLarge above
+/// MAX_REFUND_BATCH so clients can size their chunks.
+pub fn refund_batch_count(remaining_len: usize) -> Result<usize> {
+    let count = remaining_len / REFUND_BATCH_ACCOUNTS_PER_BACKER;
+    require!(
+        count > 0 && count * REFUND_BATCH_ACCOUNTS_PER_BACKER == remaining_len,
+        EscrowError::InvalidBackerAccount
+    );
+    require!(count <= MAX_REFUND_BATCH, EscrowError::BatchTooLarge);
+    Ok(count)
+}
+
+/// What a refund pays a backer with `amount` outstanding: the full amount, or after a slash their
+/// `amount / total_raised` share of the escrow balance recorded at slash time.
+pub fn refund_amount(amount: u64, slash: Option<&SlashRecord>) -> Result<u64> {
+    let Some(slash) = slash else {
+        return Ok(amount);
+    };
+    if slash.total_raised == 0 {
+        return Ok(0);
+    }
+    let share = (amount as u128)
+        .checked_mul(slash.escrow_balance as u128)
+        .ok_or(EscrowError::Overflow)?
+        / slash.total_raised as u128;
+    Ok(share.min(amount as u128) as u64)
+}
+
+/// Amount to release from the current milestone and whether that finishes it. `milestone_amount` is the
+/// milestone's full share, `released` what earlier tranches paid, `requested` the tranche (None = the rest).
+pub fn milestone_tranche(
+    milestone_amount: u64,
+    released: u64,
+    requested: Option<u64>,
+) -> Result<(u64, bool)> {
+    let remaining = milestone_amount
+        .checked_sub(released)
+        .ok_or(EscrowError::Overflow)?;
+    let amount = match requested {
+        Some(requested) => {
+            require!(
+                requested > 0 && requested <= remaining,
+                EscrowError::InvalidReleaseAmount
+            );
+            requested
+        }
+        None => remaining,
+    };
+    Ok((amount, amount == remaining))
+}
+
+/// Max recipients in a project's PayoutSplit.
+pub const MAX_SPLIT_RECIPIENTS: usize = 8;
+/// PayoutSplit account size with MAX_SPLIT_RECIPIENTS entries.
+pub const PAYOUT_SPLIT_SPACE: usize = 8 + 32 + 4 + MAX_SPLIT_RECIPIENTS * (32 + 2);
+
+/// Validates payout split entries: 1..=MAX_SPLIT_RECIPIENTS distinct recipients, each with non-zero bps,
+/// summing to 10_000.
+pub fn validate_payout_split(entries: &[SplitEntry]) -> Result<()> {
+    require!(
+        !entries.is_empty() && entries.len() <= MAX_SPLIT_RECIPIENTS,
+        EscrowError::InvalidPayoutSplit
+    );
+    let mut total = 0u32;
+    for (i, entry) in entries.iter().enumerate() {
+        require!(entry.bps > 0, EscrowError::InvalidPayoutSplit);
+        require!(
+            !entries[..i].iter().any(|e| e.recipient == entry.recipient),
+            EscrowError::InvalidPayoutSplit
+        );
+        total += entry.bps as u32;
+    }
+    require!(total == 10_000, EscrowError::InvalidPayoutSplit);
+    Ok(())
+}
+
+pub const MAX_COLLABORATORS: usize = 8;
+/// Collaborators account size with MAX_COLLABORATORS entries.
+pub const COLLABORATORS_SPACE: usize = 8 + 32 + 4 + MAX_COLLABORATORS * (32 + 1 + 2);
+
+/// Validates collaborator entries: 1..=MAX_COLLABORATORS distinct wallets, each with non-zero bps, summing
+/// to 10_000.
+pub fn validate_collaborators(entries: &[Collaborator]) -> Result<()> {
+    require!(
+        !entries.is_empty() && entries.len() <= MAX_COLLABORATORS,
+        EscrowError::InvalidCollaborators
+    );
+    let mut total = 0u32;
+    for (i, entry) in entries.iter().enumerate() {
+        require!(entry.bps > 0, EscrowError::InvalidCollaborators);
+        require!(
+            !entries[..i].iter().any(|e| e.wallet == entry.wallet),
+            EscrowError::InvalidCollaborators
+        );
+        total += entry.bps as u32;
+    }
+    require!(total == 10_000, EscrowError::InvalidCollaborators);
+    Ok(())
+}
+
+/// Per-recipient amounts for paying `amount` through `entries`. Rounding dust goes to the last recipient
+/// so the amounts always sum to `amount`.
+pub fn split_amounts(amount: u64, entries: &[SplitEntry]) -> Result<Vec<u64>> {
+    let mut amounts = Vec::with_capacity(entries.len());
+    let mut paid = 0u64;
+    for (i, entry) in entries.iter().enumerate() {
+        let share = if i + 1 == entries.len() {
+            amount.checked_sub(paid).ok_or(EscrowError::Overflow)?
+        } else {
+            (amount as u128)
+                .checked_mul(entry.bps as u128)
+                .ok_or(EscrowError::Overflow)?
+                .checked_div(10_000)
+                .ok_or(EscrowError::Overflow)? as u64
+        };
+        paid = paid.checked_add(share).ok_or(EscrowError::Overflow)?;
+        amounts.push(share);
+    }
+    Ok(amounts)
+}
+
+/// True when a single funding of `amount` satisfies the project's minimum (0 = no minimum). Once less than
+/// `min_funding` of the goal remains, smaller fundings are accepted so the goal can still be completed.
+pub fn meets_min_funding(amount: u64, min_funding: u64, remaining_goal: u64) -> bool {
+    amount >= min_funding || remaining_goal < min_funding
+}
+
+/// A pending SOL contribution may be settled for `taste_amount` when it covers the backer's `min_taste` and the
+/// project's per-funding minimum.
+pub fn check_pending_settlement(
+    taste_amount: u64,
+    min_taste: u64,
+    min_funding: u64,
+    remaining_goal: u64,
+) -> Result<()> {
+    require!(
+        taste_amount > 0 && taste_amount >= min_taste,
+        EscrowError::SettlementBelowMinimum
+    );
+    require!(
+        meets_min_funding(taste_amount, min_funding, remaining_goal),
+        EscrowError::BelowMinFunding
+    );
+    Ok(())
+}
+
+/// A backer's escrowed total after adding `to_escrow`. Fails with BackerCapExceeded when it would exceed
+/// `max_per_backer` (0 = unlimited).
+pub fn backer_total_within_cap(current: u64, to_escrow: u64, max_per_backer: u64) -> Result<u64> {
+    let total = current
+        .checked_add(to_escrow)
+        .ok_or(EscrowError::Overflow)?;
+    require!(
+        max_per_backer == 0 || total <= max_per_backer,
+        EscrowError::BackerCapExceeded
+    );
+    Ok(total)
+}
+
+/// Most an artist can push the deadline out, summed over all extensions (30 days).
+pub const MAX_DEADLINE_EXTENSION_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// Validates moving a project deadline from `current_deadline` to `new_deadline` at time `now` and returns the
+/// new cumulative extension. The current deadline must not have passed and the new one must be later.
+/// The cumulative extension may not exceed MAX_DEADLINE_EXTENSION_SECS.
+pub fn deadline_extension(
+    current_deadline: i64,
+    new_deadline: i64,
+    now: i64,
+    extended_so_far: i64,
+) -> Result<i64> {
+    require!(now <= current_deadline, EscrowError::DeadlinePassed);
+    require!(
+        new_deadline > current_deadline,
+        EscrowError::InvalidDeadlineExtension
+    );
+    let total = extended_so_far
+        .checked_add(new_deadline - current_deadline)
+        .ok_or(EscrowError::Overflow)?;
+    require!(
+        total <= MAX_DEADLINE_EXTENSION_SECS,
+        EscrowError::DeadlineExtensionTooLarge
+    );
+    Ok(total)
+}
+
+/// End of the opt-out refund window opened by a material edit at `now`. The window must be at least
+/// `min_refund_window_secs` long so backers get real time to opt out.
+pub fn material_edit_refund_window_end(
+    now: i64,
+    refund_window_secs: i64,
+    min_refund_window_secs: i64,
+) -> Result<i64> {
+    require!(
+        refund_window_secs >= min_refund_window_secs,
+        EscrowError::RefundWindowTooShort
+    );
+    Ok(now
+        .checked_add(refund_window_secs)
+        .ok_or(EscrowError::Overflow)?)
+}
+
+/// Validates raising a goal from `current_goal` to `new_goal`: it must strictly increase and stay within
+/// `original_goal * multiple_bps / 10000`.
+pub fn check_goal_raise(
+    current_goal: u64,
+    original_goal: u64,
+    new_goal: u64,
+    multiple_bps: u16,
+) -> Result<()> {
+    require!(new_goal > current_goal, EscrowError::GoalNotIncreased);
+    let cap = (original_goal as u128) * (multiple_bps as u128) / 10_000;
+    require!(new_goal as u128 <= cap, EscrowError::GoalRaiseTooLarge);
+    Ok(())
+}
+
+/// Upgrade raw Project account data to the current layout. Data is zero-extended to PROJECT_ACCOUNT_SPACE
+/// so appended fields default to zero (except `original_goal`, which takes the current goal), then `version` is set.
+/// Fails if the data is not a Project or is already current.
+pub fn migrate_project_data(data: &[u8]) -> Result<Project> {
+    require!(
+        data.len() >= 8 && &data[..8] == Project::DISCRIMINATOR,
+        EscrowError::InvalidProjectAccount
+    );
+    let mut buf = data.to_vec();
+    if buf.len() < PROJECT_ACCOUNT_SPACE {
+        buf.resize(PROJECT_ACCOUNT_SPACE, 0);
+    }
+    let mut project = Project::try_deserialize(&mut &buf[..])?;
+    require!(
+        project.version < PROJECT_VERSION,
+        EscrowError::ProjectAlreadyMigrated
+    );
+    if project.original_goal == 0 {
+        project.original_goal = project.goal;
+    }
+    project.version = PROJECT_VERSION;
+    Ok(project)
+}
+
+/// Upgrade raw ArtistState account data created before the track-record fields existed. Fails if the data is
+/// not a legacy ArtistState.
+pub fn migrate_artist_state_data(data: &[u8]) -> Result<ArtistState> {
+    require!(
+        data.len() >= 8 && &data[..8] == ArtistState::DISCRIMINATOR,
+        EscrowError::InvalidArtistStateAccount
+    );
+    require!(
+        data.len() < ARTIST_STATE_SPACE,
+        EscrowError::ArtistStateAlreadyMigrated
+    );
+    let mut buf = data.to_vec();
+    buf.resize(ARTIST_STATE_SPACE, 0);
+    ArtistState::try_deserialize(&mut &buf[..])
+}
+
+/// Upgrade raw Backer account data created before `fee_paid` existed. Fails if the data is not a legacy Backer.
+pub fn migrate_backer_data(data: &[u8]) -> Result<Backer> {
+    require!(
+        data.len() >= 8 && &data[..8] == Backer::DISCRIMINATOR,
+        EscrowError::InvalidBackerAccount
+    );
+    require!(
+        data.len() < BACKER_SPACE,
+        EscrowError::BackerAlreadyMigrated
+    );
+    let mut buf = data.to_vec();
+    buf.resize(BACKER_SPACE, 0);
+    Backer::try_deserialize(&mut &buf[..])
+}
+
 #[account]
 pub struct Project {
     pub artist: Pubkey,
@@ -775,12 +2618,125 @@ pub struct Project {
     pub total_raised: u64,
     pub backer_count: u32,
     pub current_milestone: u8,
+    /// Layout version (PROJECT_VERSION at creation; 0 = v1 account created before versioning).
+    pub version: u8,
+    /// Total seconds the deadline has been extended via extend_deadline.
+    pub deadline_extension_secs: i64,
+    /// Smallest amount accepted by a single fund_project call (at most the goal); 0 = no minimum. Waived once
+    /// less than this remains of the goal.
+    pub min_funding: u64,
+    /// Most a single backer may have in escrow (post-fee); 0 = unlimited.
+    pub max_per_backer: u64,
+    /// Minimum raise for the project to proceed; below it the project can be failed after the deadline.
+    /// `goal` stays the hard cap. 0 = no soft cap (the goal is the threshold).
+    pub soft_cap: u64,
+    /// Amount of the current milestone already paid out by partial releases.
+    pub released_in_milestone: u64,
+    /// Goal at creation (or at the last material edit); raise_goal caps `goal` relative to this.
+    pub original_goal: u64,
+    /// Set by the upgrade authority via set_fee_exempt; fund_project then charges no platform fee.
+    pub fee_exempt: bool,
+}
+
+impl Project {
+    /// Amount that must be raised by the deadline to avoid fail_project: the soft cap if set, else the goal.
+    pub fn funding_threshold(&self) -> u64 {
+        if self.soft_cap == 0 {
+            self.goal
+        } else {
+            self.soft_cap
+        }
+    }
+
+    /// Escrow still needed to reach the goal.
+    pub fn remaining_goal(&self) -> u64 {
+        self.goal.saturating_sub(self.total_raised)
+    }
+
+    /// True once any milestone funds have left escrow (a full milestone or a partial tranche).
+    pub fn has_released(&self) -> bool {
+        self.current_milestone > 0 || self.released_in_milestone > 0
+    }
+
+    /// Full share of the current milestone: its percentage of total_raised.
+    pub fn current_milestone_amount(&self) -> Result<u64> {
+        let idx = self.current_milestone as usize;
+        require!(idx < MAX_MILESTONES, EscrowError::InvalidMilestone);
+        let pct = self.milestone_percentages[idx];
+        Ok((self.total_raised as u128)
+            .checked_mul(pct as u128)
+            .ok_or(EscrowError::Overflow)?
+            .checked_div(100)
+            .ok_or(EscrowError::Overflow)? as u64)
+    }
+
+    /// Dashboard snapshot returned by get_project_stats.
+    pub fn stats(&self) -> ProjectStats {
+        ProjectStats {
+            total_raised: self.total_raised,
+            goal: self.goal,
+            backer_count: self.backer_count,
+            current_milestone: self.current_milestone,
+            effective_milestone_count: effective_milestone_count(&self.milestone_percentages) as u8,
+        }
+    }
+}
+
+/// Optional per-project funding limits set at create_project; 0 disables each one.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct FundingLimits {
+    /// Smallest single fund_project amount (at most the goal).
+    pub min_funding: u64,
+    /// Most a single backer may have in escrow (post-fee).
+    pub max_per_backer: u64,
+    /// Funding threshold for fail_project when below the goal.
+    pub soft_cap: u64,
+}
+
+/// Funding progress returned by get_project_stats.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ProjectStats {
+    pub total_raised: u64,
+    pub goal: u64,
+    pub backer_count: u32,
+    pub current_milestone: u8,
+    /// Milestones up to and including the last non-zero percentage.
+    pub effective_milestone_count: u8,
 }
 
+/// Per-artist counter and public track record. PDA seeds = [b"artist_state", artist].
 #[account]
 pub struct ArtistState {
     pub artist: Pubkey,
     pub project_count: u64,
+    /// Projects that reached Completed (last milestone released, complete_project or force_complete_project).
+    pub completed_projects: u32,
+    /// Projects cancelled by the artist, failed after missing their deadline or slashed by governance.
+    pub cancelled_projects: u32,
+    /// Post-fee $TASTE raised through fund_project and settled SOL pledges across all of the artist's projects.
+    pub total_raised_lifetime: u64,
+}
+
+impl ArtistState {
+    /// Count a project that just became Completed or Cancelled.
+    pub fn record_outcome(&mut self, status: ProjectStatus) -> Result<()> {
+        match status {
+            ProjectStatus::Completed => {
+                self.completed_projects = self
+                    .completed_projects
+                    .checked_add(1)
+                    .ok_or(EscrowError::Overflow)?;
+            }
+            ProjectStatus::Cancelled => {
+                self.cancelled_projects = self
+                    .cancelled_projects
+                    .checked_add(1)
+                    .ok_or(EscrowError::Overflow)?;
+            }
+            ProjectStatus::Active => {}
+        }
+        Ok(())
+    }
 }
 
 #[account]
@@ -789,12 +2745,90 @@ pub struct Backer {
     pub project: Pubkey,
     pub amount: u64,
     pub claimed_rwa: bool,
+    /// Platform fee (treasury + burn, including any referral cut) paid across all fundings.
+    pub fee_paid: u64,
+    /// Part of fee_paid the platform treasury received (net of referral cuts). refund_fee returns it once the
+    /// project is cancelled and resets this to 0.
+    pub treasury_fee_paid: u64,
+}
+
+/// Written by slash_project. PDA seeds = [b"slash", project]. Refunds of a slashed project split
+/// `escrow_balance` across backers by their share of `total_raised`.
+#[account]
+#[derive(Default)]
+pub struct SlashRecord {
+    /// Escrow balance when the project was slashed.
+    pub escrow_balance: u64,
+    /// Project total_raised when the project was slashed.
+    pub total_raised: u64,
 }
 
 /// One-time config: stores the governance release PDA. ReleaseMilestone/CompleteProject validate against this.
+/// Configs created before the fee fields existed must be grown with update_config before other
+/// instructions can load them as `Account<Config>`.
 #[account]
 pub struct Config {
     pub governance_release_authority: Pubkey,
+    /// fund_project fee sent to the platform treasury, in basis points.
+    pub treasury_fee_bps: u16,
+    /// fund_project fee burned (or deferred to the burn vault), in basis points.
+    pub burn_fee_bps: u16,
+    /// Share of the treasury fee paid to a referrer on referred fundings, in basis points of that fee.
+    pub referral_bps: u16,
+    /// Most raise_goal may grow a goal, in basis points of the original goal (20000 = 2x).
+    pub max_goal_multiple_bps: u16,
+    /// Shortest opt-out refund window apply_material_edit accepts, in seconds.
+    pub min_refund_window_secs: i64,
+    /// When true, the burn portion of the fee accumulates in the burn vault instead of being burned per funding.
+    pub defer_burn: bool,
+    /// $TASTE token account that receives the treasury fee. Default (unset) accepts any platform_treasury.
+    pub treasury: Pubkey,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            governance_release_authority: Pubkey::default(),
+            treasury_fee_bps: DEFAULT_TREASURY_FEE_BPS,
+            burn_fee_bps: DEFAULT_BURN_FEE_BPS,
+            referral_bps: 0,
+            max_goal_multiple_bps: DEFAULT_MAX_GOAL_MULTIPLE_BPS,
+            min_refund_window_secs: DEFAULT_MIN_REFUND_WINDOW_SECS,
+            defer_burn: false,
+            treasury: Pubkey::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Parse a Config account of any historical length. Fee bps, the goal multiple and the refund window floor
+    /// missing from older layouts take their defaults; a missing referral_bps, defer_burn or treasury reads as zero. Returns None if the discriminator or base layout doesn't match.
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        if data.len() < CONFIG_BASE_LEN || &data[..8] != Config::DISCRIMINATOR {
+            return None;
+        }
+        let mut buf = data.to_vec();
+        if buf.len() < CONFIG_SPACE {
+            buf.resize(CONFIG_SPACE, 0);
+        }
+        let mut config = Config::try_deserialize(&mut &buf[..]).ok()?;
+        if data.len() < CONFIG_FEES_LEN {
+            config.treasury_fee_bps = DEFAULT_TREASURY_FEE_BPS;
+            config.burn_fee_bps = DEFAULT_BURN_FEE_BPS;
+        }
+        if data.len() < CONFIG_GOAL_MULTIPLE_LEN {
+            config.max_goal_multiple_bps = DEFAULT_MAX_GOAL_MULTIPLE_BPS;
+        }
+        if data.len() < CONFIG_REFUND_WINDOW_LEN {
+            config.min_refund_window_secs = DEFAULT_MIN_REFUND_WINDOW_SECS;
+        }
+        Some(config)
+    }
+
+    /// True when `platform_treasury` is the configured treasury, or no treasury has been set.
+    pub fn treasury_matches(&self, platform_treasury: &Pubkey) -> bool {
+        self.treasury == Pubkey::default() || self.treasury == *platform_treasury
+    }
 }
 
 /// Per-project sum of sqrt(backer amounts) for governance early-finalize "outcome decided" math. PDA seeds = [b"vote_weight", project].
@@ -803,6 +2837,60 @@ pub struct ProjectVoteWeight {
     pub total_vote_weight: u64,
 }
 
+/// One collaborator's share of milestone payouts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SplitEntry {
+    /// Wallet whose $TASTE token account receives this share.
+    pub recipient: Pubkey,
+    pub bps: u16,
+}
+
+/// What a collaborator contributed to the release.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CollaboratorRole {
+    Artist,
+    Producer,
+    Songwriter,
+    Featured,
+    Engineer,
+    Other,
+}
+
+/// One rights holder of a project and their share.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Collaborator {
+    pub wallet: Pubkey,
+    pub role: CollaboratorRole,
+    pub bps: u16,
+}
+
+/// A project's rights holders, set by the artist. PDA seeds = [b"collaborators", project].
+#[account]
+pub struct Collaborators {
+    pub project: Pubkey,
+    pub entries: Vec<Collaborator>,
+}
+
+impl Collaborators {
+    /// The entries as a payout split, for clients that pay collaborators by their registered shares.
+    pub fn split_entries(&self) -> Vec<SplitEntry> {
+        self.entries
+            .iter()
+            .map(|c| SplitEntry {
+                recipient: c.wallet,
+                bps: c.bps,
+            })
+            .collect()
+    }
+}
+
+/// How release_milestone splits payouts among collaborators. PDA seeds = [b"payout_split", project].
+#[account]
+pub struct PayoutSplit {
+    pub project: Pubkey,
+    pub entries: Vec<SplitEntry>,
+}
+
 /// Tracks material-edit terms and refund window. Created when governance applies a material edit.
 #[account]
 pub struct ProjectTerms {
@@ -812,49 +2900,221 @@ pub struct ProjectTerms {
     pub refund_window_end: i64,
 }
 
+/// SOL pledged by a backer who holds no $TASTE, awaiting settlement by the artist.
+/// PDA seeds = [b"pending_contribution", project, backer]. Its lamports beyond rent are the pledge.
+#[account]
+pub struct PendingContribution {
+    pub backer: Pubkey,
+    pub project: Pubkey,
+    /// Pledged lamports, excluding the account's rent.
+    pub lamports: u64,
+    /// Least gross $TASTE (before the platform fee) the backer accepts for the pledge; settlement below it fails.
+    pub min_taste: u64,
+}
+
+pub const PENDING_CONTRIBUTION_SPACE: usize = 8 + 32 + 32 + 8 + 8;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    /// Must be the program upgrade authority (validated in instruction).
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = CONFIG_SPACE,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Program account (executable) for this program. Used to read programdata address.
+    /// CHECK: validated in instruction (must equal ctx.program_id)
+    pub program_account: UncheckedAccount<'info>,
+
+    /// ProgramData account for this program. Used to read upgrade_authority_address.
+    /// CHECK: validated in instruction (must match program_account's programdata_address)
+    pub program_data_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// Must be the program upgrade authority (validated in instruction). Pays rent when a legacy config is grown.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Config PDA; parsed with Config::from_account_data so legacy (shorter) layouts can be migrated.
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        owner = crate::ID,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// Program account (executable) for this program.
+    /// CHECK: validated in instruction
+    pub program_account: UncheckedAccount<'info>,
+
+    /// ProgramData account for this program.
+    /// CHECK: validated in instruction
+    pub program_data_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
-pub struct InitializeConfig<'info> {
-    /// Must be the program upgrade authority (validated in instruction).
+pub struct UpdateFeeConfig<'info> {
+    /// Must be the program upgrade authority (validated in instruction). Pays rent when a legacy config is grown.
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    /// CHECK: Config PDA; parsed with Config::from_account_data so legacy (shorter) layouts can be migrated.
     #[account(
-        init,
-        payer = authority,
-        space = 8 + 32,
+        mut,
         seeds = [b"config"],
         bump,
+        owner = crate::ID,
     )]
-    pub config: Account<'info, Config>,
+    pub config: UncheckedAccount<'info>,
 
-    /// Program account (executable) for this program. Used to read programdata address.
-    /// CHECK: validated in instruction (must equal ctx.program_id)
+    /// CHECK: validated in instruction
     pub program_account: UncheckedAccount<'info>,
-
-    /// ProgramData account for this program. Used to read upgrade_authority_address.
-    /// CHECK: validated in instruction (must match program_account's programdata_address)
+    /// CHECK: validated in instruction
     pub program_data_account: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateConfig<'info> {
-    /// Must be the program upgrade authority (validated in instruction).
+pub struct UpdateFeeTreasury<'info> {
+    /// Must be the program upgrade authority (validated in instruction). Pays rent when a legacy config is grown.
+    #[account(mut)]
     pub authority: Signer<'info>,
 
+    /// CHECK: Config PDA; parsed with Config::from_account_data so legacy (shorter) layouts can be migrated.
     #[account(
         mut,
         seeds = [b"config"],
         bump,
+        owner = crate::ID,
     )]
-    pub config: Account<'info, Config>,
+    pub config: UncheckedAccount<'info>,
+
+    #[account(constraint = new_treasury.mint == taste_mint.key() @ EscrowError::InvalidTreasury)]
+    pub new_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    pub taste_mint: InterfaceAccount<'info, Mint>,
 
-    /// Program account (executable) for this program.
     /// CHECK: validated in instruction
     pub program_account: UncheckedAccount<'info>,
+    /// CHECK: validated in instruction
+    pub program_data_account: UncheckedAccount<'info>,
 
-    /// ProgramData account for this program.
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RebuildVoteWeight<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub project: Account<'info, Project>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + 8,
+        seeds = [b"vote_weight", project.key().as_ref()],
+        bump,
+    )]
+    pub vote_weight: Account<'info, ProjectVoteWeight>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecountBackers<'info> {
+    /// Must be the program upgrade authority (validated in instruction).
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+
+    /// CHECK: validated in instruction
+    pub program_account: UncheckedAccount<'info>,
+    /// CHECK: validated in instruction
+    pub program_data_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateProject<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner checked here; discriminator and version checked in migrate_project_data (older layouts may not deserialize).
+    #[account(mut, owner = crate::ID)]
+    pub project: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateBacker<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner checked here; discriminator and size checked in migrate_backer_data (legacy layouts do not deserialize).
+    #[account(mut, owner = crate::ID)]
+    pub backer: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateArtistState<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner checked here; discriminator and size checked in migrate_artist_state_data (legacy layouts do not deserialize).
+    #[account(mut, owner = crate::ID)]
+    pub artist_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepBurnVault<'info> {
+    /// CHECK: PDA validated by seeds
+    #[account(seeds = [b"burn_vault"], bump)]
+    pub burn_vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = taste_mint,
+        associated_token::authority = burn_vault_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub burn_vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub taste_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeExempt<'info> {
+    /// Must be the program upgrade authority (validated in instruction).
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+
+    /// CHECK: validated in instruction
+    pub program_account: UncheckedAccount<'info>,
     /// CHECK: validated in instruction
     pub program_data_account: UncheckedAccount<'info>,
 }
@@ -891,7 +3151,7 @@ pub struct CreateProject<'info> {
     #[account(
         init_if_needed,
         payer = artist,
-        space = 8 + 32 + 8,
+        space = ARTIST_STATE_SPACE,
         seeds = [b"artist_state", artist.key().as_ref()],
         bump,
     )]
@@ -900,7 +3160,7 @@ pub struct CreateProject<'info> {
     #[account(
         init,
         payer = artist,
-        space = 8 + 32 + 4 + MAX_PROJECT_NAME_LEN + 8 + (2 * MAX_MILESTONES) + 8 + 1 + 32 + 8 + 4 + 1,
+        space = PROJECT_ACCOUNT_SPACE,
         seeds = [b"project", artist.key().as_ref(), artist_state.project_count.to_le_bytes().as_ref()],
         bump,
     )]
@@ -949,67 +3209,268 @@ pub struct InitializeProjectTerms<'info> {
 }
 
 #[derive(Accounts)]
-pub struct FundProject<'info> {
+pub struct FundProject<'info> {
+    #[account(mut)]
+    pub backer_wallet: Signer<'info>,
+
+    #[account(mut, has_one = taste_mint)]
+    pub project: Box<Account<'info, Project>>,
+
+    #[account(mut, seeds = [b"artist_state", project.artist.as_ref()], bump)]
+    pub artist_state: Box<Account<'info, ArtistState>>,
+
+    #[account(
+        init_if_needed,
+        payer = backer_wallet,
+        space = BACKER_SPACE,
+        seeds = [b"backer", project.key().as_ref(), backer_wallet.key().as_ref()],
+        bump,
+    )]
+    pub backer: Box<Account<'info, Backer>>,
+
+    #[account(
+        mut,
+        constraint = backer_token_account.mint == project.taste_mint @ EscrowError::InvalidBackerTokenAccount,
+    )]
+    pub backer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"escrow", project.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub platform_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA validated by seeds
+    #[account(seeds = [b"burn_vault"], bump)]
+    pub burn_vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = backer_wallet,
+        associated_token::mint = taste_mint,
+        associated_token::authority = burn_vault_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub burn_vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Config PDA; fee settings fall back to defaults when uninitialized or a legacy layout.
+    #[account(seeds = [b"config"], bump)]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub taste_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        init_if_needed,
+        payer = backer_wallet,
+        space = 8 + 8,
+        seeds = [b"vote_weight", project.key().as_ref()],
+        bump,
+    )]
+    pub vote_weight: Box<Account<'info, ProjectVoteWeight>>,
+
+    /// Optional referrer's $TASTE account; receives Config.referral_bps of the treasury fee.
+    #[account(
+        mut,
+        constraint = referrer_token_account.mint == project.taste_mint @ EscrowError::InvalidReferrer,
+        constraint = referrer_token_account.owner != backer_wallet.key() @ EscrowError::InvalidReferrer,
+    )]
+    pub referrer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Optional taste_token ProtocolState; funding fails while it is paused.
+    /// CHECK: address and contents validated by taste_token::protocol_paused
+    pub protocol_state: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct FundProjectWithSol<'info> {
+    #[account(mut)]
+    pub backer_wallet: Signer<'info>,
+
+    pub project: Box<Account<'info, Project>>,
+
+    #[account(
+        init_if_needed,
+        payer = backer_wallet,
+        space = PENDING_CONTRIBUTION_SPACE,
+        seeds = [b"pending_contribution", project.key().as_ref(), backer_wallet.key().as_ref()],
+        bump,
+    )]
+    pub pending_contribution: Account<'info, PendingContribution>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettlePendingContribution<'info> {
+    #[account(mut)]
+    pub artist: Signer<'info>,
+
+    #[account(mut, has_one = artist @ EscrowError::NotArtist, has_one = taste_mint)]
+    pub project: Box<Account<'info, Project>>,
+
+    /// CHECK: receives the PendingContribution's rent; bound to pending_contribution.backer.
+    #[account(mut, address = pending_contribution.backer @ EscrowError::NotBacker)]
+    pub backer_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = backer_wallet,
+        seeds = [b"pending_contribution", project.key().as_ref(), backer_wallet.key().as_ref()],
+        bump,
+    )]
+    pub pending_contribution: Account<'info, PendingContribution>,
+
+    #[account(
+        init_if_needed,
+        payer = artist,
+        space = BACKER_SPACE,
+        seeds = [b"backer", project.key().as_ref(), backer_wallet.key().as_ref()],
+        bump,
+    )]
+    pub backer: Box<Account<'info, Backer>>,
+
+    #[account(
+        mut,
+        constraint = artist_token_account.mint == project.taste_mint @ EscrowError::InvalidBackerTokenAccount,
+    )]
+    pub artist_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"escrow", project.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub platform_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA validated by seeds
+    #[account(seeds = [b"burn_vault"], bump)]
+    pub burn_vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = artist,
+        associated_token::mint = taste_mint,
+        associated_token::authority = burn_vault_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub burn_vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Config PDA; fee settings fall back to defaults when uninitialized or a legacy layout.
+    #[account(seeds = [b"config"], bump)]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = artist,
+        space = 8 + 8,
+        seeds = [b"vote_weight", project.key().as_ref()],
+        bump,
+    )]
+    pub vote_weight: Box<Account<'info, ProjectVoteWeight>>,
+
+    #[account(mut, seeds = [b"artist_state", project.artist.as_ref()], bump)]
+    pub artist_state: Box<Account<'info, ArtistState>>,
+
+    #[account(mut)]
+    pub taste_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// Optional taste_token ProtocolState; settlement fails while it is paused.
+    /// CHECK: address and contents validated by taste_token::protocol_paused
+    pub protocol_state: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelPendingContribution<'info> {
+    #[account(mut)]
+    pub backer_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        close = backer_wallet,
+        seeds = [b"pending_contribution", pending_contribution.project.as_ref(), backer_wallet.key().as_ref()],
+        bump,
+    )]
+    pub pending_contribution: Account<'info, PendingContribution>,
+}
+
+#[derive(Accounts)]
+pub struct MintReceipt<'info> {
     #[account(mut)]
     pub backer_wallet: Signer<'info>,
 
-    #[account(mut, has_one = taste_mint)]
     pub project: Box<Account<'info, Project>>,
 
     #[account(
-        init_if_needed,
-        payer = backer_wallet,
-        space = 8 + 32 + 32 + 8 + 1,
-        seeds = [b"backer", project.key().as_ref(), backer_wallet.key().as_ref()],
-        bump,
+        constraint = backer.wallet == backer_wallet.key(),
+        constraint = backer.project == project.key(),
     )]
     pub backer: Box<Account<'info, Backer>>,
 
-    #[account(mut)]
-    pub backer_token_account: InterfaceAccount<'info, TokenAccount>,
-
-    #[account(mut)]
-    pub escrow: InterfaceAccount<'info, TokenAccount>,
-
-    #[account(mut)]
-    pub platform_treasury: InterfaceAccount<'info, TokenAccount>,
-
+    /// PDA: mint authority for the receipt mint; signs for mint_to and CreateV1.
     /// CHECK: PDA validated by seeds
-    #[account(seeds = [b"burn_vault"], bump)]
-    pub burn_vault_authority: UncheckedAccount<'info>,
+    #[account(seeds = [b"receipt_authority", project.key().as_ref(), backer_wallet.key().as_ref()], bump)]
+    pub receipt_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = backer_wallet,
+        seeds = [b"receipt", project.key().as_ref(), backer_wallet.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = receipt_authority,
+        mint::freeze_authority = receipt_authority,
+    )]
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
         payer = backer_wallet,
-        associated_token::mint = taste_mint,
-        associated_token::authority = burn_vault_authority,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = backer_wallet,
         associated_token::token_program = token_program,
     )]
-    pub burn_vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub backer_receipt_ata: InterfaceAccount<'info, TokenAccount>,
 
+    /// Metaplex metadata PDA for receipt_mint; validated in handler.
+    /// CHECK: Validated against Metadata::find_pda(receipt_mint)
     #[account(mut)]
-    pub taste_mint: InterfaceAccount<'info, Mint>,
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Metaplex master edition PDA for receipt_mint; validated in handler.
+    /// CHECK: Validated against MasterEdition::find_pda(receipt_mint)
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// Metaplex Token Metadata program (MPL_TOKEN_METADATA_ID).
+    /// CHECK: Validated in instruction
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    /// Sysvar Instructions (required by Metaplex CreateV1).
+    /// CHECK: Required by Metaplex
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
     pub system_program: Program<'info, System>,
-
-    #[account(
-        init_if_needed,
-        payer = backer_wallet,
-        space = 8 + 8,
-        seeds = [b"vote_weight", project.key().as_ref()],
-        bump,
-    )]
-    pub vote_weight: Box<Account<'info, ProjectVoteWeight>>,
 }
 
 #[derive(Accounts)]
-pub struct MintReceipt<'info> {
+pub struct BackfillReceipt<'info> {
+    /// Must be the program upgrade authority (validated in instruction). Pays for the receipt accounts.
     #[account(mut)]
-    pub backer_wallet: Signer<'info>,
+    pub authority: Signer<'info>,
 
     pub project: Box<Account<'info, Project>>,
 
+    /// CHECK: the legacy backer's wallet; bound to `backer` by constraint. Does not sign.
+    pub backer_wallet: UncheckedAccount<'info>,
+
     #[account(
         constraint = backer.wallet == backer_wallet.key(),
         constraint = backer.project == project.key(),
@@ -1023,23 +3484,23 @@ pub struct MintReceipt<'info> {
 
     #[account(
         init,
-        payer = backer_wallet,
+        payer = authority,
         seeds = [b"receipt", project.key().as_ref(), backer_wallet.key().as_ref()],
         bump,
         mint::decimals = 0,
         mint::authority = receipt_authority,
         mint::freeze_authority = receipt_authority,
     )]
-    pub receipt_mint: InterfaceAccount<'info, Mint>,
+    pub receipt_mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         init_if_needed,
-        payer = backer_wallet,
+        payer = authority,
         associated_token::mint = receipt_mint,
         associated_token::authority = backer_wallet,
         associated_token::token_program = token_program,
     )]
-    pub backer_receipt_ata: InterfaceAccount<'info, TokenAccount>,
+    pub backer_receipt_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Metaplex metadata PDA for receipt_mint; validated in handler.
     /// CHECK: Validated against Metadata::find_pda(receipt_mint)
@@ -1059,74 +3520,358 @@ pub struct MintReceipt<'info> {
     /// CHECK: Required by Metaplex
     pub sysvar_instructions: UncheckedAccount<'info>,
 
+    /// CHECK: validated in instruction
+    pub program_account: UncheckedAccount<'info>,
+    /// CHECK: validated in instruction
+    pub program_data_account: UncheckedAccount<'info>,
+
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ReleaseMilestone<'info> {
-    pub governance_authority: Signer<'info>,
+pub struct GetProjectStats<'info> {
+    pub project: Box<Account<'info, Project>>,
+}
+
+#[derive(Accounts)]
+pub struct BurnReceipt<'info> {
+    #[account(mut)]
+    pub backer_wallet: Signer<'info>,
+
+    pub project: Box<Account<'info, Project>>,
 
     #[account(
-        seeds = [b"config"],
+        mut,
+        seeds = [b"receipt", project.key().as_ref(), backer_wallet.key().as_ref()],
         bump,
-        constraint = config.governance_release_authority == governance_authority.key() @ EscrowError::GovernanceAuthorityMismatch
     )]
-    pub config: Account<'info, Config>,
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = backer_wallet,
+        associated_token::token_program = token_program,
+    )]
+    pub backer_receipt_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseMilestone<'info> {
+    pub governance_authority: Signer<'info>,
+
+    /// CHECK: Config PDA; parsed with Config::from_account_data so configs on an older layout still load.
+    /// governance_release_authority is checked in the instruction.
+    #[account(seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+
+    #[account(mut, seeds = [b"escrow", project.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for escrow authority
+    #[account(seeds = [b"project", project.key().as_ref()], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub artist_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub taste_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Optional taste_token ProtocolState; releases fail while it is paused.
+    /// CHECK: address and contents validated by taste_token::protocol_paused
+    pub protocol_state: Option<UncheckedAccount<'info>>,
+
+    /// Artist track record; releasing the last milestone counts the completion.
+    #[account(mut, seeds = [b"artist_state", project.artist.as_ref()], bump)]
+    pub artist_state: Account<'info, ArtistState>,
+
+    /// The project's PayoutSplit; may be uninitialized, in which case the artist receives the whole release.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(seeds = [b"payout_split", project.key().as_ref()], bump)]
+    pub payout_split: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteProject<'info> {
+    pub governance_authority: Signer<'info>,
+
+    /// CHECK: Config PDA; parsed with Config::from_account_data so configs on an older layout still load.
+    /// governance_release_authority is checked in the instruction.
+    #[account(seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub project: Account<'info, Project>,
 
+    #[account(mut, seeds = [b"artist_state", project.artist.as_ref()], bump)]
+    pub artist_state: Account<'info, ArtistState>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProjectAccounts<'info> {
     #[account(mut)]
+    pub artist: Signer<'info>,
+
+    #[account(has_one = artist @ EscrowError::NotArtist)]
+    pub project: Account<'info, Project>,
+
+    #[account(mut, seeds = [b"escrow", project.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for escrow authority
+    #[account(seeds = [b"project", project.key().as_ref()], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = artist,
+        seeds = [b"vote_weight", project.key().as_ref()],
+        bump,
+    )]
+    pub vote_weight: Option<Account<'info, ProjectVoteWeight>>,
+
+    #[account(
+        mut,
+        close = artist,
+        seeds = [b"project_terms", project.key().as_ref()],
+        bump,
+    )]
+    pub project_terms: Option<Account<'info, ProjectTerms>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawEscrowDust<'info> {
+    pub artist: Signer<'info>,
+
+    #[account(has_one = artist @ EscrowError::NotArtist, has_one = taste_mint)]
+    pub project: Account<'info, Project>,
+
+    #[account(mut, seeds = [b"escrow", project.key().as_ref()], bump)]
     pub escrow: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA for escrow authority
     #[account(seeds = [b"project", project.key().as_ref()], bump)]
     pub escrow_authority: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    pub artist_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = taste_mint, token::authority = artist)]
+    pub artist_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub taste_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ForceCompleteProject<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub project: Account<'info, Project>,
+
+    #[account(mut, seeds = [b"artist_state", project.artist.as_ref()], bump)]
+    pub artist_state: Account<'info, ArtistState>,
+
+    /// CHECK: validated in instruction
+    pub program_account: UncheckedAccount<'info>,
+    /// CHECK: validated in instruction
+    pub program_data_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendDeadline<'info> {
+    pub artist: Signer<'info>,
+
+    #[account(mut, has_one = artist @ EscrowError::NotArtist)]
+    pub project: Account<'info, Project>,
+}
+
+#[derive(Accounts)]
+pub struct RaiseGoal<'info> {
+    pub artist: Signer<'info>,
+
+    #[account(mut, has_one = artist @ EscrowError::NotArtist)]
+    pub project: Account<'info, Project>,
+
+    /// CHECK: Config PDA; parsed with Config::from_account_data so configs on an older layout still load.
+    #[account(seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPayoutSplit<'info> {
+    #[account(mut)]
+    pub artist: Signer<'info>,
+
+    #[account(has_one = artist @ EscrowError::NotArtist)]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        init_if_needed,
+        payer = artist,
+        space = PAYOUT_SPLIT_SPACE,
+        seeds = [b"payout_split", project.key().as_ref()],
+        bump,
+    )]
+    pub payout_split: Account<'info, PayoutSplit>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCollaborators<'info> {
+    #[account(mut)]
+    pub artist: Signer<'info>,
+
+    #[account(has_one = artist @ EscrowError::NotArtist)]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        init_if_needed,
+        payer = artist,
+        space = COLLABORATORS_SPACE,
+        seeds = [b"collaborators", project.key().as_ref()],
+        bump,
+    )]
+    pub collaborators: Account<'info, Collaborators>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct DeadlineExtended {
+    pub project: Pubkey,
+    pub old_deadline: i64,
+    pub new_deadline: i64,
+    /// Cumulative extension after this change.
+    pub total_extension_secs: i64,
+}
+
+#[event]
+pub struct GoalRaised {
+    pub project: Pubkey,
+    pub old_goal: u64,
+    pub new_goal: u64,
+}
+
+#[event]
+pub struct ProjectFunded {
+    pub project: Pubkey,
+    /// Backer wallet.
+    pub backer: Pubkey,
+    /// Amount debited from the backer, fees included.
+    pub gross_amount: u64,
+    pub to_escrow: u64,
+    /// Treasury fee net of any referral cut.
+    pub fee_treasury: u64,
+    pub fee_burn: u64,
+    /// Part of the treasury fee paid to the referrer (0 when not referred).
+    pub fee_referral: u64,
+    pub new_total_raised: u64,
+}
+
+#[event]
+pub struct MilestoneReleased {
+    pub project: Pubkey,
+    /// Milestone index the tranche was paid from.
+    pub milestone: u8,
+    pub amount: u64,
+    /// False for a partial release that leaves part of the milestone unpaid.
+    pub completes_milestone: bool,
+}
+
+#[event]
+pub struct ProjectCompleted {
+    pub project: Pubkey,
+    pub total_raised: u64,
+}
+
+#[event]
+pub struct ProjectSlashed {
+    pub project: Pubkey,
+    /// Escrow balance left for pro-rata refunds.
+    pub escrow_balance: u64,
+    pub total_raised: u64,
+}
+
+#[event]
+pub struct ReceiptMinted {
+    pub project: Pubkey,
+    /// Backer wallet.
+    pub backer: Pubkey,
+    /// Receipt NFT mint PDA [b"receipt", project, backer].
+    pub receipt_mint: Pubkey,
+    pub metadata_uri: String,
+}
 
-    pub taste_mint: InterfaceAccount<'info, Mint>,
-    pub token_program: Interface<'info, TokenInterface>,
+#[event]
+pub struct ReceiptBurned {
+    pub project: Pubkey,
+    /// Backer wallet.
+    pub backer: Pubkey,
+    /// Receipt NFT mint PDA [b"receipt", project, backer].
+    pub receipt_mint: Pubkey,
 }
 
 #[derive(Accounts)]
-pub struct CompleteProject<'info> {
-    pub governance_authority: Signer<'info>,
+pub struct CancelProject<'info> {
+    pub artist: Signer<'info>,
 
-    #[account(
-        seeds = [b"config"],
-        bump,
-        constraint = config.governance_release_authority == governance_authority.key() @ EscrowError::GovernanceAuthorityMismatch
-    )]
-    pub config: Account<'info, Config>,
+    #[account(mut, has_one = artist)]
+    pub project: Account<'info, Project>,
 
+    #[account(mut, seeds = [b"artist_state", artist.key().as_ref()], bump)]
+    pub artist_state: Account<'info, ArtistState>,
+}
+
+#[derive(Accounts)]
+pub struct FailProject<'info> {
     #[account(mut)]
     pub project: Account<'info, Project>,
+
+    #[account(mut, seeds = [b"artist_state", project.artist.as_ref()], bump)]
+    pub artist_state: Account<'info, ArtistState>,
 }
 
 #[derive(Accounts)]
-pub struct ForceCompleteProject<'info> {
+pub struct SlashProject<'info> {
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub governance_authority: Signer<'info>,
+
+    /// CHECK: Config PDA; parsed with Config::from_account_data so configs on an older layout still load.
+    /// governance_release_authority is checked in the instruction.
+    #[account(seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub project: Account<'info, Project>,
 
-    /// CHECK: validated in instruction
-    pub program_account: UncheckedAccount<'info>,
-    /// CHECK: validated in instruction
-    pub program_data_account: UncheckedAccount<'info>,
-}
+    #[account(seeds = [b"escrow", project.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
 
-#[derive(Accounts)]
-pub struct CancelProject<'info> {
-    pub artist: Signer<'info>,
+    #[account(
+        init,
+        payer = governance_authority,
+        space = SLASH_RECORD_SPACE,
+        seeds = [b"slash", project.key().as_ref()],
+        bump,
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
 
-    #[account(mut, has_one = artist)]
-    pub project: Account<'info, Project>,
+    /// Artist track record; the slash counts as a cancelled project.
+    #[account(mut, seeds = [b"artist_state", project.artist.as_ref()], bump)]
+    pub artist_state: Account<'info, ArtistState>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -1146,7 +3891,7 @@ pub struct Refund<'info> {
     #[account(mut)]
     pub backer_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(mut, seeds = [b"escrow", project.key().as_ref()], bump)]
     pub escrow: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA
@@ -1160,21 +3905,83 @@ pub struct Refund<'info> {
     )]
     pub vote_weight: Account<'info, ProjectVoteWeight>,
 
+    /// CHECK: SlashRecord PDA; empty unless the project was slashed. Read in instruction.
+    #[account(seeds = [b"slash", project.key().as_ref()], bump)]
+    pub slash_record: UncheckedAccount<'info>,
+
     pub taste_mint: InterfaceAccount<'info, Mint>,
     pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct ApplyMaterialEdit<'info> {
-    #[account(mut)]
-    pub governance_authority: Signer<'info>,
+pub struct RefundBatch<'info> {
+    #[account(has_one = taste_mint)]
+    pub project: Account<'info, Project>,
 
     #[account(
-        seeds = [b"config"],
+        mut,
+        seeds = [b"escrow", project.key().as_ref()],
         bump,
-        constraint = config.governance_release_authority == governance_authority.key() @ EscrowError::GovernanceAuthorityMismatch
     )]
-    pub config: Account<'info, Config>,
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA
+    #[account(seeds = [b"project", project.key().as_ref()], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vote_weight", project.key().as_ref()],
+        bump,
+    )]
+    pub vote_weight: Account<'info, ProjectVoteWeight>,
+
+    /// CHECK: SlashRecord PDA; empty unless the project was slashed. Read in instruction.
+    #[account(seeds = [b"slash", project.key().as_ref()], bump)]
+    pub slash_record: UncheckedAccount<'info>,
+
+    pub taste_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RefundFee<'info> {
+    /// Owner of the platform treasury token account.
+    pub treasury_authority: Signer<'info>,
+
+    #[account(has_one = taste_mint)]
+    pub project: Account<'info, Project>,
+
+    #[account(mut, has_one = project)]
+    pub backer: Account<'info, Backer>,
+
+    #[account(
+        mut,
+        constraint = backer_token_account.owner == backer.wallet
+            && backer_token_account.mint == project.taste_mint @ EscrowError::InvalidBackerTokenAccount,
+    )]
+    pub backer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub platform_treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Config PDA; fee settings fall back to defaults when uninitialized or a legacy layout.
+    #[account(seeds = [b"config"], bump)]
+    pub config: UncheckedAccount<'info>,
+
+    pub taste_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyMaterialEdit<'info> {
+    #[account(mut)]
+    pub governance_authority: Signer<'info>,
+
+    /// CHECK: Config PDA; parsed with Config::from_account_data so configs on an older layout still load.
+    /// governance_release_authority is checked in the instruction.
+    #[account(seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub project: Account<'info, Project>,
@@ -1214,7 +4021,7 @@ pub struct OptOutRefund<'info> {
     #[account(mut)]
     pub backer_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(mut, seeds = [b"escrow", project.key().as_ref()], bump)]
     pub escrow: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA
@@ -1236,12 +4043,900 @@ pub struct OptOutRefund<'info> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sqrt_u64_is_floor_root_across_range() {
+        // Must agree with governance's sqrt_u64, which compares against the original Babylonian version.
+        let mut inputs = vec![0u64, 1, 2, 3, 4, 8, 9, 15, 16, u64::MAX, u64::MAX - 1];
+        for shift in 1..64 {
+            let p = 1u64 << shift;
+            inputs.extend([p - 1, p, p + 1]);
+        }
+        for root in [1_000u64, 3_037_000_499, u32::MAX as u64] {
+            inputs.extend([root * root - 1, root * root]);
+        }
+        for x in inputs {
+            let r = sqrt_u64(x) as u128;
+            assert!(
+                r * r <= x as u128 && (r + 1) * (r + 1) > x as u128,
+                "sqrt_u64({x})"
+            );
+        }
+        assert_eq!(sqrt_u64(u64::MAX), u32::MAX as u64);
+    }
+
+    #[test]
+    fn test_platform_fee_split_custom_bps() {
+        let amount = 1_000_000u64;
+        assert_eq!(
+            platform_fee_split(amount, DEFAULT_TREASURY_FEE_BPS, DEFAULT_BURN_FEE_BPS).unwrap(),
+            (20_000, 20_000, 960_000)
+        );
+        assert_eq!(
+            platform_fee_split(amount, 150, 50).unwrap(),
+            (15_000, 5_000, 980_000)
+        );
+        assert_eq!(platform_fee_split(amount, 0, 0).unwrap(), (0, 0, amount));
+    }
+
+    #[test]
+    fn test_platform_fee_split_rounding() {
+        let nominal = |amount: u64, bps: u16| amount as f64 * bps as f64 / 10_000.0;
+        let mut amounts: Vec<u64> = (0..=1_000).collect();
+        amounts.extend([
+            1_000_001,
+            123_456_789,
+            1_000_000_000_007,
+            u64::MAX / 10_000,
+            u64::MAX,
+        ]);
+        for (t, b) in [(200u16, 200u16), (150, 50), (1, 0), (0, 300), (333, 667)] {
+            for &amount in &amounts {
+                let (fee_treasury, fee_burn, to_escrow) = platform_fee_split(amount, t, b).unwrap();
+                assert_eq!(
+                    fee_treasury as u128 + fee_burn as u128 + to_escrow as u128,
+                    amount as u128
+                );
+                // The total fee is floored once, so it never exceeds nominal and is short by less than 1 unit.
+                let fee_total = fee_treasury + fee_burn;
+                let expected = (amount as u128 * (t as u128 + b as u128) / 10_000) as u64;
+                assert_eq!(fee_total, expected);
+                if amount <= 1_000_000_000_007 {
+                    assert!((fee_total as f64) <= nominal(amount, t + b));
+                    assert!(nominal(amount, t + b) - (fee_total as f64) < 1.0);
+                    assert!((fee_treasury as f64 - nominal(amount, t)).abs() <= 1.0);
+                    assert!((fee_burn as f64 - nominal(amount, b)).abs() <= 1.0);
+                }
+            }
+        }
+        // At the default 2% + 2%: 1 and 25 units are below one unit of fee; 99 units pay 3 (nominal 3.96).
+        assert_eq!(platform_fee_split(1, 200, 200).unwrap(), (0, 0, 1));
+        assert_eq!(platform_fee_split(25, 200, 200).unwrap(), (0, 1, 24));
+        assert_eq!(platform_fee_split(99, 200, 200).unwrap(), (1, 2, 96));
+        // Two floored legs would have charged 1 + 1; the single floor charges 3, within 1 unit of nominal.
+        assert_eq!(platform_fee_split(99, 200, 200).unwrap().2, 99 - 3);
+    }
+
+    #[test]
+    fn test_fee_exempt_project_funds_escrow_in_full() {
+        let config = Config::default();
+        let goal = 1_000_000u64;
+        // Exempt: the backer pays exactly the goal and all of it lands in escrow.
+        let (t, b) = funding_fee_bps(true, &config);
+        assert_eq!(platform_fee_split(goal, t, b).unwrap(), (0, 0, goal));
+        // Standard: 4% is taken, so the same payment raises only 96% of the goal.
+        let (t, b) = funding_fee_bps(false, &config);
+        let (fee_treasury, fee_burn, to_escrow) = platform_fee_split(goal, t, b).unwrap();
+        assert_eq!(fee_treasury + fee_burn, 40_000);
+        assert_eq!(to_escrow, 960_000);
+    }
+
+    #[test]
+    fn test_fee_paid_accumulates_four_percent_and_treasury_leg_net_of_referral() {
+        let config = Config::default();
+        let (t, b) = funding_fee_bps(false, &config);
+        let (mut fee_paid, mut treasury_fee_paid) = (0u64, 0u64);
+        // The middle funding is referred: 25% of its treasury fee goes to the referrer, not the treasury.
+        for (gross, referral_bps) in [(1_000_000u64, 0u16), (250_000, 2_500), (50_000, 0)] {
+            let (fee_treasury, _, to_escrow) = platform_fee_split(gross, t, b).unwrap();
+            fee_paid += gross - to_escrow;
+            treasury_fee_paid += fee_treasury - referral_cut(fee_treasury, referral_bps).unwrap();
+        }
+        assert_eq!(fee_paid, 1_300_000 * 4 / 100);
+        // refund_fee returns exactly what the treasury received: 2% of 1_300_000 less the 1_250 referral cut.
+        assert_eq!(treasury_fee_paid, 26_000 - 1_250);
+    }
+
+    #[test]
+    fn test_referral_cut_from_treasury_fee() {
+        let (fee_treasury, fee_burn, to_escrow) = platform_fee_split(1_000_000, 200, 200).unwrap();
+        let cut = referral_cut(fee_treasury, 2_500).unwrap();
+        assert_eq!(cut, 5_000);
+        // The referral comes out of the treasury share; the backer's total cost is unchanged.
+        assert_eq!((fee_treasury - cut) + cut + fee_burn + to_escrow, 1_000_000);
+        assert_eq!(referral_cut(fee_treasury, 0).unwrap(), 0);
+        assert_eq!(referral_cut(fee_treasury, 10_000).unwrap(), fee_treasury);
+        assert!(referral_cut(fee_treasury, 10_001).is_err());
+    }
+
+    #[test]
+    fn test_validate_receipt_uri() {
+        assert!(validate_receipt_uri("https://example.com/receipt.json").is_ok());
+        assert!(validate_receipt_uri(
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        )
+        .is_ok());
+        assert!(validate_receipt_uri("ar://abc123").is_ok());
+        assert!(validate_receipt_uri("").is_err());
+        assert!(validate_receipt_uri("https://").is_err());
+        assert!(validate_receipt_uri("http://example.com/receipt.json").is_err());
+        assert!(validate_receipt_uri("example.com/receipt.json").is_err());
+        let long = format!("https://example.com/{}", "x".repeat(200));
+        assert!(validate_receipt_uri(&long).is_err());
+    }
+
+    #[test]
+    fn test_validate_fee_bps_upper_bound() {
+        assert!(validate_fee_bps(500, 500).is_ok());
+        assert!(validate_fee_bps(MAX_PLATFORM_FEE_BPS, 0).is_ok());
+        assert!(validate_fee_bps(600, 401).is_err());
+        assert!(validate_fee_bps(u16::MAX, u16::MAX).is_err());
+    }
+
+    #[test]
+    fn test_config_from_legacy_data_uses_default_fees() {
+        let authority = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let mut legacy = Config::DISCRIMINATOR.to_vec();
+        legacy.extend_from_slice(authority.as_ref());
+        let parsed = Config::from_account_data(&legacy).unwrap();
+        assert_eq!(parsed.governance_release_authority, authority);
+        assert_eq!(parsed.treasury_fee_bps, DEFAULT_TREASURY_FEE_BPS);
+        assert_eq!(parsed.burn_fee_bps, DEFAULT_BURN_FEE_BPS);
+
+        assert_eq!(parsed.referral_bps, 0);
+
+        let mut current = Vec::new();
+        Config {
+            governance_release_authority: authority,
+            treasury_fee_bps: 300,
+            burn_fee_bps: 100,
+            referral_bps: 2_500,
+            max_goal_multiple_bps: 15_000,
+            min_refund_window_secs: 3_600,
+            defer_burn: true,
+            treasury,
+        }
+        .try_serialize(&mut current)
+        .unwrap();
+        assert_eq!(current.len(), CONFIG_SPACE);
+        let parsed = Config::from_account_data(&current).unwrap();
+        assert_eq!((parsed.treasury_fee_bps, parsed.burn_fee_bps), (300, 100));
+        assert_eq!(parsed.referral_bps, 2_500);
+        assert_eq!(parsed.max_goal_multiple_bps, 15_000);
+        assert_eq!(parsed.min_refund_window_secs, 3_600);
+        assert!(parsed.defer_burn);
+        assert!(parsed.treasury_matches(&treasury));
+        assert!(!parsed.treasury_matches(&Pubkey::new_unique()));
+        // Refund-window-era layout burns per funding and accepts any treasury (pre-rotation behaviour).
+        let parsed = Config::from_account_data(&current[..CONFIG_REFUND_WINDOW_LEN]).unwrap();
+        assert_eq!(parsed.min_refund_window_secs, 3_600);
+        assert!(!parsed.defer_burn);
+        assert!(parsed.treasury_matches(&Pubkey::new_unique()));
+        // Goal-multiple-era layout reads the refund window floor as the default.
+        let parsed = Config::from_account_data(&current[..CONFIG_GOAL_MULTIPLE_LEN]).unwrap();
+        assert_eq!(parsed.max_goal_multiple_bps, 15_000);
+        assert_eq!(
+            parsed.min_refund_window_secs,
+            DEFAULT_MIN_REFUND_WINDOW_SECS
+        );
+        // Referral-era layout keeps its settings and reads the goal multiple as the default.
+        let parsed = Config::from_account_data(&current[..CONFIG_REFERRAL_LEN]).unwrap();
+        assert_eq!(parsed.referral_bps, 2_500);
+        assert_eq!(parsed.max_goal_multiple_bps, DEFAULT_MAX_GOAL_MULTIPLE_BPS);
+        // Fee-era layout without referral_bps keeps its fees and reads referral as 0.
+        let parsed = Config::from_account_data(&current[..CONFIG_FEES_LEN]).unwrap();
+        assert_eq!((parsed.treasury_fee_bps, parsed.burn_fee_bps), (300, 100));
+        assert_eq!(parsed.referral_bps, 0);
+        assert!(Config::from_account_data(&legacy[..20]).is_none());
+    }
+
+    fn backer_entry(project: &Pubkey, amount: u64) -> (Pubkey, Backer) {
+        let wallet = Pubkey::new_unique();
+        let (address, _) = Pubkey::find_program_address(
+            &[b"backer", project.as_ref(), wallet.as_ref()],
+            &crate::ID,
+        );
+        let backer = Backer {
+            wallet,
+            project: *project,
+            amount,
+            claimed_rwa: false,
+            fee_paid: 0,
+            treasury_fee_paid: 0,
+        };
+        (address, backer)
+    }
+
+    #[test]
+    fn test_count_active_backers_corrects_drift() {
+        let project = Pubkey::new_unique();
+        // Drifted on-chain count: 10, while only two backers still have funds in.
+        let drifted_count = 10u32;
+        let backers = vec![
+            backer_entry(&project, 500),
+            backer_entry(&project, 0),
+            backer_entry(&project, 1),
+        ];
+        let recounted = count_active_backers(&project, &backers).unwrap();
+        assert_eq!(recounted, 2);
+        assert_ne!(recounted, drifted_count);
+
+        let duplicated = vec![backers[0].clone(), backers[0].clone()];
+        assert!(count_active_backers(&project, &duplicated).is_err());
+        let other_project = vec![backer_entry(&Pubkey::new_unique(), 5)];
+        assert!(count_active_backers(&project, &other_project).is_err());
+        let wrong_address = vec![(Pubkey::new_unique(), backers[0].1.clone())];
+        assert!(count_active_backers(&project, &wrong_address).is_err());
+    }
+
+    #[test]
+    fn test_rebuilt_vote_weight_from_three_backers() {
+        let project = Pubkey::new_unique();
+        let backers = vec![
+            backer_entry(&project, 100),
+            backer_entry(&project, 2_500),
+            backer_entry(&project, 9),
+        ];
+        assert_eq!(
+            rebuilt_vote_weight(&project, &backers, 3).unwrap(),
+            10 + 50 + 3
+        );
+        // A refunded backer adds nothing and is not counted as active.
+        let mut refunded = backers.clone();
+        refunded[1].1.amount = 0;
+        assert_eq!(rebuilt_vote_weight(&project, &refunded, 2).unwrap(), 13);
+        // Leaving a backer out is caught by the backer count.
+        assert!(rebuilt_vote_weight(&project, &backers[..2], 3).is_err());
+        let duplicated = vec![backers[0].clone(), backers[0].clone(), backers[1].clone()];
+        assert!(rebuilt_vote_weight(&project, &duplicated, 3).is_err());
+    }
+
+    #[test]
+    fn test_migrate_v1_project_defaults_new_fields() {
+        let project = Project {
+            artist: Pubkey::new_unique(),
+            name: "x".repeat(MAX_PROJECT_NAME_LEN),
+            goal: 1_000,
+            milestone_percentages: [20, 20, 20, 20, 20],
+            deadline: 1_800_000_000,
+            status: ProjectStatus::Active,
+            taste_mint: Pubkey::new_unique(),
+            total_raised: 500,
+            backer_count: 7,
+            current_milestone: 2,
+            version: 0,
+            deadline_extension_secs: 0,
+            min_funding: 0,
+            max_per_backer: 0,
+            soft_cap: 0,
+            released_in_milestone: 0,
+            original_goal: 1_000,
+            fee_exempt: false,
+        };
+        let mut data = Vec::new();
+        project.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), PROJECT_ACCOUNT_SPACE);
+        // v1 layout: no trailing fields. With a max-length name the old account is exactly this long.
+        data.truncate(PROJECT_V1_SPACE);
+        assert!(Project::try_deserialize(&mut data.as_slice()).is_err());
+
+        let migrated = migrate_project_data(&data).unwrap();
+        assert_eq!(migrated.version, PROJECT_VERSION);
+        assert_eq!(migrated.deadline_extension_secs, 0);
+        assert_eq!(migrated.min_funding, 0);
+        assert_eq!(migrated.max_per_backer, 0);
+        assert_eq!(migrated.soft_cap, 0);
+        assert_eq!(migrated.released_in_milestone, 0);
+        assert_eq!(migrated.original_goal, migrated.goal);
+        assert!(!migrated.fee_exempt);
+        assert_eq!(migrated.funding_threshold(), migrated.goal);
+        assert_eq!(migrated.name, project.name);
+        assert_eq!(migrated.total_raised, 500);
+        assert_eq!(migrated.backer_count, 7);
+        assert_eq!(migrated.current_milestone, 2);
+
+        // Already-current projects are rejected; non-Project data is rejected.
+        let mut current = Vec::new();
+        migrated.try_serialize(&mut current).unwrap();
+        assert!(migrate_project_data(&current).is_err());
+        assert!(migrate_project_data(&[0u8; 16]).is_err());
+    }
+
+    #[test]
+    fn test_migrate_v2_project() {
+        let mut data = Vec::new();
+        Project {
+            artist: Pubkey::new_unique(),
+            name: "short".to_string(),
+            goal: 1_000,
+            milestone_percentages: [100, 0, 0, 0, 0],
+            deadline: 1_800_000_000,
+            status: ProjectStatus::Active,
+            taste_mint: Pubkey::new_unique(),
+            total_raised: 0,
+            backer_count: 0,
+            current_milestone: 0,
+            version: 2,
+            deadline_extension_secs: 0,
+            min_funding: 0,
+            max_per_backer: 0,
+            soft_cap: 0,
+            released_in_milestone: 0,
+            original_goal: 1_000,
+            fee_exempt: false,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        let migrated = migrate_project_data(&data).unwrap();
+        assert_eq!(migrated.version, PROJECT_VERSION);
+        assert_eq!(migrated.name, "short");
+    }
+
+    #[test]
+    fn test_migrate_legacy_backer_defaults_fee_paid() {
+        let project = Pubkey::new_unique();
+        let (_, backer) = backer_entry(&project, 96_000);
+        let mut current = Vec::new();
+        Backer {
+            fee_paid: 2_000,
+            treasury_fee_paid: 1_000,
+            ..backer.clone()
+        }
+        .try_serialize(&mut current)
+        .unwrap();
+        assert_eq!(current.len(), BACKER_SPACE);
+        assert!(migrate_backer_data(&current).is_err());
+
+        let legacy = &current[..LEGACY_BACKER_SPACE];
+        assert!(Backer::try_deserialize(&mut &legacy[..]).is_err());
+        let migrated = migrate_backer_data(legacy).unwrap();
+        assert_eq!(migrated.wallet, backer.wallet);
+        assert_eq!(migrated.amount, 96_000);
+        assert_eq!(migrated.fee_paid, 0);
+        assert_eq!(migrated.treasury_fee_paid, 0);
+
+        let mut not_backer = legacy.to_vec();
+        not_backer[0] ^= 1;
+        assert!(migrate_backer_data(&not_backer).is_err());
+    }
+
+    #[test]
+    fn test_artist_state_records_outcomes_and_migrates() {
+        let artist = Pubkey::new_unique();
+        let mut legacy = ArtistState::DISCRIMINATOR.to_vec();
+        legacy.extend_from_slice(artist.as_ref());
+        legacy.extend_from_slice(&3u64.to_le_bytes());
+        assert_eq!(legacy.len(), LEGACY_ARTIST_STATE_SPACE);
+        assert!(ArtistState::try_deserialize(&mut &legacy[..]).is_err());
+
+        let mut state = migrate_artist_state_data(&legacy).unwrap();
+        assert_eq!(state.artist, artist);
+        assert_eq!(state.project_count, 3);
+        assert_eq!((state.completed_projects, state.cancelled_projects), (0, 0));
+        assert_eq!(state.total_raised_lifetime, 0);
+
+        state.record_outcome(ProjectStatus::Completed).unwrap();
+        state.record_outcome(ProjectStatus::Cancelled).unwrap();
+        state.record_outcome(ProjectStatus::Cancelled).unwrap();
+        state.record_outcome(ProjectStatus::Active).unwrap();
+        assert_eq!((state.completed_projects, state.cancelled_projects), (1, 2));
+
+        let mut current = Vec::new();
+        state.try_serialize(&mut current).unwrap();
+        assert_eq!(current.len(), ARTIST_STATE_SPACE);
+        assert!(migrate_artist_state_data(&current).is_err());
+    }
+
+    #[test]
+    fn test_refund_batch_count_cap() {
+        assert_eq!(refund_batch_count(2).unwrap(), 1);
+        assert_eq!(
+            refund_batch_count(MAX_REFUND_BATCH * REFUND_BATCH_ACCOUNTS_PER_BACKER).unwrap(),
+            MAX_REFUND_BATCH
+        );
+        assert_eq!(
+            refund_batch_count((MAX_REFUND_BATCH + 1) * REFUND_BATCH_ACCOUNTS_PER_BACKER),
+            Err(EscrowError::BatchTooLarge.into())
+        );
+        assert_eq!(
+            refund_batch_count(3),
+            Err(EscrowError::InvalidBackerAccount.into())
+        );
+        assert!(refund_batch_count(0).is_err());
+    }
+
+    #[test]
+    fn test_slashed_refund_is_pro_rata() {
+        assert_eq!(refund_amount(300, None).unwrap(), 300);
+        // 1_000 raised, 400 released before the slash: every backer gets 60% back.
+        let slash = SlashRecord {
+            escrow_balance: 600,
+            total_raised: 1_000,
+        };
+        let payouts: Vec<u64> = [250, 750]
+            .iter()
+            .map(|a| refund_amount(*a, Some(&slash)).unwrap())
+            .collect();
+        assert_eq!(payouts, vec![150, 450]);
+        assert_eq!(payouts.iter().sum::<u64>(), slash.escrow_balance);
+        // Nothing released yet: full refunds.
+        let untouched = SlashRecord {
+            escrow_balance: 1_000,
+            total_raised: 1_000,
+        };
+        assert_eq!(refund_amount(250, Some(&untouched)).unwrap(), 250);
+        // Rounding never pays out more than was recorded.
+        let odd = SlashRecord {
+            escrow_balance: 1,
+            total_raised: 3,
+        };
+        assert_eq!(refund_amount(1, Some(&odd)).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_two_partial_releases_complete_milestone() {
+        let milestone_amount = 1_000u64;
+        let (first, done) = milestone_tranche(milestone_amount, 0, Some(400)).unwrap();
+        assert_eq!((first, done), (400, false));
+        let (second, done) = milestone_tranche(milestone_amount, first, Some(600)).unwrap();
+        assert_eq!((second, done), (600, true));
+
+        // None releases the rest; over-release and empty tranches are rejected.
+        assert_eq!(
+            milestone_tranche(milestone_amount, 400, None).unwrap(),
+            (600, true)
+        );
+        assert_eq!(
+            milestone_tranche(milestone_amount, 0, None).unwrap(),
+            (1_000, true)
+        );
+        assert!(milestone_tranche(milestone_amount, 400, Some(601)).is_err());
+        assert!(milestone_tranche(milestone_amount, 400, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_payout_split_producer_60_40() {
+        let artist = Pubkey::new_unique();
+        let producer = Pubkey::new_unique();
+        let entries = [
+            SplitEntry {
+                recipient: artist,
+                bps: 6_000,
+            },
+            SplitEntry {
+                recipient: producer,
+                bps: 4_000,
+            },
+        ];
+        validate_payout_split(&entries).unwrap();
+        assert_eq!(
+            split_amounts(1_000_000, &entries).unwrap(),
+            vec![600_000, 400_000]
+        );
+        // Dust from rounding goes to the last recipient.
+        assert_eq!(split_amounts(1_001, &entries).unwrap(), vec![600, 401]);
+    }
+
+    #[test]
+    fn test_payout_split_bps_sum_validation() {
+        let entry = |bps| SplitEntry {
+            recipient: Pubkey::new_unique(),
+            bps,
+        };
+        assert!(validate_payout_split(&[entry(6_000), entry(3_999)]).is_err());
+        assert!(validate_payout_split(&[entry(6_000), entry(4_001)]).is_err());
+        assert!(validate_payout_split(&[entry(10_000), entry(0)]).is_err());
+        assert!(validate_payout_split(&[]).is_err());
+        let too_many: Vec<SplitEntry> = (0..MAX_SPLIT_RECIPIENTS + 1).map(|_| entry(1)).collect();
+        assert!(validate_payout_split(&too_many).is_err());
+        let dup = entry(5_000);
+        assert!(validate_payout_split(&[dup, dup]).is_err());
+        assert!(validate_payout_split(&[entry(10_000)]).is_ok());
+    }
+
+    #[test]
+    fn test_collaborators_validation() {
+        let entry = |role, bps| Collaborator {
+            wallet: Pubkey::new_unique(),
+            role,
+            bps,
+        };
+        let artist = entry(CollaboratorRole::Artist, 7_000);
+        let producer = entry(CollaboratorRole::Producer, 3_000);
+        validate_collaborators(&[artist, producer]).unwrap();
+        assert!(
+            validate_collaborators(&[artist, entry(CollaboratorRole::Producer, 2_999)]).is_err()
+        );
+        assert!(validate_collaborators(&[artist, entry(CollaboratorRole::Other, 3_001)]).is_err());
+        assert!(validate_collaborators(&[
+            entry(CollaboratorRole::Artist, 10_000),
+            entry(CollaboratorRole::Other, 0)
+        ])
+        .is_err());
+        assert!(validate_collaborators(&[]).is_err());
+        let too_many: Vec<Collaborator> = (0..MAX_COLLABORATORS + 1)
+            .map(|_| entry(CollaboratorRole::Featured, 1))
+            .collect();
+        assert!(validate_collaborators(&too_many).is_err());
+        // The same wallet cannot appear twice, even under different roles.
+        let same_wallet = Collaborator {
+            role: CollaboratorRole::Songwriter,
+            ..artist
+        };
+        assert!(validate_collaborators(&[
+            Collaborator {
+                bps: 5_000,
+                ..artist
+            },
+            Collaborator {
+                bps: 5_000,
+                ..same_wallet
+            }
+        ])
+        .is_err());
+
+        let registry = Collaborators {
+            project: Pubkey::new_unique(),
+            entries: vec![artist, producer],
+        };
+        let split = registry.split_entries();
+        validate_payout_split(&split).unwrap();
+        assert_eq!(split_amounts(1_000, &split).unwrap(), vec![700, 300]);
+    }
+
+    #[test]
+    fn test_min_funding_boundary() {
+        assert!(!meets_min_funding(999, 1_000, 10_000));
+        assert!(meets_min_funding(1_000, 1_000, 10_000));
+        assert!(meets_min_funding(1_001, 1_000, 10_000));
+        // Default of 0 accepts any amount, including dust.
+        assert!(meets_min_funding(1, 0, 10_000));
+        assert!(meets_min_funding(0, 0, 10_000));
+        // Once less than the minimum remains, a smaller final top-up can complete the goal.
+        assert!(!meets_min_funding(400, 1_000, 1_000));
+        assert!(meets_min_funding(400, 1_000, 999));
+        assert!(meets_min_funding(1, 1_000, 0));
+    }
+
+    #[test]
+    fn test_pending_settlement_minimums() {
+        assert!(check_pending_settlement(500, 500, 0, 10_000).is_ok());
+        // Below what the backer agreed to accept for their SOL.
+        assert!(check_pending_settlement(499, 500, 0, 10_000).is_err());
+        // A settlement must credit something even when the backer set no minimum.
+        assert!(check_pending_settlement(0, 0, 0, 10_000).is_err());
+        // The project's per-funding minimum still applies, except for a final top-up.
+        assert!(check_pending_settlement(500, 0, 1_000, 10_000).is_err());
+        assert!(check_pending_settlement(1_000, 0, 1_000, 10_000).is_ok());
+        assert!(check_pending_settlement(500, 0, 1_000, 500).is_ok());
+    }
+
+    #[test]
+    fn test_backer_cap_repeated_fundings() {
+        let cap = 1_000u64;
+        let mut total = 0u64;
+        for _ in 0..4 {
+            total = backer_total_within_cap(total, 250, cap).unwrap();
+        }
+        assert_eq!(total, cap);
+        assert_eq!(
+            backer_total_within_cap(total, 1, cap),
+            Err(EscrowError::BackerCapExceeded.into())
+        );
+        assert_eq!(
+            backer_total_within_cap(900, 101, cap),
+            Err(EscrowError::BackerCapExceeded.into())
+        );
+        // 0 = unlimited.
+        assert_eq!(
+            backer_total_within_cap(u64::MAX - 1, 1, 0).unwrap(),
+            u64::MAX
+        );
+        assert!(backer_total_within_cap(u64::MAX, 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_deadline_extension_cap() {
+        let deadline = 1_800_000_000i64;
+        let now = deadline - 100;
+        let day = 24 * 60 * 60;
+
+        // Within the cap, including up to exactly the cap.
+        assert_eq!(
+            deadline_extension(deadline, deadline + 10 * day, now, 0).unwrap(),
+            10 * day
+        );
+        assert_eq!(
+            deadline_extension(deadline, deadline + 20 * day, now, 10 * day).unwrap(),
+            MAX_DEADLINE_EXTENSION_SECS
+        );
+        // Beyond the cap, in one step or cumulatively.
+        assert!(deadline_extension(deadline, deadline + 31 * day, now, 0).is_err());
+        assert!(deadline_extension(deadline, deadline + 21 * day, now, 10 * day).is_err());
+        // Deadline already passed, or not actually later.
+        assert!(deadline_extension(deadline, deadline + day, deadline + 1, 0).is_err());
+        assert!(deadline_extension(deadline, deadline, now, 0).is_err());
+    }
+
+    #[test]
+    fn test_material_edit_refund_window_floor() {
+        let now = 1_800_000_000i64;
+        let min = DEFAULT_MIN_REFUND_WINDOW_SECS;
+        // Sub-minimum windows (including the 1-second window) are rejected.
+        assert!(material_edit_refund_window_end(now, 1, min).is_err());
+        assert!(material_edit_refund_window_end(now, min - 1, min).is_err());
+        // At or above the minimum the window ends refund_window_secs from now.
+        assert_eq!(
+            material_edit_refund_window_end(now, min, min).unwrap(),
+            now + min
+        );
+        assert_eq!(
+            material_edit_refund_window_end(now, 2 * min, min).unwrap(),
+            now + 2 * min
+        );
+        assert!(material_edit_refund_window_end(i64::MAX, min, min).is_err());
+    }
+
+    #[test]
+    fn test_goal_raise_cap() {
+        // Within the multiple, including raising in steps up to exactly the cap.
+        assert!(check_goal_raise(1_000, 1_000, 1_500, DEFAULT_MAX_GOAL_MULTIPLE_BPS).is_ok());
+        assert!(check_goal_raise(1_500, 1_000, 2_000, DEFAULT_MAX_GOAL_MULTIPLE_BPS).is_ok());
+        // Beyond the multiple of the original goal, even when the current goal is already raised.
+        assert!(check_goal_raise(1_000, 1_000, 2_001, DEFAULT_MAX_GOAL_MULTIPLE_BPS).is_err());
+        assert!(check_goal_raise(2_000, 1_000, 2_001, DEFAULT_MAX_GOAL_MULTIPLE_BPS).is_err());
+        assert!(check_goal_raise(1_000, 1_000, 1_100, 10_000).is_err());
+        // Not an increase.
+        assert!(check_goal_raise(1_000, 1_000, 1_000, DEFAULT_MAX_GOAL_MULTIPLE_BPS).is_err());
+        assert!(check_goal_raise(1_500, 1_000, 1_200, DEFAULT_MAX_GOAL_MULTIPLE_BPS).is_err());
+        // No overflow at the top of the range.
+        assert!(check_goal_raise(u64::MAX - 1, u64::MAX, u64::MAX, u16::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_fail_project_conditions() {
+        let deadline = 1_800_000_000i64;
+        // Deadline passed under goal: can fail.
+        assert!(check_can_fail_project(
+            ProjectStatus::Active,
+            deadline,
+            999,
+            1_000,
+            false,
+            deadline
+        )
+        .is_ok());
+        // Deadline passed at goal: rejected.
+        assert!(check_can_fail_project(
+            ProjectStatus::Active,
+            deadline,
+            1_000,
+            1_000,
+            false,
+            deadline + 1
+        )
+        .is_err());
+        // Before deadline, or not Active: rejected.
+        assert!(check_can_fail_project(
+            ProjectStatus::Active,
+            deadline,
+            0,
+            1_000,
+            false,
+            deadline - 1
+        )
+        .is_err());
+        assert!(check_can_fail_project(
+            ProjectStatus::Completed,
+            deadline,
+            0,
+            1_000,
+            false,
+            deadline
+        )
+        .is_err());
+        assert!(check_can_fail_project(
+            ProjectStatus::Cancelled,
+            deadline,
+            0,
+            1_000,
+            false,
+            deadline
+        )
+        .is_err());
+        // Under goal after the deadline, but a milestone (or tranche) was already paid out: rejected, since
+        // full-amount refunds would drain the escrow before every backer is repaid.
+        assert!(check_can_fail_project(
+            ProjectStatus::Active,
+            deadline,
+            999,
+            1_000,
+            true,
+            deadline
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_escrow_dust_after_uneven_milestones() {
+        // 33/33/34 of an amount not divisible by 100: each floored tranche leaves a remainder in escrow.
+        let mut project = Project {
+            artist: Pubkey::new_unique(),
+            name: "dust".to_string(),
+            goal: 1_000_000,
+            milestone_percentages: [33, 33, 34, 0, 0],
+            deadline: 1_800_000_000,
+            status: ProjectStatus::Active,
+            taste_mint: Pubkey::new_unique(),
+            total_raised: 1_000_003,
+            backer_count: 1,
+            current_milestone: 0,
+            version: PROJECT_VERSION,
+            deadline_extension_secs: 0,
+            min_funding: 0,
+            max_per_backer: 0,
+            soft_cap: 0,
+            released_in_milestone: 0,
+            original_goal: 1_000_000,
+            fee_exempt: false,
+        };
+        let mut escrow = project.total_raised;
+        while project.current_milestone < 3 {
+            assert!(check_can_withdraw_dust(
+                ProjectStatus::Completed,
+                project.current_milestone,
+                &project.milestone_percentages,
+                escrow
+            )
+            .is_err());
+            escrow -= project.current_milestone_amount().unwrap();
+            project.current_milestone += 1;
+        }
+        // floor(1_000_003 * 33 / 100) * 2 + floor(1_000_003 * 34 / 100) = 1_000_001.
+        assert_eq!(escrow, 2);
+        assert!(check_can_withdraw_dust(
+            ProjectStatus::Completed,
+            project.current_milestone,
+            &project.milestone_percentages,
+            escrow
+        )
+        .is_ok());
+        assert!(check_can_withdraw_dust(
+            ProjectStatus::Active,
+            project.current_milestone,
+            &project.milestone_percentages,
+            escrow
+        )
+        .is_err());
+        // Once swept, the escrow can be closed and there is nothing left to withdraw.
+        assert!(check_can_close_project(ProjectStatus::Completed, escrow).is_err());
+        assert!(check_can_withdraw_dust(
+            ProjectStatus::Completed,
+            project.current_milestone,
+            &project.milestone_percentages,
+            0
+        )
+        .is_err());
+        assert!(check_can_close_project(ProjectStatus::Completed, 0).is_ok());
+    }
+
+    #[test]
+    fn test_close_project_requires_completed_and_empty_escrow() {
+        assert!(check_can_close_project(ProjectStatus::Completed, 0).is_ok());
+        assert!(check_can_close_project(ProjectStatus::Completed, 1).is_err());
+        assert!(check_can_close_project(ProjectStatus::Active, 0).is_err());
+        assert!(check_can_close_project(ProjectStatus::Cancelled, 0).is_err());
+    }
+
+    #[test]
+    fn test_project_stats() {
+        let project = Project {
+            artist: Pubkey::new_unique(),
+            name: "stats".to_string(),
+            goal: 1_000,
+            milestone_percentages: [40, 60, 0, 0, 0],
+            deadline: 1_800_000_000,
+            status: ProjectStatus::Active,
+            taste_mint: Pubkey::new_unique(),
+            total_raised: 750,
+            backer_count: 3,
+            current_milestone: 1,
+            version: PROJECT_VERSION,
+            deadline_extension_secs: 0,
+            min_funding: 0,
+            max_per_backer: 0,
+            soft_cap: 0,
+            released_in_milestone: 0,
+            original_goal: 1_000,
+            fee_exempt: false,
+        };
+        assert_eq!(
+            project.stats(),
+            ProjectStats {
+                total_raised: 750,
+                goal: 1_000,
+                backer_count: 3,
+                current_milestone: 1,
+                effective_milestone_count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_soft_cap_threshold() {
+        let deadline = 1_800_000_000i64;
+        let project = Project {
+            artist: Pubkey::new_unique(),
+            name: "soft".to_string(),
+            goal: 1_000,
+            milestone_percentages: [100, 0, 0, 0, 0],
+            deadline,
+            status: ProjectStatus::Active,
+            taste_mint: Pubkey::new_unique(),
+            total_raised: 0,
+            backer_count: 0,
+            current_milestone: 0,
+            version: PROJECT_VERSION,
+            deadline_extension_secs: 0,
+            min_funding: 0,
+            max_per_backer: 0,
+            soft_cap: 600,
+            released_in_milestone: 0,
+            original_goal: 1_000,
+            fee_exempt: false,
+        };
+        assert_eq!(project.funding_threshold(), 600);
+        // Reached the soft cap but not the hard cap: the project proceeds.
+        assert!(check_can_fail_project(
+            ProjectStatus::Active,
+            deadline,
+            700,
+            project.funding_threshold(),
+            project.has_released(),
+            deadline
+        )
+        .is_err());
+        // Under the soft cap after the deadline: refundable via fail_project.
+        assert!(check_can_fail_project(
+            ProjectStatus::Active,
+            deadline,
+            599,
+            project.funding_threshold(),
+            project.has_released(),
+            deadline
+        )
+        .is_ok());
+    }
+
     #[test]
     fn test_milestone_percentages_sum() {
         let valid: [u16; MAX_MILESTONES] = [20, 20, 20, 20, 20];
         assert_eq!(valid.iter().sum::<u16>(), 100);
         let invalid: [u16; MAX_MILESTONES] = [25, 25, 25, 24, 0];
         assert_ne!(invalid.iter().sum::<u16>(), 100);
+        assert!(validate_milestone_percentages(&valid).is_ok());
+        assert!(validate_milestone_percentages(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_milestone_zeros_only_trailing() {
+        assert!(validate_milestone_percentages(&[50, 50, 0, 0, 0]).is_ok());
+        assert!(validate_milestone_percentages(&[100, 0, 0, 0, 0]).is_ok());
+        assert!(validate_milestone_percentages(&[50, 0, 50, 0, 0]).is_err());
+        assert!(validate_milestone_percentages(&[0, 50, 0, 50, 0]).is_err());
+        assert!(validate_milestone_percentages(&[0, 0, 0, 0, 100]).is_err());
+        assert!(validate_milestone_percentages(&[0, 0, 0, 0, 0]).is_err());
+        assert!(validate_milestone_percentages(&[u16::MAX, 101, 0, 0, 0]).is_err());
     }
 
     #[test]
@@ -1,5 +1,5 @@
 //! TasteMaker RWA revenue distribution.
-//! Artist deposits TASTE; holders claim proportional share based on RWA token balance.
+//! Artist deposits TASTE (or another quote token, one per epoch); holders claim proportional share based on RWA token balance.
 
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
@@ -12,6 +12,190 @@ declare_id!("dWGsAdXVDbU7oEoZPKu5rbCBDnSLvaoCjLAXBVr7i7Q");
 #[cfg(feature = "devnet")]
 declare_id!("C7qE7zNk7YA9rLhqRejFpMPH9y2Ds8rYZs2WEyhxUUWK");
 
+/// Current DistributionEpoch account size (including label, period, holder snapshot fields and quote mint).
+pub const DISTRIBUTION_EPOCH_SPACE: usize =
+    8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 32 + 8 + 8 + 8 + 8 + 32;
+/// Size of epochs created before label/period were added; grown by migrate_epoch.
+const DISTRIBUTION_EPOCH_BASE_LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8;
+/// Current RevenueConfig account size (including the deposit fee).
+pub const REVENUE_CONFIG_SPACE: usize = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 2 + 32;
+/// Size of configs created before the deposit fee was added; grown by migrate_rev_config.
+const REVENUE_CONFIG_BASE_LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 8;
+/// Upper bound for the platform fee on revenue deposits (10%).
+pub const MAX_DEPOSIT_FEE_BPS: u16 = 1_000;
+/// HolderClaim account size. Claims made before partial claims existed are shorter (no `remaining`) and no
+/// longer load; they were always full claims, so claim_revenue still rejects a second claim.
+pub const HOLDER_CLAIM_SPACE: usize = 8 + 1 + 8 + 8;
+/// HolderSnapshot account size.
+pub const HOLDER_SNAPSHOT_SPACE: usize = 8 + 8;
+/// HolderLock: discriminator + epoch + holder + amount.
+pub const HOLDER_LOCK_SPACE: usize = 8 + 32 + 32 + 8;
+/// StreamPool account size.
+pub const STREAM_POOL_SPACE: usize = 8 + 32 + 16 + 8 + 8 + 8;
+/// StreamPosition account size.
+pub const STREAM_POSITION_SPACE: usize = 8 + 8 + 16 + 8;
+/// Fixed-point scale of StreamPool.acc_per_share.
+pub const STREAM_PRECISION: u128 = 1_000_000_000_000;
+
+/// Upgradeable loader: Program variant.
+const UPGRADEABLE_LOADER_PROGRAM_STATE: u8 = 2;
+/// Upgradeable loader: ProgramData variant.
+const UPGRADEABLE_LOADER_PROGRAM_DATA_STATE: u8 = 3;
+const MIN_PROGRAM_ACCOUNT_LEN: usize = 36;
+const MIN_PROGRAMDATA_METADATA_LEN: usize = 45;
+
+fn require_upgrade_authority(
+    program_id: &Pubkey,
+    program_account_key: &Pubkey,
+    program_account_data: &[u8],
+    program_data_account_key: &Pubkey,
+    program_data_account_data: &[u8],
+    authority_key: &Pubkey,
+) -> Result<()> {
+    require!(
+        program_account_key == program_id,
+        RevError::NotUpgradeAuthority
+    );
+    require!(
+        program_account_data.len() >= MIN_PROGRAM_ACCOUNT_LEN
+            && u32::from_le_bytes(program_account_data[0..4].try_into().unwrap())
+                == UPGRADEABLE_LOADER_PROGRAM_STATE as u32,
+        RevError::NotUpgradeAuthority
+    );
+    let programdata_address =
+        Pubkey::new_from_array(program_account_data[4..36].try_into().unwrap());
+    require!(
+        program_data_account_key == &programdata_address,
+        RevError::NotUpgradeAuthority
+    );
+    require!(
+        program_data_account_data.len() >= MIN_PROGRAMDATA_METADATA_LEN
+            && u32::from_le_bytes(program_data_account_data[0..4].try_into().unwrap())
+                == UPGRADEABLE_LOADER_PROGRAM_DATA_STATE as u32,
+        RevError::NotUpgradeAuthority
+    );
+    let option_byte = program_data_account_data[12];
+    require!(option_byte == 1, RevError::NotUpgradeAuthority);
+    let upgrade_authority =
+        Pubkey::new_from_array(program_data_account_data[13..45].try_into().unwrap());
+    require!(
+        upgrade_authority == *authority_key,
+        RevError::NotUpgradeAuthority
+    );
+    Ok(())
+}
+
+/// Fails when the optional taste_token ProtocolState passed in says the protocol is paused.
+fn require_not_paused(protocol_state: Option<&AccountInfo>) -> Result<()> {
+    if let Some(info) = protocol_state {
+        require!(
+            !taste_token::protocol_paused(info)?,
+            RevError::ProtocolPaused
+        );
+    }
+    Ok(())
+}
+
+/// Splits a revenue deposit into (platform fee, holder amount), rounding the fee down.
+pub fn deposit_split(amount: u64, deposit_fee_bps: u16) -> Result<(u64, u64)> {
+    let fee = (amount as u128)
+        .checked_mul(deposit_fee_bps as u128)
+        .ok_or(RevError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(RevError::Overflow)? as u64;
+    let holder_amount = amount.checked_sub(fee).ok_or(RevError::Overflow)?;
+    Ok((fee, holder_amount))
+}
+
+/// A holder's pro-rata share of an epoch: holder_balance / total_rwa_supply of the epoch amount.
+pub fn holder_revenue_share(
+    holder_balance: u64,
+    epoch_amount: u64,
+    total_rwa_supply: u64,
+) -> Result<u64> {
+    Ok((holder_balance as u128)
+        .checked_mul(epoch_amount as u128)
+        .ok_or(RevError::Overflow)?
+        .checked_div(total_rwa_supply as u128)
+        .ok_or(RevError::Overflow)? as u64)
+}
+
+/// Amount paid out by one claim_revenue call: `requested` (when given) capped at the holder's unclaimed
+/// entitlement, otherwise the whole entitlement.
+pub fn claim_amount(entitlement: u64, requested: Option<u64>) -> Result<u64> {
+    match requested {
+        Some(amount) => {
+            require!(amount > 0, RevError::InvalidAmount);
+            require!(amount <= entitlement, RevError::ClaimExceedsShare);
+            Ok(amount)
+        }
+        None => Ok(entitlement),
+    }
+}
+
+/// Vault holding revenue paid in `quote_mint`: the [b"rev_vault", project] authority's associated token account
+/// for the config's $TASTE mint (the original vault), or the token account PDA [b"rev_vault", project, quote_mint]
+/// (created by init_quote_vault) for any other mint.
+pub fn revenue_vault_address(
+    project: &Pubkey,
+    quote_mint: &Pubkey,
+    taste_mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    if quote_mint == taste_mint {
+        let (authority, _) =
+            Pubkey::find_program_address(&[b"rev_vault", project.as_ref()], &crate::ID);
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &authority,
+            quote_mint,
+            token_program,
+        )
+    } else {
+        Pubkey::find_program_address(
+            &[b"rev_vault", project.as_ref(), quote_mint.as_ref()],
+            &crate::ID,
+        )
+        .0
+    }
+}
+
+/// Revenue accrued to `staked` RWA at `acc_per_share`, scaled down by STREAM_PRECISION. A position's pending
+/// reward is this minus its reward_debt.
+pub fn accrued_reward(staked: u64, acc_per_share: u128) -> Result<u128> {
+    Ok((staked as u128)
+        .checked_mul(acc_per_share)
+        .ok_or(RevError::Overflow)?
+        / STREAM_PRECISION)
+}
+
+/// Transfer RWA (a transfer-hook mint) via Token-2022, resolving the hook's extra accounts from `hook_accounts`
+/// (the hook program and its extra-account-metas PDA, passed in remaining_accounts).
+#[allow(clippy::too_many_arguments)]
+fn transfer_rwa<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from.clone(),
+        mint.clone(),
+        to.clone(),
+        authority.clone(),
+        hook_accounts,
+        amount,
+        decimals,
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
 #[program]
 pub mod revenue_distribution {
     use super::*;
@@ -30,14 +214,53 @@ pub mod revenue_distribution {
         config.artist_authority = ctx.accounts.project.artist;
         config.total_distributed = 0;
         config.epoch_count = 0;
+        config.deposit_fee_bps = 0;
+        config.fee_treasury = Pubkey::default();
 
         msg!("Revenue config initialized for project {}", config.project);
         Ok(())
     }
 
-    /// Artist deposits TASTE into the revenue vault. Creates a new distribution epoch.
-    pub fn deposit_revenue(ctx: Context<DepositRevenue>, amount: u64) -> Result<()> {
+    /// Create the vault for revenue paid in a token other than $TASTE (e.g. USDC), owned by the
+    /// [b"rev_vault", project] authority. Permissionless; payer covers rent. $TASTE keeps its original vault.
+    pub fn init_quote_vault(ctx: Context<InitQuoteVault>) -> Result<()> {
+        require!(
+            ctx.accounts.quote_mint.key() != ctx.accounts.rev_config.taste_mint,
+            RevError::QuoteMintMismatch
+        );
+        msg!(
+            "Quote vault {} created for mint {} project {}",
+            ctx.accounts.quote_vault.key(),
+            ctx.accounts.quote_mint.key(),
+            ctx.accounts.rev_config.project
+        );
+        Ok(())
+    }
+
+    /// Artist deposits revenue into the vault for `quote_mint` ($TASTE or another token whose vault exists, see
+    /// init_quote_vault). Creates a new distribution epoch paid out in that mint.
+    /// `label` (UTF-8, zero-padded) and `period_start`/`period_end` (unix seconds, 0 = unset) describe what the revenue covers.
+    /// A non-zero `registration_window_secs` makes it a snapshot epoch: holders record their RWA balance with
+    /// register_holder until the window ends, and claims (open once it has ended) pay from those balances.
+    /// 0 keeps claims on live balances.
+    pub fn deposit_revenue(
+        ctx: Context<DepositRevenue>,
+        amount: u64,
+        label: [u8; 32],
+        period_start: i64,
+        period_end: i64,
+        registration_window_secs: i64,
+    ) -> Result<()> {
+        require_not_paused(ctx.accounts.protocol_state.as_deref())?;
         require!(amount > 0, RevError::InvalidAmount);
+        require!(
+            period_start >= 0 && period_end >= period_start,
+            RevError::InvalidPeriod
+        );
+        require!(
+            registration_window_secs >= 0,
+            RevError::InvalidRegistrationWindow
+        );
         require!(
             ctx.accounts.artist_authority.key() == ctx.accounts.rev_config.artist_authority,
             RevError::NotArtist
@@ -48,6 +271,7 @@ pub mod revenue_distribution {
         );
 
         let config = &mut ctx.accounts.rev_config;
+        let (fee, holder_amount) = deposit_split(amount, config.deposit_fee_bps)?;
         let epoch_index = config.epoch_count;
         config.epoch_count = epoch_index.checked_add(1).ok_or(RevError::Overflow)?;
 
@@ -55,71 +279,230 @@ pub mod revenue_distribution {
         let epoch = &mut ctx.accounts.distribution_epoch;
         epoch.project = config.project;
         epoch.epoch_index = epoch_index;
-        epoch.amount = amount;
+        epoch.amount = holder_amount;
         epoch.total_rwa_supply = ctx.accounts.rwa_state.minted;
         epoch.claimed_count = 0;
         epoch.total_claimed = 0;
         epoch.created_at = clock.unix_timestamp;
+        epoch.label = label;
+        epoch.period_start = period_start;
+        epoch.period_end = period_end;
+        epoch.registration_ends_at = if registration_window_secs > 0 {
+            clock
+                .unix_timestamp
+                .checked_add(registration_window_secs)
+                .ok_or(RevError::Overflow)?
+        } else {
+            0
+        };
+        epoch.snapshot_total = 0;
+        epoch.quote_mint = ctx.accounts.quote_mint.key();
 
         anchor_spl::token_interface::transfer_checked(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
                 TransferChecked {
                     from: ctx.accounts.artist_source.to_account_info(),
-                    mint: ctx.accounts.taste_mint.to_account_info(),
+                    mint: ctx.accounts.quote_mint.to_account_info(),
                     to: ctx.accounts.rev_vault.to_account_info(),
                     authority: ctx.accounts.artist_authority.to_account_info(),
                 },
             ),
-            amount,
-            ctx.accounts.taste_mint.decimals,
+            holder_amount,
+            ctx.accounts.quote_mint.decimals,
         )?;
 
+        if fee > 0 {
+            let fee_account = ctx
+                .accounts
+                .fee_treasury_account
+                .as_ref()
+                .ok_or(RevError::MissingFeeTreasury)?;
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.artist_source.to_account_info(),
+                        mint: ctx.accounts.quote_mint.to_account_info(),
+                        to: fee_account.to_account_info(),
+                        authority: ctx.accounts.artist_authority.to_account_info(),
+                    },
+                ),
+                fee,
+                ctx.accounts.quote_mint.decimals,
+            )?;
+        }
+
         config.total_distributed = config
             .total_distributed
-            .checked_add(amount)
+            .checked_add(amount - fee)
             .ok_or(RevError::Overflow)?;
 
         msg!(
-            "Deposited {} TASTE for project {} epoch {}",
+            "Deposited {} of mint {} for project {} epoch {} ({} fee)",
             amount,
+            epoch.quote_mint,
             config.project,
-            epoch_index
+            epoch_index,
+            fee
         );
         Ok(())
     }
 
-    /// Holder claims their proportional share for a specific epoch.
-    pub fn claim_revenue(ctx: Context<ClaimRevenue>) -> Result<()> {
-        let config = &ctx.accounts.rev_config;
+    /// Holder records their current RWA balance for a snapshot epoch, once, while its registration window is open.
+    /// Claims for the epoch then pay balance / snapshot_total of it, whatever the holder's balance is later. The
+    /// balance moves into the [b"holder_lock_vault", project] vault (recorded in a HolderLock) so the same tokens
+    /// cannot be registered again from another wallet; withdraw_registered_rwa returns them once the window has
+    /// closed. remaining_accounts: the transfer hook's extra accounts (its extra-account-metas PDA and the hook
+    /// program).
+    pub fn register_holder<'info>(
+        ctx: Context<'_, '_, '_, 'info, RegisterHolder<'info>>,
+    ) -> Result<()> {
         let epoch = &mut ctx.accounts.distribution_epoch;
+        require!(
+            epoch.project == ctx.accounts.rev_config.project,
+            RevError::EpochMismatch
+        );
+        require!(epoch.uses_snapshot(), RevError::SnapshotNotEnabled);
+        require!(
+            epoch.registration_open(Clock::get()?.unix_timestamp),
+            RevError::RegistrationClosed
+        );
+        let balance = ctx.accounts.holder_rwa_account.amount;
+        require!(balance > 0, RevError::NoRwaBalance);
+
+        transfer_rwa(
+            &ctx.accounts.rwa_token_program.to_account_info(),
+            &ctx.accounts.holder_rwa_account.to_account_info(),
+            &ctx.accounts.rwa_mint.to_account_info(),
+            &ctx.accounts.holder_lock_vault.to_account_info(),
+            &ctx.accounts.holder.to_account_info(),
+            ctx.remaining_accounts,
+            balance,
+            ctx.accounts.rwa_mint.decimals,
+            &[],
+        )?;
+        let lock = &mut ctx.accounts.holder_lock;
+        lock.epoch = epoch.key();
+        lock.holder = ctx.accounts.holder.key();
+        lock.amount = balance;
+
+        ctx.accounts.holder_snapshot.balance = balance;
+        epoch.snapshot_total = epoch
+            .snapshot_total
+            .checked_add(balance)
+            .ok_or(RevError::Overflow)?;
 
-        require!(epoch.project == config.project, RevError::EpochMismatch);
-        require!(epoch.total_rwa_supply > 0, RevError::ZeroSupply);
+        msg!(
+            "Registered {} RWA for holder {} epoch {} (snapshot total {})",
+            balance,
+            ctx.accounts.holder.key(),
+            epoch.epoch_index,
+            epoch.snapshot_total
+        );
+        Ok(())
+    }
 
-        let holder_balance = ctx.accounts.holder_rwa_account.amount;
-        require!(holder_balance > 0, RevError::NoRwaBalance);
+    /// Returns the RWA register_holder locked for the holder and closes the HolderLock (rent back to them). Allowed
+    /// once the epoch's registration window has closed, or the epoch itself has been closed; the snapshot and any
+    /// claim are unaffected. remaining_accounts: the transfer hook's extra accounts, as for register_holder.
+    pub fn withdraw_registered_rwa<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawRegisteredRwa<'info>>,
+    ) -> Result<()> {
+        let epoch_info = ctx.accounts.distribution_epoch.to_account_info();
+        if !epoch_info.data_is_empty() {
+            require!(epoch_info.owner == &crate::ID, RevError::EpochMismatch);
+            let data = epoch_info.try_borrow_data()?;
+            let epoch = DistributionEpoch::try_deserialize(&mut &data[..])?;
+            require!(
+                !epoch.registration_open(Clock::get()?.unix_timestamp),
+                RevError::RegisteredRwaLocked
+            );
+        }
+        let amount = ctx.accounts.holder_lock.amount;
+        let project = ctx.accounts.rev_config.project;
+        let seeds: &[&[u8]] = &[
+            b"holder_lock_vault",
+            project.as_ref(),
+            &[ctx.bumps.holder_lock_vault_authority],
+        ];
+        transfer_rwa(
+            &ctx.accounts.rwa_token_program.to_account_info(),
+            &ctx.accounts.holder_lock_vault.to_account_info(),
+            &ctx.accounts.rwa_mint.to_account_info(),
+            &ctx.accounts.holder_rwa_account.to_account_info(),
+            &ctx.accounts.holder_lock_vault_authority.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.rwa_mint.decimals,
+            &[seeds],
+        )?;
+        msg!(
+            "Registered RWA withdrawn: epoch {} holder {} amount {}",
+            ctx.accounts.holder_lock.epoch,
+            ctx.accounts.holder.key(),
+            amount
+        );
+        Ok(())
+    }
 
-        let computed = (holder_balance as u128)
-            .checked_mul(epoch.amount as u128)
-            .ok_or(RevError::Overflow)?
-            .checked_div(epoch.total_rwa_supply as u128)
-            .ok_or(RevError::Overflow)? as u64;
-        let remaining = epoch.amount.saturating_sub(epoch.total_claimed);
-        let share = computed.min(remaining);
+    /// Holder claims their pro-rata share of an epoch. With `amount`, only that much is paid now and the rest
+    /// stays claimable in HolderClaim.remaining for a follow-up call while the epoch is open. The entitlement is
+    /// fixed at the first claim: by the registered HolderSnapshot for snapshot epochs (after the registration
+    /// window), otherwise by the holder's live RWA balance. Returns the amount paid (as return data) so a client
+    /// or calling program can chain it, e.g. into an OTC buy offer funded with the claimed revenue.
+    pub fn claim_revenue(ctx: Context<ClaimRevenue>, amount: Option<u64>) -> Result<u64> {
+        let config = &ctx.accounts.rev_config;
+        let epoch = &mut ctx.accounts.distribution_epoch;
+        let holder_claim = &mut ctx.accounts.holder_claim;
 
-        require!(share > 0, RevError::ZeroShare);
+        require!(epoch.project == config.project, RevError::EpochMismatch);
+        require!(epoch.total_rwa_supply > 0, RevError::ZeroSupply);
+        require!(
+            ctx.accounts.quote_mint.key() == epoch.quote_mint_or(&config.taste_mint),
+            RevError::QuoteMintMismatch
+        );
 
-        if ctx.accounts.holder_claim.claimed {
+        if holder_claim.claimed {
             return Err(RevError::AlreadyClaimed.into());
         }
+        let first_claim = holder_claim.amount == 0;
+        let entitlement = if first_claim && epoch.uses_snapshot() {
+            require!(
+                !epoch.registration_open(Clock::get()?.unix_timestamp),
+                RevError::RegistrationOpen
+            );
+            let snapshot = ctx
+                .accounts
+                .holder_snapshot
+                .as_ref()
+                .ok_or(RevError::NotRegistered)?;
+            holder_revenue_share(snapshot.balance, epoch.amount, epoch.snapshot_total)?
+        } else if first_claim {
+            let holder_balance = ctx.accounts.holder_rwa_account.amount;
+            require!(holder_balance > 0, RevError::NoRwaBalance);
+            holder_revenue_share(holder_balance, epoch.amount, epoch.total_rwa_supply)?
+        } else {
+            holder_claim.remaining
+        };
+        let remaining = epoch.amount.saturating_sub(epoch.total_claimed);
+        let entitlement = entitlement.min(remaining);
 
-        ctx.accounts.holder_claim.claimed = true;
-        ctx.accounts.holder_claim.amount = share;
-        epoch.claimed_count = epoch
-            .claimed_count
-            .checked_add(1)
+        require!(entitlement > 0, RevError::ZeroShare);
+        let share = claim_amount(entitlement, amount)?;
+
+        holder_claim.amount = holder_claim
+            .amount
+            .checked_add(share)
             .ok_or(RevError::Overflow)?;
+        holder_claim.remaining = entitlement - share;
+        holder_claim.claimed = holder_claim.remaining == 0;
+        if first_claim {
+            epoch.claimed_count = epoch
+                .claimed_count
+                .checked_add(1)
+                .ok_or(RevError::Overflow)?;
+        }
         epoch.total_claimed = epoch
             .total_claimed
             .checked_add(share)
@@ -134,21 +517,334 @@ pub mod revenue_distribution {
                 ctx.accounts.token_program.to_account_info(),
                 TransferChecked {
                     from: ctx.accounts.rev_vault.to_account_info(),
-                    mint: ctx.accounts.taste_mint.to_account_info(),
+                    mint: ctx.accounts.quote_mint.to_account_info(),
                     to: ctx.accounts.holder_dest.to_account_info(),
                     authority: ctx.accounts.rev_vault_authority.to_account_info(),
                 },
                 &[seeds],
             ),
             share,
-            ctx.accounts.taste_mint.decimals,
+            ctx.accounts.quote_mint.decimals,
         )?;
 
         msg!(
-            "Claimed {} TASTE for holder {} epoch {}",
+            "Claimed {} of mint {} for holder {} epoch {} ({} remaining)",
             share,
+            ctx.accounts.quote_mint.key(),
+            ctx.accounts.holder.key(),
+            epoch.epoch_index,
+            ctx.accounts.holder_claim.remaining
+        );
+        Ok(share)
+    }
+
+    /// Set the platform fee taken from each revenue deposit (at most MAX_DEPOSIT_FEE_BPS; 0 = none) and the wallet
+    /// whose token account for the deposit's mint receives it. Only the program upgrade authority.
+    pub fn set_deposit_fee(
+        ctx: Context<SetDepositFee>,
+        deposit_fee_bps: u16,
+        fee_treasury: Pubkey,
+    ) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+        require!(
+            deposit_fee_bps <= MAX_DEPOSIT_FEE_BPS,
+            RevError::InvalidDepositFeeBps
+        );
+
+        let config = &mut ctx.accounts.rev_config;
+        config.deposit_fee_bps = deposit_fee_bps;
+        config.fee_treasury = fee_treasury;
+        msg!(
+            "Deposit fee set to {} bps (treasury {}) for project {}",
+            deposit_fee_bps,
+            fee_treasury,
+            config.project
+        );
+        Ok(())
+    }
+
+    /// Artist retires `amount` RWA tokens held by the project's buyback authority (RWA the artist bought back and
+    /// sent to its [b"buyback", project] PDA), burning them via rwa_token::retire_rwa_tokens so total_supply and minted shrink and later
+    /// epochs pay the remaining holders more. Requires the RWA distribution to be closed.
+    pub fn execute_buyback(ctx: Context<ExecuteBuyback>, amount: u64) -> Result<()> {
+        require!(amount > 0, RevError::InvalidAmount);
+        let project = ctx.accounts.rev_config.project;
+        let seeds: &[&[u8]] = &[b"buyback", project.as_ref(), &[ctx.bumps.buyback_authority]];
+        let signer_seeds = &[seeds];
+        rwa_token::cpi::retire_rwa_tokens(
+            CpiContext::new_with_signer(
+                ctx.accounts.rwa_token_program.to_account_info(),
+                rwa_token::cpi::accounts::RetireRwaTokens {
+                    holder: ctx.accounts.buyback_authority.to_account_info(),
+                    rwa_state: ctx.accounts.rwa_state.to_account_info(),
+                    rwa_mint: ctx.accounts.rwa_mint.to_account_info(),
+                    holder_rwa_account: ctx.accounts.buyback_rwa_account.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        msg!(
+            "Bought back and retired {} RWA for project {}",
+            amount,
+            project
+        );
+        Ok(())
+    }
+
+    /// Grow a RevenueConfig created before the deposit fee existed to the current layout (fee off).
+    /// Permissionless; payer tops up rent.
+    pub fn migrate_rev_config(ctx: Context<MigrateRevConfig>) -> Result<()> {
+        let config_info = ctx.accounts.rev_config.to_account_info();
+        {
+            let data = config_info.try_borrow_data()?;
+            require!(
+                data.len() >= REVENUE_CONFIG_BASE_LEN && &data[..8] == RevenueConfig::DISCRIMINATOR,
+                RevError::InvalidRevConfigAccount
+            );
+        }
+        if config_info.data_len() >= REVENUE_CONFIG_SPACE {
+            return Ok(());
+        }
+        rwa_token::grow_account(
+            &config_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            REVENUE_CONFIG_SPACE,
+        )?;
+        msg!("Migrated revenue config {}", config_info.key());
+        Ok(())
+    }
+
+    /// Grow an epoch created before label/period or the snapshot fields existed to the current layout (new fields
+    /// zeroed, so it stays a live-balance epoch). Permissionless; payer tops up rent.
+    pub fn migrate_epoch(ctx: Context<MigrateEpoch>) -> Result<()> {
+        let epoch_info = ctx.accounts.distribution_epoch.to_account_info();
+        {
+            let data = epoch_info.try_borrow_data()?;
+            require!(
+                data.len() >= DISTRIBUTION_EPOCH_BASE_LEN
+                    && &data[..8] == DistributionEpoch::DISCRIMINATOR,
+                RevError::InvalidEpochAccount
+            );
+        }
+        if epoch_info.data_len() >= DISTRIBUTION_EPOCH_SPACE {
+            return Ok(());
+        }
+        rwa_token::grow_account(
+            &epoch_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            DISTRIBUTION_EPOCH_SPACE,
+        )?;
+        msg!("Migrated epoch {}", epoch_info.key());
+        Ok(())
+    }
+
+    /// Create the project's streaming revenue pool and its vaults (the pool PDA's $TASTE and RWA associated token
+    /// accounts). Permissionless; payer covers rent.
+    pub fn init_stream_pool(ctx: Context<InitStreamPool>) -> Result<()> {
+        let pool = &mut ctx.accounts.stream_pool;
+        pool.project = ctx.accounts.rev_config.project;
+        msg!("Stream pool created for project {}", pool.project);
+        Ok(())
+    }
+
+    /// Artist adds revenue to the streaming pool. No epoch account is created: the deposit is folded into
+    /// acc_per_share for the RWA staked right now, or held as undistributed until someone stakes.
+    pub fn deposit_streaming_revenue(
+        ctx: Context<DepositStreamingRevenue>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, RevError::InvalidAmount);
+        require!(
+            ctx.accounts.artist_authority.key() == ctx.accounts.rev_config.artist_authority,
+            RevError::NotArtist
+        );
+        let (fee, net) = deposit_split(amount, ctx.accounts.rev_config.deposit_fee_bps)?;
+
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.artist_source.to_account_info(),
+                    mint: ctx.accounts.taste_mint.to_account_info(),
+                    to: ctx.accounts.stream_vault.to_account_info(),
+                    authority: ctx.accounts.artist_authority.to_account_info(),
+                },
+            ),
+            net,
+            ctx.accounts.taste_mint.decimals,
+        )?;
+        if fee > 0 {
+            let fee_account = ctx
+                .accounts
+                .fee_treasury_account
+                .as_ref()
+                .ok_or(RevError::MissingFeeTreasury)?;
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.artist_source.to_account_info(),
+                        mint: ctx.accounts.taste_mint.to_account_info(),
+                        to: fee_account.to_account_info(),
+                        authority: ctx.accounts.artist_authority.to_account_info(),
+                    },
+                ),
+                fee,
+                ctx.accounts.taste_mint.decimals,
+            )?;
+        }
+
+        let pool = &mut ctx.accounts.stream_pool;
+        pool.deposit(net)?;
+        let config = &mut ctx.accounts.rev_config;
+        config.total_distributed = config
+            .total_distributed
+            .checked_add(net)
+            .ok_or(RevError::Overflow)?;
+
+        msg!(
+            "Streamed {} TASTE to project {} ({} staked)",
+            net,
+            pool.project,
+            pool.total_staked
+        );
+        Ok(())
+    }
+
+    /// Fold revenue deposited while nothing was staked into acc_per_share. Permissionless.
+    pub fn update_pool(ctx: Context<UpdatePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.stream_pool;
+        require!(pool.total_staked > 0, RevError::NothingStaked);
+        pool.update()?;
+        Ok(())
+    }
+
+    /// Holder stakes RWA into the pool vault; it earns from every later deposit. Pending revenue on an existing
+    /// stake is settled first. remaining_accounts: the transfer hook's extra accounts (its extra-account-metas PDA
+    /// and the hook program).
+    pub fn stake_streaming<'info>(
+        ctx: Context<'_, '_, '_, 'info, StakeStreaming<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, RevError::InvalidAmount);
+
+        transfer_rwa(
+            &ctx.accounts.rwa_token_program.to_account_info(),
+            &ctx.accounts.holder_rwa_account.to_account_info(),
+            &ctx.accounts.rwa_mint.to_account_info(),
+            &ctx.accounts.stake_vault.to_account_info(),
+            &ctx.accounts.holder.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.rwa_mint.decimals,
+            &[],
+        )?;
+
+        let pool = &mut ctx.accounts.stream_pool;
+        let position = &mut ctx.accounts.stream_position;
+        position.settle(pool.acc_per_share)?;
+        position.staked = position
+            .staked
+            .checked_add(amount)
+            .ok_or(RevError::Overflow)?;
+        pool.total_staked = pool
+            .total_staked
+            .checked_add(amount)
+            .ok_or(RevError::Overflow)?;
+        position.reward_debt = accrued_reward(position.staked, pool.acc_per_share)?;
+
+        msg!(
+            "Holder {} staked {} RWA ({} total)",
+            ctx.accounts.holder.key(),
+            amount,
+            position.staked
+        );
+        Ok(())
+    }
+
+    /// Holder withdraws staked RWA; revenue earned so far stays claimable. remaining_accounts as for
+    /// stake_streaming.
+    pub fn unstake_streaming<'info>(
+        ctx: Context<'_, '_, '_, 'info, StakeStreaming<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, RevError::InvalidAmount);
+        let pool = &mut ctx.accounts.stream_pool;
+        let position = &mut ctx.accounts.stream_position;
+        require!(amount <= position.staked, RevError::InsufficientStake);
+
+        position.settle(pool.acc_per_share)?;
+        position.staked -= amount;
+        position.reward_debt = accrued_reward(position.staked, pool.acc_per_share)?;
+        pool.total_staked -= amount;
+
+        let project = pool.project;
+        let seeds: &[&[u8]] = &[b"stream_pool", project.as_ref(), &[ctx.bumps.stream_pool]];
+        transfer_rwa(
+            &ctx.accounts.rwa_token_program.to_account_info(),
+            &ctx.accounts.stake_vault.to_account_info(),
+            &ctx.accounts.rwa_mint.to_account_info(),
+            &ctx.accounts.holder_rwa_account.to_account_info(),
+            &ctx.accounts.stream_pool.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.rwa_mint.decimals,
+            &[seeds],
+        )?;
+
+        msg!(
+            "Holder {} unstaked {} RWA",
             ctx.accounts.holder.key(),
-            epoch.epoch_index
+            amount
+        );
+        Ok(())
+    }
+
+    /// Holder claims everything their stake has earned since the last claim (staked * acc_per_share - reward_debt,
+    /// plus revenue settled by earlier stake changes).
+    pub fn claim_streaming(ctx: Context<ClaimStreaming>) -> Result<()> {
+        let pool = &mut ctx.accounts.stream_pool;
+        let position = &mut ctx.accounts.stream_position;
+        pool.update()?;
+        position.settle(pool.acc_per_share)?;
+        let share = position.owed;
+        require!(share > 0, RevError::ZeroShare);
+        position.owed = 0;
+
+        let project = pool.project;
+        let seeds: &[&[u8]] = &[b"stream_pool", project.as_ref(), &[ctx.bumps.stream_pool]];
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.stream_vault.to_account_info(),
+                    mint: ctx.accounts.taste_mint.to_account_info(),
+                    to: ctx.accounts.holder_dest.to_account_info(),
+                    authority: ctx.accounts.stream_pool.to_account_info(),
+                },
+                &[seeds],
+            ),
+            share,
+            ctx.accounts.taste_mint.decimals,
+        )?;
+
+        msg!(
+            "Claimed {} streamed TASTE for holder {}",
+            share,
+            ctx.accounts.holder.key()
         );
         Ok(())
     }
@@ -202,6 +898,42 @@ pub enum RevError {
     AlreadyClaimed,
     #[msg("Epoch not fully claimed; close only when all revenue claimed or dust remains")]
     EpochNotFullyClaimed,
+    #[msg("Invalid epoch period")]
+    InvalidPeriod,
+    #[msg("Account is not a distribution epoch")]
+    InvalidEpochAccount,
+    #[msg("Account is not a revenue config")]
+    InvalidRevConfigAccount,
+    #[msg("Claim amount exceeds the holder's remaining share")]
+    ClaimExceedsShare,
+    #[msg("Registration window must not be negative")]
+    InvalidRegistrationWindow,
+    #[msg("Epoch does not use holder snapshots")]
+    SnapshotNotEnabled,
+    #[msg("Holder registration window has closed")]
+    RegistrationClosed,
+    #[msg("Holder registration window is still open")]
+    RegistrationOpen,
+    #[msg("Holder did not register for this snapshot epoch")]
+    NotRegistered,
+    #[msg("Mint does not match the epoch's quote mint")]
+    QuoteMintMismatch,
+    #[msg("Deposit fee bps exceeds maximum")]
+    InvalidDepositFeeBps,
+    #[msg("Fee treasury account required when a deposit fee is set")]
+    MissingFeeTreasury,
+    #[msg("Fee account is not owned by the configured fee treasury")]
+    InvalidFeeTreasury,
+    #[msg("Signer is not the program upgrade authority")]
+    NotUpgradeAuthority,
+    #[msg("No RWA is staked in the stream pool")]
+    NothingStaked,
+    #[msg("Unstake amount exceeds the staked balance")]
+    InsufficientStake,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("Registered RWA stays locked until the registration window closes")]
+    RegisteredRwaLocked,
 }
 
 #[account]
@@ -212,6 +944,10 @@ pub struct RevenueConfig {
     pub artist_authority: Pubkey,
     pub total_distributed: u64,
     pub epoch_count: u64,
+    /// Platform fee taken from each deposit before holders are paid, in basis points. Set by the upgrade authority.
+    pub deposit_fee_bps: u16,
+    /// Owner of the token accounts that receive the deposit fee.
+    pub fee_treasury: Pubkey,
 }
 
 #[account]
@@ -223,14 +959,138 @@ pub struct DistributionEpoch {
     pub claimed_count: u64,
     pub total_claimed: u64,
     pub created_at: i64,
+    /// Human-readable label, UTF-8 zero-padded (e.g. "Q1 2025 streaming").
+    pub label: [u8; 32],
+    /// Start of the period this revenue covers (unix seconds). 0 = unset.
+    pub period_start: i64,
+    /// End of the period this revenue covers (unix seconds). 0 = unset.
+    pub period_end: i64,
+    /// End of the holder registration window (unix seconds). 0 = no snapshot: claims use live RWA balances.
+    pub registration_ends_at: i64,
+    /// Sum of balances registered with register_holder; the claim denominator for snapshot epochs.
+    pub snapshot_total: u64,
+    /// Mint the epoch's revenue is paid in. Default (epochs from before multi-mint) = the config's $TASTE mint.
+    pub quote_mint: Pubkey,
+}
+
+impl DistributionEpoch {
+    /// True if claims pay from registered HolderSnapshots rather than live balances.
+    pub fn uses_snapshot(&self) -> bool {
+        self.registration_ends_at != 0
+    }
+
+    /// Mint claims for this epoch pay out in: `quote_mint`, or `taste_mint` for epochs that predate it.
+    pub fn quote_mint_or(&self, taste_mint: &Pubkey) -> Pubkey {
+        if self.quote_mint == Pubkey::default() {
+            *taste_mint
+        } else {
+            self.quote_mint
+        }
+    }
+
+    /// True while holders can still register (snapshot epochs only). Claims wait until it has closed.
+    pub fn registration_open(&self, now: i64) -> bool {
+        self.uses_snapshot() && now <= self.registration_ends_at
+    }
 }
 
 #[account]
 pub struct HolderClaim {
+    /// True once the holder's whole entitlement has been paid.
     pub claimed: bool,
+    /// Total paid to the holder so far.
+    pub amount: u64,
+    /// Entitlement still claimable after a partial claim.
+    pub remaining: u64,
+}
+
+/// A holder's RWA balance registered for a snapshot epoch. PDA seeds = [b"holder_snapshot", project, epoch_index, holder].
+#[account]
+pub struct HolderSnapshot {
+    pub balance: u64,
+}
+
+/// RWA tokens register_holder moved into the [b"holder_lock_vault", project] vault (seeds = [b"holder_lock", epoch,
+/// holder]); closed by withdraw_registered_rwa. Kept apart from the HolderSnapshot so claims can still read the
+/// registered balance after the tokens are returned.
+#[account]
+pub struct HolderLock {
+    pub epoch: Pubkey,
+    pub holder: Pubkey,
     pub amount: u64,
 }
 
+/// Continuous revenue pool for a project (PDA seeds = [b"stream_pool", project]); owns the stream vault and the
+/// staked RWA. MasterChef-style: each deposit raises acc_per_share by amount / total_staked.
+#[account]
+pub struct StreamPool {
+    pub project: Pubkey,
+    /// Cumulative $TASTE paid per staked RWA base unit, scaled by STREAM_PRECISION.
+    pub acc_per_share: u128,
+    pub total_staked: u64,
+    /// Deposited while nothing was staked; folded into acc_per_share by the next update.
+    pub undistributed: u64,
+    pub total_deposited: u64,
+}
+
+impl StreamPool {
+    /// Record a deposit and spread it over the current stake.
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(RevError::Overflow)?;
+        self.undistributed = self
+            .undistributed
+            .checked_add(amount)
+            .ok_or(RevError::Overflow)?;
+        self.update()
+    }
+
+    /// Fold undistributed revenue into acc_per_share (no-op while nothing is staked).
+    pub fn update(&mut self) -> Result<()> {
+        if self.total_staked == 0 || self.undistributed == 0 {
+            return Ok(());
+        }
+        let increment = (self.undistributed as u128)
+            .checked_mul(STREAM_PRECISION)
+            .ok_or(RevError::Overflow)?
+            / self.total_staked as u128;
+        self.acc_per_share = self
+            .acc_per_share
+            .checked_add(increment)
+            .ok_or(RevError::Overflow)?;
+        self.undistributed = 0;
+        Ok(())
+    }
+}
+
+/// A holder's stake in the stream pool (PDA seeds = [b"stream_position", project, holder]).
+#[account]
+pub struct StreamPosition {
+    pub staked: u64,
+    /// accrued_reward(staked, acc_per_share) at the last settlement; revenue before it is not owed again.
+    pub reward_debt: u128,
+    /// Settled but not yet claimed.
+    pub owed: u64,
+}
+
+impl StreamPosition {
+    /// Move revenue accrued since the last settlement into `owed` and reset reward_debt to `acc_per_share`.
+    pub fn settle(&mut self, acc_per_share: u128) -> Result<()> {
+        let accrued = accrued_reward(self.staked, acc_per_share)?;
+        let pending = accrued
+            .checked_sub(self.reward_debt)
+            .ok_or(RevError::Overflow)?;
+        self.owed = self
+            .owed
+            .checked_add(u64::try_from(pending).map_err(|_| RevError::Overflow)?)
+            .ok_or(RevError::Overflow)?;
+        self.reward_debt = accrued;
+        Ok(())
+    }
+}
+
 #[derive(Accounts)]
 pub struct InitializeRevenueConfig<'info> {
     #[account(mut)]
@@ -242,7 +1102,7 @@ pub struct InitializeRevenueConfig<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + 32 + 32 + 32 + 32 + 8 + 8,
+        space = REVENUE_CONFIG_SPACE,
         seeds = [b"rev_config", project.key().as_ref()],
         bump,
     )]
@@ -278,7 +1138,7 @@ pub struct InitializeRevenueConfig<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(amount: u64)]
+#[instruction(amount: u64, label: [u8; 32], period_start: i64, period_end: i64)]
 pub struct DepositRevenue<'info> {
     #[account(mut)]
     pub artist_authority: Signer<'info>,
@@ -296,7 +1156,7 @@ pub struct DepositRevenue<'info> {
     #[account(
         init,
         payer = artist_authority,
-        space = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8,
+        space = DISTRIBUTION_EPOCH_SPACE,
         seeds = [
             b"epoch",
             rev_config.project.as_ref(),
@@ -309,15 +1169,14 @@ pub struct DepositRevenue<'info> {
     #[account(
         mut,
         constraint = artist_source.owner == artist_authority.key(),
-        constraint = artist_source.mint == taste_mint.key(),
+        constraint = artist_source.mint == quote_mint.key(),
     )]
     pub artist_source: InterfaceAccount<'info, TokenAccount>,
 
+    /// Vault for `quote_mint` (see revenue_vault_address).
     #[account(
         mut,
-        associated_token::mint = taste_mint,
-        associated_token::authority = rev_vault_authority,
-        associated_token::token_program = token_program,
+        address = revenue_vault_address(&rev_config.project, &quote_mint.key(), &rev_config.taste_mint, &token_program.key()),
     )]
     pub rev_vault: InterfaceAccount<'info, TokenAccount>,
 
@@ -328,26 +1187,263 @@ pub struct DepositRevenue<'info> {
     )]
     pub rev_vault_authority: UncheckedAccount<'info>,
 
-    pub taste_mint: InterfaceAccount<'info, Mint>,
+    /// Mint the epoch is paid in: the config's $TASTE mint or another token with a quote vault.
+    pub quote_mint: InterfaceAccount<'info, Mint>,
 
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+
+    /// Fee treasury's `quote_mint` account; required when deposit_fee_bps > 0.
+    #[account(
+        mut,
+        constraint = fee_treasury_account.owner == rev_config.fee_treasury @ RevError::InvalidFeeTreasury,
+        constraint = fee_treasury_account.mint == quote_mint.key(),
+    )]
+    pub fee_treasury_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional taste_token ProtocolState; deposits fail while it is paused.
+    /// CHECK: address and contents validated by taste_token::protocol_paused
+    pub protocol_state: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimRevenue<'info> {
-    #[account(mut)]
-    pub holder: Signer<'info>,
+pub struct SetDepositFee<'info> {
+    /// Must be the program upgrade authority (validated in instruction).
+    pub authority: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [b"rev_config", rev_config.project.as_ref()],
         bump,
     )]
-    pub rev_config: Box<Account<'info, RevenueConfig>>,
+    pub rev_config: Account<'info, RevenueConfig>,
 
-    #[account(
-        mut,
-        seeds = [
+    /// CHECK: validated in instruction
+    pub program_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated in instruction
+    pub program_data_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteBuyback<'info> {
+    pub artist_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"rev_config", rev_config.project.as_ref()],
+        bump,
+        constraint = rev_config.artist_authority == artist_authority.key() @ RevError::NotArtist,
+    )]
+    pub rev_config: Account<'info, RevenueConfig>,
+
+    /// CHECK: PDA that holds the bought-back RWA.
+    #[account(seeds = [b"buyback", rev_config.project.as_ref()], bump)]
+    pub buyback_authority: UncheckedAccount<'info>,
+
+    /// CHECK: rwa_token RwaState for the project; validated by rwa_token.
+    #[account(mut)]
+    pub rwa_state: UncheckedAccount<'info>,
+
+    /// CHECK: rwa_token RWA mint for the project; validated by rwa_token.
+    #[account(mut, address = rev_config.rwa_mint)]
+    pub rwa_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = buyback_rwa_account.owner == buyback_authority.key(),
+        constraint = buyback_rwa_account.mint == rev_config.rwa_mint,
+    )]
+    pub buyback_rwa_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub rwa_token_program: Program<'info, rwa_token::program::RwaToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateRevConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner checked here; discriminator and length checked in instruction (may be the pre-fee layout).
+    #[account(mut, owner = crate::ID)]
+    pub rev_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitQuoteVault<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"rev_config", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub rev_config: Account<'info, RevenueConfig>,
+
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [b"rev_vault", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub rev_vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"rev_vault", rev_config.project.as_ref(), quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = rev_vault_authority,
+        token::token_program = token_program,
+    )]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterHolder<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        seeds = [b"rev_config", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub rev_config: Box<Account<'info, RevenueConfig>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"epoch",
+            rev_config.project.as_ref(),
+            &distribution_epoch.epoch_index.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub distribution_epoch: Box<Account<'info, DistributionEpoch>>,
+
+    #[account(
+        mut,
+        constraint = holder_rwa_account.owner == holder.key(),
+        constraint = holder_rwa_account.mint == rev_config.rwa_mint,
+    )]
+    pub holder_rwa_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = holder,
+        space = HOLDER_SNAPSHOT_SPACE,
+        seeds = [
+            b"holder_snapshot",
+            rev_config.project.as_ref(),
+            &distribution_epoch.epoch_index.to_le_bytes(),
+            holder.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub holder_snapshot: Box<Account<'info, HolderSnapshot>>,
+
+    #[account(
+        init,
+        payer = holder,
+        space = HOLDER_LOCK_SPACE,
+        seeds = [b"holder_lock", distribution_epoch.key().as_ref(), holder.key().as_ref()],
+        bump,
+    )]
+    pub holder_lock: Box<Account<'info, HolderLock>>,
+
+    #[account(address = rev_config.rwa_mint)]
+    pub rwa_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA owning the project's holder lock vault
+    #[account(seeds = [b"holder_lock_vault", rev_config.project.as_ref()], bump)]
+    pub holder_lock_vault_authority: UncheckedAccount<'info>,
+
+    /// Holds RWA locked by registrations until withdraw_registered_rwa.
+    #[account(
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = rwa_mint,
+        associated_token::authority = holder_lock_vault_authority,
+        associated_token::token_program = rwa_token_program,
+    )]
+    pub holder_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub rwa_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawRegisteredRwa<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        seeds = [b"rev_config", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub rev_config: Box<Account<'info, RevenueConfig>>,
+
+    /// The lock's epoch; may already be closed.
+    /// CHECK: address bound to holder_lock.epoch; window checked in instruction when the account still exists
+    #[account(address = holder_lock.epoch @ RevError::EpochMismatch)]
+    pub distribution_epoch: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = holder,
+        has_one = holder,
+        seeds = [b"holder_lock", holder_lock.epoch.as_ref(), holder.key().as_ref()],
+        bump,
+    )]
+    pub holder_lock: Box<Account<'info, HolderLock>>,
+
+    #[account(
+        mut,
+        constraint = holder_rwa_account.owner == holder.key(),
+        constraint = holder_rwa_account.mint == rev_config.rwa_mint,
+    )]
+    pub holder_rwa_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = rev_config.rwa_mint)]
+    pub rwa_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA signer for the project's holder lock vault
+    #[account(seeds = [b"holder_lock_vault", rev_config.project.as_ref()], bump)]
+    pub holder_lock_vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = rwa_mint,
+        associated_token::authority = holder_lock_vault_authority,
+        associated_token::token_program = rwa_token_program,
+    )]
+    pub holder_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub rwa_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRevenue<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        seeds = [b"rev_config", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub rev_config: Box<Account<'info, RevenueConfig>>,
+
+    #[account(
+        mut,
+        seeds = [
             b"epoch",
             rev_config.project.as_ref(),
             &distribution_epoch.epoch_index.to_le_bytes(),
@@ -365,14 +1461,14 @@ pub struct ClaimRevenue<'info> {
     #[account(
         mut,
         constraint = holder_dest.owner == holder.key(),
-        constraint = holder_dest.mint == taste_mint.key(),
+        constraint = holder_dest.mint == quote_mint.key(),
     )]
     pub holder_dest: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
         payer = holder,
-        space = 8 + 1 + 8,
+        space = HOLDER_CLAIM_SPACE,
         seeds = [
             b"holder_claim",
             rev_config.project.as_ref(),
@@ -390,21 +1486,246 @@ pub struct ClaimRevenue<'info> {
     )]
     pub rev_vault_authority: UncheckedAccount<'info>,
 
+    /// Vault for the epoch's quote mint (see revenue_vault_address).
     #[account(
         mut,
+        address = revenue_vault_address(&rev_config.project, &quote_mint.key(), &rev_config.taste_mint, &token_program.key()),
+    )]
+    pub rev_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The epoch's quote mint (checked in instruction).
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// Holder's registration; required on the first claim of a snapshot epoch.
+    #[account(
+        seeds = [
+            b"holder_snapshot",
+            rev_config.project.as_ref(),
+            &distribution_epoch.epoch_index.to_le_bytes(),
+            holder.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub holder_snapshot: Option<Box<Account<'info, HolderSnapshot>>>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateEpoch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner checked here; discriminator and length checked in instruction (may be the pre-label layout).
+    #[account(mut, owner = crate::ID)]
+    pub distribution_epoch: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitStreamPool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"rev_config", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub rev_config: Box<Account<'info, RevenueConfig>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = STREAM_POOL_SPACE,
+        seeds = [b"stream_pool", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub stream_pool: Box<Account<'info, StreamPool>>,
+
+    #[account(
+        init,
+        payer = payer,
         associated_token::mint = taste_mint,
-        associated_token::authority = rev_vault_authority,
+        associated_token::authority = stream_pool,
         associated_token::token_program = token_program,
     )]
-    pub rev_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub stream_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = rwa_mint,
+        associated_token::authority = stream_pool,
+        associated_token::token_program = rwa_token_program,
+    )]
+    pub stake_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    #[account(address = rev_config.taste_mint)]
     pub taste_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    #[account(address = rev_config.rwa_mint)]
+    pub rwa_mint: Box<InterfaceAccount<'info, Mint>>,
+
     pub token_program: Interface<'info, TokenInterface>,
+    pub rwa_token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct DepositStreamingRevenue<'info> {
+    pub artist_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rev_config", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub rev_config: Box<Account<'info, RevenueConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"stream_pool", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub stream_pool: Box<Account<'info, StreamPool>>,
+
+    #[account(
+        mut,
+        constraint = artist_source.owner == artist_authority.key(),
+        constraint = artist_source.mint == taste_mint.key(),
+    )]
+    pub artist_source: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = taste_mint,
+        associated_token::authority = stream_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub stream_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = rev_config.taste_mint)]
+    pub taste_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Fee treasury's $TASTE account; required when deposit_fee_bps > 0.
+    #[account(
+        mut,
+        constraint = fee_treasury_account.owner == rev_config.fee_treasury @ RevError::InvalidFeeTreasury,
+        constraint = fee_treasury_account.mint == taste_mint.key(),
+    )]
+    pub fee_treasury_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePool<'info> {
+    #[account(
+        mut,
+        seeds = [b"stream_pool", stream_pool.project.as_ref()],
+        bump,
+    )]
+    pub stream_pool: Account<'info, StreamPool>,
+}
+
+#[derive(Accounts)]
+pub struct StakeStreaming<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        seeds = [b"rev_config", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub rev_config: Box<Account<'info, RevenueConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"stream_pool", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub stream_pool: Box<Account<'info, StreamPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = STREAM_POSITION_SPACE,
+        seeds = [b"stream_position", rev_config.project.as_ref(), holder.key().as_ref()],
+        bump,
+    )]
+    pub stream_position: Box<Account<'info, StreamPosition>>,
+
+    #[account(
+        mut,
+        constraint = holder_rwa_account.owner == holder.key(),
+        constraint = holder_rwa_account.mint == rev_config.rwa_mint,
+    )]
+    pub holder_rwa_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = rwa_mint,
+        associated_token::authority = stream_pool,
+        associated_token::token_program = rwa_token_program,
+    )]
+    pub stake_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = rev_config.rwa_mint)]
+    pub rwa_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub rwa_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimStreaming<'info> {
+    pub holder: Signer<'info>,
+
+    #[account(
+        seeds = [b"rev_config", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub rev_config: Box<Account<'info, RevenueConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"stream_pool", rev_config.project.as_ref()],
+        bump,
+    )]
+    pub stream_pool: Box<Account<'info, StreamPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"stream_position", rev_config.project.as_ref(), holder.key().as_ref()],
+        bump,
+    )]
+    pub stream_position: Box<Account<'info, StreamPosition>>,
+
+    #[account(
+        mut,
+        associated_token::mint = taste_mint,
+        associated_token::authority = stream_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub stream_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = holder_dest.owner == holder.key(),
+        constraint = holder_dest.mint == taste_mint.key(),
+    )]
+    pub holder_dest: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = rev_config.taste_mint)]
+    pub taste_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 #[derive(Accounts)]
 pub struct CloseEpoch<'info> {
     #[account(mut)]
@@ -445,3 +1766,230 @@ pub struct CloseEpoch<'info> {
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_epoch() -> DistributionEpoch {
+        let mut label = [0u8; 32];
+        label[..17].copy_from_slice(b"Q1 2025 streaming");
+        DistributionEpoch {
+            project: Pubkey::new_unique(),
+            epoch_index: 3,
+            amount: 1_000,
+            total_rwa_supply: 10_000,
+            claimed_count: 0,
+            total_claimed: 0,
+            created_at: 1_745_000_000,
+            label,
+            period_start: 1_735_689_600,
+            period_end: 1_743_465_599,
+            registration_ends_at: 0,
+            snapshot_total: 0,
+            quote_mint: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn test_buyback_reduces_supply_and_grows_holder_pool() {
+        let epoch_amount = 800u64;
+        let holder_balance = 100u64;
+        let minted = 1_000u64;
+        assert_eq!(
+            holder_revenue_share(holder_balance, epoch_amount, minted).unwrap(),
+            80
+        );
+
+        // Retiring 200 bought-back RWA shrinks the supply, so the same holder earns more per deposit.
+        let (_, minted_after) = rwa_token::retire_supply(minted, minted, 200).unwrap();
+        assert_eq!(minted_after, 800);
+        assert_eq!(
+            holder_revenue_share(holder_balance, epoch_amount, minted_after).unwrap(),
+            100
+        );
+    }
+
+    #[test]
+    fn test_deposit_fee_split() {
+        // 5% fee; holders get the rest.
+        assert_eq!(deposit_split(1_000, 500).unwrap(), (50, 950));
+        assert_eq!(deposit_split(1_000, 0).unwrap(), (0, 1_000));
+        // Rounds the fee down.
+        assert_eq!(deposit_split(19, MAX_DEPOSIT_FEE_BPS).unwrap(), (1, 18));
+    }
+
+    #[test]
+    fn test_partial_claim_half_then_remainder() {
+        let share = holder_revenue_share(2_500, 1_000, 10_000).unwrap();
+        assert_eq!(share, 250);
+
+        let first = claim_amount(share, Some(share / 2)).unwrap();
+        assert_eq!(first, 125);
+        let remaining = share - first;
+        // Follow-up claim without an amount takes the rest; asking for more than is left fails.
+        assert!(claim_amount(remaining, Some(remaining + 1)).is_err());
+        assert_eq!(claim_amount(remaining, None).unwrap(), 125);
+        assert_eq!(first + remaining, share);
+        assert!(claim_amount(share, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_share_ignores_later_transfers() {
+        let mut epoch = sample_epoch();
+        assert!(!epoch.uses_snapshot());
+        assert!(!epoch.registration_open(epoch.created_at));
+        epoch.registration_ends_at = epoch.created_at + 3_600;
+        assert!(epoch.registration_open(epoch.created_at + 3_600));
+        assert!(!epoch.registration_open(epoch.created_at + 3_601));
+
+        // Two holders register 300 and 100 of a 10_000 supply; everyone else stays out.
+        let (first, second) = (300u64, 100u64);
+        epoch.snapshot_total = first + second;
+        // Registered tokens sit in the lock vault until the window closes, so neither balance can be registered
+        // again from another wallet; payouts follow the registered balances.
+        assert_eq!(
+            holder_revenue_share(first, epoch.amount, epoch.snapshot_total).unwrap(),
+            750
+        );
+        assert_eq!(
+            holder_revenue_share(second, epoch.amount, epoch.snapshot_total).unwrap(),
+            250
+        );
+    }
+
+    #[test]
+    fn test_holder_lock_space() {
+        let lock = HolderLock {
+            epoch: Pubkey::new_unique(),
+            holder: Pubkey::new_unique(),
+            amount: u64::MAX,
+        };
+        let mut data = Vec::new();
+        lock.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), HOLDER_LOCK_SPACE);
+    }
+
+    #[test]
+    fn test_quote_mint_and_vaults() {
+        let taste_mint = Pubkey::new_unique();
+        let usdc = Pubkey::new_unique();
+        let mut epoch = sample_epoch();
+        // Epochs from before multi-mint pay in $TASTE.
+        assert_eq!(epoch.quote_mint_or(&taste_mint), taste_mint);
+        epoch.quote_mint = usdc;
+        assert_eq!(epoch.quote_mint_or(&taste_mint), usdc);
+
+        let project = epoch.project;
+        let token_program = anchor_spl::token_2022::ID;
+        let (authority, _) =
+            Pubkey::find_program_address(&[b"rev_vault", project.as_ref()], &crate::ID);
+        assert_eq!(
+            revenue_vault_address(&project, &taste_mint, &taste_mint, &token_program),
+            anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                &authority,
+                &taste_mint,
+                &token_program
+            )
+        );
+        let usdc_vault = revenue_vault_address(&project, &usdc, &taste_mint, &token_program);
+        assert_eq!(
+            usdc_vault,
+            Pubkey::find_program_address(
+                &[b"rev_vault", project.as_ref(), usdc.as_ref()],
+                &crate::ID
+            )
+            .0
+        );
+        assert_ne!(usdc_vault, authority);
+    }
+
+    #[test]
+    fn test_streaming_two_deposits_with_claim_between() {
+        let mut pool = StreamPool {
+            project: Pubkey::new_unique(),
+            acc_per_share: 0,
+            total_staked: 0,
+            undistributed: 0,
+            total_deposited: 0,
+        };
+        let empty = || StreamPosition {
+            staked: 0,
+            reward_debt: 0,
+            owed: 0,
+        };
+        let (mut alice, mut bob) = (empty(), empty());
+
+        // Alice stakes 300, Bob 100 (as stake_streaming does: settle, add stake, reset debt).
+        for (position, amount) in [(&mut alice, 300u64), (&mut bob, 100u64)] {
+            position.settle(pool.acc_per_share).unwrap();
+            position.staked += amount;
+            pool.total_staked += amount;
+            position.reward_debt = accrued_reward(position.staked, pool.acc_per_share).unwrap();
+        }
+
+        pool.deposit(1_000).unwrap();
+        alice.settle(pool.acc_per_share).unwrap();
+        assert_eq!(alice.owed, 750);
+        alice.owed = 0;
+
+        // Second deposit: Alice's next claim is only the increment; Bob collects both deposits at once.
+        pool.deposit(2_000).unwrap();
+        alice.settle(pool.acc_per_share).unwrap();
+        assert_eq!(alice.owed, 1_500);
+        bob.settle(pool.acc_per_share).unwrap();
+        assert_eq!(bob.owed, 750);
+        // Nothing new accrues without a deposit.
+        alice.settle(pool.acc_per_share).unwrap();
+        assert_eq!(alice.owed, 1_500);
+        assert_eq!(pool.total_deposited, 3_000);
+    }
+
+    #[test]
+    fn test_stream_deposit_with_nothing_staked_waits_for_stake() {
+        let mut pool = StreamPool {
+            project: Pubkey::new_unique(),
+            acc_per_share: 0,
+            total_staked: 0,
+            undistributed: 0,
+            total_deposited: 0,
+        };
+        pool.deposit(500).unwrap();
+        assert_eq!((pool.acc_per_share, pool.undistributed), (0, 500));
+        pool.total_staked = 1_000;
+        pool.update().unwrap();
+        assert_eq!(pool.undistributed, 0);
+        assert_eq!(accrued_reward(1_000, pool.acc_per_share).unwrap(), 500);
+    }
+
+    #[test]
+    fn test_epoch_label_and_period_round_trip() {
+        let epoch = sample_epoch();
+        let mut data = Vec::new();
+        epoch.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), DISTRIBUTION_EPOCH_SPACE);
+
+        let decoded = DistributionEpoch::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(decoded.label, epoch.label);
+        assert_eq!(&decoded.label[..17], b"Q1 2025 streaming");
+        assert_eq!(decoded.period_start, epoch.period_start);
+        assert_eq!(decoded.period_end, epoch.period_end);
+        assert_eq!(decoded.amount, epoch.amount);
+    }
+
+    #[test]
+    fn test_migrated_legacy_epoch_has_empty_label() {
+        let epoch = sample_epoch();
+        let mut data = Vec::new();
+        epoch.try_serialize(&mut data).unwrap();
+        // Pre-label layout, then zero-extended as migrate_epoch's resize does.
+        data.truncate(DISTRIBUTION_EPOCH_BASE_LEN);
+        assert!(DistributionEpoch::try_deserialize(&mut data.as_slice()).is_err());
+        data.resize(DISTRIBUTION_EPOCH_SPACE, 0);
+
+        let decoded = DistributionEpoch::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(decoded.label, [0u8; 32]);
+        assert_eq!((decoded.period_start, decoded.period_end), (0, 0));
+        assert_eq!(decoded.total_rwa_supply, epoch.total_rwa_supply);
+    }
+}
@@ -209,6 +209,236 @@ fn truncate_to_char_boundary(s: &str, max_bytes: usize) -> &str {
     &s[..len]
 }
 
+/// RWA tokens a backer can claim: their escrowed amount's pro-rata slice of `total_supply`.
+/// Shared by claim_rwa_tokens, claim_rwa_tokens_legacy and backer_share_preview.
+pub fn backer_rwa_share(backer_amount: u64, total_supply: u64, total_raised: u64) -> Result<u64> {
+    require!(total_raised > 0, RwaError::InvalidAmounts);
+    Ok((backer_amount as u128)
+        .checked_mul(total_supply as u128)
+        .ok_or(RwaError::Overflow)?
+        .checked_div(total_raised as u128)
+        .ok_or(RwaError::Overflow)? as u64)
+}
+
+/// remaining_accounts per extra wallet in claim_rwa_tokens_consolidated: (wallet signer, Backer, ClaimRecord PDA).
+pub const CONSOLIDATED_ACCOUNTS_PER_WALLET: usize = 3;
+/// Most extra wallets one consolidated claim can merge.
+pub const MAX_CONSOLIDATED_WALLETS: usize = 4;
+
+/// Number of extra wallets in a consolidated claim's remaining_accounts (whole groups, at most the cap).
+pub fn consolidated_wallet_count(remaining_len: usize) -> Result<usize> {
+    let count = remaining_len / CONSOLIDATED_ACCOUNTS_PER_WALLET;
+    require!(
+        count * CONSOLIDATED_ACCOUNTS_PER_WALLET == remaining_len,
+        RwaError::InvalidConsolidation
+    );
+    require!(
+        count <= MAX_CONSOLIDATED_WALLETS,
+        RwaError::InvalidConsolidation
+    );
+    Ok(count)
+}
+
+/// Escrowed total behind a consolidated claim: the primary Backer plus each extra wallet's Backer.
+/// `extra` pairs each Backer with whether its wallet already claimed. Every record must belong to `project`,
+/// have a contribution, and come from a distinct wallet that has not claimed.
+pub fn consolidated_backer_amount(
+    project: &Pubkey,
+    primary: &Backer,
+    extra: &[(Backer, bool)],
+) -> Result<u64> {
+    require!(primary.project == *project, RwaError::WrongProject);
+    let mut total = primary.amount;
+    let mut wallets = vec![primary.wallet];
+    for (backer, claimed) in extra {
+        require!(!claimed, RwaError::AlreadyClaimed);
+        require!(backer.project == *project, RwaError::WrongProject);
+        require!(backer.amount > 0, RwaError::NoContribution);
+        require!(
+            !wallets.contains(&backer.wallet),
+            RwaError::InvalidConsolidation
+        );
+        wallets.push(backer.wallet);
+        total = total.checked_add(backer.amount).ok_or(RwaError::Overflow)?;
+    }
+    Ok(total)
+}
+
+/// Current RwaConfig account size (including strict_splits and strict_completion).
+pub const RWA_CONFIG_SPACE: usize = 8 + 32 + 1 + 1;
+/// Size of configs created before strict_splits was added; grown by set_split_policy or set_completion_policy.
+const RWA_CONFIG_BASE_LEN: usize = 8 + 32;
+
+/// Current RwaState account size (including holder_count).
+pub const RWA_STATE_SPACE: usize = RWA_STATE_BASE_LEN + 8;
+/// Size of RwaStates created before holder_count was added; see migrate_rwa_state.
+const RWA_STATE_BASE_LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+
+/// Upgrade raw RwaState data created before `holder_count` existed. Fails if the data is not a legacy RwaState.
+pub fn migrate_rwa_state_data(data: &[u8]) -> Result<RwaState> {
+    require!(
+        data.len() >= RWA_STATE_BASE_LEN && &data[..8] == RwaState::DISCRIMINATOR,
+        RwaError::InvalidRwaState
+    );
+    require!(
+        data.len() < RWA_STATE_SPACE,
+        RwaError::RwaStateAlreadyMigrated
+    );
+    let mut buf = data.to_vec();
+    buf.resize(RWA_STATE_SPACE, 0);
+    RwaState::try_deserialize(&mut &buf[..])
+}
+
+/// Validate a rights split. Lenient (default): revenue_split_bps + artist_split_bps <= 10_000, with any
+/// remainder going to the platform. Strict: the splits must sum to exactly 10_000.
+pub fn validate_split(revenue_split_bps: u16, artist_split_bps: u16, strict: bool) -> Result<()> {
+    let total = revenue_split_bps.saturating_add(artist_split_bps);
+    require!(total <= 10_000, RwaError::InvalidSplit);
+    require!(!strict || total == 10_000, RwaError::SplitNotExact);
+    Ok(())
+}
+
+/// Completed-project check for initialize_rwa_mint. Strict completion additionally requires every milestone
+/// released and the escrow drained, which a project completed by force_complete_project may not satisfy.
+pub fn check_rwa_mint_ready(
+    status: ProjectStatus,
+    current_milestone: u8,
+    milestone_percentages: &[u16; project_escrow::MAX_MILESTONES],
+    escrow_amount: u64,
+    strict_completion: bool,
+) -> Result<()> {
+    require!(
+        status == ProjectStatus::Completed,
+        RwaError::ProjectNotCompleted
+    );
+    if strict_completion {
+        require!(
+            current_milestone as usize
+                >= project_escrow::effective_milestone_count(milestone_percentages)
+                && escrow_amount == 0,
+            RwaError::ProjectNotFullyExecuted
+        );
+    }
+    Ok(())
+}
+
+/// Load an RwaConfig of any historical length.
+fn load_rwa_config(info: &AccountInfo) -> Result<RwaConfig> {
+    let data = info.try_borrow_data()?;
+    RwaConfig::from_account_data(&data).ok_or(error!(RwaError::InvalidRwaConfig))
+}
+
+/// Require `release_authority` to be the governance_release_authority in project_escrow's Config (any layout).
+fn require_release_authority(config: &AccountInfo, release_authority: &Pubkey) -> Result<()> {
+    let data = config.try_borrow_data()?;
+    let config = project_escrow::Config::from_account_data(&data)
+        .ok_or(error!(RwaError::NotReleaseAuthority))?;
+    require_keys_eq!(
+        config.governance_release_authority,
+        *release_authority,
+        RwaError::NotReleaseAuthority
+    );
+    Ok(())
+}
+
+/// Grow a program-owned account created with an older, shorter layout to `space` (new bytes zeroed), topping
+/// up rent from `payer`. No-op if already large enough. Also used by governance, otc_market and
+/// revenue_distribution for their own account migrations.
+pub fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    if info.data_len() >= space {
+        return Ok(());
+    }
+    let required = Rent::get()?.minimum_balance(space);
+    let shortfall = required.saturating_sub(info.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    info.resize(space)?;
+    Ok(())
+}
+
+/// Write `value` into a program-owned account, growing it to `space` (and topping up rent from `payer`) when
+/// it was created with an older, shorter layout.
+pub fn write_grown_account<'info, T: AccountSerialize>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    value: &T,
+) -> Result<()> {
+    grow_account(info, payer, system_program, space)?;
+    let mut data = info.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Write `config` back, growing an RwaConfig created with an older, shorter layout (authority tops up rent).
+fn write_rwa_config<'info>(
+    config_info: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    config: &RwaConfig,
+) -> Result<()> {
+    write_grown_account(
+        config_info,
+        authority,
+        system_program,
+        RWA_CONFIG_SPACE,
+        config,
+    )
+}
+
+/// Whether strict splits are enabled. A missing RwaConfig means lenient mode.
+fn read_strict_splits(info: &AccountInfo) -> Result<bool> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(false);
+    }
+    let data = info.try_borrow_data()?;
+    let config = RwaConfig::from_account_data(&data).ok_or(error!(RwaError::InvalidRwaConfig))?;
+    Ok(config.strict_splits)
+}
+
+/// Signed difference between the mint's actual supply and RwaState.minted (supply - minted).
+pub fn minted_supply_delta(mint_supply: u64, minted: u64) -> Result<i64> {
+    i64::try_from(mint_supply as i128 - minted as i128).map_err(|_| error!(RwaError::Overflow))
+}
+
+/// (total_supply, minted) after retiring `amount` burned RWA tokens.
+pub fn retire_supply(total_supply: u64, minted: u64, amount: u64) -> Result<(u64, u64)> {
+    require!(amount > 0, RwaError::InvalidAmounts);
+    Ok((
+        total_supply.checked_sub(amount).ok_or(RwaError::Overflow)?,
+        minted.checked_sub(amount).ok_or(RwaError::Overflow)?,
+    ))
+}
+
+/// True if a Metaplex metadata account already holds data (owned by Token Metadata, non-empty), so CreateV1
+/// would fail and must be skipped.
+/// True when sha256 of `document` equals `expected` (RwaRights.terms_hash). The document travels in
+/// instruction data, so on-chain checks cover documents that fit in one transaction; larger documents
+/// are hashed off-chain and compared against the same terms_hash.
+pub fn terms_hash_matches(expected: &[u8; 32], document: &[u8]) -> bool {
+    solana_sha256_hasher::hash(document).to_bytes() == *expected
+}
+
+pub fn metadata_initialized(owner: &Pubkey, data_len: usize) -> bool {
+    *owner == MPL_TOKEN_METADATA_ID && data_len > 0
+}
+
 /// Base URL for RWA metadata API (used by initialize_rwa_metadata_by_governance).
 const RWA_METADATA_BASE_URL: &str = "https://tastemaker.music";
 
@@ -238,6 +468,7 @@ pub mod rwa_token {
 
         let config = &mut ctx.accounts.rwa_config;
         config.transfer_hook_program_id = transfer_hook_program_id;
+        config.strict_splits = false;
         msg!(
             "RwaConfig initialized: transfer_hook_program_id = {}",
             config.transfer_hook_program_id
@@ -245,11 +476,73 @@ pub mod rwa_token {
         Ok(())
     }
 
+    /// Toggle strict rights splits (revenue + artist must equal 10_000 bps). Only the program upgrade authority.
+    /// Grows an RwaConfig created before strict_splits existed (readers accept either layout).
+    pub fn set_split_policy(ctx: Context<SetSplitPolicy>, strict_splits: bool) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+
+        let config_info = ctx.accounts.rwa_config.to_account_info();
+        let mut config = load_rwa_config(&config_info)?;
+        config.strict_splits = strict_splits;
+        write_rwa_config(
+            &config_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &config,
+        )?;
+        msg!("RwaConfig strict_splits = {}", strict_splits);
+        Ok(())
+    }
+
+    /// Toggle strict completion: initialize_rwa_mint then also requires all milestones released and the project
+    /// escrow empty. Only the program upgrade authority. Grows an RwaConfig created before the flag existed.
+    pub fn set_completion_policy(
+        ctx: Context<SetCompletionPolicy>,
+        strict_completion: bool,
+    ) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+
+        let config_info = ctx.accounts.rwa_config.to_account_info();
+        let mut config = load_rwa_config(&config_info)?;
+        config.strict_completion = strict_completion;
+        write_rwa_config(
+            &config_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &config,
+        )?;
+        msg!("RwaConfig strict_completion = {}", strict_completion);
+        Ok(())
+    }
+
     pub fn initialize_rwa_mint(ctx: Context<InitializeRwaMint>, total_supply: u64) -> Result<()> {
-        require!(
-            ctx.accounts.project.status == ProjectStatus::Completed,
-            RwaError::ProjectNotCompleted
-        );
+        let rwa_config = load_rwa_config(&ctx.accounts.rwa_config)?;
+        let project = &ctx.accounts.project;
+        check_rwa_mint_ready(
+            project.status,
+            project.current_milestone,
+            &project.milestone_percentages,
+            ctx.accounts.escrow.amount,
+            rwa_config.strict_completion,
+        )?;
         require!(
             ctx.accounts.token_program.key() == TOKEN_2022_PROGRAM_ID,
             RwaError::InvalidTokenProgram
@@ -263,8 +556,7 @@ pub mod rwa_token {
             RwaError::InvalidMintPda
         );
         require!(
-            ctx.accounts.rwa_transfer_hook_program.key()
-                == ctx.accounts.rwa_config.transfer_hook_program_id,
+            ctx.accounts.rwa_transfer_hook_program.key() == rwa_config.transfer_hook_program_id,
             RwaError::InvalidTransferHookProgram
         );
 
@@ -274,6 +566,7 @@ pub mod rwa_token {
         state.total_supply = total_supply;
         state.minted = 0;
         state.mint_frozen = false;
+        state.holder_count = 0;
 
         create_rwa_mint_with_transfer_hook(
             &ctx.accounts.authority.to_account_info(),
@@ -281,7 +574,7 @@ pub mod rwa_token {
             &ctx.accounts.rwa_mint_authority.to_account_info(),
             &ctx.accounts.token_program.to_account_info(),
             &ctx.accounts.system_program.to_account_info(),
-            ctx.accounts.rwa_config.transfer_hook_program_id,
+            rwa_config.transfer_hook_program_id,
             &ctx.accounts.rwa_transfer_hook_program.to_account_info(),
             &ctx.accounts.extra_account_metas.to_account_info(),
             state.project,
@@ -298,15 +591,12 @@ pub mod rwa_token {
         ctx: Context<InitializeRwaMintByGovernance>,
         total_supply: u64,
     ) -> Result<()> {
-        require!(
-            ctx.accounts.release_authority.key()
-                == ctx.accounts.config.governance_release_authority,
-            RwaError::NotReleaseAuthority
-        );
+        require_release_authority(&ctx.accounts.config, &ctx.accounts.release_authority.key())?;
         require!(
             ctx.accounts.project.status == ProjectStatus::Completed,
             RwaError::ProjectNotCompleted
         );
+        let rwa_config = load_rwa_config(&ctx.accounts.rwa_config)?;
         require!(
             ctx.accounts.token_program.key() == TOKEN_2022_PROGRAM_ID,
             RwaError::InvalidTokenProgram
@@ -320,8 +610,7 @@ pub mod rwa_token {
             RwaError::InvalidMintPda
         );
         require!(
-            ctx.accounts.rwa_transfer_hook_program.key()
-                == ctx.accounts.rwa_config.transfer_hook_program_id,
+            ctx.accounts.rwa_transfer_hook_program.key() == rwa_config.transfer_hook_program_id,
             RwaError::InvalidTransferHookProgram
         );
 
@@ -331,6 +620,7 @@ pub mod rwa_token {
         state.total_supply = total_supply;
         state.minted = 0;
         state.mint_frozen = false;
+        state.holder_count = 0;
 
         create_rwa_mint_with_transfer_hook(
             &ctx.accounts.payer.to_account_info(),
@@ -338,7 +628,7 @@ pub mod rwa_token {
             &ctx.accounts.rwa_mint_authority.to_account_info(),
             &ctx.accounts.token_program.to_account_info(),
             &ctx.accounts.system_program.to_account_info(),
-            ctx.accounts.rwa_config.transfer_hook_program_id,
+            rwa_config.transfer_hook_program_id,
             &ctx.accounts.rwa_transfer_hook_program.to_account_info(),
             &ctx.accounts.extra_account_metas.to_account_info(),
             state.project,
@@ -369,10 +659,11 @@ pub mod rwa_token {
             ctx.accounts.authority.key() == state.authority,
             RwaError::NotAuthority
         );
-        require!(
-            revenue_split_bps.saturating_add(artist_split_bps) <= 10_000,
-            RwaError::InvalidSplit
-        );
+        validate_split(
+            revenue_split_bps,
+            artist_split_bps,
+            read_strict_splits(&ctx.accounts.rwa_config)?,
+        )?;
         require!(
             terms_uri.len() <= MAX_TERMS_URI_LEN,
             RwaError::TermsUriTooLong
@@ -419,6 +710,19 @@ pub mod rwa_token {
         Ok(())
     }
 
+    /// Read-only: check a terms document against RwaRights.terms_hash (sha256 of the document bytes).
+    /// Returns true on match via return data; does not modify state.
+    pub fn verify_terms(ctx: Context<VerifyTerms>, document: Vec<u8>) -> Result<bool> {
+        let rights = &ctx.accounts.rwa_rights;
+        let matches = terms_hash_matches(&rights.terms_hash, &document);
+        msg!(
+            "Terms verification for project {}: {}",
+            rights.project,
+            matches
+        );
+        Ok(matches)
+    }
+
     /// One-time init of RwaRights by governance (last-milestone finalize). Callable only by config.governance_release_authority.
     pub fn initialize_rwa_rights_by_governance(
         ctx: Context<InitializeRwaRightsByGovernance>,
@@ -431,15 +735,12 @@ pub mod rwa_token {
         terms_uri: String,
         jurisdiction: String,
     ) -> Result<()> {
-        require!(
-            ctx.accounts.release_authority.key()
-                == ctx.accounts.config.governance_release_authority,
-            RwaError::NotReleaseAuthority
-        );
-        require!(
-            revenue_split_bps.saturating_add(artist_split_bps) <= 10_000,
-            RwaError::InvalidSplit
-        );
+        require_release_authority(&ctx.accounts.config, &ctx.accounts.release_authority.key())?;
+        validate_split(
+            revenue_split_bps,
+            artist_split_bps,
+            read_strict_splits(&ctx.accounts.rwa_config)?,
+        )?;
         require!(
             terms_uri.len() <= MAX_TERMS_URI_LEN,
             RwaError::TermsUriTooLong
@@ -528,11 +829,7 @@ pub mod rwa_token {
 
         require!(!ctx.accounts.claim_record.claimed, RwaError::AlreadyClaimed);
 
-        let share = (backer_account.amount as u128)
-            .checked_mul(total_supply as u128)
-            .ok_or(RwaError::Overflow)?
-            .checked_div(total_raised as u128)
-            .ok_or(RwaError::Overflow)? as u64;
+        let share = backer_rwa_share(backer_account.amount, total_supply, total_raised)?;
 
         require!(share > 0, RwaError::ZeroShare);
 
@@ -541,7 +838,15 @@ pub mod rwa_token {
             .ok_or(RwaError::Overflow)?;
         require!(new_minted <= total_supply, RwaError::ExceedsSupply);
 
+        let new_holder_count = ctx
+            .accounts
+            .rwa_state
+            .holder_count
+            .checked_add(1)
+            .ok_or(RwaError::Overflow)?;
+
         ctx.accounts.rwa_state.minted = new_minted;
+        ctx.accounts.rwa_state.holder_count = new_holder_count;
         ctx.accounts.claim_record.claimed = true;
 
         let (_, bump) = Pubkey::find_program_address(
@@ -595,11 +900,7 @@ pub mod rwa_token {
 
         require!(!ctx.accounts.claim_record.claimed, RwaError::AlreadyClaimed);
 
-        let share = (backer_account.amount as u128)
-            .checked_mul(total_supply as u128)
-            .ok_or(RwaError::Overflow)?
-            .checked_div(total_raised as u128)
-            .ok_or(RwaError::Overflow)? as u64;
+        let share = backer_rwa_share(backer_account.amount, total_supply, total_raised)?;
 
         require!(share > 0, RwaError::ZeroShare);
 
@@ -608,7 +909,15 @@ pub mod rwa_token {
             .ok_or(RwaError::Overflow)?;
         require!(new_minted <= total_supply, RwaError::ExceedsSupply);
 
+        let new_holder_count = ctx
+            .accounts
+            .rwa_state
+            .holder_count
+            .checked_add(1)
+            .ok_or(RwaError::Overflow)?;
+
         ctx.accounts.rwa_state.minted = new_minted;
+        ctx.accounts.rwa_state.holder_count = new_holder_count;
         ctx.accounts.claim_record.claimed = true;
 
         let (_, bump) = Pubkey::find_program_address(
@@ -633,6 +942,181 @@ pub mod rwa_token {
         Ok(())
     }
 
+    /// Claim once for a backer who funded from several wallets: the share of all their Backer records is minted to
+    /// `backer`. remaining_accounts are (wallet signer, Backer, ClaimRecord PDA [b"claim", project, wallet]) per extra
+    /// wallet; each extra wallet's ClaimRecord is marked claimed so none of them can claim again. No receipt is burned.
+    pub fn claim_rwa_tokens_consolidated<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimRwaTokensConsolidated<'info>>,
+    ) -> Result<()> {
+        let total_supply = ctx.accounts.rwa_state.total_supply;
+        let project_key = ctx.accounts.rwa_state.project;
+
+        require!(!ctx.accounts.rwa_state.mint_frozen, RwaError::MintFrozen);
+        require!(
+            ctx.accounts.project.status == ProjectStatus::Completed,
+            RwaError::ProjectNotCompleted
+        );
+        require!(
+            ctx.accounts.backer_account.amount > 0,
+            RwaError::NoContribution
+        );
+        require!(!ctx.accounts.claim_record.claimed, RwaError::AlreadyClaimed);
+
+        let wallet_count = consolidated_wallet_count(ctx.remaining_accounts.len())?;
+        let mut extra = Vec::with_capacity(wallet_count);
+        for group in ctx
+            .remaining_accounts
+            .chunks(CONSOLIDATED_ACCOUNTS_PER_WALLET)
+        {
+            let (wallet, backer_info, claim_info) = (&group[0], &group[1], &group[2]);
+            require!(wallet.is_signer, RwaError::InvalidConsolidation);
+            require!(
+                backer_info.owner == &project_escrow::ID,
+                RwaError::InvalidConsolidation
+            );
+            let backer = {
+                let data = backer_info.try_borrow_data()?;
+                Backer::try_deserialize(&mut &data[..])?
+            };
+            require!(backer.wallet == wallet.key(), RwaError::NotBacker);
+
+            let (claim_pda, claim_bump) = Pubkey::find_program_address(
+                &[b"claim", project_key.as_ref(), wallet.key.as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                claim_info.key() == claim_pda,
+                RwaError::InvalidConsolidation
+            );
+            let claimed = if claim_info.data_is_empty() {
+                let space = 8 + 1;
+                let seeds: &[&[u8]] = &[
+                    b"claim",
+                    project_key.as_ref(),
+                    wallet.key.as_ref(),
+                    &[claim_bump],
+                ];
+                let signer_seeds = &[seeds];
+                anchor_lang::system_program::create_account(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::CreateAccount {
+                            from: ctx.accounts.backer.to_account_info(),
+                            to: claim_info.clone(),
+                        },
+                        signer_seeds,
+                    ),
+                    Rent::get()?.minimum_balance(space),
+                    space as u64,
+                    ctx.program_id,
+                )?;
+                false
+            } else {
+                require!(
+                    claim_info.owner == ctx.program_id,
+                    RwaError::InvalidConsolidation
+                );
+                let data = claim_info.try_borrow_data()?;
+                ClaimRecord::try_deserialize(&mut &data[..])?.claimed
+            };
+            extra.push((backer, claimed));
+
+            let mut data = claim_info.try_borrow_mut_data()?;
+            ClaimRecord { claimed: true }.try_serialize(&mut &mut data[..])?;
+        }
+
+        let amount =
+            consolidated_backer_amount(&project_key, &ctx.accounts.backer_account, &extra)?;
+        let share = backer_rwa_share(amount, total_supply, ctx.accounts.project.total_raised)?;
+        require!(share > 0, RwaError::ZeroShare);
+
+        let state = &mut ctx.accounts.rwa_state;
+        let new_minted = state.minted.checked_add(share).ok_or(RwaError::Overflow)?;
+        require!(new_minted <= total_supply, RwaError::ExceedsSupply);
+        state.minted = new_minted;
+        state.holder_count = state
+            .holder_count
+            .checked_add(1)
+            .ok_or(RwaError::Overflow)?;
+        ctx.accounts.claim_record.claimed = true;
+
+        let seeds: &[&[u8]] = &[
+            b"rwa_mint_authority",
+            project_key.as_ref(),
+            &[ctx.bumps.rwa_mint_authority],
+        ];
+        let signer_seeds = &[seeds];
+        anchor_spl::token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.rwa_mint.to_account_info(),
+                    to: ctx.accounts.backer_token_account.to_account_info(),
+                    authority: ctx.accounts.rwa_mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            share,
+        )?;
+        msg!(
+            "Claimed {} RWA tokens for {} consolidated wallets",
+            share,
+            wallet_count + 1
+        );
+        Ok(())
+    }
+
+    /// Read-only: RWA tokens `backer_account` is eligible to claim against `rwa_state`, using the same math as
+    /// claim_rwa_tokens. Returns 0 for backers with nothing in escrow. Calling this for every Backer of the
+    /// project (e.g. from getProgramAccounts filtered by project) reconstructs the full eligibility table.
+    pub fn backer_share_preview(ctx: Context<BackerSharePreview>) -> Result<u64> {
+        let amount = ctx.accounts.backer_account.amount;
+        if amount == 0 {
+            return Ok(0);
+        }
+        backer_rwa_share(
+            amount,
+            ctx.accounts.rwa_state.total_supply,
+            ctx.accounts.project.total_raised,
+        )
+    }
+
+    /// Read-only: returns `rwa_mint.supply - rwa_state.minted`. Negative when holders burned RWA directly through
+    /// the token program instead of retire_rwa_tokens. Does not modify state; `minted` stays authoritative for
+    /// revenue splits (see RwaState::minted).
+    pub fn reconcile_minted(ctx: Context<ReconcileMinted>) -> Result<i64> {
+        let delta =
+            minted_supply_delta(ctx.accounts.rwa_mint.supply, ctx.accounts.rwa_state.minted)?;
+        msg!(
+            "RWA mint supply {} vs minted {} for project {}: delta {}",
+            ctx.accounts.rwa_mint.supply,
+            ctx.accounts.rwa_state.minted,
+            ctx.accounts.rwa_state.project,
+            delta
+        );
+        Ok(delta)
+    }
+
+    /// Permissionless: grow an RwaState created before `holder_count` existed to RWA_STATE_SPACE. The count starts
+    /// at 0, so claims made before the migration are not reflected. Legacy states must be migrated before claims,
+    /// revenue deposits or anything else can load them.
+    pub fn migrate_rwa_state(ctx: Context<MigrateRwaState>) -> Result<()> {
+        let state_info = ctx.accounts.rwa_state.to_account_info();
+        let state = {
+            let data = state_info.try_borrow_data()?;
+            migrate_rwa_state_data(&data)?
+        };
+        write_grown_account(
+            &state_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            RWA_STATE_SPACE,
+            &state,
+        )?;
+        msg!("RwaState {} migrated", state_info.key());
+        Ok(())
+    }
+
     pub fn close_distribution(ctx: Context<CloseDistribution>) -> Result<()> {
         let state = &mut ctx.accounts.rwa_state;
         require!(!state.mint_frozen, RwaError::MintFrozen);
@@ -645,6 +1129,38 @@ pub mod rwa_token {
         Ok(())
     }
 
+    /// Holder burns `amount` of their RWA tokens and retires them from `total_supply` and `minted`, growing every
+    /// remaining holder's share of future revenue. Only after close_distribution, so unclaimed backers' shares
+    /// (computed from total_supply) are not affected. Used by revenue_distribution::execute_buyback.
+    pub fn retire_rwa_tokens(ctx: Context<RetireRwaTokens>, amount: u64) -> Result<()> {
+        let state = &ctx.accounts.rwa_state;
+        require!(state.mint_frozen, RwaError::DistributionNotClosed);
+        let (total_supply, minted) = retire_supply(state.total_supply, state.minted, amount)?;
+
+        anchor_spl::token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.rwa_mint.to_account_info(),
+                    from: ctx.accounts.holder_rwa_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let state = &mut ctx.accounts.rwa_state;
+        state.total_supply = total_supply;
+        state.minted = minted;
+        msg!(
+            "Retired {} RWA tokens for project {} (supply now {})",
+            amount,
+            state.project,
+            total_supply
+        );
+        Ok(())
+    }
+
     /// One-time init of Metaplex Token Metadata for the RWA mint. Callable only by `rwa_state.authority`.
     /// Name/symbol/uri are bounded; second call fails (metadata guard prevents re-init).
     pub fn initialize_rwa_metadata(
@@ -709,15 +1225,12 @@ pub mod rwa_token {
 
     /// Called by governance when the last milestone is released, after initialize_rwa_mint_by_governance.
     /// Creates Metaplex metadata so wallets can display name, symbol, and image.
-    /// Only the config's governance_release_authority may call this.
+    /// Only the config's governance_release_authority may call this. If the metadata account already exists
+    /// (created by another path), CreateV1 is skipped and only the guard is initialized, so the call can be retried.
     pub fn initialize_rwa_metadata_by_governance(
         ctx: Context<InitializeRwaMetadataByGovernance>,
     ) -> Result<()> {
-        require!(
-            ctx.accounts.release_authority.key()
-                == ctx.accounts.config.governance_release_authority,
-            RwaError::NotReleaseAuthority
-        );
+        require_release_authority(&ctx.accounts.config, &ctx.accounts.release_authority.key())?;
         require!(
             ctx.accounts.project.status == ProjectStatus::Completed,
             RwaError::ProjectNotCompleted
@@ -750,6 +1263,17 @@ pub mod rwa_token {
         );
         require!(uri.len() <= MAX_URI_LEN, RwaError::MetadataUriTooLong);
 
+        if metadata_initialized(
+            ctx.accounts.metadata.owner,
+            ctx.accounts.metadata.data_len(),
+        ) {
+            msg!(
+                "RWA metadata already exists for mint {}; guard initialized only",
+                ctx.accounts.rwa_mint.key()
+            );
+            return Ok(());
+        }
+
         let (_, bump) = Pubkey::find_program_address(
             &[b"rwa_mint_authority", state.project.as_ref()],
             ctx.program_id,
@@ -838,11 +1362,44 @@ pub enum RwaError {
     InvalidMintPda,
     #[msg("Transfer hook program does not match RwaConfig")]
     InvalidTransferHookProgram,
+    #[msg("Distribution must be closed before retiring RWA supply")]
+    DistributionNotClosed,
+    #[msg("Strict splits: revenue split + artist split must equal 10000 bps")]
+    SplitNotExact,
+    #[msg("Account is not an RwaConfig")]
+    InvalidRwaConfig,
+    #[msg("Consolidated claim wallets or accounts are invalid")]
+    InvalidConsolidation,
+    #[msg("Strict completion: all milestones must be released and the escrow drained")]
+    ProjectNotFullyExecuted,
+    #[msg("Account is not an RwaState")]
+    InvalidRwaState,
+    #[msg("RwaState already uses the current layout")]
+    RwaStateAlreadyMigrated,
 }
 
 #[account]
 pub struct RwaConfig {
     pub transfer_hook_program_id: Pubkey,
+    /// When true, rights splits must sum to exactly 10_000 bps. Otherwise any remainder goes to the platform.
+    pub strict_splits: bool,
+    /// When true, initialize_rwa_mint also requires every milestone released and the project escrow empty.
+    pub strict_completion: bool,
+}
+
+impl RwaConfig {
+    /// Parse an RwaConfig of any historical length; missing strict_splits / strict_completion read as false.
+    /// Returns None if the discriminator or base layout doesn't match.
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        if data.len() < RWA_CONFIG_BASE_LEN || &data[..8] != RwaConfig::DISCRIMINATOR {
+            return None;
+        }
+        let mut buf = data.to_vec();
+        if buf.len() < RWA_CONFIG_SPACE {
+            buf.resize(RWA_CONFIG_SPACE, 0);
+        }
+        RwaConfig::try_deserialize(&mut &buf[..]).ok()
+    }
 }
 
 #[account]
@@ -850,8 +1407,15 @@ pub struct RwaState {
     pub project: Pubkey,
     pub authority: Pubkey,
     pub total_supply: u64,
+    /// RWA tokens minted by claims, less those retired via retire_rwa_tokens. Authoritative for revenue splits:
+    /// deposit_revenue snapshots it as the epoch's total_rwa_supply. Tokens burned directly through the token
+    /// program stay counted here (their revenue share is never claimed); reconcile_minted reports the gap.
     pub minted: u64,
     pub mint_frozen: bool,
+    /// Number of backers who have claimed RWA tokens (receipt or legacy path). Counts original
+    /// claimants only; secondary holders who receive tokens by transfer are not tracked. States migrated by
+    /// migrate_rwa_state count only claims made after the migration.
+    pub holder_count: u64,
 }
 
 #[account]
@@ -895,7 +1459,7 @@ pub struct InitializeRwaConfig<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32,
+        space = RWA_CONFIG_SPACE,
         seeds = [b"rwa_config"],
         bump,
     )]
@@ -932,6 +1496,10 @@ pub struct InitializeRwaRights<'info> {
     )]
     pub rwa_rights: Account<'info, RwaRights>,
 
+    /// CHECK: RwaConfig PDA; may be uninitialized (lenient splits). Parsed in instruction.
+    #[account(seeds = [b"rwa_config"], bump)]
+    pub rwa_config: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -943,7 +1511,9 @@ pub struct InitializeRwaRightsByGovernance<'info> {
     /// Must equal config.governance_release_authority (validated in instruction).
     pub release_authority: Signer<'info>,
 
-    pub config: Account<'info, project_escrow::Config>,
+    /// CHECK: project_escrow Config PDA; parsed with Config::from_account_data so older layouts still load.
+    #[account(seeds = [b"config"], bump, seeds::program = project_escrow::ID)]
+    pub config: UncheckedAccount<'info>,
     pub project: Account<'info, Project>,
 
     #[account(
@@ -962,6 +1532,48 @@ pub struct InitializeRwaRightsByGovernance<'info> {
     )]
     pub rwa_rights: Account<'info, RwaRights>,
 
+    /// CHECK: RwaConfig PDA; may be uninitialized (lenient splits). Parsed in instruction.
+    #[account(seeds = [b"rwa_config"], bump)]
+    pub rwa_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetSplitPolicy<'info> {
+    /// Must be the program upgrade authority (validated in instruction).
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: owner checked here; parsed in instruction (may be the pre-strict_splits layout).
+    #[account(mut, seeds = [b"rwa_config"], bump, owner = crate::ID)]
+    pub rwa_config: UncheckedAccount<'info>,
+
+    /// CHECK: validated in instruction (must equal ctx.program_id)
+    pub program_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated in instruction (must match program_account's programdata_address)
+    pub program_data_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCompletionPolicy<'info> {
+    /// Must be the program upgrade authority (validated in instruction).
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: owner checked here; parsed in instruction (may be an older, shorter layout).
+    #[account(mut, seeds = [b"rwa_config"], bump, owner = crate::ID)]
+    pub rwa_config: UncheckedAccount<'info>,
+
+    /// CHECK: validated in instruction (must equal ctx.program_id)
+    pub program_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated in instruction (must match program_account's programdata_address)
+    pub program_data_account: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -984,6 +1596,15 @@ pub struct UpdateRwaRights<'info> {
     pub rwa_rights: Account<'info, RwaRights>,
 }
 
+#[derive(Accounts)]
+pub struct VerifyTerms<'info> {
+    #[account(
+        seeds = [b"rwa_rights", rwa_rights.project.as_ref()],
+        bump,
+    )]
+    pub rwa_rights: Account<'info, RwaRights>,
+}
+
 #[derive(Accounts)]
 pub struct InitializeRwaMint<'info> {
     #[account(mut)]
@@ -991,20 +1612,26 @@ pub struct InitializeRwaMint<'info> {
 
     pub project: Account<'info, Project>,
 
+    /// Project escrow; must be empty when RwaConfig.strict_completion is set.
+    #[account(
+        seeds = [b"escrow", project.key().as_ref()],
+        bump,
+        seeds::program = project_escrow::ID,
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 32 + 8 + 8 + 1,
+        space = RWA_STATE_SPACE,
         seeds = [b"rwa_state", project.key().as_ref()],
         bump,
     )]
     pub rwa_state: Account<'info, RwaState>,
 
-    #[account(
-        seeds = [b"rwa_config"],
-        bump,
-    )]
-    pub rwa_config: Account<'info, RwaConfig>,
+    /// CHECK: owner checked here; parsed in instruction (may be an older, shorter layout).
+    #[account(seeds = [b"rwa_config"], bump, owner = crate::ID)]
+    pub rwa_config: UncheckedAccount<'info>,
 
     /// Created manually with TransferHook extension; address validated in handler.
     /// CHECK: Validated in instruction (must be rwa_mint PDA)
@@ -1036,23 +1663,23 @@ pub struct InitializeRwaMintByGovernance<'info> {
     /// Must equal config.governance_release_authority (validated in handler).
     pub release_authority: Signer<'info>,
 
-    pub config: Account<'info, project_escrow::Config>,
+    /// CHECK: project_escrow Config PDA; parsed with Config::from_account_data so older layouts still load.
+    #[account(seeds = [b"config"], bump, seeds::program = project_escrow::ID)]
+    pub config: UncheckedAccount<'info>,
     pub project: Account<'info, Project>,
 
     #[account(
         init,
         payer = payer,
-        space = 8 + 32 + 32 + 8 + 8 + 1,
+        space = RWA_STATE_SPACE,
         seeds = [b"rwa_state", project.key().as_ref()],
         bump,
     )]
     pub rwa_state: Account<'info, RwaState>,
 
-    #[account(
-        seeds = [b"rwa_config"],
-        bump,
-    )]
-    pub rwa_config: Account<'info, RwaConfig>,
+    /// CHECK: owner checked here; parsed in instruction (may be an older, shorter layout).
+    #[account(seeds = [b"rwa_config"], bump, owner = crate::ID)]
+    pub rwa_config: UncheckedAccount<'info>,
 
     /// Created manually with TransferHook extension; address validated in handler.
     /// CHECK: Validated in instruction (must be rwa_mint PDA)
@@ -1187,6 +1814,95 @@ pub struct ClaimRwaTokensLegacy<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Same as ClaimRwaTokensLegacy; extra wallets' Backer and ClaimRecord accounts come in remaining_accounts.
+#[derive(Accounts)]
+pub struct ClaimRwaTokensConsolidated<'info> {
+    #[account(mut)]
+    pub backer: Signer<'info>,
+
+    #[account(
+        constraint = backer_account.wallet == backer.key() @ RwaError::NotBacker,
+        constraint = backer_account.project == rwa_state.project @ RwaError::WrongProject,
+    )]
+    pub backer_account: Account<'info, Backer>,
+
+    #[account(constraint = project.key() == rwa_state.project)]
+    pub project: Account<'info, Project>,
+
+    #[account(
+        mut,
+        seeds = [b"rwa_state", rwa_state.project.as_ref()],
+        bump,
+    )]
+    pub rwa_state: Account<'info, RwaState>,
+
+    #[account(
+        mut,
+        seeds = [b"rwa_mint", rwa_state.project.as_ref()],
+        bump,
+    )]
+    pub rwa_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA for mint authority
+    #[account(seeds = [b"rwa_mint_authority", rwa_state.project.as_ref()], bump)]
+    pub rwa_mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = backer,
+        space = 8 + 1,
+        seeds = [b"claim", rwa_state.project.as_ref(), backer.key().as_ref()],
+        bump,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = backer,
+        associated_token::mint = rwa_mint,
+        associated_token::authority = backer,
+        associated_token::token_program = token_program,
+    )]
+    pub backer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BackerSharePreview<'info> {
+    #[account(constraint = backer_account.project == rwa_state.project @ RwaError::WrongProject)]
+    pub backer_account: Account<'info, Backer>,
+
+    #[account(constraint = project.key() == rwa_state.project @ RwaError::WrongProject)]
+    pub project: Account<'info, Project>,
+
+    #[account(seeds = [b"rwa_state", rwa_state.project.as_ref()], bump)]
+    pub rwa_state: Account<'info, RwaState>,
+}
+
+#[derive(Accounts)]
+pub struct ReconcileMinted<'info> {
+    #[account(seeds = [b"rwa_state", rwa_state.project.as_ref()], bump)]
+    pub rwa_state: Account<'info, RwaState>,
+
+    #[account(seeds = [b"rwa_mint", rwa_state.project.as_ref()], bump)]
+    pub rwa_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateRwaState<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner checked here; discriminator and size checked in migrate_rwa_state_data (legacy layouts do not deserialize).
+    #[account(mut, owner = crate::ID)]
+    pub rwa_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct CloseDistribution<'info> {
     pub authority: Signer<'info>,
@@ -1195,6 +1911,34 @@ pub struct CloseDistribution<'info> {
     pub rwa_state: Account<'info, RwaState>,
 }
 
+#[derive(Accounts)]
+pub struct RetireRwaTokens<'info> {
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rwa_state", rwa_state.project.as_ref()],
+        bump,
+    )]
+    pub rwa_state: Account<'info, RwaState>,
+
+    #[account(
+        mut,
+        seeds = [b"rwa_mint", rwa_state.project.as_ref()],
+        bump,
+    )]
+    pub rwa_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = holder_rwa_account.owner == holder.key(),
+        constraint = holder_rwa_account.mint == rwa_mint.key(),
+    )]
+    pub holder_rwa_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 #[derive(Accounts)]
 pub struct InitializeRwaMetadata<'info> {
     #[account(mut)]
@@ -1250,7 +1994,9 @@ pub struct InitializeRwaMetadataByGovernance<'info> {
     /// Must equal config.governance_release_authority (validated in handler).
     pub release_authority: Signer<'info>,
 
-    pub config: Account<'info, project_escrow::Config>,
+    /// CHECK: project_escrow Config PDA; parsed with Config::from_account_data so older layouts still load.
+    #[account(seeds = [b"config"], bump, seeds::program = project_escrow::ID)]
+    pub config: UncheckedAccount<'info>,
     pub project: Account<'info, Project>,
 
     #[account(constraint = rwa_state.authority == project.artist)]
@@ -1318,9 +2064,184 @@ mod tests {
         assert!(10_001u16.saturating_add(0) > 10_000);
     }
 
+    #[test]
+    fn test_strict_splits_require_exact_total() {
+        // Lenient (default): remainder allowed, goes to the platform.
+        assert!(validate_split(7_000, 2_000, false).is_ok());
+        assert!(validate_split(7_000, 3_001, false).is_err());
+        // Strict: a sub-100% split is rejected, an exact one accepted.
+        assert!(validate_split(7_000, 2_000, true).is_err());
+        assert!(validate_split(7_000, 3_000, true).is_ok());
+        assert!(validate_split(0, 10_000, true).is_ok());
+        assert!(validate_split(7_000, 3_001, true).is_err());
+    }
+
+    #[test]
+    fn test_rwa_config_legacy_layout_is_lenient() {
+        let mut legacy = RwaConfig::DISCRIMINATOR.to_vec();
+        legacy.extend_from_slice(&[7u8; 32]);
+        let parsed = RwaConfig::from_account_data(&legacy).unwrap();
+        assert_eq!(
+            parsed.transfer_hook_program_id,
+            Pubkey::new_from_array([7u8; 32])
+        );
+        assert!(!parsed.strict_splits);
+        assert!(!parsed.strict_completion);
+        assert!(RwaConfig::from_account_data(&legacy[..RWA_CONFIG_BASE_LEN - 1]).is_none());
+
+        // A config grown for strict_splits but predating strict_completion.
+        legacy.push(1);
+        let parsed = RwaConfig::from_account_data(&legacy).unwrap();
+        assert!(parsed.strict_splits);
+        assert!(!parsed.strict_completion);
+    }
+
+    #[test]
+    fn test_strict_completion_rejects_force_completed_undrained_project() {
+        let percentages = [50, 50, 0, 0, 0];
+        // Force-completed with every milestone released but funds left in escrow.
+        assert!(
+            check_rwa_mint_ready(ProjectStatus::Completed, 2, &percentages, 1_000, false).is_ok()
+        );
+        assert!(
+            check_rwa_mint_ready(ProjectStatus::Completed, 2, &percentages, 1_000, true).is_err()
+        );
+        // Marked Completed without all milestones released.
+        assert!(check_rwa_mint_ready(ProjectStatus::Completed, 1, &percentages, 0, true).is_err());
+        // Fully executed.
+        assert!(check_rwa_mint_ready(ProjectStatus::Completed, 2, &percentages, 0, true).is_ok());
+        assert!(check_rwa_mint_ready(ProjectStatus::Active, 2, &percentages, 0, false).is_err());
+    }
+
+    #[test]
+    fn test_backer_share_preview_matches_claim_math() {
+        let total_supply = 1_000_000u64;
+        let amounts = [500u64, 1_250, 3_333, 94_917];
+        let total_raised: u64 = amounts.iter().sum();
+        let mut distributed = 0u64;
+        for amount in amounts {
+            let share = backer_rwa_share(amount, total_supply, total_raised).unwrap();
+            let claim_math = (amount as u128 * total_supply as u128 / total_raised as u128) as u64;
+            assert_eq!(share, claim_math);
+            distributed += share;
+        }
+        // Rounding down never over-allocates the supply.
+        assert!(distributed <= total_supply);
+        assert!(total_supply - distributed < amounts.len() as u64);
+        assert!(backer_rwa_share(1, total_supply, 0).is_err());
+    }
+
+    #[test]
+    fn test_consolidated_claim_merges_two_backer_records() {
+        let project = Pubkey::new_unique();
+        let backer = |wallet: Pubkey, amount: u64| Backer {
+            wallet,
+            project,
+            amount,
+            claimed_rwa: false,
+            fee_paid: 0,
+            treasury_fee_paid: 0,
+        };
+        let primary = backer(Pubkey::new_unique(), 300);
+        let second = backer(Pubkey::new_unique(), 700);
+
+        let merged =
+            consolidated_backer_amount(&project, &primary, &[(second.clone(), false)]).unwrap();
+        assert_eq!(merged, 1_000);
+        // One claim over both records gets the combined share of a 10_000 supply raised at 4_000.
+        assert_eq!(backer_rwa_share(merged, 10_000, 4_000).unwrap(), 2_500);
+
+        // A wallet that already claimed, repeats the primary, or backs another project is rejected.
+        assert!(consolidated_backer_amount(&project, &primary, &[(second.clone(), true)]).is_err());
+        assert!(
+            consolidated_backer_amount(&project, &primary, &[(primary.clone(), false)]).is_err()
+        );
+        let mut other = second.clone();
+        other.project = Pubkey::new_unique();
+        assert!(consolidated_backer_amount(&project, &primary, &[(other, false)]).is_err());
+
+        assert_eq!(consolidated_wallet_count(6).unwrap(), 2);
+        assert!(consolidated_wallet_count(5).is_err());
+        assert!(consolidated_wallet_count(3 * (MAX_CONSOLIDATED_WALLETS + 1)).is_err());
+    }
+
+    #[test]
+    fn test_retire_supply_reduces_supply_and_minted() {
+        assert_eq!(retire_supply(1_000, 1_000, 100).unwrap(), (900, 900));
+        assert_eq!(retire_supply(1_000, 998, 10).unwrap(), (990, 988));
+        assert!(retire_supply(1_000, 50, 51).is_err());
+        assert!(retire_supply(1_000, 1_000, 0).is_err());
+    }
+
+    #[test]
+    fn test_minted_supply_delta_detects_external_burn() {
+        let minted = 1_000u64;
+        assert_eq!(minted_supply_delta(minted, minted).unwrap(), 0);
+        // A holder burned 40 directly through the token program: supply drops, minted does not.
+        assert_eq!(minted_supply_delta(minted - 40, minted).unwrap(), -40);
+        // retire_rwa_tokens keeps both in step.
+        let (_, retired_minted) = retire_supply(1_000, minted, 60).unwrap();
+        assert_eq!(
+            minted_supply_delta(minted - 100, retired_minted).unwrap(),
+            -40
+        );
+        assert!(minted_supply_delta(u64::MAX, 0).is_err());
+    }
+
+    #[test]
+    fn test_existing_metadata_skips_create() {
+        // Uninitialized PDA: system-owned and empty, so CreateV1 runs.
+        assert!(!metadata_initialized(&anchor_lang::system_program::ID, 0));
+        // Already created by Metaplex: skipped on retry.
+        assert!(metadata_initialized(&MPL_TOKEN_METADATA_ID, 679));
+        // Data under any other owner is not Metaplex metadata.
+        assert!(!metadata_initialized(&Pubkey::new_unique(), 679));
+        assert!(!metadata_initialized(&MPL_TOKEN_METADATA_ID, 0));
+    }
+
     #[test]
     fn test_rwa_rights_string_limits() {
         const _: () = assert!(MAX_TERMS_URI_LEN == 200);
         const _: () = assert!(MAX_JURISDICTION_LEN == 50);
     }
+
+    #[test]
+    fn test_migrate_rwa_state_data_zeroes_holder_count() {
+        let state = RwaState {
+            project: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            total_supply: 1_000_000,
+            minted: 400_000,
+            mint_frozen: false,
+            holder_count: 2,
+        };
+        let mut current = Vec::new();
+        state.try_serialize(&mut current).unwrap();
+        assert_eq!(current.len(), RWA_STATE_SPACE);
+        // Current-layout states are left alone.
+        assert!(migrate_rwa_state_data(&current).is_err());
+
+        let legacy = &current[..RWA_STATE_BASE_LEN];
+        let migrated = migrate_rwa_state_data(legacy).unwrap();
+        assert_eq!(migrated.project, state.project);
+        assert_eq!(migrated.minted, 400_000);
+        assert_eq!(migrated.holder_count, 0);
+
+        // Anything without the RwaState discriminator is rejected.
+        let mut other = legacy.to_vec();
+        other[0] ^= 1;
+        assert!(migrate_rwa_state_data(&other).is_err());
+    }
+
+    #[test]
+    fn test_terms_hash_matches() {
+        let document = b"TasteMaker ownership terms v1";
+        let expected = solana_sha256_hasher::hash(document).to_bytes();
+        assert!(terms_hash_matches(&expected, document));
+        assert!(!terms_hash_matches(
+            &expected,
+            b"TasteMaker ownership terms v2"
+        ));
+        assert!(!terms_hash_matches(&[0u8; 32], b""));
+    }
 }
@@ -1,9 +1,22 @@
 //! TasteMaker $TASTE platform token.
 //! Token-2022 compatible (use token_2022 program when deploying). 9 decimals.
 //! Max supply 1B $TASTE (whitepaper). Mint authority can be revoked after TGE.
+//! Team/investor allocations vest on-chain: the mint authority mints a schedule's total into a PDA vault at
+//! creation (so it counts against MAX_SUPPLY and survives revoking the mint authority) and the beneficiary
+//! pulls the vested part with claim_vested.
+//! An optional MintPolicy (seeds = [b"mint_policy"]) caps how much can be minted per rolling window.
+//! Holders can lock $TASTE in a StakeAccount; governance reads it to boost vote weight.
+//! ProtocolState (seeds = [b"protocol_state"]) is the platform-wide pause switch, set by the upgrade authority.
+//! While paused, fund_project, release_milestone, accept_offer and deposit_revenue fail in their programs.
 
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, MintTo, SetAuthority, TokenAccount, TokenInterface};
+use anchor_spl::token_interface::{
+    Mint, MintTo, SetAuthority, TokenAccount, TokenInterface, TransferChecked,
+};
+use mpl_token_metadata::{
+    accounts::Metadata, instructions::CreateV1CpiBuilder, types::TokenStandard,
+    ID as MPL_TOKEN_METADATA_ID,
+};
 
 // Anchor programs must be deployed at their declared ID.
 // We support devnet vs localnet IDs via a build-time feature so CI/local tests keep working.
@@ -15,6 +28,183 @@ declare_id!("2c6qsaK5o1mjUxSvJmfCDzfCcaim8c9hEmNZrBbc4Bxo");
 
 const DECIMALS: u8 = 9;
 pub const MAX_SUPPLY: u64 = 1_000_000_000 * (10u64).pow(DECIMALS as u32);
+pub const VESTING_SCHEDULE_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 8;
+pub const MINT_POLICY_SPACE: usize = 8 + 8 + 8 + 8 + 8;
+pub const STAKE_ACCOUNT_SPACE: usize = 8 + 32 + 8 + 8 + 2 + 1;
+pub const PROTOCOL_STATE_SPACE: usize = 8 + 1;
+
+/// Longest stake lock; locking this long earns MAX_STAKE_MULTIPLIER_BPS.
+pub const MAX_STAKE_LOCK_SECS: i64 = 365 * 24 * 60 * 60;
+/// Multiplier (bps) for a stake locked MAX_STAKE_LOCK_SECS: 2x. A lock of 0 earns 1x.
+pub const MAX_STAKE_MULTIPLIER_BPS: u16 = 20_000;
+
+/// Upgradeable loader: Program variant.
+const UPGRADEABLE_LOADER_PROGRAM_STATE: u8 = 2;
+/// Upgradeable loader: ProgramData variant.
+const UPGRADEABLE_LOADER_PROGRAM_DATA_STATE: u8 = 3;
+const MIN_PROGRAM_ACCOUNT_LEN: usize = 36;
+const MIN_PROGRAMDATA_METADATA_LEN: usize = 45;
+
+/// Max lengths for $TASTE metadata (aligned with Metaplex Token Metadata).
+const MAX_NAME_LEN: usize = 32;
+const MAX_SYMBOL_LEN: usize = 10;
+const MAX_URI_LEN: usize = 200;
+
+fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    require!(name.len() <= MAX_NAME_LEN, TasteError::MetadataNameTooLong);
+    require!(
+        symbol.len() <= MAX_SYMBOL_LEN,
+        TasteError::MetadataSymbolTooLong
+    );
+    require!(uri.len() <= MAX_URI_LEN, TasteError::MetadataUriTooLong);
+    Ok(())
+}
+
+fn require_upgrade_authority(
+    program_id: &Pubkey,
+    program_account_key: &Pubkey,
+    program_account_data: &[u8],
+    program_data_account_key: &Pubkey,
+    program_data_account_data: &[u8],
+    authority_key: &Pubkey,
+) -> Result<()> {
+    require!(
+        program_account_key == program_id,
+        TasteError::NotUpgradeAuthority
+    );
+    require!(
+        program_account_data.len() >= MIN_PROGRAM_ACCOUNT_LEN
+            && u32::from_le_bytes(program_account_data[0..4].try_into().unwrap())
+                == UPGRADEABLE_LOADER_PROGRAM_STATE as u32,
+        TasteError::NotUpgradeAuthority
+    );
+    let programdata_address =
+        Pubkey::new_from_array(program_account_data[4..36].try_into().unwrap());
+    require!(
+        program_data_account_key == &programdata_address,
+        TasteError::NotUpgradeAuthority
+    );
+    require!(
+        program_data_account_data.len() >= MIN_PROGRAMDATA_METADATA_LEN
+            && u32::from_le_bytes(program_data_account_data[0..4].try_into().unwrap())
+                == UPGRADEABLE_LOADER_PROGRAM_DATA_STATE as u32,
+        TasteError::NotUpgradeAuthority
+    );
+    let option_byte = program_data_account_data[12];
+    require!(option_byte == 1, TasteError::NotUpgradeAuthority);
+    let upgrade_authority =
+        Pubkey::new_from_array(program_data_account_data[13..45].try_into().unwrap());
+    require!(
+        upgrade_authority == *authority_key,
+        TasteError::NotUpgradeAuthority
+    );
+    Ok(())
+}
+
+/// Address of the ProtocolState PDA.
+pub fn protocol_state_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"protocol_state"], &crate::ID).0
+}
+
+/// Whether the protocol is paused, read from the account passed as ProtocolState. Other programs call this
+/// on their optional `protocol_state` account. A ProtocolState that was never created reads as not paused.
+pub fn protocol_paused(info: &AccountInfo) -> Result<bool> {
+    require!(
+        info.key() == protocol_state_address(),
+        TasteError::InvalidProtocolState
+    );
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(false);
+    }
+    let data = info.try_borrow_data()?;
+    let state = ProtocolState::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(TasteError::InvalidProtocolState))?;
+    Ok(state.paused)
+}
+
+/// Count `amount` against the policy's current window, starting a new window once window_secs have passed.
+/// max_per_window == 0 means no cap.
+pub fn apply_mint_policy(policy: &mut MintPolicy, amount: u64, now: i64) -> Result<()> {
+    if policy.max_per_window == 0 {
+        return Ok(());
+    }
+    let window_end = policy
+        .window_start
+        .checked_add(policy.window_secs)
+        .ok_or(TasteError::Overflow)?;
+    if now >= window_end {
+        policy.window_start = now;
+        policy.minted_in_window = 0;
+    }
+    let minted = policy
+        .minted_in_window
+        .checked_add(amount)
+        .ok_or(TasteError::Overflow)?;
+    require!(minted <= policy.max_per_window, TasteError::MintRateLimited);
+    policy.minted_in_window = minted;
+    Ok(())
+}
+
+/// A new policy needs a positive cap and window. Once a policy exists (`current`), set_mint_policy may only tighten
+/// it: a cap no higher and a window no shorter than the current ones.
+pub fn check_mint_policy_update(
+    current: Option<&MintPolicy>,
+    max_per_window: u64,
+    window_secs: i64,
+) -> Result<()> {
+    require!(
+        max_per_window > 0 && window_secs > 0,
+        TasteError::InvalidMintPolicy
+    );
+    if let Some(current) = current {
+        require!(
+            max_per_window <= current.max_per_window && window_secs >= current.window_secs,
+            TasteError::MintPolicyLoosened
+        );
+    }
+    Ok(())
+}
+
+/// Apply the MintPolicy PDA to a mint if it has been set; an uninitialized policy leaves minting uncapped.
+fn enforce_mint_policy(policy_info: &AccountInfo, amount: u64) -> Result<()> {
+    if policy_info.owner != &crate::ID || policy_info.data_is_empty() {
+        return Ok(());
+    }
+    let mut policy = {
+        let data = policy_info.try_borrow_data()?;
+        MintPolicy::try_deserialize(&mut &data[..])?
+    };
+    apply_mint_policy(&mut policy, amount, Clock::get()?.unix_timestamp)?;
+    let mut data = policy_info.try_borrow_mut_data()?;
+    policy.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Multiplier for a stake with `remaining_lock_secs` left: linear from 1x (10_000 bps) at no lock to
+/// MAX_STAKE_MULTIPLIER_BPS at MAX_STAKE_LOCK_SECS or more.
+pub fn stake_multiplier_bps(remaining_lock_secs: i64) -> u16 {
+    let lock = remaining_lock_secs.clamp(0, MAX_STAKE_LOCK_SECS) as u64;
+    let bonus = (MAX_STAKE_MULTIPLIER_BPS as u64 - 10_000) * lock / MAX_STAKE_LOCK_SECS as u64;
+    (10_000 + bonus) as u16
+}
+
+/// Amount of a schedule vested at `now`: 0 before the cliff, linear from start_ts over duration_secs, then total.
+pub fn vested_amount(
+    total: u64,
+    start_ts: i64,
+    cliff_secs: i64,
+    duration_secs: i64,
+    now: i64,
+) -> u64 {
+    let elapsed = now.saturating_sub(start_ts);
+    if elapsed < cliff_secs || elapsed <= 0 {
+        return 0;
+    }
+    if elapsed >= duration_secs {
+        return total;
+    }
+    (total as u128 * elapsed as u128 / duration_secs as u128) as u64
+}
 
 #[error_code]
 pub enum TasteError {
@@ -22,6 +212,44 @@ pub enum TasteError {
     ExceedsMaxSupply,
     #[msg("Signer is not the mint authority")]
     InvalidMintAuthority,
+    #[msg("Vesting needs total > 0, duration > 0 and cliff within duration")]
+    InvalidVestingSchedule,
+    #[msg("Nothing vested to claim")]
+    NothingToClaim,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("New mint authority must be a different, non-default key")]
+    InvalidNewAuthority,
+    #[msg("Mint exceeds the per-window emission cap")]
+    MintRateLimited,
+    #[msg("Mint policy cap and window must be positive")]
+    InvalidMintPolicy,
+    #[msg("Signer is not the approved delegate of the source account")]
+    NotApprovedDelegate,
+    #[msg("Burn exceeds the delegated amount")]
+    ExceedsDelegatedAmount,
+    #[msg("Metadata name too long")]
+    MetadataNameTooLong,
+    #[msg("Metadata symbol too long")]
+    MetadataSymbolTooLong,
+    #[msg("Metadata URI too long")]
+    MetadataUriTooLong,
+    #[msg("Invalid metadata account")]
+    InvalidMetadataAccount,
+    #[msg("Invalid token metadata program")]
+    InvalidTokenMetadataProgram,
+    #[msg("Stake amount must be positive and lock at most MAX_STAKE_LOCK_SECS")]
+    InvalidStake,
+    #[msg("Stake is still locked")]
+    StakeLocked,
+    #[msg("Nothing staked")]
+    NothingStaked,
+    #[msg("Only program upgrade authority can pause the protocol")]
+    NotUpgradeAuthority,
+    #[msg("Account is not the ProtocolState PDA")]
+    InvalidProtocolState,
+    #[msg("Mint policy can only be tightened: no higher cap, no shorter window")]
+    MintPolicyLoosened,
 }
 
 #[program]
@@ -33,6 +261,51 @@ pub mod taste_token {
         Ok(())
     }
 
+    /// One-time init of Metaplex Token Metadata for the $TASTE mint (name "TasteMaker", symbol "TASTE").
+    /// Mint authority only; a second call fails on the metadata guard.
+    pub fn initialize_taste_metadata(
+        ctx: Context<InitializeTasteMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        validate_metadata(&name, &symbol, &uri)?;
+        let (metadata_pda, _) = Metadata::find_pda(&ctx.accounts.mint.key());
+        require!(
+            ctx.accounts.metadata.key() == metadata_pda,
+            TasteError::InvalidMetadataAccount
+        );
+        require!(
+            ctx.accounts.token_metadata_program.key() == MPL_TOKEN_METADATA_ID,
+            TasteError::InvalidTokenMetadataProgram
+        );
+
+        CreateV1CpiBuilder::new(ctx.accounts.token_metadata_program.as_ref())
+            .metadata(ctx.accounts.metadata.as_ref())
+            .master_edition(None)
+            .mint(ctx.accounts.mint.as_ref(), false)
+            .authority(ctx.accounts.mint_authority.as_ref())
+            .payer(ctx.accounts.mint_authority.as_ref())
+            .update_authority(ctx.accounts.mint_authority.as_ref(), true)
+            .system_program(ctx.accounts.system_program.as_ref())
+            .sysvar_instructions(ctx.accounts.sysvar_instructions.as_ref())
+            .spl_token_program(Some(ctx.accounts.token_program.as_ref()))
+            .name(name)
+            .symbol(symbol)
+            .uri(uri)
+            .seller_fee_basis_points(0)
+            .primary_sale_happened(false)
+            .is_mutable(true)
+            .token_standard(TokenStandard::Fungible)
+            .invoke()?;
+
+        msg!(
+            "$TASTE metadata initialized for mint {}",
+            ctx.accounts.mint.key()
+        );
+        Ok(())
+    }
+
     pub fn mint_to_treasury(ctx: Context<MintToTreasury>, amount: u64) -> Result<()> {
         let supply = ctx.accounts.mint.supply;
         require!(
@@ -42,6 +315,7 @@ pub mod taste_token {
                 <= MAX_SUPPLY,
             TasteError::ExceedsMaxSupply
         );
+        enforce_mint_policy(&ctx.accounts.mint_policy, amount)?;
         let cpi_accounts = MintTo {
             mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.treasury.to_account_info(),
@@ -62,6 +336,7 @@ pub mod taste_token {
                 <= MAX_SUPPLY,
             TasteError::ExceedsMaxSupply
         );
+        enforce_mint_policy(&ctx.accounts.mint_policy, amount)?;
         let cpi_accounts = MintTo {
             mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.recipient.to_account_info(),
@@ -89,6 +364,27 @@ pub mod taste_token {
         Ok(())
     }
 
+    /// Burn from another wallet's account as its approved delegate; Token-2022 decrements delegated_amount.
+    pub fn burn_from(ctx: Context<BurnFrom>, amount: u64) -> Result<()> {
+        require!(
+            amount <= ctx.accounts.source.delegated_amount,
+            TasteError::ExceedsDelegatedAmount
+        );
+        anchor_spl::token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token_interface::Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.source.to_account_info(),
+                    authority: ctx.accounts.delegate.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        msg!("Delegate burned {} $TASTE", amount);
+        Ok(())
+    }
+
     pub fn freeze_mint_authority(ctx: Context<FreezeMintAuthority>) -> Result<()> {
         let cpi_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -105,6 +401,296 @@ pub mod taste_token {
         msg!("Mint authority revoked for $TASTE mint");
         Ok(())
     }
+
+    /// Lock `amount` more $TASTE for at least `lock_secs`. Adding to a stake never shortens its lock; the
+    /// multiplier is recomputed from the remaining lock.
+    pub fn stake(ctx: Context<Stake>, amount: u64, lock_secs: i64) -> Result<()> {
+        require!(
+            amount > 0 && (0..=MAX_STAKE_LOCK_SECS).contains(&lock_secs),
+            TasteError::InvalidStake
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let unlock_ts = now
+            .checked_add(lock_secs)
+            .ok_or(TasteError::Overflow)?
+            .max(ctx.accounts.stake.unlock_ts);
+
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        let stake = &mut ctx.accounts.stake;
+        stake.owner = ctx.accounts.owner.key();
+        stake.amount = stake
+            .amount
+            .checked_add(amount)
+            .ok_or(TasteError::Overflow)?;
+        stake.unlock_ts = unlock_ts;
+        stake.multiplier_bps = stake_multiplier_bps(unlock_ts - now);
+        stake.bump = ctx.bumps.stake;
+        msg!(
+            "Staked {} $TASTE until {} ({} bps)",
+            stake.amount,
+            unlock_ts,
+            stake.multiplier_bps
+        );
+        Ok(())
+    }
+
+    /// Withdraw the whole stake once unlock_ts has passed.
+    pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
+        let stake = &ctx.accounts.stake;
+        require!(stake.amount > 0, TasteError::NothingStaked);
+        require!(
+            Clock::get()?.unix_timestamp >= stake.unlock_ts,
+            TasteError::StakeLocked
+        );
+        let amount = stake.amount;
+        let owner_key = stake.owner;
+        let seeds: &[&[u8]] = &[b"stake", owner_key.as_ref(), &[stake.bump]];
+        let signer_seeds = &[seeds];
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.stake.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        let stake = &mut ctx.accounts.stake;
+        stake.amount = 0;
+        stake.multiplier_bps = 0;
+        msg!("Unstaked {} $TASTE", amount);
+        Ok(())
+    }
+
+    /// Set the emission cap applied to every mint: at most max_per_window per window_secs. Once set, the cap can
+    /// only be tightened (see check_mint_policy_update) and the current window keeps its start and count, so holders
+    /// can rely on it. Mint authority only.
+    pub fn set_mint_policy(
+        ctx: Context<SetMintPolicy>,
+        max_per_window: u64,
+        window_secs: i64,
+    ) -> Result<()> {
+        let policy = &mut ctx.accounts.mint_policy;
+        // A freshly created policy is all zeroes; a set one always has a positive window.
+        let existing = policy.window_secs > 0;
+        check_mint_policy_update(existing.then_some(&**policy), max_per_window, window_secs)?;
+        if !existing {
+            policy.window_start = Clock::get()?.unix_timestamp;
+        }
+        policy.max_per_window = max_per_window;
+        policy.window_secs = window_secs;
+        msg!("Mint policy: max {} per {}s", max_per_window, window_secs);
+        Ok(())
+    }
+
+    /// Hand the mint authority to another key (e.g. a DAO multisig) without revoking minting.
+    pub fn transfer_mint_authority(
+        ctx: Context<TransferMintAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        require!(
+            new_authority != Pubkey::default()
+                && new_authority != ctx.accounts.mint_authority.key(),
+            TasteError::InvalidNewAuthority
+        );
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.mint_authority.to_account_info(),
+                account_or_mint: ctx.accounts.mint.to_account_info(),
+            },
+        );
+        anchor_spl::token_interface::set_authority(
+            cpi_ctx,
+            anchor_spl::token_interface::spl_token_2022::instruction::AuthorityType::MintTokens,
+            Some(new_authority),
+        )?;
+        msg!("Mint authority for $TASTE mint moved to {}", new_authority);
+        Ok(())
+    }
+
+    /// Mint authority locks `total` for `beneficiary` in a vesting vault. Nothing vests before
+    /// start_ts + cliff_secs; the full amount is vested at start_ts + duration_secs. `schedule_id` tells apart
+    /// several schedules for the same beneficiary (e.g. 0, 1, 2, ...).
+    pub fn create_vesting_schedule(
+        ctx: Context<CreateVestingSchedule>,
+        total: u64,
+        start_ts: i64,
+        cliff_secs: i64,
+        duration_secs: i64,
+        schedule_id: u64,
+    ) -> Result<()> {
+        require!(
+            total > 0 && duration_secs > 0 && (0..=duration_secs).contains(&cliff_secs),
+            TasteError::InvalidVestingSchedule
+        );
+        let supply = ctx.accounts.mint.supply;
+        require!(
+            supply
+                .checked_add(total)
+                .ok_or(TasteError::ExceedsMaxSupply)?
+                <= MAX_SUPPLY,
+            TasteError::ExceedsMaxSupply
+        );
+        enforce_mint_policy(&ctx.accounts.mint_policy, total)?;
+        let schedule = &mut ctx.accounts.schedule;
+        schedule.beneficiary = ctx.accounts.beneficiary.key();
+        schedule.total = total;
+        schedule.claimed = 0;
+        schedule.start_ts = start_ts;
+        schedule.cliff_secs = cliff_secs;
+        schedule.duration_secs = duration_secs;
+        schedule.bump = ctx.bumps.schedule;
+        schedule.schedule_id = schedule_id;
+
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.mint_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        anchor_spl::token_interface::mint_to(cpi_ctx, total)?;
+        msg!(
+            "Vesting schedule {}: {} $TASTE for {}",
+            schedule_id,
+            total,
+            schedule.beneficiary
+        );
+        Ok(())
+    }
+
+    /// Beneficiary pulls everything vested so far that has not been claimed yet.
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        let schedule = &ctx.accounts.schedule;
+        let vested = vested_amount(
+            schedule.total,
+            schedule.start_ts,
+            schedule.cliff_secs,
+            schedule.duration_secs,
+            Clock::get()?.unix_timestamp,
+        );
+        let amount = vested
+            .checked_sub(schedule.claimed)
+            .ok_or(TasteError::Overflow)?;
+        require!(amount > 0, TasteError::NothingToClaim);
+
+        let beneficiary_key = schedule.beneficiary;
+        let schedule_id = schedule.schedule_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            b"vesting",
+            beneficiary_key.as_ref(),
+            schedule_id.as_ref(),
+            &[schedule.bump],
+        ];
+        let signer_seeds = &[seeds];
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    authority: ctx.accounts.schedule.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        let schedule = &mut ctx.accounts.schedule;
+        schedule.claimed = schedule
+            .claimed
+            .checked_add(amount)
+            .ok_or(TasteError::Overflow)?;
+        msg!("Claimed {} vested $TASTE", amount);
+        Ok(())
+    }
+
+    /// Upgrade authority only: pause or resume the protocol. Creates ProtocolState on first use.
+    pub fn set_protocol_paused(ctx: Context<SetProtocolPaused>, paused: bool) -> Result<()> {
+        let program_account = ctx.accounts.program_account.try_borrow_data()?;
+        let program_data_account = ctx.accounts.program_data_account.try_borrow_data()?;
+        require_upgrade_authority(
+            ctx.program_id,
+            &ctx.accounts.program_account.key(),
+            &program_account,
+            &ctx.accounts.program_data_account.key(),
+            &program_data_account,
+            &ctx.accounts.authority.key(),
+        )?;
+        ctx.accounts.protocol_state.paused = paused;
+        msg!("Protocol paused: {}", paused);
+        Ok(())
+    }
+}
+
+/// One-time guard: once this PDA exists, initialize_taste_metadata cannot run again.
+#[account]
+pub struct TasteMetadataGuard {}
+
+/// Platform-wide pause switch (seeds = [b"protocol_state"]). Absent until set_protocol_paused is first called.
+#[account]
+pub struct ProtocolState {
+    pub paused: bool,
+}
+
+/// Emission cap (seeds = [b"mint_policy"]). Absent until set_mint_policy is first called.
+#[account]
+pub struct MintPolicy {
+    pub max_per_window: u64,
+    pub window_secs: i64,
+    pub window_start: i64,
+    pub minted_in_window: u64,
+}
+
+/// Locked $TASTE for one owner (seeds = [b"stake", owner]); tokens sit in the [b"stake_vault", stake] vault.
+#[account]
+pub struct StakeAccount {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub unlock_ts: i64,
+    /// Vote multiplier in bps, fixed when the stake was last topped up (see stake_multiplier_bps).
+    pub multiplier_bps: u16,
+    pub bump: u8,
+}
+
+impl StakeAccount {
+    /// Stake amount scaled by its multiplier; governance adds sqrt of this to a backer's vote weight.
+    pub fn boosted_amount(&self) -> u64 {
+        (self.amount as u128 * self.multiplier_bps as u128 / 10_000).min(u64::MAX as u128) as u64
+    }
+}
+
+/// Vesting schedule for one beneficiary (seeds = [b"vesting", beneficiary, schedule_id (u64 LE)]). The vault it
+/// owns holds total - claimed.
+#[account]
+pub struct VestingSchedule {
+    pub beneficiary: Pubkey,
+    pub total: u64,
+    pub claimed: u64,
+    pub start_ts: i64,
+    pub cliff_secs: i64,
+    pub duration_secs: i64,
+    pub bump: u8,
+    pub schedule_id: u64,
 }
 
 #[derive(Accounts)]
@@ -141,6 +727,47 @@ pub struct InitializeMint<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct InitializeTasteMetadata<'info> {
+    #[account(mut)]
+    pub mint_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"taste_mint"],
+        bump,
+        constraint = mint.mint_authority == Some(mint_authority.key()).into() @ TasteError::InvalidMintAuthority
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// One-time guard: init here so second call fails (account already exists).
+    #[account(
+        init,
+        payer = mint_authority,
+        space = 8,
+        seeds = [b"taste_metadata"],
+        bump,
+    )]
+    pub metadata_guard: Account<'info, TasteMetadataGuard>,
+
+    /// Metaplex metadata PDA (['metadata', MPL_TOKEN_METADATA_ID, mint]); validated in handler.
+    /// CHECK: Validated against Metadata::find_pda(mint.key()) in instruction
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Metaplex Token Metadata program
+    /// CHECK: Validated in instruction (must be MPL_TOKEN_METADATA_ID)
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Sysvar Instructions (required by Metaplex CreateV1)
+    /// CHECK: Required by Metaplex
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 #[derive(Accounts)]
 pub struct MintToTreasury<'info> {
     pub mint_authority: Signer<'info>,
@@ -154,6 +781,11 @@ pub struct MintToTreasury<'info> {
     #[account(mut)]
     pub treasury: InterfaceAccount<'info, TokenAccount>,
 
+    /// MintPolicy PDA; may be uninitialized, in which case minting is uncapped.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(mut, seeds = [b"mint_policy"], bump)]
+    pub mint_policy: UncheckedAccount<'info>,
+
     pub token_program: Interface<'info, TokenInterface>,
 }
 
@@ -170,6 +802,11 @@ pub struct MintToRecipient<'info> {
     #[account(mut)]
     pub recipient: InterfaceAccount<'info, TokenAccount>,
 
+    /// MintPolicy PDA; may be uninitialized, in which case minting is uncapped.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(mut, seeds = [b"mint_policy"], bump)]
+    pub mint_policy: UncheckedAccount<'info>,
+
     pub token_program: Interface<'info, TokenInterface>,
 }
 
@@ -186,6 +823,194 @@ pub struct FreezeMintAuthority<'info> {
     pub token_program: Interface<'info, TokenInterface>,
 }
 
+#[derive(Accounts)]
+pub struct SetMintPolicy<'info> {
+    #[account(mut)]
+    pub mint_authority: Signer<'info>,
+
+    #[account(
+        constraint = mint.mint_authority == Some(mint_authority.key()).into() @ TasteError::InvalidMintAuthority
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = mint_authority,
+        space = MINT_POLICY_SPACE,
+        seeds = [b"mint_policy"],
+        bump,
+    )]
+    pub mint_policy: Account<'info, MintPolicy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolPaused<'info> {
+    /// Must be the program upgrade authority (validated in instruction).
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PROTOCOL_STATE_SPACE,
+        seeds = [b"protocol_state"],
+        bump,
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    /// CHECK: validated in instruction
+    pub program_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated in instruction
+    pub program_data_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferMintAuthority<'info> {
+    pub mint_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = mint.mint_authority == Some(mint_authority.key()).into() @ TasteError::InvalidMintAuthority
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(total: u64, start_ts: i64, cliff_secs: i64, duration_secs: i64, schedule_id: u64)]
+pub struct CreateVestingSchedule<'info> {
+    #[account(mut)]
+    pub mint_authority: Signer<'info>,
+
+    /// CHECK: any wallet; only its key is recorded
+    pub beneficiary: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"taste_mint"],
+        bump,
+        constraint = mint.mint_authority == Some(mint_authority.key()).into() @ TasteError::InvalidMintAuthority
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = mint_authority,
+        space = VESTING_SCHEDULE_SPACE,
+        seeds = [b"vesting", beneficiary.key().as_ref(), schedule_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub schedule: Account<'info, VestingSchedule>,
+
+    #[account(
+        init,
+        payer = mint_authority,
+        token::mint = mint,
+        token::authority = schedule,
+        token::token_program = token_program,
+        seeds = [b"vesting_vault", schedule.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// MintPolicy PDA; may be uninitialized, in which case minting is uncapped.
+    /// CHECK: address validated by seeds; contents parsed in instruction
+    #[account(mut, seeds = [b"mint_policy"], bump)]
+    pub mint_policy: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", beneficiary.key().as_ref(), schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = schedule.bump,
+        has_one = beneficiary,
+    )]
+    pub schedule: Account<'info, VestingSchedule>,
+
+    #[account(mut, seeds = [b"vesting_vault", schedule.key().as_ref()], bump)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = vault.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = mint)]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [b"taste_mint"], bump)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = STAKE_ACCOUNT_SPACE,
+        seeds = [b"stake", owner.key().as_ref()],
+        bump,
+    )]
+    pub stake: Account<'info, StakeAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        token::mint = mint,
+        token::authority = stake,
+        token::token_program = token_program,
+        seeds = [b"stake_vault", stake.key().as_ref()],
+        bump,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint, token::authority = owner)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", owner.key().as_ref()],
+        bump = stake.bump,
+        has_one = owner,
+    )]
+    pub stake: Account<'info, StakeAccount>,
+
+    #[account(mut, seeds = [b"stake_vault", stake.key().as_ref()], bump)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [b"taste_mint"], bump)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = mint)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 #[derive(Accounts)]
 pub struct Burn<'info> {
     #[account(mut)]
@@ -200,6 +1025,22 @@ pub struct Burn<'info> {
     pub token_program: Interface<'info, TokenInterface>,
 }
 
+#[derive(Accounts)]
+pub struct BurnFrom<'info> {
+    pub delegate: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = source.delegate == Some(delegate.key()).into() @ TasteError::NotApprovedDelegate
+    )]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = source.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,4 +1059,163 @@ mod tests {
         assert!(supply.checked_add(amount1).unwrap() <= MAX_SUPPLY);
         assert!(supply.checked_add(amount2).unwrap() > MAX_SUPPLY);
     }
+
+    #[test]
+    fn test_stake_multiplier() {
+        assert_eq!(stake_multiplier_bps(0), 10_000);
+        assert_eq!(stake_multiplier_bps(-5), 10_000);
+        assert_eq!(stake_multiplier_bps(MAX_STAKE_LOCK_SECS / 2), 15_000);
+        assert_eq!(
+            stake_multiplier_bps(MAX_STAKE_LOCK_SECS),
+            MAX_STAKE_MULTIPLIER_BPS
+        );
+        assert_eq!(stake_multiplier_bps(i64::MAX), MAX_STAKE_MULTIPLIER_BPS);
+
+        let stake = StakeAccount {
+            owner: Pubkey::new_unique(),
+            amount: 1_000,
+            unlock_ts: 0,
+            multiplier_bps: 15_000,
+            bump: 255,
+        };
+        assert_eq!(stake.boosted_amount(), 1_500);
+        let max = StakeAccount {
+            amount: MAX_SUPPLY,
+            multiplier_bps: MAX_STAKE_MULTIPLIER_BPS,
+            ..stake
+        };
+        assert_eq!(max.boosted_amount(), 2 * MAX_SUPPLY);
+    }
+
+    #[test]
+    fn test_metadata_length_bounds() {
+        assert!(
+            validate_metadata("TasteMaker", "TASTE", "https://tastemaker.music/taste.json").is_ok()
+        );
+        let name = "n".repeat(MAX_NAME_LEN);
+        let symbol = "S".repeat(MAX_SYMBOL_LEN);
+        let uri = "u".repeat(MAX_URI_LEN);
+        assert!(validate_metadata(&name, &symbol, &uri).is_ok());
+        assert!(validate_metadata(&format!("{name}n"), &symbol, &uri).is_err());
+        assert!(validate_metadata(&name, &format!("{symbol}S"), &uri).is_err());
+        assert!(validate_metadata(&name, &symbol, &format!("{uri}u")).is_err());
+    }
+
+    #[test]
+    fn test_mint_policy_window() {
+        let mut policy = MintPolicy {
+            max_per_window: 1_000,
+            window_secs: 60,
+            window_start: 100,
+            minted_in_window: 0,
+        };
+        assert!(apply_mint_policy(&mut policy, 600, 110).is_ok());
+        assert!(apply_mint_policy(&mut policy, 400, 120).is_ok());
+        // Cap reached: one more unit fails and is not recorded.
+        assert!(apply_mint_policy(&mut policy, 1, 159).is_err());
+        assert_eq!(policy.minted_in_window, 1_000);
+        // Window rolled over: the cap is available again.
+        assert!(apply_mint_policy(&mut policy, 1_000, 160).is_ok());
+        assert_eq!(policy.window_start, 160);
+        assert!(apply_mint_policy(&mut policy, 1_001, 300).is_err());
+
+        let mut uncapped = MintPolicy {
+            max_per_window: 0,
+            ..policy
+        };
+        assert!(apply_mint_policy(&mut uncapped, u64::MAX, 300).is_ok());
+    }
+
+    #[test]
+    fn test_mint_policy_update_only_tightens() {
+        // First policy: cap and window must be positive; 0 no longer means uncapped.
+        assert!(check_mint_policy_update(None, 1_000, 60).is_ok());
+        assert!(check_mint_policy_update(None, 0, 60).is_err());
+        assert!(check_mint_policy_update(None, 1_000, 0).is_err());
+
+        let current = MintPolicy {
+            max_per_window: 1_000,
+            window_secs: 60,
+            window_start: 100,
+            minted_in_window: 400,
+        };
+        assert!(check_mint_policy_update(Some(&current), 1_000, 60).is_ok());
+        assert!(check_mint_policy_update(Some(&current), 500, 120).is_ok());
+        // Lifting the cap, shortening the window or removing the cap are all rejected.
+        assert!(check_mint_policy_update(Some(&current), 1_001, 60).is_err());
+        assert!(check_mint_policy_update(Some(&current), 1_000, 59).is_err());
+        assert!(check_mint_policy_update(Some(&current), 0, 60).is_err());
+    }
+
+    #[test]
+    fn test_vested_amount_pre_cliff() {
+        let total = 1_200 * 10u64.pow(DECIMALS as u32);
+        // 1y cliff on a 4y schedule starting at t=1000.
+        let (start, cliff, duration) = (1_000, 365 * 86_400, 4 * 365 * 86_400);
+        assert_eq!(vested_amount(total, start, cliff, duration, 0), 0);
+        assert_eq!(vested_amount(total, start, cliff, duration, start), 0);
+        assert_eq!(
+            vested_amount(total, start, cliff, duration, start + cliff - 1),
+            0
+        );
+    }
+
+    #[test]
+    fn test_vested_amount_linear_after_cliff() {
+        let total = 1_200 * 10u64.pow(DECIMALS as u32);
+        let (start, cliff, duration) = (1_000, 365 * 86_400, 4 * 365 * 86_400);
+        // At the cliff a quarter has vested, not zero.
+        assert_eq!(
+            vested_amount(total, start, cliff, duration, start + cliff),
+            total / 4
+        );
+        assert_eq!(
+            vested_amount(total, start, cliff, duration, start + duration / 2),
+            total / 2
+        );
+        // No cliff: vesting starts immediately.
+        assert_eq!(vested_amount(100, 0, 0, 10, 3), 30);
+    }
+
+    #[test]
+    fn test_vested_amount_fully_vested() {
+        let total = MAX_SUPPLY;
+        let (start, cliff, duration) = (1_000, 100, 1_000);
+        assert_eq!(
+            vested_amount(total, start, cliff, duration, start + duration),
+            total
+        );
+        assert_eq!(
+            vested_amount(total, start, cliff, duration, i64::MAX),
+            total
+        );
+        // Large totals do not overflow mid-schedule.
+        assert_eq!(
+            vested_amount(total, start, cliff, duration, start + duration - 1),
+            (total as u128 * 999 / 1_000) as u64
+        );
+    }
+
+    #[test]
+    fn test_protocol_paused_reads_state() {
+        let key = protocol_state_address();
+        let state_data = |paused: bool| {
+            let mut data = Vec::new();
+            ProtocolState { paused }.try_serialize(&mut data).unwrap();
+            data
+        };
+        let check = |key: &Pubkey, owner: &Pubkey, mut data: Vec<u8>| {
+            let mut lamports = 0u64;
+            let info =
+                AccountInfo::new(key, false, false, &mut lamports, &mut data, owner, false, 0);
+            protocol_paused(&info)
+        };
+
+        assert!(check(&key, &crate::ID, state_data(true)).unwrap());
+        assert!(!check(&key, &crate::ID, state_data(false)).unwrap());
+        // Never created: not paused.
+        assert!(!check(&key, &Pubkey::default(), Vec::new()).unwrap());
+        // Any other account is rejected rather than read as unpaused.
+        assert!(check(&Pubkey::new_unique(), &crate::ID, state_data(true)).is_err());
+    }
 }
@@ -1,5 +1,6 @@
 /**
- * One-time script: initialize governance Config PDA (allow_early_finalize, min_voting_period_secs).
+ * One-time script: initialize governance Config PDA (allow_early_finalize, min_voting_period_secs, min_backers_for_governance, quorum_basis,
+ * min_participation_bps, quorum_bps, material_edit_threshold_bps, execution_delay_secs, min_vote_contribution).
  *
  * Only the program upgrade authority can call this. Use on devnet to enable early finalize and short voting periods.
  *
@@ -11,7 +12,14 @@
  * - SOLANA_RPC_URL or ANCHOR_PROVIDER_URL: RPC endpoint
  * - SOLANA_KEYPAIR: upgrade authority keypair
  * - GOVERNANCE_PROGRAM_ID: override (defaults to devnet id)
- * - MIN_VOTING_PERIOD_SECS: min period for new proposals (default 60)
+ * - MIN_VOTING_PERIOD_SECS: min period for new proposals (default 3600; the program rejects less than 1h unless built with `--features test`)
+ * - MIN_BACKERS_FOR_GOVERNANCE: min backer count before milestone proposals (default 0 = no minimum)
+ * - QUORUM_BASIS: "raised" (default) or "goal"; denominator for the finalize quorum threshold
+ * - MIN_PARTICIPATION_BPS: early-finalize turnout floor in bps of total vote weight (default 0 = no floor)
+ * - QUORUM_BPS: finalize quorum in bps of the quorum basis, 1..=10000 (default 2000)
+ * - MATERIAL_EDIT_THRESHOLD_BPS: share of for + against votes a material edit needs, e.g. 6667 (default 0 = simple majority)
+ * - EXECUTION_DELAY_SECS: delay between a milestone proposal passing and execute_proposal (default 0 = release at finalize)
+ * - MIN_VOTE_CONTRIBUTION: minimum backer contribution in lamports to cast a vote (default 0 = any contribution)
  * - ALLOW_NON_DEVNET=1: allow running on non-devnet (e.g. mainnet) when cluster guard is enabled
  */
 
@@ -38,9 +46,52 @@ const GOVERNANCE_PROGRAM_ID = new PublicKey(
 );
 
 const MIN_VOTING_PERIOD_SECS = (() => {
-  const s = process.env.MIN_VOTING_PERIOD_SECS ?? "60";
+  const s = process.env.MIN_VOTING_PERIOD_SECS ?? "3600";
   const n = parseInt(s, 10);
-  return Number.isNaN(n) || n < 1 ? 60 : n;
+  return Number.isNaN(n) || n < 1 ? 3600 : n;
+})();
+
+const MIN_BACKERS_FOR_GOVERNANCE = (() => {
+  const s = process.env.MIN_BACKERS_FOR_GOVERNANCE ?? "0";
+  const n = parseInt(s, 10);
+  return Number.isNaN(n) || n < 0 ? 0 : n;
+})();
+
+/** QuorumBasis enum index: 0 = Raised, 1 = Goal. */
+const QUORUM_BASIS = (process.env.QUORUM_BASIS ?? "raised").toLowerCase() === "goal" ? 1 : 0;
+
+const MIN_PARTICIPATION_BPS = (() => {
+  const s = process.env.MIN_PARTICIPATION_BPS ?? "0";
+  const n = parseInt(s, 10);
+  return Number.isNaN(n) || n < 0 || n > 10_000 ? 0 : n;
+})();
+
+const QUORUM_BPS = (() => {
+  const s = process.env.QUORUM_BPS ?? "2000";
+  const n = parseInt(s, 10);
+  return Number.isNaN(n) || n < 1 || n > 10_000 ? 2000 : n;
+})();
+
+const MATERIAL_EDIT_THRESHOLD_BPS = (() => {
+  const s = process.env.MATERIAL_EDIT_THRESHOLD_BPS ?? "0";
+  const n = parseInt(s, 10);
+  return Number.isNaN(n) || n < 0 || n > 10_000 ? 0 : n;
+})();
+
+const EXECUTION_DELAY_SECS = (() => {
+  const s = process.env.EXECUTION_DELAY_SECS ?? "0";
+  const n = parseInt(s, 10);
+  return Number.isNaN(n) || n < 0 ? 0 : n;
+})();
+
+const MIN_VOTE_CONTRIBUTION = (() => {
+  const s = process.env.MIN_VOTE_CONTRIBUTION ?? "0";
+  try {
+    const n = BigInt(s);
+    return n < BigInt(0) ? BigInt(0) : n;
+  } catch {
+    return BigInt(0);
+  }
 })();
 
 const DEFAULT_DEVNET_DEPLOY_KEYPAIR = path.join(
@@ -109,6 +160,13 @@ async function main() {
   console.log("Upgrade authority:", payer.publicKey.toBase58());
   console.log("Config PDA:", configPda.toBase58());
   console.log("allow_early_finalize: true, min_voting_period_secs:", MIN_VOTING_PERIOD_SECS);
+  console.log("min_backers_for_governance:", MIN_BACKERS_FOR_GOVERNANCE);
+  console.log("quorum_basis:", QUORUM_BASIS === 1 ? "goal" : "raised");
+  console.log("min_participation_bps:", MIN_PARTICIPATION_BPS);
+  console.log("quorum_bps:", QUORUM_BPS);
+  console.log("material_edit_threshold_bps:", MATERIAL_EDIT_THRESHOLD_BPS);
+  console.log("execution_delay_secs:", EXECUTION_DELAY_SECS);
+  console.log("min_vote_contribution:", MIN_VOTE_CONTRIBUTION.toString());
 
   const existing = await connection.getAccountInfo(configPda, "confirmed");
   if (existing) {
@@ -121,10 +179,17 @@ async function main() {
   console.log("ProgramData:", programDataAddress.toBase58());
 
   const allowEarlyFinalize = true;
-  const data = Buffer.alloc(8 + 1 + 8);
+  const data = Buffer.alloc(8 + 1 + 8 + 4 + 1 + 2 + 2 + 2 + 8 + 8);
   anchorDiscriminator("initialize_config").copy(data, 0);
   data.writeUInt8(allowEarlyFinalize ? 1 : 0, 8);
   data.writeBigInt64LE(BigInt(MIN_VOTING_PERIOD_SECS), 9);
+  data.writeUInt32LE(MIN_BACKERS_FOR_GOVERNANCE, 17);
+  data.writeUInt8(QUORUM_BASIS, 21);
+  data.writeUInt16LE(MIN_PARTICIPATION_BPS, 22);
+  data.writeUInt16LE(QUORUM_BPS, 24);
+  data.writeUInt16LE(MATERIAL_EDIT_THRESHOLD_BPS, 26);
+  data.writeBigInt64LE(BigInt(EXECUTION_DELAY_SECS), 28);
+  data.writeBigUInt64LE(MIN_VOTE_CONTRIBUTION, 36);
 
   const ix = new TransactionInstruction({
     programId: GOVERNANCE_PROGRAM_ID,
@@ -139,12 +139,24 @@ async function main() {
 
   const rwaConfigPda = getRwaConfigPda(programId);
   const rwaExtraAccountMetasPda = getRwaExtraAccountMetasPda(rwaMintPda);
+  const projectInfo = await connection.getAccountInfo(projectPda, "confirmed");
+  if (!projectInfo) {
+    console.error("Project account not found:", projectPda.toBase58());
+    process.exit(1);
+    return;
+  }
+  // Escrow is a project_escrow PDA; the project account's owner is that program.
+  const [escrowPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("escrow"), projectPda.toBuffer()],
+    projectInfo.owner
+  );
 
   const tx = await (program.methods as any)
     .initializeRwaMint(new anchor.BN(RWA_TOTAL_SUPPLY))
     .accounts({
       authority: authority.publicKey,
       project: projectPda,
+      escrow: escrowPda,
       rwaState: rwaStatePda,
       rwaConfig: rwaConfigPda,
       rwaMint: rwaMintPda,
@@ -78,9 +78,9 @@ describe("tastemaker-programs integration", () => {
     );
     return { authority, tokenAccount: getAssociatedTokenAddressSync(mint, authority) };
   }
-  function getProposalAttemptPda(project: PublicKey, govProgramId: PublicKey): PublicKey {
+  function getProposalAttemptPda(project: PublicKey, milestone: number, govProgramId: PublicKey): PublicKey {
     return PublicKey.findProgramAddressSync(
-      [Buffer.from("proposal_attempt"), project.toBuffer()],
+      [Buffer.from("proposal_attempt"), project.toBuffer(), Buffer.from([milestone])],
       govProgramId
     )[0];
   }
@@ -200,7 +200,8 @@ describe("tastemaker-programs integration", () => {
         "Integration Test",
         new anchor.BN(GOAL),
         MILESTONES,
-        deadline
+        deadline,
+        { minFunding: new anchor.BN(0), maxPerBacker: new anchor.BN(0), softCap: new anchor.BN(0) }
       )
       .accounts({
         artist: artist.publicKey,
@@ -265,7 +266,7 @@ describe("tastemaker-programs integration", () => {
 
   it("artist creates proposal for milestone 0", async () => {
     const projectPda = getProjectPda(artist.publicKey, 0, projectEscrowId);
-    const proposalAttemptPda = getProposalAttemptPda(projectPda, governanceId);
+    const proposalAttemptPda = getProposalAttemptPda(projectPda, 0, governanceId);
     const proposalPda = getProposalPda(projectPda, 0, 0, governanceId);
     const votingPeriodSecs = new anchor.BN(24 * 3600);
 
@@ -275,7 +276,9 @@ describe("tastemaker-programs integration", () => {
         0,
         "https://proof.example/m0",
         votingPeriodSecs,
-        new anchor.BN(0)
+        new anchor.BN(0),
+        "Milestone 0",
+        Array.from(Buffer.alloc(32))
       )
       .accounts({
         artist: artist.publicKey,
@@ -312,7 +315,7 @@ describe("tastemaker-programs integration", () => {
     );
 
     await governanceProgram.methods
-      .castVote(true)
+      .castVote({ for: {} })
       .accounts({
         proposal: proposalPda,
         voter: backer1.publicKey,
@@ -1,7 +1,7 @@
 /**
- * OTC market program tests: create_offer, cancel_offer, accept_offer invariants and failures.
+ * OTC market program tests: create_offer, cancel_offer, accept_offer invariants and failures, plus auctions.
  * Run after exhaustive (same validator) or with: anchor test (add this to test script).
- * Covers: NotToken2022, InvalidAmount, InvalidPrice, NotMaker, TakerIsMaker, OfferExpired, OfferNotOpen (double-fill).
+ * Covers: NotToken2022, InvalidAmount, InvalidPrice, NotMaker, TakerIsMaker, OfferExpired, OfferNotOpen (double-fill), BidTooLow, AuctionNotEnded, AuctionHasBids.
  */
 
 import * as anchor from "@coral-xyz/anchor";
@@ -18,7 +18,9 @@ import {
   createAssociatedTokenAccountInstruction,
   createAssociatedTokenAccountIdempotentInstruction,
   createMintToInstruction,
+  createInitializeAccount3Instruction,
   createMint,
+  getAccount,
   TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -77,6 +79,22 @@ describe("otc_market", function () {
     )[0];
   }
 
+  /** Auction PDA, escrow authority and the asset / bid escrow ATAs for an auction created at `nonce`. */
+  function auctionPdas(auctionMaker: PublicKey, nonce: number) {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(nonce));
+    const escrowAuthority = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), auctionMaker.toBuffer(), buf],
+      otcProgramId
+    )[0];
+    return {
+      auction: PublicKey.findProgramAddressSync([Buffer.from("auction"), auctionMaker.toBuffer(), buf], otcProgramId)[0],
+      escrowAuthority,
+      escrowAta: getAssociatedTokenAddressSync(assetMint, escrowAuthority, true, TOKEN_2022_PROGRAM_ID),
+      bidEscrowAta: getAssociatedTokenAddressSync(quoteMint, escrowAuthority, true, TOKEN_2022_PROGRAM_ID),
+    };
+  }
+
   before(async () => {
     maker = Keypair.generate();
     taker = Keypair.generate();
@@ -154,7 +172,7 @@ describe("otc_market", function () {
 
     await expect(
       otcProgram.methods
-        .createOffer(amount, price, { sell: {} }, expirySlot)
+        .createOffer(amount, price, { sell: {} }, expirySlot, new anchor.BN(0), null, null)
         .accounts({
           maker: maker.publicKey,
           makerState,
@@ -204,7 +222,7 @@ describe("otc_market", function () {
 
     await expect(
       otcProgram.methods
-        .createOffer(new anchor.BN(0), price, { sell: {} }, expirySlot)
+        .createOffer(new anchor.BN(0), price, { sell: {} }, expirySlot, new anchor.BN(0), null, null)
         .accounts({
           maker: maker.publicKey,
           makerState,
@@ -254,7 +272,7 @@ describe("otc_market", function () {
 
     await expect(
       otcProgram.methods
-        .createOffer(amount, new anchor.BN(0), { sell: {} }, expirySlot)
+        .createOffer(amount, new anchor.BN(0), { sell: {} }, expirySlot, new anchor.BN(0), null, null)
         .accounts({
           maker: maker.publicKey,
           makerState,
@@ -360,7 +378,7 @@ describe("otc_market", function () {
     const tx = new Transaction().add(createEscrowAtaIx);
     tx.add(
       await otcProgram.methods
-        .createOffer(amount, price, { sell: {} }, expirySlot)
+        .createOffer(amount, price, { sell: {} }, expirySlot, new anchor.BN(0), null, null)
         .accounts({
           maker: maker.publicKey,
           makerState,
@@ -503,7 +521,7 @@ describe("otc_market", function () {
 
     await expect(
       otcProgram.methods
-        .acceptOffer()
+        .acceptOffer(amount, null, null)
         .accounts({
           taker: taker.publicKey,
           offer,
@@ -563,7 +581,7 @@ describe("otc_market", function () {
     const tx = new Transaction().add(createEscrowAtaIx);
     tx.add(
       await otcProgram.methods
-        .createOffer(amount, price, { sell: {} }, expirySlot)
+        .createOffer(amount, price, { sell: {} }, expirySlot, new anchor.BN(0), null, null)
         .accounts({
           maker: maker.publicKey,
           makerState,
@@ -607,7 +625,7 @@ describe("otc_market", function () {
 
     await expect(
       otcProgram.methods
-        .acceptOffer()
+        .acceptOffer(amount, null, null)
         .accounts({
           taker: maker.publicKey,
           offer,
@@ -669,7 +687,7 @@ describe("otc_market", function () {
     const tx = new Transaction().add(createEscrowAtaIx);
     tx.add(
       await otcProgram.methods
-        .createOffer(amount, price, { sell: {} }, pastExpirySlot)
+        .createOffer(amount, price, { sell: {} }, pastExpirySlot, new anchor.BN(0), null, null)
         .accounts({
           maker: maker.publicKey,
           makerState,
@@ -726,7 +744,7 @@ describe("otc_market", function () {
 
     await expect(
       otcProgram.methods
-        .acceptOffer()
+        .acceptOffer(amount, null, null)
         .accounts({
           taker: taker.publicKey,
           offer,
@@ -746,6 +764,131 @@ describe("otc_market", function () {
     ).to.be.rejected;
   });
 
+  it("reclaim_expired_offer fails before expiry, then anyone returns an expired escrow to the maker", async () => {
+    const [makerState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("maker"), maker.publicKey.toBuffer()],
+      otcProgramId
+    );
+    const makerAssetAta = getAssociatedTokenAddressSync(assetMint, maker.publicKey, false, TOKEN_2022_PROGRAM_ID);
+    const makerQuoteAta = getAssociatedTokenAddressSync(quoteMint, maker.publicKey, false, TOKEN_2022_PROGRAM_ID);
+    const reclaim = (nonce: number) => {
+      const escrowAuthority = escrowAuthorityPda(nonce);
+      return otcProgram.methods
+        .reclaimExpiredOffer()
+        .accounts({
+          caller: taker.publicKey,
+          offer: offerPda(nonce),
+          makerState,
+          assetMint,
+          quoteMint,
+          makerAssetAta,
+          makerQuoteAta,
+          escrowAuthority,
+          escrowAta: getAssociatedTokenAddressSync(assetMint, escrowAuthority, true, TOKEN_2022_PROGRAM_ID),
+          assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+          quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([taker])
+        .rpc();
+    };
+
+    // Offer 1 is still live (used by the accept tests below).
+    await expect(reclaim(1)).to.be.rejectedWith(/OfferNotExpired/);
+
+    const before = await provider.connection.getTokenAccountBalance(makerAssetAta);
+    await reclaim(expiredOfferNonce as number);
+    const after = await provider.connection.getTokenAccountBalance(makerAssetAta);
+    expect(BigInt(after.value.amount) - BigInt(before.value.amount)).to.equal(BigInt(amount.toString()));
+    const offerAcc = await otcProgram.account.offer.fetch(offerPda(expiredOfferNonce as number));
+    expect(offerAcc.status.cancelled !== undefined).to.be.true;
+    await expect(reclaim(expiredOfferNonce as number)).to.be.rejectedWith(/OfferNotOpen/);
+  });
+
+  it("cancel_offer and accept_offer reject an escrow ATA from another nonce or mint (InvalidEscrowAccount)", async () => {
+    const offer = offerPda(1);
+    const ata = (mint: PublicKey, owner: PublicKey, offCurve = false) =>
+      getAssociatedTokenAddressSync(mint, owner, offCurve, TOKEN_2022_PROGRAM_ID);
+    const escrowAuthority = escrowAuthorityPda(1);
+    // Offer 0's (cancelled) asset escrow and offer 1's quote-mint ATA are both real token accounts, neither is offer 1's escrow.
+    const staleEscrowAta = ata(assetMint, escrowAuthorityPda(0), true);
+    const wrongMintEscrowAta = ata(quoteMint, escrowAuthority, true);
+    const takerAssetAta = ata(assetMint, taker.publicKey);
+    const takerQuoteAta = ata(quoteMint, taker.publicKey);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, wrongMintEscrowAta, escrowAuthority, quoteMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, ata(quoteMint, maker.publicKey), maker.publicKey, quoteMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, takerAssetAta, taker.publicKey, assetMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, takerQuoteAta, taker.publicKey, quoteMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID)
+      ),
+      [maker]
+    );
+    const [otcConfig] = PublicKey.findProgramAddressSync([Buffer.from("otc_config")], otcProgramId);
+    if (!(await provider.connection.getAccountInfo(otcConfig))) {
+      const [programDataAccount] = PublicKey.findProgramAddressSync(
+        [otcProgramId.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      );
+      await otcProgram.methods
+        .initializeOtcConfig(null, provider.wallet.publicKey, false, PublicKey.default, PublicKey.default)
+        .accounts({
+          authority: provider.wallet.publicKey,
+          otcConfig,
+          programAccount: otcProgramId,
+          programDataAccount,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    for (const escrowAta of [staleEscrowAta, wrongMintEscrowAta]) {
+      await expect(
+        otcProgram.methods
+          .cancelOffer()
+          .accounts({
+            maker: maker.publicKey,
+            offer,
+            assetMint,
+            quoteMint,
+            makerAssetAta: ata(assetMint, maker.publicKey),
+            makerQuoteAta: ata(quoteMint, maker.publicKey),
+            escrowAuthority,
+            escrowAta,
+            assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+            quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([maker])
+          .rpc()
+      ).to.be.rejectedWith(/InvalidEscrowAccount/);
+      await expect(
+        otcProgram.methods
+          .acceptOffer(amount, null, null)
+          .accounts({
+            taker: taker.publicKey,
+            offer,
+            assetMint,
+            quoteMint,
+            makerAssetAta: ata(assetMint, maker.publicKey),
+            takerAssetAta,
+            makerQuoteAta: ata(quoteMint, maker.publicKey),
+            takerQuoteAta,
+            escrowAuthority,
+            escrowAta,
+            otcConfig,
+            // Escrow constraints are checked before the treasury's, so any quote token account will do here.
+            treasuryQuoteAta: takerQuoteAta,
+            assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+            quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([taker])
+          .rpc()
+      ).to.be.rejectedWith(/InvalidEscrowAccount/);
+    }
+    const offerAcc = await otcProgram.account.offer.fetch(offer);
+    expect(offerAcc.status.open !== undefined).to.be.true;
+  });
+
   it("accept_offer succeeds (sell) then second accept fails (OfferNotOpen)", async () => {
     const offer = offerPda(1);
     const makerAta = getAssociatedTokenAddressSync(
@@ -843,8 +986,47 @@ describe("otc_market", function () {
       TOKEN_2022_PROGRAM_ID
     );
 
-    await otcProgram.methods
-      .acceptOffer()
+    // The maker reprices while the taker's accept (signed at the old price) is in flight: the taker's limits abort it.
+    const reprice = (newPrice: anchor.BN) =>
+      otcProgram.methods
+        .updateOffer(newPrice, null, null)
+        .accounts({
+          maker: maker.publicKey,
+          offer,
+          quoteMint,
+          makerQuoteAta,
+          escrowAuthority,
+          escrowAta,
+          quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([maker])
+        .rpc();
+    const acceptWithLimits = (maxPrice: anchor.BN | null, minAmount: anchor.BN | null) =>
+      otcProgram.methods
+        .acceptOffer(amount, maxPrice, minAmount)
+        .accounts({
+          taker: taker.publicKey,
+          offer,
+          assetMint,
+          quoteMint,
+          makerAssetAta: makerAta,
+          takerAssetAta: takerAta,
+          makerQuoteAta,
+          takerQuoteAta,
+          escrowAuthority,
+          escrowAta,
+          assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+          quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([taker])
+        .rpc();
+    await reprice(price.muln(2));
+    await expect(acceptWithLimits(price, null)).to.be.rejectedWith(/PriceChanged/);
+    await expect(acceptWithLimits(null, amount.addn(1))).to.be.rejectedWith(/PriceChanged/);
+    await reprice(price);
+
+    const sig = await otcProgram.methods
+      .acceptOffer(amount, price, amount)
       .accounts({
         taker: taker.publicKey,
         offer,
@@ -860,14 +1042,31 @@ describe("otc_market", function () {
         quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
       })
       .signers([taker])
-      .rpc();
+      .rpc({ commitment: "confirmed" });
 
     const offerAcc = await otcProgram.account.offer.fetch(offer);
     expect(offerAcc.status.taken !== undefined).to.be.true;
 
+    const txInfo = await provider.connection.getTransaction(sig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(otcProgramId, otcProgram.coder);
+    const events = Array.from(parser.parseLogs(txInfo?.meta?.logMessages ?? []));
+    const accepted = events.find((e) => e.name === "offerAccepted");
+    expect(accepted).to.not.be.undefined;
+    expect(accepted!.data.offer.toBase58()).to.equal(offer.toBase58());
+    expect(accepted!.data.maker.toBase58()).to.equal(maker.publicKey.toBase58());
+    expect(accepted!.data.taker.toBase58()).to.equal(taker.publicKey.toBase58());
+    expect(accepted!.data.mint.toBase58()).to.equal(assetMint.toBase58());
+    expect(accepted!.data.nonce.toNumber()).to.equal(1);
+    expect(accepted!.data.amount.toString()).to.equal(amount.toString());
+    expect(accepted!.data.price.toString()).to.equal(price.toString());
+    expect(accepted!.data.remainingAmount.toNumber()).to.equal(offerAcc.amount.toNumber());
+
     await expect(
       otcProgram.methods
-        .acceptOffer()
+        .acceptOffer(amount, null, null)
         .accounts({
           taker: taker.publicKey,
           offer,
@@ -886,4 +1085,818 @@ describe("otc_market", function () {
         .rpc()
     ).to.be.rejected;
   });
+
+  it("cancel_offers_batch cancels three offers at once", async () => {
+    const [makerState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("maker"), maker.publicKey.toBuffer()],
+      otcProgramId
+    );
+    const makerAssetAta = getAssociatedTokenAddressSync(
+      assetMint,
+      maker.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const makerQuoteAta = getAssociatedTokenAddressSync(
+      quoteMint,
+      maker.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const expirySlot = new anchor.BN((await provider.connection.getSlot()) + 100000);
+
+    const nonces: number[] = [];
+    for (let i = 0; i < 3; i++) {
+      const state = await otcProgram.account.makerState.fetch(makerState);
+      const nonce = state.nonce.toNumber();
+      nonces.push(nonce);
+      const escrowAuthority = escrowAuthorityPda(nonce);
+      const escrowAta = getAssociatedTokenAddressSync(
+        assetMint,
+        escrowAuthority,
+        true, // allowOwnerOffCurve: escrow authority is a PDA
+        TOKEN_2022_PROGRAM_ID
+      );
+      const tx = new Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(
+          maker.publicKey,
+          escrowAta,
+          escrowAuthority,
+          assetMint,
+          TOKEN_2022_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID
+        )
+      );
+      tx.add(
+        await otcProgram.methods
+          .createOffer(amount, price, { sell: {} }, expirySlot, new anchor.BN(0), null, null)
+          .accounts({
+            maker: maker.publicKey,
+            makerState,
+            offer: offerPda(nonce),
+            assetMint,
+            makerAssetAta,
+            makerQuoteAta,
+            escrowAuthority,
+            escrowAta,
+            quoteMint,
+            assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+            quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction()
+      );
+      await sendAndConfirmTransaction(provider.connection, tx, [maker]);
+    }
+
+    const before = await provider.connection.getTokenAccountBalance(makerAssetAta);
+    const remainingAccounts = nonces.flatMap((nonce) => {
+      const escrowAuthority = escrowAuthorityPda(nonce);
+      return [
+        { pubkey: offerPda(nonce), isSigner: false, isWritable: true },
+        { pubkey: escrowAuthority, isSigner: false, isWritable: false },
+        {
+          pubkey: getAssociatedTokenAddressSync(
+            assetMint,
+            escrowAuthority,
+            true,
+            TOKEN_2022_PROGRAM_ID
+          ),
+          isSigner: false,
+          isWritable: true,
+        },
+        { pubkey: assetMint, isSigner: false, isWritable: false },
+        { pubkey: makerAssetAta, isSigner: false, isWritable: true },
+      ];
+    });
+
+    await otcProgram.methods
+      .cancelOffersBatch()
+      .accounts({
+        maker: maker.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([maker])
+      .rpc();
+
+    for (const nonce of nonces) {
+      const offerAcc = await otcProgram.account.offer.fetch(offerPda(nonce));
+      expect(offerAcc.status.cancelled !== undefined).to.be.true;
+    }
+    const after = await provider.connection.getTokenAccountBalance(makerAssetAta);
+    expect(BigInt(after.value.amount) - BigInt(before.value.amount)).to.equal(
+      BigInt(amount.toString()) * BigInt(3)
+    );
+  });
+
+  it("create_offer is blocked at max_open_offers and freed by cancelling", async () => {
+    const [otcConfig] = PublicKey.findProgramAddressSync([Buffer.from("otc_config")], otcProgramId);
+    const [programDataAccount] = PublicKey.findProgramAddressSync(
+      [otcProgramId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    const adminAccounts = {
+      authority: provider.wallet.publicKey,
+      otcConfig,
+      programAccount: otcProgramId,
+      programDataAccount,
+      systemProgram: SystemProgram.programId,
+    };
+    if (!(await provider.connection.getAccountInfo(otcConfig))) {
+      await otcProgram.methods
+        .initializeOtcConfig(null, provider.wallet.publicKey, false, PublicKey.default, PublicKey.default)
+        .accounts(adminAccounts)
+        .rpc();
+    }
+    const config = await otcProgram.account.otcConfig.fetch(otcConfig);
+    const setCap = (cap: number) =>
+      otcProgram.methods
+        .updateOtcConfig(
+          config.feeBps,
+          config.treasury,
+          config.restrictAssets,
+          config.rwaTokenProgram,
+          config.projectEscrowProgram,
+          cap
+        )
+        .accounts(adminAccounts)
+        .rpc();
+
+    // Fresh maker so earlier offers don't count toward the cap.
+    const capMaker = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(capMaker.publicKey, 2e9);
+    await provider.connection.confirmTransaction(sig);
+    const capMakerAssetAta = getAssociatedTokenAddressSync(assetMint, capMaker.publicKey, false, TOKEN_2022_PROGRAM_ID);
+    const capMakerQuoteAta = getAssociatedTokenAddressSync(quoteMint, capMaker.publicKey, false, TOKEN_2022_PROGRAM_ID);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        createAssociatedTokenAccountInstruction(capMaker.publicKey, capMakerAssetAta, capMaker.publicKey, assetMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        createAssociatedTokenAccountInstruction(capMaker.publicKey, capMakerQuoteAta, capMaker.publicKey, quoteMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        createMintToInstruction(assetMint, capMakerAssetAta, maker.publicKey, BigInt(amount.toString()) * 3n, [], TOKEN_2022_PROGRAM_ID)
+      ),
+      [capMaker, maker]
+    );
+    const [capMakerState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("maker"), capMaker.publicKey.toBuffer()],
+      otcProgramId
+    );
+    const noncePdas = (nonce: number) => {
+      const buf = Buffer.alloc(8);
+      buf.writeBigUInt64LE(BigInt(nonce));
+      const escrowAuthority = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), capMaker.publicKey.toBuffer(), buf],
+        otcProgramId
+      )[0];
+      return {
+        offer: PublicKey.findProgramAddressSync([Buffer.from("offer"), capMaker.publicKey.toBuffer(), buf], otcProgramId)[0],
+        escrowAuthority,
+        escrowAta: getAssociatedTokenAddressSync(assetMint, escrowAuthority, true, TOKEN_2022_PROGRAM_ID),
+      };
+    };
+    const expirySlot = new anchor.BN((await provider.connection.getSlot()) + 100000);
+    const createOffer = async (nonce: number) => {
+      const { offer, escrowAuthority, escrowAta } = noncePdas(nonce);
+      const tx = new Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(capMaker.publicKey, escrowAta, escrowAuthority, assetMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        await otcProgram.methods
+          .createOffer(amount, price, { sell: {} }, expirySlot, new anchor.BN(0), null, null)
+          .accounts({
+            maker: capMaker.publicKey,
+            makerState: capMakerState,
+            offer,
+            assetMint,
+            makerAssetAta: capMakerAssetAta,
+            makerQuoteAta: capMakerQuoteAta,
+            escrowAuthority,
+            escrowAta,
+            quoteMint,
+            assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+            quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction()
+      );
+      await sendAndConfirmTransaction(provider.connection, tx, [capMaker]);
+    };
+
+    await setCap(1);
+    try {
+      await createOffer(0);
+      await expect(createOffer(1)).to.be.rejectedWith(/MaxOpenOffersReached|0x/);
+
+      const { offer, escrowAuthority, escrowAta } = noncePdas(0);
+      await otcProgram.methods
+        .cancelOffer()
+        .accounts({
+          maker: capMaker.publicKey,
+          offer,
+          makerState: capMakerState,
+          assetMint,
+          quoteMint,
+          makerAssetAta: capMakerAssetAta,
+          makerQuoteAta: capMakerQuoteAta,
+          escrowAuthority,
+          escrowAta,
+          assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+          quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([capMaker])
+        .rpc();
+      expect((await otcProgram.account.makerState.fetch(capMakerState)).openOffers).to.equal(0);
+
+      await createOffer(1);
+      expect((await otcProgram.account.makerState.fetch(capMakerState)).openOffers).to.equal(1);
+    } finally {
+      await setCap(0);
+    }
+  });
+
+  it("private offer can only be accepted by its allowed taker (NotAllowedTaker)", async () => {
+    const [makerState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("maker"), maker.publicKey.toBuffer()],
+      otcProgramId
+    );
+    const nonce = (await otcProgram.account.makerState.fetch(makerState)).nonce.toNumber();
+    const offer = offerPda(nonce);
+    const escrowAuthority = escrowAuthorityPda(nonce);
+    const escrowAta = getAssociatedTokenAddressSync(assetMint, escrowAuthority, true, TOKEN_2022_PROGRAM_ID);
+    const ata = (mint: PublicKey, owner: PublicKey) =>
+      getAssociatedTokenAddressSync(mint, owner, false, TOKEN_2022_PROGRAM_ID);
+    const makerAssetAta = ata(assetMint, maker.publicKey);
+    const makerQuoteAta = ata(quoteMint, maker.publicKey);
+
+    const outsider = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(outsider.publicKey, 2e9);
+    await provider.connection.confirmTransaction(sig);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        createAssociatedTokenAccountInstruction(outsider.publicKey, ata(assetMint, outsider.publicKey), outsider.publicKey, assetMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        createAssociatedTokenAccountInstruction(outsider.publicKey, ata(quoteMint, outsider.publicKey), outsider.publicKey, quoteMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        createMintToInstruction(quoteMint, ata(quoteMint, outsider.publicKey), maker.publicKey, price.toNumber(), [], TOKEN_2022_PROGRAM_ID),
+        createMintToInstruction(quoteMint, ata(quoteMint, taker.publicKey), maker.publicKey, price.toNumber(), [], TOKEN_2022_PROGRAM_ID),
+        createMintToInstruction(assetMint, makerAssetAta, maker.publicKey, amount.toNumber(), [], TOKEN_2022_PROGRAM_ID)
+      ),
+      [outsider, maker]
+    );
+
+    const expirySlot = new anchor.BN((await provider.connection.getSlot()) + 100000);
+    const tx = new Transaction().add(
+      createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, escrowAta, escrowAuthority, assetMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+      await otcProgram.methods
+        .createOffer(amount, price, { sell: {} }, expirySlot, new anchor.BN(0), null, taker.publicKey)
+        .accounts({
+          maker: maker.publicKey,
+          makerState,
+          offer,
+          assetMint,
+          makerAssetAta,
+          makerQuoteAta,
+          escrowAuthority,
+          escrowAta,
+          quoteMint,
+          assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+          quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction()
+    );
+    await sendAndConfirmTransaction(provider.connection, tx, [maker]);
+    expect((await otcProgram.account.offer.fetch(offer)).allowedTaker.toBase58()).to.equal(
+      taker.publicKey.toBase58()
+    );
+
+    const accept = (signer: Keypair) =>
+      otcProgram.methods
+        .acceptOffer(amount, null, null)
+        .accounts({
+          taker: signer.publicKey,
+          offer,
+          assetMint,
+          quoteMint,
+          makerAssetAta,
+          takerAssetAta: ata(assetMint, signer.publicKey),
+          makerQuoteAta,
+          takerQuoteAta: ata(quoteMint, signer.publicKey),
+          escrowAuthority,
+          escrowAta,
+          assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+          quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([signer])
+        .rpc();
+
+    await expect(accept(outsider)).to.be.rejectedWith(/NotAllowedTaker/);
+    await accept(taker);
+    expect((await otcProgram.account.offer.fetch(offer)).status.taken !== undefined).to.be.true;
+  });
+
+  it("get_offer_state returns the remaining amount and price after a partial fill", async () => {
+    const nonce = (await otcProgram.account.makerState.fetch(makerStatePda())).nonce.toNumber();
+    const offer = offerPda(nonce);
+    const escrowAuthority = escrowAuthorityPda(nonce);
+    const escrowAta = getAssociatedTokenAddressSync(assetMint, escrowAuthority, true, TOKEN_2022_PROGRAM_ID);
+    const ata = (mint: PublicKey, owner: PublicKey) =>
+      getAssociatedTokenAddressSync(mint, owner, false, TOKEN_2022_PROGRAM_ID);
+    const makerAssetAta = ata(assetMint, maker.publicKey);
+    const makerQuoteAta = ata(quoteMint, maker.publicKey);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        createMintToInstruction(quoteMint, ata(quoteMint, taker.publicKey), maker.publicKey, price.toNumber(), [], TOKEN_2022_PROGRAM_ID),
+        createMintToInstruction(assetMint, makerAssetAta, maker.publicKey, amount.toNumber(), [], TOKEN_2022_PROGRAM_ID)
+      ),
+      [maker]
+    );
+
+    const expirySlot = new anchor.BN((await provider.connection.getSlot()) + 100000);
+    const tx = new Transaction().add(
+      createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, escrowAta, escrowAuthority, assetMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+      await otcProgram.methods
+        .createOffer(amount, price, { sell: {} }, expirySlot, new anchor.BN(0), null, null)
+        .accounts({
+          maker: maker.publicKey,
+          makerState: makerStatePda(),
+          offer,
+          assetMint,
+          makerAssetAta,
+          makerQuoteAta,
+          escrowAuthority,
+          escrowAta,
+          quoteMint,
+          assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+          quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction()
+    );
+    await sendAndConfirmTransaction(provider.connection, tx, [maker]);
+
+    const fillAmount = amount.divn(4);
+    await otcProgram.methods
+      .acceptOffer(fillAmount, null, null)
+      .accounts({
+        taker: taker.publicKey,
+        offer,
+        assetMint,
+        quoteMint,
+        makerAssetAta,
+        takerAssetAta: ata(assetMint, taker.publicKey),
+        makerQuoteAta,
+        takerQuoteAta: ata(quoteMint, taker.publicKey),
+        escrowAuthority,
+        escrowAta,
+        assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+        quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([taker])
+      .rpc({ commitment: "confirmed" });
+
+    const state = await otcProgram.methods.getOfferState().accounts({ offer }).view();
+    const offerAcc = await otcProgram.account.offer.fetch(offer);
+    expect(state.remainingAmount.toString()).to.equal(offerAcc.amount.toString());
+    expect(state.remainingAmount.toString()).to.equal(amount.sub(fillAmount).toString());
+    expect(state.remainingPrice.toString()).to.equal(offerAcc.price.toString());
+    expect(state.remainingPrice.toString()).to.equal(price.muln(3).divn(4).toString());
+    expect(state.filled.toString()).to.equal(fillAmount.toString());
+    expect(state.status.open !== undefined).to.be.true;
+    expect(state.expirySlot.toString()).to.equal(offerAcc.expirySlot.toString());
+    expect(state.expiryUnix.toString()).to.equal(offerAcc.expiryUnix.toString());
+  });
+
+  it("expiry_unix expires an offer by timestamp; with both bounds 0 the offer never expires", async () => {
+    const ata = (mint: PublicKey, owner: PublicKey) =>
+      getAssociatedTokenAddressSync(mint, owner, false, TOKEN_2022_PROGRAM_ID);
+    const makerAssetAta = ata(assetMint, maker.publicKey);
+    const makerQuoteAta = ata(quoteMint, maker.publicKey);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        createMintToInstruction(quoteMint, ata(quoteMint, taker.publicKey), maker.publicKey, price.toNumber(), [], TOKEN_2022_PROGRAM_ID),
+        createMintToInstruction(assetMint, makerAssetAta, maker.publicKey, amount.muln(2).toNumber(), [], TOKEN_2022_PROGRAM_ID)
+      ),
+      [maker]
+    );
+    const create = async (expiryUnix: anchor.BN) => {
+      const nonce = (await otcProgram.account.makerState.fetch(makerStatePda())).nonce.toNumber();
+      const offer = offerPda(nonce);
+      const escrowAuthority = escrowAuthorityPda(nonce);
+      const escrowAta = getAssociatedTokenAddressSync(assetMint, escrowAuthority, true, TOKEN_2022_PROGRAM_ID);
+      const tx = new Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, escrowAta, escrowAuthority, assetMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        await otcProgram.methods
+          .createOffer(amount, price, { sell: {} }, new anchor.BN(0), expiryUnix, null, null)
+          .accounts({
+            maker: maker.publicKey,
+            makerState: makerStatePda(),
+            offer,
+            assetMint,
+            makerAssetAta,
+            makerQuoteAta,
+            escrowAuthority,
+            escrowAta,
+            quoteMint,
+            assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+            quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction()
+      );
+      await sendAndConfirmTransaction(provider.connection, tx, [maker]);
+      return { offer, escrowAuthority, escrowAta };
+    };
+    const accept = ({ offer, escrowAuthority, escrowAta }: Awaited<ReturnType<typeof create>>) =>
+      otcProgram.methods
+        .acceptOffer(amount.divn(4), null, null)
+        .accounts({
+          taker: taker.publicKey,
+          offer,
+          assetMint,
+          quoteMint,
+          makerAssetAta,
+          takerAssetAta: ata(assetMint, taker.publicKey),
+          makerQuoteAta,
+          takerQuoteAta: ata(quoteMint, taker.publicKey),
+          escrowAuthority,
+          escrowAta,
+          assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+          quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([taker])
+        .rpc();
+
+    // expiry_slot 0 is unused, so the timestamp alone decides: 1 is long past.
+    const expired = await create(new anchor.BN(1));
+    await expect(accept(expired)).to.be.rejectedWith(/OfferExpired/);
+
+    const open = await create(new anchor.BN(0));
+    await accept(open);
+    const offerAcc = await otcProgram.account.offer.fetch(open.offer);
+    expect(offerAcc.expirySlot.toString()).to.equal("0");
+    expect(offerAcc.expiryUnix.toString()).to.equal("0");
+    expect(offerAcc.filled.toString()).to.equal(amount.divn(4).toString());
+  });
+
+  it("update_offer: reprices Sell offers without a quote account, moves Buy escrow with the price, and pins the escrow", async () => {
+    const ata = (mint: PublicKey, owner: PublicKey) =>
+      getAssociatedTokenAddressSync(mint, owner, false, TOKEN_2022_PROGRAM_ID);
+    const makerAssetAta = ata(assetMint, maker.publicKey);
+    const makerQuoteAta = ata(quoteMint, maker.publicKey);
+    const balance = async (account: PublicKey) =>
+      (await getAccount(provider.connection, account, "confirmed", TOKEN_2022_PROGRAM_ID)).amount;
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        createMintToInstruction(quoteMint, makerQuoteAta, maker.publicKey, price.muln(2).toNumber(), [], TOKEN_2022_PROGRAM_ID),
+        createMintToInstruction(assetMint, makerAssetAta, maker.publicKey, amount.toNumber(), [], TOKEN_2022_PROGRAM_ID)
+      ),
+      [maker]
+    );
+    const create = async (offerType: { sell: Record<string, never> } | { buy: Record<string, never> }) => {
+      const nonce = (await otcProgram.account.makerState.fetch(makerStatePda())).nonce.toNumber();
+      const offer = offerPda(nonce);
+      const escrowAuthority = escrowAuthorityPda(nonce);
+      const escrowMint = "sell" in offerType ? assetMint : quoteMint;
+      const escrowAta = getAssociatedTokenAddressSync(escrowMint, escrowAuthority, true, TOKEN_2022_PROGRAM_ID);
+      const tx = new Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, escrowAta, escrowAuthority, escrowMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        await otcProgram.methods
+          .createOffer(amount, price, offerType, new anchor.BN(0), new anchor.BN(0), null, null)
+          .accounts({
+            maker: maker.publicKey,
+            makerState: makerStatePda(),
+            offer,
+            assetMint,
+            makerAssetAta,
+            makerQuoteAta,
+            escrowAuthority,
+            escrowAta,
+            quoteMint,
+            assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+            quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction()
+      );
+      await sendAndConfirmTransaction(provider.connection, tx, [maker]);
+      return { offer, escrowAuthority, escrowAta };
+    };
+    const update = (
+      signer: Keypair,
+      { offer, escrowAuthority, escrowAta }: Awaited<ReturnType<typeof create>>,
+      newPrice: anchor.BN,
+      quoteAta: PublicKey | null,
+      expiry: { slot: anchor.BN | null; unix: anchor.BN | null } = { slot: null, unix: null }
+    ) =>
+      otcProgram.methods
+        .updateOffer(newPrice, expiry.slot, expiry.unix)
+        .accounts({
+          maker: signer.publicKey,
+          offer,
+          quoteMint,
+          makerQuoteAta: quoteAta,
+          escrowAuthority,
+          escrowAta,
+          quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([signer])
+        .rpc();
+
+    // Sell: nothing moves, so the maker's quote account is not needed.
+    const sell = await create({ sell: {} });
+    await update(maker, sell, price.muln(3), null, { slot: new anchor.BN(0), unix: new anchor.BN(4_000_000_000) });
+    const sellAcc = await otcProgram.account.offer.fetch(sell.offer);
+    expect(sellAcc.price.toString()).to.equal(price.muln(3).toString());
+    expect(sellAcc.expiryUnix.toString()).to.equal("4000000000");
+    await expect(update(taker, sell, price, null)).to.be.rejectedWith(/NotMaker/);
+    // The maker's own asset ATA is not the offer's escrow.
+    await expect(update(maker, { ...sell, escrowAta: makerAssetAta }, price, null)).to.be.rejectedWith(/InvalidEscrowAccount/);
+
+    // Buy: the escrowed quote follows the price.
+    const buy = await create({ buy: {} });
+    expect(await balance(buy.escrowAta)).to.equal(BigInt(price.toString()));
+    await expect(update(maker, buy, price.muln(2), null)).to.be.rejectedWith(/QuoteAccountRequired/);
+    const makerQuoteBefore = await balance(makerQuoteAta);
+    await update(maker, buy, price.muln(2), makerQuoteAta);
+    expect(await balance(buy.escrowAta)).to.equal(BigInt(price.muln(2).toString()));
+    expect(await balance(makerQuoteAta)).to.equal(makerQuoteBefore - BigInt(price.toString()));
+    await update(maker, buy, price.divn(2), makerQuoteAta);
+    expect(await balance(buy.escrowAta)).to.equal(BigInt(price.divn(2).toString()));
+    expect(await balance(makerQuoteAta)).to.equal(makerQuoteBefore + BigInt(price.divn(2).toString()));
+    // A Buy offer's escrow is the quote ATA; the asset-mint ATA of the same authority is refused.
+    const wrongMintEscrow = getAssociatedTokenAddressSync(assetMint, buy.escrowAuthority, true, TOKEN_2022_PROGRAM_ID);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, wrongMintEscrow, buy.escrowAuthority, assetMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID)
+      ),
+      [maker]
+    );
+    await expect(update(maker, { ...buy, escrowAta: wrongMintEscrow }, price, makerQuoteAta)).to.be.rejectedWith(/InvalidEscrowAccount/);
+  });
+
+  describe("auctions", () => {
+    const ata = (mint: PublicKey, owner: PublicKey) =>
+      getAssociatedTokenAddressSync(mint, owner, false, TOKEN_2022_PROGRAM_ID);
+    const balance = async (account: PublicKey) =>
+      (await getAccount(provider.connection, account, "confirmed", TOKEN_2022_PROGRAM_ID)).amount;
+    const reserve = new anchor.BN(1_000_000);
+    const increment = new anchor.BN(100_000);
+    let otcConfig: PublicKey;
+    let programDataAccount: PublicKey;
+    let bidder: Keypair;
+
+    before(async () => {
+      [otcConfig] = PublicKey.findProgramAddressSync([Buffer.from("otc_config")], otcProgramId);
+      [programDataAccount] = PublicKey.findProgramAddressSync(
+        [otcProgramId.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      );
+      if (!(await provider.connection.getAccountInfo(otcConfig))) {
+        await otcProgram.methods
+          .initializeOtcConfig(null, provider.wallet.publicKey, false, PublicKey.default, PublicKey.default)
+          .accounts({
+            authority: provider.wallet.publicKey,
+            otcConfig,
+            programAccount: otcProgramId,
+            programDataAccount,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+      bidder = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(bidder.publicKey, 2e9);
+      await provider.connection.confirmTransaction(sig);
+      await sendAndConfirmTransaction(
+        provider.connection,
+        new Transaction().add(
+          createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, ata(assetMint, bidder.publicKey), bidder.publicKey, assetMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+          createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, ata(quoteMint, bidder.publicKey), bidder.publicKey, quoteMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+          createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, ata(quoteMint, taker.publicKey), taker.publicKey, quoteMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+          createMintToInstruction(quoteMint, ata(quoteMint, bidder.publicKey), maker.publicKey, 10_000_000, [], TOKEN_2022_PROGRAM_ID),
+          createMintToInstruction(quoteMint, ata(quoteMint, taker.publicKey), maker.publicKey, 10_000_000, [], TOKEN_2022_PROGRAM_ID)
+        ),
+        [maker]
+      );
+    });
+
+    const createAuction = async (auctionMaker: Keypair, endSlot: number) => {
+      const [makerState] = PublicKey.findProgramAddressSync(
+        [Buffer.from("maker"), auctionMaker.publicKey.toBuffer()],
+        otcProgramId
+      );
+      const existing = await provider.connection.getAccountInfo(makerState);
+      const nonce = existing ? (await otcProgram.account.makerState.fetch(makerState)).nonce.toNumber() : 0;
+      const pdas = auctionPdas(auctionMaker.publicKey, nonce);
+      const tx = new Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(auctionMaker.publicKey, pdas.escrowAta, pdas.escrowAuthority, assetMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        createAssociatedTokenAccountIdempotentInstruction(auctionMaker.publicKey, pdas.bidEscrowAta, pdas.escrowAuthority, quoteMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+        await otcProgram.methods
+          .createAuction(amount, reserve, increment, new anchor.BN(endSlot), null)
+          .accounts({
+            maker: auctionMaker.publicKey,
+            makerState,
+            auction: pdas.auction,
+            otcConfig,
+            assetMint,
+            quoteMint,
+            makerAssetAta: ata(assetMint, auctionMaker.publicKey),
+            escrowAuthority: pdas.escrowAuthority,
+            escrowAta: pdas.escrowAta,
+            assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction()
+      );
+      await sendAndConfirmTransaction(provider.connection, tx, [auctionMaker]);
+      return { ...pdas, makerState };
+    };
+    type AuctionAccounts = Awaited<ReturnType<typeof createAuction>>;
+
+    const placeBid = (a: AuctionAccounts, who: Keypair, bid: anchor.BN, previousBidder: PublicKey | null, bidEscrowAta = a.bidEscrowAta) =>
+      otcProgram.methods
+        .placeBid(bid)
+        .accounts({
+          bidder: who.publicKey,
+          auction: a.auction,
+          quoteMint,
+          bidderQuoteAta: ata(quoteMint, who.publicKey),
+          previousBidderQuoteAta: previousBidder ? ata(quoteMint, previousBidder) : null,
+          escrowAuthority: a.escrowAuthority,
+          bidEscrowAta,
+          quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([who])
+        .rpc();
+
+    const cancelAuction = (a: AuctionAccounts, auctionMaker: Keypair) =>
+      otcProgram.methods
+        .cancelAuction()
+        .accounts({
+          maker: auctionMaker.publicKey,
+          auction: a.auction,
+          makerState: a.makerState,
+          assetMint,
+          makerAssetAta: ata(assetMint, auctionMaker.publicKey),
+          escrowAuthority: a.escrowAuthority,
+          escrowAta: a.escrowAta,
+          assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([auctionMaker])
+        .rpc();
+
+    it("outbidding refunds the previous bidder and settle pays the maker, treasury and winner", async () => {
+      const config = await otcProgram.account.otcConfig.fetch(otcConfig);
+      const treasuryQuoteAta = getAssociatedTokenAddressSync(quoteMint, config.treasury, true, TOKEN_2022_PROGRAM_ID);
+      await sendAndConfirmTransaction(
+        provider.connection,
+        new Transaction().add(
+          createAssociatedTokenAccountIdempotentInstruction(maker.publicKey, treasuryQuoteAta, config.treasury, quoteMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+          createMintToInstruction(assetMint, ata(assetMint, maker.publicKey), maker.publicKey, amount.toNumber(), [], TOKEN_2022_PROGRAM_ID)
+        ),
+        [maker]
+      );
+      const openBefore = (await otcProgram.account.makerState.fetch(makerStatePda())).openOffers;
+      const endSlot = (await provider.connection.getSlot("confirmed")) + 40;
+      const a = await createAuction(maker, endSlot);
+      expect(await balance(a.escrowAta)).to.equal(BigInt(amount.toString()));
+      expect((await otcProgram.account.makerState.fetch(makerStatePda())).openOffers).to.equal(openBefore + 1);
+
+      // A bid must go to this auction's bid escrow ATA, not another account of the same authority.
+      const strayEscrow = Keypair.generate();
+      await sendAndConfirmTransaction(
+        provider.connection,
+        new Transaction().add(
+          SystemProgram.createAccount({
+            fromPubkey: maker.publicKey,
+            newAccountPubkey: strayEscrow.publicKey,
+            lamports: await provider.connection.getMinimumBalanceForRentExemption(170),
+            space: 170,
+            programId: TOKEN_2022_PROGRAM_ID,
+          }),
+          createInitializeAccount3Instruction(strayEscrow.publicKey, quoteMint, a.escrowAuthority, TOKEN_2022_PROGRAM_ID)
+        ),
+        [maker, strayEscrow]
+      );
+      await expect(placeBid(a, taker, reserve, null, strayEscrow.publicKey)).to.be.rejectedWith(/InvalidEscrowAccount/);
+
+      await expect(placeBid(a, taker, reserve.subn(1), null)).to.be.rejectedWith(/BidTooLow/);
+      const takerQuoteBefore = await balance(ata(quoteMint, taker.publicKey));
+      await placeBid(a, taker, reserve, null);
+      expect(await balance(a.bidEscrowAta)).to.equal(BigInt(reserve.toString()));
+      expect(await balance(ata(quoteMint, taker.publicKey))).to.equal(takerQuoteBefore - BigInt(reserve.toString()));
+
+      // The next bid must clear the increment; outbidding refunds the previous bidder in full.
+      const winningBid = reserve.add(increment);
+      await expect(placeBid(a, bidder, winningBid.subn(1), taker.publicKey)).to.be.rejectedWith(/BidTooLow/);
+      await expect(placeBid(a, bidder, winningBid, null)).to.be.rejectedWith(/MissingPreviousBidder/);
+      await placeBid(a, bidder, winningBid, taker.publicKey);
+      expect(await balance(ata(quoteMint, taker.publicKey))).to.equal(takerQuoteBefore);
+      expect(await balance(a.bidEscrowAta)).to.equal(BigInt(winningBid.toString()));
+      const auction = await otcProgram.account.auction.fetch(a.auction);
+      expect(auction.highestBidder.equals(bidder.publicKey)).to.be.true;
+      expect(auction.highestBid.toString()).to.equal(winningBid.toString());
+      await expect(cancelAuction(a, maker)).to.be.rejectedWith(/AuctionHasBids/);
+
+      const settle = () =>
+        otcProgram.methods
+          .settleAuction()
+          .accounts({
+            settler: taker.publicKey,
+            auction: a.auction,
+            assetMint,
+            quoteMint,
+            makerAssetAta: ata(assetMint, maker.publicKey),
+            makerQuoteAta: ata(quoteMint, maker.publicKey),
+            winnerAssetAta: ata(assetMint, bidder.publicKey),
+            escrowAuthority: a.escrowAuthority,
+            escrowAta: a.escrowAta,
+            bidEscrowAta: a.bidEscrowAta,
+            makerState: a.makerState,
+            otcConfig,
+            treasuryQuoteAta,
+            assetTokenProgram: TOKEN_2022_PROGRAM_ID,
+            quoteTokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([taker])
+          .rpc();
+      await expect(settle()).to.be.rejectedWith(/AuctionNotEnded/);
+
+      while ((await provider.connection.getSlot("confirmed")) <= endSlot) {
+        await new Promise((r) => setTimeout(r, 400));
+      }
+      await expect(placeBid(a, taker, winningBid.add(increment), bidder.publicKey)).to.be.rejectedWith(/AuctionEnded/);
+
+      const makerQuoteBefore = await balance(ata(quoteMint, maker.publicKey));
+      const treasuryBefore = await balance(treasuryQuoteAta);
+      const winnerAssetBefore = await balance(ata(assetMint, bidder.publicKey));
+      await settle();
+      const fee = (BigInt(winningBid.toString()) * BigInt(config.feeBps)) / 10_000n;
+      expect(await balance(ata(quoteMint, maker.publicKey))).to.equal(makerQuoteBefore + BigInt(winningBid.toString()) - fee);
+      expect(await balance(treasuryQuoteAta)).to.equal(treasuryBefore + fee);
+      expect(await balance(ata(assetMint, bidder.publicKey))).to.equal(winnerAssetBefore + BigInt(amount.toString()));
+      expect(await balance(a.escrowAta)).to.equal(0n);
+      expect(await balance(a.bidEscrowAta)).to.equal(0n);
+      expect((await otcProgram.account.auction.fetch(a.auction)).status).to.deep.equal({ settled: {} });
+      expect((await otcProgram.account.makerState.fetch(makerStatePda())).openOffers).to.equal(openBefore);
+      await expect(settle()).to.be.rejectedWith(/AuctionNotOpen/);
+    });
+
+    it("cancel_auction returns the asset before any bid, and auctions count toward max_open_offers", async () => {
+      const adminAccounts = {
+        authority: provider.wallet.publicKey,
+        otcConfig,
+        programAccount: otcProgramId,
+        programDataAccount,
+        systemProgram: SystemProgram.programId,
+      };
+      const config = await otcProgram.account.otcConfig.fetch(otcConfig);
+      const setCap = (cap: number) =>
+        otcProgram.methods
+          .updateOtcConfig(config.feeBps, config.treasury, config.restrictAssets, config.rwaTokenProgram, config.projectEscrowProgram, cap)
+          .accounts(adminAccounts)
+          .rpc();
+
+      const auctionMaker = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(auctionMaker.publicKey, 2e9);
+      await provider.connection.confirmTransaction(sig);
+      const makerAssetAta = ata(assetMint, auctionMaker.publicKey);
+      await sendAndConfirmTransaction(
+        provider.connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(auctionMaker.publicKey, makerAssetAta, auctionMaker.publicKey, assetMint, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID),
+          createMintToInstruction(assetMint, makerAssetAta, maker.publicKey, BigInt(amount.toString()) * 2n, [], TOKEN_2022_PROGRAM_ID)
+        ),
+        [auctionMaker, maker]
+      );
+      const endSlot = (await provider.connection.getSlot("confirmed")) + 100_000;
+
+      await setCap(1);
+      try {
+        const first = await createAuction(auctionMaker, endSlot);
+        expect((await otcProgram.account.makerState.fetch(first.makerState)).openOffers).to.equal(1);
+        await expect(createAuction(auctionMaker, endSlot)).to.be.rejectedWith(/MaxOpenOffersReached|0x/);
+
+        // Only the maker can cancel, and only this auction's escrow can be drained.
+        await expect(cancelAuction(first, taker)).to.be.rejected;
+        await expect(cancelAuction({ ...first, escrowAta: ata(assetMint, auctionMaker.publicKey) }, auctionMaker)).to.be.rejectedWith(/InvalidEscrowAccount/);
+        await cancelAuction(first, auctionMaker);
+        expect(await balance(makerAssetAta)).to.equal(BigInt(amount.toString()) * 2n);
+        expect(await balance(first.escrowAta)).to.equal(0n);
+        expect((await otcProgram.account.auction.fetch(first.auction)).status).to.deep.equal({ cancelled: {} });
+        expect((await otcProgram.account.makerState.fetch(first.makerState)).openOffers).to.equal(0);
+        await expect(cancelAuction(first, auctionMaker)).to.be.rejectedWith(/AuctionNotOpen/);
+        await expect(placeBid(first, bidder, reserve, null)).to.be.rejectedWith(/AuctionNotOpen/);
+
+        // The freed slot takes a new auction; once it has a bid the maker can no longer cancel it.
+        const second = await createAuction(auctionMaker, endSlot);
+        await placeBid(second, bidder, reserve, null);
+        await expect(cancelAuction(second, auctionMaker)).to.be.rejectedWith(/AuctionHasBids/);
+      } finally {
+        await setCap(0);
+      }
+    });
+  });
 });